@@ -59,7 +59,7 @@ mod bbsplus_example {
             .map(|m| hex::decode(m).unwrap())
             .collect();
         let (commitment_with_proof, secret_prover_blind) =
-            Commitment::<BBSplus<S::Ciphersuite>>::commit(Some(&committed_messages))?;
+            Commitment::<BBSplus<S::Ciphersuite>>::commit(Some(&committed_messages), None)?;
 
         log::info!("Send the commitment with the proof to the Issuer");
         log::info!("Messages added by the Issuer to be signed");
@@ -79,6 +79,7 @@ mod bbsplus_example {
             Some(&header),
             Some(&messages),
             None,
+            None,
         )?;
 
         log::info!("Blind Signature Verification...");
@@ -89,6 +90,7 @@ mod bbsplus_example {
                 Some(&messages),
                 Some(&committed_messages),
                 Some(&secret_prover_blind),
+                None,
                 None
             )
             .is_ok());
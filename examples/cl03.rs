@@ -66,7 +66,7 @@ mod cl03_example {
             issuer_keypair.public_key(),
             &a_bases,
             Some(&unrevealed_message_indexes),
-        );
+        ).unwrap();
 
         log::info!("Computation of a Zero-Knowledge proof-of-knowledge of committed messages");
         let zkpok = ZKPoK::<CL03<S::Ciphersuite>>::generate_proof(
@@ -77,7 +77,7 @@ mod cl03_example {
             &a_bases,
             None,
             &unrevealed_message_indexes,
-        );
+        ).unwrap();
 
         log::info!("Verification of the Zero-Knowledge proof and computation of a blind signature");
         let blind_signature = BlindSignature::<CL03<S::Ciphersuite>>::blind_sign(
@@ -91,24 +91,24 @@ mod cl03_example {
             None,
             &unrevealed_message_indexes,
             Some(&revealed_message_indexes),
-        );
+        ).unwrap();
 
         log::info!("Signature unblinding and verification...");
         let unblinded_signature = blind_signature.unblind_sign(&commitment);
         let verify = unblinded_signature.verify_multiattr(issuer_keypair.public_key(), &a_bases, &messages);
 
         assert!(
-            verify,
+            verify.is_ok(),
             "Error! The unblinded signature verification should PASS!"
         );
         log::info!("Signature is VALID!");
 
-        let (sd_messages, sd_bases) = unblinded_signature.disclose_selectively(&messages, a_bases.clone(), issuer_keypair.public_key(), &unrevealed_message_indexes);
+        let (sd_messages, sd_bases) = unblinded_signature.disclose_selectively(&messages, a_bases.clone(), issuer_keypair.public_key(), &unrevealed_message_indexes).unwrap();
         let verify =
         unblinded_signature.verify_multiattr(issuer_keypair.public_key(), &sd_bases, &sd_messages);
 
         assert!(
-            verify,
+            verify.is_ok(),
             "Error! The unblinded signature verification should PASS!"
         );
         log::info!("SD Signature is VALID!");
@@ -129,7 +129,7 @@ mod cl03_example {
             &a_bases,
             &messages,
             &unrevealed_message_indexes,
-        );
+        ).unwrap();
 
         //Verifier verifies the Signature Proof of Knowledge
         log::info!("Signature Proof of Knowledge verification...");
@@ -143,7 +143,7 @@ mod cl03_example {
         );
 
         assert!(
-            valid_proof,
+            valid_proof.is_ok(),
             "Error! The signature proof of knowledge should PASS!"
         );
         log::info!("Signature Proof of Knowledge is VALID!");
@@ -153,7 +153,9 @@ mod cl03_example {
 #[cfg(feature = "cl03")]
 fn main() {
     use std::env;
-    use zkryptium::schemes::algorithms::CL03_CL1024_SHA256;
+    use zkryptium::schemes::algorithms::{
+        CL03_CL1024_SHA256, CL03_CL2048_SHA256, CL03_CL3072_SHA384, CL03_CL3072_SHA512,
+    };
 
     dotenv::dotenv().ok();
     env_logger::init();
@@ -164,7 +166,10 @@ fn main() {
         println!(
             "Usage: {} <cipher_suite>
                 Ciphersuites:
-                    - CL1024-SHA-256",
+                    - CL1024-SHA-256
+                    - CL2048-SHA-256
+                    - CL3072-SHA-384
+                    - CL3072-SHA-512",
             args[0]
         );
         return;
@@ -178,6 +183,21 @@ fn main() {
             log::info!("Ciphersuite: CL1024-SHA-256");
             cl03_example::cl03_main::<CL03_CL1024_SHA256>();
         }
+        "CL2048-SHA-256" => {
+            println!("\n");
+            log::info!("Ciphersuite: CL2048-SHA-256");
+            cl03_example::cl03_main::<CL03_CL2048_SHA256>();
+        }
+        "CL3072-SHA-384" => {
+            println!("\n");
+            log::info!("Ciphersuite: CL3072-SHA-384");
+            cl03_example::cl03_main::<CL03_CL3072_SHA384>();
+        }
+        "CL3072-SHA-512" => {
+            println!("\n");
+            log::info!("Ciphersuite: CL3072-SHA-512");
+            cl03_example::cl03_main::<CL03_CL3072_SHA512>();
+        }
         _ => {
             println!("Unknown cipher suite: {}", cipher_suite);
             // Handle other cipher suites or raise an error if necessary
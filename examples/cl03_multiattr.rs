@@ -16,7 +16,7 @@
 mod cl03_example {
     use digest::Digest;
     use zkryptium::cl03::bases::Bases;
-    use zkryptium::cl03::ciphersuites::{CL1024Sha256, CLCiphersuite};
+    use zkryptium::cl03::ciphersuites::CLCiphersuite;
     use zkryptium::cl03::keys::CL03CommitmentPublicKey;
     use zkryptium::keys::pair::KeyPair;
     use zkryptium::schemes::algorithms::{Ciphersuite, Scheme, CL03};
@@ -55,24 +55,24 @@ mod cl03_example {
         let revealed_message_indexes = [0, 2];
         let unrevealed_message_indexes = undisclosed_message_indexes;
 
-        let signature = Signature::<CL03<CL1024Sha256>>::sign_multiattr(
+        let signature = Signature::<CL03<S::Ciphersuite>>::sign_multiattr(
             issuer_keypair.public_key(),
             issuer_keypair.private_key(),
             &a_bases,
             &messages
-        );
+        ).unwrap();
         let verify = signature.verify_multiattr(issuer_keypair.public_key(), &a_bases, &messages);
 
         log::info!("Bases:\t{:?}", a_bases);
         log::info!("Messages:\t{:?}", messages);
 
         assert!(
-            verify,
+            verify.is_ok(),
             "Error! The signature verification should PASS!"
         );
         log::info!("Signature is VALID!");
 
-        let (sd_messages, sd_bases) = signature.disclose_selectively(&messages, a_bases.clone(), issuer_keypair.public_key(), undisclosed_message_indexes.as_slice());
+        let (sd_messages, sd_bases) = signature.disclose_selectively(&messages, a_bases.clone(), issuer_keypair.public_key(), undisclosed_message_indexes.as_slice()).unwrap();
 
         log::info!("SDBases:\t{:?}", sd_bases);
         log::info!("SDMessages:\t{:?}", sd_messages);
@@ -80,7 +80,7 @@ mod cl03_example {
         let verify = signature.verify_multiattr(issuer_keypair.public_key(), &sd_bases, &sd_messages);
 
         assert!(
-            verify,
+            verify.is_ok(),
             "Error! The signature verification should PASS!"
         );
 
@@ -108,7 +108,7 @@ mod cl03_example {
             &a_bases,
             &messages,
             &unrevealed_message_indexes,
-        );
+        ).unwrap();
 
         //Verifier verifies the Signature Proof of Knowledge
         log::info!("Signature Proof of Knowledge verification...");
@@ -122,7 +122,7 @@ mod cl03_example {
         );
 
         assert!(
-            valid_proof,
+            valid_proof.is_ok(),
             "Error! The signature proof of knowledge should PASS!"
         );
         log::info!("Signature Proof of Knowledge is VALID!");
@@ -132,7 +132,9 @@ mod cl03_example {
 #[cfg(feature = "cl03")]
 fn main() {
     use std::env;
-    use zkryptium::schemes::algorithms::CL03_CL1024_SHA256;
+    use zkryptium::schemes::algorithms::{
+        CL03_CL1024_SHA256, CL03_CL2048_SHA256, CL03_CL3072_SHA384, CL03_CL3072_SHA512,
+    };
 
     dotenv::dotenv().ok();
     env_logger::init();
@@ -143,7 +145,10 @@ fn main() {
         println!(
             "Usage: {} <cipher_suite>
                 Ciphersuites:
-                    - CL1024-SHA-256",
+                    - CL1024-SHA-256
+                    - CL2048-SHA-256
+                    - CL3072-SHA-384
+                    - CL3072-SHA-512",
             args[0]
         );
         return;
@@ -157,6 +162,21 @@ fn main() {
             log::info!("Ciphersuite: CL1024-SHA-256");
             cl03_example::cl03_main::<CL03_CL1024_SHA256>();
         }
+        "CL2048-SHA-256" => {
+            println!("\n");
+            log::info!("Ciphersuite: CL2048-SHA-256");
+            cl03_example::cl03_main::<CL03_CL2048_SHA256>();
+        }
+        "CL3072-SHA-384" => {
+            println!("\n");
+            log::info!("Ciphersuite: CL3072-SHA-384");
+            cl03_example::cl03_main::<CL03_CL3072_SHA384>();
+        }
+        "CL3072-SHA-512" => {
+            println!("\n");
+            log::info!("Ciphersuite: CL3072-SHA-512");
+            cl03_example::cl03_main::<CL03_CL3072_SHA512>();
+        }
         _ => {
             println!("Unknown cipher suite: {}", cipher_suite);
             // Handle other cipher suites or raise an error if necessary
@@ -0,0 +1,227 @@
+/* Generated with cbindgen from src/ffi.rs, do not edit manually. */
+
+#ifndef ZKRYPTIUM_H
+#define ZKRYPTIUM_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+// BLS12-381-SHA-256 ciphersuite identifier.
+#define ZKRYPTIUM_BBS_BLS12_381_SHA_256 0
+
+// BLS12-381-SHAKE-256 ciphersuite identifier.
+#define ZKRYPTIUM_BBS_BLS12_381_SHAKE_256 1
+
+// Result of a call. The codes from 100 on map the variants of [`Error`].
+typedef enum ZkryptiumStatus {
+  ZKRYPTIUM_STATUS_OK = 0,
+  ZKRYPTIUM_STATUS_NULL_POINTER = 1,
+  ZKRYPTIUM_STATUS_INVALID_CIPHERSUITE = 2,
+  ZKRYPTIUM_STATUS_PANIC = 3,
+  ZKRYPTIUM_STATUS_KEY_GEN_ERROR = 100,
+  ZKRYPTIUM_STATUS_KEY_DESERIALIZATION_ERROR = 101,
+  ZKRYPTIUM_STATUS_BLIND_SIGN_ERROR = 102,
+  ZKRYPTIUM_STATUS_SIGNATURE_GENERATION_ERROR = 103,
+  ZKRYPTIUM_STATUS_INVALID_SIGNATURE = 104,
+  ZKRYPTIUM_STATUS_HASH_TO_SCALAR_ERROR = 105,
+  ZKRYPTIUM_STATUS_MAP_MESSAGE_TO_SCALAR_ERROR = 106,
+  ZKRYPTIUM_STATUS_NOT_ENOUGH_GENERATORS = 107,
+  ZKRYPTIUM_STATUS_G1_IDENTITY_ERROR = 108,
+  ZKRYPTIUM_STATUS_DESERIALIZATION_ERROR = 109,
+  ZKRYPTIUM_STATUS_SIGNATURE_VERIFICATION_ERROR = 110,
+  ZKRYPTIUM_STATUS_PROOF_GEN_ERROR = 111,
+  ZKRYPTIUM_STATUS_BLIND_PROOF_GEN_ERROR = 112,
+  ZKRYPTIUM_STATUS_UNSPECIFIED = 113,
+  ZKRYPTIUM_STATUS_UPDATE_SIGNATURE_ERROR = 114,
+  ZKRYPTIUM_STATUS_INVALID_PROOF_OF_KNOWLEDGE_SIGNATURE = 115,
+  ZKRYPTIUM_STATUS_PO_KS_VERIFICATION_ERROR = 116,
+  ZKRYPTIUM_STATUS_UNESPECTED_ERROR = 117,
+  ZKRYPTIUM_STATUS_INVALID_COMMITMENT = 118,
+  ZKRYPTIUM_STATUS_INVALID_COMMITMENT_PROOF = 119,
+  ZKRYPTIUM_STATUS_CHALLENGE_COMPUTATION_FAILED = 120,
+  ZKRYPTIUM_STATUS_INVALID_PSEUDONYM = 121,
+  ZKRYPTIUM_STATUS_BATCH_VERIFICATION_ERROR = 122,
+  ZKRYPTIUM_STATUS_RANGE_PROOF_GEN_ERROR = 123,
+  ZKRYPTIUM_STATUS_INVALID_RANGE_PROOF = 124,
+  ZKRYPTIUM_STATUS_RANGE_PROOF_VERIFICATION_ERROR = 125,
+  ZKRYPTIUM_STATUS_NOT_ENOUGH_BASES = 126,
+  ZKRYPTIUM_STATUS_INVALID_MESSAGE_INDEX = 127,
+  ZKRYPTIUM_STATUS_NOT_INVERTIBLE_ELEMENT = 128,
+  ZKRYPTIUM_STATUS_INVALID_RANGE_BOUNDS = 129,
+  ZKRYPTIUM_STATUS_CIPHERSUITE_MISMATCH = 130,
+  ZKRYPTIUM_STATUS_INVALID_PUBLIC_KEY = 131,
+  ZKRYPTIUM_STATUS_INVALID_PROOF_OF_POSSESSION = 132,
+} ZkryptiumStatus;
+
+// Octet string owned by the library, to be released with [`zkryptium_buffer_free`].
+typedef struct ZkryptiumBuffer {
+  uint8_t *data;
+  size_t len;
+} ZkryptiumBuffer;
+
+// Borrowed octet string.
+typedef struct ZkryptiumBytes {
+  const uint8_t *data;
+  size_t len;
+} ZkryptiumBytes;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Releases a buffer returned by the library, zeroing its content. Passing a buffer with a null `data` pointer is a no-op.
+//
+// # Safety
+// `buffer` must have been returned by the library and not released yet.
+void zkryptium_buffer_free(ZkryptiumBuffer buffer);
+
+// Returns a static, NUL-terminated description of `status`.
+const char *zkryptium_status_message(ZkryptiumStatus status);
+
+// Generates a keypair from `key_material` (see [`KeyPair::generate`]). `out_sk` receives the 32 bytes secret key and `out_pk` the 96 bytes public key.
+//
+// # Safety
+// Every non-null pointer must be valid for the given length, and the output pointers must be valid for writes.
+ZkryptiumStatus zkryptium_bbs_keygen(uint32_t ciphersuite,
+                                     ZkryptiumBytes key_material,
+                                     ZkryptiumBytes key_info,
+                                     ZkryptiumBytes key_dst,
+                                     ZkryptiumBuffer *out_sk,
+                                     ZkryptiumBuffer *out_pk);
+
+// Signs `messages` (see [`Signature::sign`]). `out_signature` receives the 80 bytes signature.
+//
+// # Safety
+// Every non-null pointer must be valid for the given length, and the output pointers must be valid for writes.
+ZkryptiumStatus zkryptium_bbs_sign(uint32_t ciphersuite,
+                                   ZkryptiumBytes sk,
+                                   ZkryptiumBytes pk,
+                                   ZkryptiumBytes header,
+                                   const ZkryptiumBytes *messages,
+                                   size_t messages_len,
+                                   ZkryptiumBuffer *out_signature);
+
+// Verifies a signature (see [`Signature::verify`]). Returns [`ZkryptiumStatus::Ok`] only if the signature is valid.
+//
+// # Safety
+// Every non-null pointer must be valid for the given length.
+ZkryptiumStatus zkryptium_bbs_verify(uint32_t ciphersuite,
+                                     ZkryptiumBytes pk,
+                                     ZkryptiumBytes signature,
+                                     ZkryptiumBytes header,
+                                     const ZkryptiumBytes *messages,
+                                     size_t messages_len);
+
+// Computes a proof of knowledge of a signature disclosing the messages at `disclosed_indexes` (see [`PoKSignature::proof_gen`]).
+//
+// # Safety
+// Every non-null pointer must be valid for the given length, and the output pointers must be valid for writes.
+ZkryptiumStatus zkryptium_bbs_proof_gen(uint32_t ciphersuite,
+                                        ZkryptiumBytes pk,
+                                        ZkryptiumBytes signature,
+                                        ZkryptiumBytes header,
+                                        ZkryptiumBytes ph,
+                                        const ZkryptiumBytes *messages,
+                                        size_t messages_len,
+                                        const size_t *disclosed_indexes,
+                                        size_t disclosed_indexes_len,
+                                        ZkryptiumBuffer *out_proof);
+
+// Verifies a proof of knowledge of a signature (see [`PoKSignature::proof_verify`]). Returns [`ZkryptiumStatus::Ok`] only if the proof is valid.
+//
+// # Safety
+// Every non-null pointer must be valid for the given length.
+ZkryptiumStatus zkryptium_bbs_proof_verify(uint32_t ciphersuite,
+                                           ZkryptiumBytes pk,
+                                           ZkryptiumBytes proof,
+                                           ZkryptiumBytes header,
+                                           ZkryptiumBytes ph,
+                                           const ZkryptiumBytes *disclosed_messages,
+                                           size_t disclosed_messages_len,
+                                           const size_t *disclosed_indexes,
+                                           size_t disclosed_indexes_len);
+
+// Commits to the messages to be blindly signed (see [`Commitment::commit`]). `out_secret_prover_blind` receives the 32 bytes `secret_prover_blind`.
+//
+// # Safety
+// Every non-null pointer must be valid for the given length, and the output pointers must be valid for writes.
+ZkryptiumStatus zkryptium_bbs_commit(uint32_t ciphersuite,
+                                     const ZkryptiumBytes *committed_messages,
+                                     size_t committed_messages_len,
+                                     ZkryptiumBuffer *out_commitment_with_proof,
+                                     ZkryptiumBuffer *out_secret_prover_blind);
+
+// Computes a blind signature over `messages` and the committed messages (see [`BlindSignature::blind_sign`]). `out_signature` receives the 80 bytes signature.
+//
+// # Safety
+// Every non-null pointer must be valid for the given length, and the output pointers must be valid for writes.
+ZkryptiumStatus zkryptium_bbs_blind_sign(uint32_t ciphersuite,
+                                         ZkryptiumBytes sk,
+                                         ZkryptiumBytes pk,
+                                         ZkryptiumBytes commitment_with_proof,
+                                         ZkryptiumBytes header,
+                                         const ZkryptiumBytes *messages,
+                                         size_t messages_len,
+                                         ZkryptiumBytes signer_blind,
+                                         ZkryptiumBuffer *out_signature);
+
+// Verifies a blind signature (see [`BlindSignature::verify`]). Returns [`ZkryptiumStatus::Ok`] only if the signature is valid.
+//
+// # Safety
+// Every non-null pointer must be valid for the given length.
+ZkryptiumStatus zkryptium_bbs_blind_verify(uint32_t ciphersuite,
+                                           ZkryptiumBytes pk,
+                                           ZkryptiumBytes signature,
+                                           ZkryptiumBytes header,
+                                           const ZkryptiumBytes *messages,
+                                           size_t messages_len,
+                                           const ZkryptiumBytes *committed_messages,
+                                           size_t committed_messages_len,
+                                           ZkryptiumBytes secret_prover_blind,
+                                           ZkryptiumBytes signer_blind);
+
+// Computes a proof of knowledge of a blind signature (see [`PoKSignature::blind_proof_gen`]).
+//
+// # Safety
+// Every non-null pointer must be valid for the given length, and the output pointers must be valid for writes.
+ZkryptiumStatus zkryptium_bbs_blind_proof_gen(uint32_t ciphersuite,
+                                              ZkryptiumBytes pk,
+                                              ZkryptiumBytes signature,
+                                              ZkryptiumBytes header,
+                                              ZkryptiumBytes ph,
+                                              const ZkryptiumBytes *messages,
+                                              size_t messages_len,
+                                              const ZkryptiumBytes *committed_messages,
+                                              size_t committed_messages_len,
+                                              const size_t *disclosed_indexes,
+                                              size_t disclosed_indexes_len,
+                                              const size_t *disclosed_commitment_indexes,
+                                              size_t disclosed_commitment_indexes_len,
+                                              ZkryptiumBytes secret_prover_blind,
+                                              ZkryptiumBytes signer_blind,
+                                              ZkryptiumBuffer *out_proof);
+
+// Verifies a proof of knowledge of a blind signature, `L` being the number of messages known to the Signer (see [`PoKSignature::blind_proof_verify`]). Returns [`ZkryptiumStatus::Ok`] only if the proof is valid.
+//
+// # Safety
+// Every non-null pointer must be valid for the given length.
+ZkryptiumStatus zkryptium_bbs_blind_proof_verify(uint32_t ciphersuite,
+                                                 ZkryptiumBytes pk,
+                                                 ZkryptiumBytes proof,
+                                                 ZkryptiumBytes header,
+                                                 ZkryptiumBytes ph,
+                                                 size_t L,
+                                                 const ZkryptiumBytes *disclosed_messages,
+                                                 size_t disclosed_messages_len,
+                                                 const ZkryptiumBytes *disclosed_committed_messages,
+                                                 size_t disclosed_committed_messages_len,
+                                                 const size_t *disclosed_indexes,
+                                                 size_t disclosed_indexes_len,
+                                                 const size_t *disclosed_commitment_indexes,
+                                                 size_t disclosed_commitment_indexes_len);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* ZKRYPTIUM_H */
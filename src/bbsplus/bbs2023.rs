@@ -0,0 +1,631 @@
+// Copyright 2023 Fondazione LINKS
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Base and derived proof values of the W3C Data Integrity [`bbs-2023`](https://www.w3.org/TR/vc-di-bbs/) cryptosuite.
+//!
+//! JSON-LD processing is left to the caller: the operations of this module take the canonical N-Quads statements of the document
+//! (each one terminated by `"\n"`), already relabeled with the HMAC based blank node labels (see [`shuffled_label_map`]) and sorted,
+//! together with the indexes of the statements selected by the mandatory and selective pointers.
+//! The canonical proof configuration is also given as N-Quads.
+
+use super::{
+    ciphersuites::Bls12381Sha256,
+    keys::{BBSplusPublicKey, BBSplusSecretKey},
+    signature::BBSplusSignature,
+};
+use crate::{
+    errors::Error,
+    schemes::{
+        algorithms::BBSplus,
+        generics::{PoKSignature, Signature},
+    },
+    utils::{
+        cbor::Value,
+        encoding::{base64url_decode, base64url_encode},
+    },
+};
+use alloc::{borrow::ToOwned, boxed::Box, collections::BTreeMap, format, string::String, vec::Vec};
+use core::fmt;
+use hmac::{Hmac, Mac};
+#[cfg(feature = "std")]
+use rand::thread_rng;
+use rand::{CryptoRng, RngCore};
+use sha2::{Digest, Sha256};
+use zeroize::Zeroize;
+
+/// Length of the HMAC key used to compute the blank node labels
+pub const HMAC_KEY_LENGTH: usize = 32;
+/// Length of the BBS header, i.e. the hash of the proof configuration followed by the hash of the mandatory statements
+pub const BBS_HEADER_LENGTH: usize = 64;
+
+/// CBOR tag of a base proof value (header bytes `0xd9 0x5d 0x02`)
+const BASE_PROOF_TAG: u64 = 0x5d02;
+/// CBOR tag of a derived proof value (header bytes `0xd9 0x5d 0x03`)
+const DERIVED_PROOF_TAG: u64 = 0x5d03;
+/// Multibase prefix of the base64url (no padding) encoding
+const MULTIBASE_BASE64URL: char = 'u';
+
+type CS = Bls12381Sha256;
+
+/// # Description
+/// Computes the blank node label map of the `bbs-2023` cryptosuite: every canonical blank node label (e.g., `c14n0`) is mapped to
+/// `b<i>`, where `i` is the position of the HMAC of that label among the sorted HMACs of all the labels.
+///
+/// # Inputs:
+/// * `hmac_key` (REQUIRED), the HMAC key of the base proof.
+/// * `canonical_labels` (REQUIRED), the canonical blank node labels of the document.
+///
+/// # Output:
+/// * a map from the canonical labels to the shuffled labels.
+///
+pub fn shuffled_label_map(
+    hmac_key: &[u8; HMAC_KEY_LENGTH],
+    canonical_labels: &[&str],
+) -> BTreeMap<String, String> {
+    let hmac_ids: BTreeMap<&str, String> = canonical_labels
+        .iter()
+        .map(|&label| {
+            let mut mac = <Hmac<Sha256> as Mac>::new_from_slice(hmac_key)
+                .expect("HMAC accepts keys of any length");
+            mac.update(label.as_bytes());
+            let digest = mac.finalize().into_bytes();
+            (
+                label,
+                format!("{}{}", MULTIBASE_BASE64URL, base64url_encode(&digest)),
+            )
+        })
+        .collect();
+
+    let mut sorted_ids: Vec<&String> = hmac_ids.values().collect();
+    sorted_ids.sort();
+
+    hmac_ids
+        .iter()
+        .map(|(&label, id)| {
+            let index = sorted_ids.binary_search(&id).unwrap_or_default();
+            (label.to_owned(), format!("b{}", index))
+        })
+        .collect()
+}
+
+/// BBS header binding the proof configuration and the mandatory statements
+fn bbs_header(proof_config: &str, mandatory_statements: &[&str]) -> [u8; BBS_HEADER_LENGTH] {
+    let mut header = [0u8; BBS_HEADER_LENGTH];
+    header[..32].copy_from_slice(&Sha256::digest(proof_config.as_bytes()));
+    let mut mandatory_hash = Sha256::new();
+    mandatory_statements
+        .iter()
+        .for_each(|s| mandatory_hash.update(s.as_bytes()));
+    header[32..].copy_from_slice(&mandatory_hash.finalize());
+    header
+}
+
+/// Splits `statements` into the mandatory ones and the BBS messages, i.e. the non-mandatory ones
+fn group_statements<'a>(
+    statements: &'a [String],
+    mandatory_indexes: &[usize],
+) -> Result<(Vec<&'a str>, Vec<Vec<u8>>), Error> {
+    if mandatory_indexes.iter().any(|&i| i >= statements.len()) {
+        return Err(Error::InvalidMessageIndex);
+    }
+    let mut mandatory = Vec::new();
+    let mut messages = Vec::new();
+    for (i, statement) in statements.iter().enumerate() {
+        if mandatory_indexes.contains(&i) {
+            mandatory.push(statement.as_str());
+        } else {
+            messages.push(statement.as_bytes().to_vec());
+        }
+    }
+    Ok((mandatory, messages))
+}
+
+fn encode_proof_value(tag: u64, components: Vec<Value>) -> String {
+    let cbor = Value::Tag(tag, Box::new(Value::Array(components))).to_bytes();
+    format!("{}{}", MULTIBASE_BASE64URL, base64url_encode(&cbor))
+}
+
+fn decode_proof_value(proof_value: &str, tag: u64, components: usize) -> Result<Vec<Value>, Error> {
+    let cbor = proof_value
+        .strip_prefix(MULTIBASE_BASE64URL)
+        .and_then(base64url_decode)
+        .ok_or_else(|| {
+            Error::DeserializationError("proofValue is not base64url multibase encoded".to_owned())
+        })?;
+    let components_values = Value::from_bytes(&cbor)?.untag(tag)?.into_array()?;
+    if components_values.len() != components {
+        return Err(Error::DeserializationError(
+            "Unexpected number of proofValue components".to_owned(),
+        ));
+    }
+    Ok(components_values)
+}
+
+fn into_array<const N: usize>(value: Value, component: &str) -> Result<[u8; N], Error> {
+    value
+        .into_bytes()?
+        .try_into()
+        .map_err(|_| Error::DeserializationError(format!("Invalid {} length", component)))
+}
+
+fn indexes_to_cbor(indexes: &[usize]) -> Value {
+    Value::Array(indexes.iter().map(|&i| Value::Unsigned(i as u64)).collect())
+}
+
+fn indexes_from_cbor(value: Value) -> Result<Vec<usize>, Error> {
+    value
+        .into_array()?
+        .into_iter()
+        .map(Value::into_usize)
+        .collect()
+}
+
+/// Base proof of the `bbs-2023` cryptosuite, created by the Issuer and sent to the Holder.
+#[derive(Clone, PartialEq, Eq)]
+pub struct BaseProof {
+    pub bbs_signature: [u8; BBSplusSignature::BYTES],
+    pub bbs_header: [u8; BBS_HEADER_LENGTH],
+    pub public_key: BBSplusPublicKey,
+    pub hmac_key: [u8; HMAC_KEY_LENGTH],
+    pub mandatory_pointers: Vec<String>,
+}
+
+impl BaseProof {
+    /// # Description
+    /// Creates a base proof, signing the non-mandatory statements with the hashes of the proof configuration and of the mandatory statements as BBS header.
+    ///
+    /// # Inputs:
+    /// * `sk` (REQUIRED), the secret key of the Issuer.
+    /// * `pk` (REQUIRED), the public key of the Issuer.
+    /// * `hmac_key` (REQUIRED), the HMAC key used to compute the blank node labels of `statements`.
+    /// * `proof_config` (REQUIRED), the canonical proof configuration.
+    /// * `statements` (REQUIRED), the sorted canonical statements of the document, relabeled with [`shuffled_label_map`].
+    /// * `mandatory_indexes` (REQUIRED), the indexes of the statements selected by `mandatory_pointers`.
+    /// * `mandatory_pointers` (REQUIRED), the JSON pointers of the claims the Holder will always disclose.
+    ///
+    /// # Output:
+    /// a [`BaseProof`] or [`Error`].
+    ///
+    pub fn create(
+        sk: &BBSplusSecretKey,
+        pk: &BBSplusPublicKey,
+        hmac_key: &[u8; HMAC_KEY_LENGTH],
+        proof_config: &str,
+        statements: &[String],
+        mandatory_indexes: &[usize],
+        mandatory_pointers: Vec<String>,
+    ) -> Result<Self, Error> {
+        let (mandatory, messages) = group_statements(statements, mandatory_indexes)?;
+        let bbs_header = bbs_header(proof_config, &mandatory);
+        let signature = Signature::<BBSplus<CS>>::sign(Some(&messages), sk, pk, Some(&bbs_header))?;
+
+        Ok(Self {
+            bbs_signature: signature.to_bytes(),
+            bbs_header,
+            public_key: pk.clone(),
+            hmac_key: *hmac_key,
+            mandatory_pointers,
+        })
+    }
+
+    /// # Description
+    /// Verifies the base proof received by the Holder against the statements of the document.
+    ///
+    /// # Inputs:
+    /// * `proof_config` (REQUIRED), the canonical proof configuration.
+    /// * `statements` (REQUIRED), the sorted canonical statements of the document, relabeled with [`shuffled_label_map`].
+    /// * `mandatory_indexes` (REQUIRED), the indexes of the statements selected by the mandatory pointers.
+    ///
+    /// # Output:
+    /// a result: [`Ok`] or [`Error`].
+    ///
+    pub fn verify(
+        &self,
+        proof_config: &str,
+        statements: &[String],
+        mandatory_indexes: &[usize],
+    ) -> Result<(), Error> {
+        let (mandatory, messages) = group_statements(statements, mandatory_indexes)?;
+        if bbs_header(proof_config, &mandatory) != self.bbs_header {
+            return Err(Error::SignatureVerificationError);
+        }
+        Signature::<BBSplus<CS>>::from_bytes(&self.bbs_signature)?.verify(
+            &self.public_key,
+            Some(&messages),
+            Some(&self.bbs_header),
+        )
+    }
+
+    /// Encodes the base proof as the `proofValue` of a Data Integrity proof
+    pub fn to_proof_value(&self) -> String {
+        encode_proof_value(
+            BASE_PROOF_TAG,
+            vec![
+                Value::Bytes(self.bbs_signature.to_vec()),
+                Value::Bytes(self.bbs_header.to_vec()),
+                Value::Bytes(self.public_key.to_bytes().to_vec()),
+                Value::Bytes(self.hmac_key.to_vec()),
+                Value::Array(
+                    self.mandatory_pointers
+                        .iter()
+                        .map(|p| Value::Text(p.clone()))
+                        .collect(),
+                ),
+            ],
+        )
+    }
+
+    pub fn from_proof_value(proof_value: &str) -> Result<Self, Error> {
+        let mut components = decode_proof_value(proof_value, BASE_PROOF_TAG, 5)?.into_iter();
+        let mut next = || components.next().unwrap();
+        let bbs_signature = into_array(next(), "bbsSignature")?;
+        let bbs_header = into_array(next(), "bbsHeader")?;
+        let public_key = BBSplusPublicKey::from_bytes(&into_array::<96>(next(), "publicKey")?)?;
+        let hmac_key = into_array(next(), "hmacKey")?;
+        let mandatory_pointers = next()
+            .into_array()?
+            .into_iter()
+            .map(Value::into_text)
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            bbs_signature,
+            bbs_header,
+            public_key,
+            hmac_key,
+            mandatory_pointers,
+        })
+    }
+}
+
+impl Drop for BaseProof {
+    fn drop(&mut self) {
+        self.hmac_key.zeroize();
+    }
+}
+
+impl fmt::Debug for BaseProof {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BaseProof")
+            .field("bbs_signature", &self.bbs_signature)
+            .field("bbs_header", &self.bbs_header)
+            .field("public_key", &self.public_key)
+            .field("hmac_key", &"<redacted>")
+            .field("mandatory_pointers", &self.mandatory_pointers)
+            .finish()
+    }
+}
+
+/// Derived proof of the `bbs-2023` cryptosuite, created by the Holder from a [`BaseProof`] and sent to the Verifier
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct DerivedProof {
+    pub bbs_proof: Vec<u8>,
+    /// Map from the canonical blank node labels of the revealed document to the labels of the base document
+    pub label_map: BTreeMap<String, String>,
+    /// Indexes of the mandatory statements among the statements of the revealed document
+    pub mandatory_indexes: Vec<usize>,
+    /// Indexes of the disclosed messages among the BBS messages, i.e. among the non-mandatory statements of the base document
+    pub selective_indexes: Vec<usize>,
+    pub presentation_header: Vec<u8>,
+}
+
+impl DerivedProof {
+    /// # Description
+    /// Creates a derived proof disclosing the mandatory statements and the statements selected by the Holder.
+    ///
+    /// # Inputs:
+    /// * `base_proof` (REQUIRED), the [`BaseProof`] received from the Issuer.
+    /// * `statements` (REQUIRED), the sorted canonical statements of the base document, relabeled with [`shuffled_label_map`].
+    /// * `mandatory_indexes` (REQUIRED), the indexes of the statements selected by the mandatory pointers of `base_proof`.
+    /// * `selective_indexes` (REQUIRED), the indexes of the statements selected by the selective pointers.
+    /// * `label_map` (REQUIRED), the map from the canonical blank node labels of the revealed document to the labels of the base document.
+    /// * `presentation_header` (OPTIONAL), an octet string bound to the proof.
+    ///
+    /// # Output:
+    /// a [`DerivedProof`] or [`Error`].
+    ///
+    #[cfg(feature = "std")]
+    pub fn create(
+        base_proof: &BaseProof,
+        statements: &[String],
+        mandatory_indexes: &[usize],
+        selective_indexes: &[usize],
+        label_map: BTreeMap<String, String>,
+        presentation_header: Option<&[u8]>,
+    ) -> Result<Self, Error> {
+        Self::create_with_rng(
+            base_proof,
+            statements,
+            mandatory_indexes,
+            selective_indexes,
+            label_map,
+            presentation_header,
+            &mut thread_rng(),
+        )
+    }
+
+    /// # Description
+    /// Same as [`Self::create`], drawing the randomness of the BBS proof from `rng`.
+    ///
+    /// # Inputs:
+    /// * `rng` (REQUIRED), a cryptographically secure random number generator.
+    /// * the other inputs are the same as [`Self::create`].
+    ///
+    /// # Output:
+    /// a [`DerivedProof`] or [`Error`].
+    ///
+    pub fn create_with_rng<R: RngCore + CryptoRng>(
+        base_proof: &BaseProof,
+        statements: &[String],
+        mandatory_indexes: &[usize],
+        selective_indexes: &[usize],
+        label_map: BTreeMap<String, String>,
+        presentation_header: Option<&[u8]>,
+        rng: &mut R,
+    ) -> Result<Self, Error> {
+        if selective_indexes.iter().any(|&i| i >= statements.len()) {
+            return Err(Error::InvalidMessageIndex);
+        }
+        let (_, messages) = group_statements(statements, mandatory_indexes)?;
+
+        let non_mandatory_indexes: Vec<usize> = (0..statements.len())
+            .filter(|i| !mandatory_indexes.contains(i))
+            .collect();
+        let mut bbs_disclosed_indexes: Vec<usize> = selective_indexes
+            .iter()
+            .filter_map(|i| non_mandatory_indexes.iter().position(|j| j == i))
+            .collect();
+        bbs_disclosed_indexes.sort_unstable();
+        bbs_disclosed_indexes.dedup();
+
+        let mut combined_indexes: Vec<usize> = mandatory_indexes
+            .iter()
+            .chain(selective_indexes)
+            .copied()
+            .collect();
+        combined_indexes.sort_unstable();
+        combined_indexes.dedup();
+        let mut relative_mandatory_indexes: Vec<usize> = mandatory_indexes
+            .iter()
+            .filter_map(|i| combined_indexes.iter().position(|j| j == i))
+            .collect();
+        relative_mandatory_indexes.sort_unstable();
+        relative_mandatory_indexes.dedup();
+
+        let presentation_header = presentation_header.unwrap_or(&[]).to_vec();
+        let bbs_proof = PoKSignature::<BBSplus<CS>>::proof_gen_with_rng(
+            &base_proof.public_key,
+            &base_proof.bbs_signature,
+            Some(&base_proof.bbs_header),
+            Some(&presentation_header),
+            Some(&messages),
+            Some(&bbs_disclosed_indexes),
+            rng,
+        )?;
+
+        Ok(Self {
+            bbs_proof: bbs_proof.to_bytes(),
+            label_map,
+            mandatory_indexes: relative_mandatory_indexes,
+            selective_indexes: bbs_disclosed_indexes,
+            presentation_header,
+        })
+    }
+
+    /// # Description
+    /// Verifies the derived proof against the statements of the revealed document.
+    ///
+    /// # Inputs:
+    /// * `pk` (REQUIRED), the public key of the Issuer.
+    /// * `proof_config` (REQUIRED), the canonical proof configuration.
+    /// * `statements` (REQUIRED), the sorted canonical statements of the revealed document, relabeled with [`Self::label_map`].
+    ///
+    /// # Output:
+    /// a result: [`Ok`] or [`Error`].
+    ///
+    pub fn verify(
+        &self,
+        pk: &BBSplusPublicKey,
+        proof_config: &str,
+        statements: &[String],
+    ) -> Result<(), Error> {
+        let (mandatory, disclosed_messages) =
+            group_statements(statements, &self.mandatory_indexes)?;
+        let bbs_header = bbs_header(proof_config, &mandatory);
+        PoKSignature::<BBSplus<CS>>::from_bytes(&self.bbs_proof)?.proof_verify(
+            pk,
+            Some(&disclosed_messages),
+            Some(&self.selective_indexes),
+            Some(&bbs_header),
+            Some(&self.presentation_header),
+        )
+    }
+
+    /// Encodes the derived proof as the `proofValue` of a Data Integrity proof
+    pub fn to_proof_value(&self) -> Result<String, Error> {
+        let parse_label = |label: &str, prefix: &str| {
+            label
+                .strip_prefix(prefix)
+                .and_then(|n| n.parse::<u64>().ok())
+                .map(Value::Unsigned)
+                .ok_or_else(|| Error::ProofGenError(format!("Invalid blank node label {}", label)))
+        };
+        let compressed_label_map = self
+            .label_map
+            .iter()
+            .map(|(k, v)| Ok((parse_label(k, "c14n")?, parse_label(v, "b")?)))
+            .collect::<Result<_, Error>>()?;
+
+        Ok(encode_proof_value(
+            DERIVED_PROOF_TAG,
+            vec![
+                Value::Bytes(self.bbs_proof.clone()),
+                Value::Map(compressed_label_map),
+                indexes_to_cbor(&self.mandatory_indexes),
+                indexes_to_cbor(&self.selective_indexes),
+                Value::Bytes(self.presentation_header.clone()),
+            ],
+        ))
+    }
+
+    pub fn from_proof_value(proof_value: &str) -> Result<Self, Error> {
+        let mut components = decode_proof_value(proof_value, DERIVED_PROOF_TAG, 5)?.into_iter();
+        let mut next = || components.next().unwrap();
+        let bbs_proof = next().into_bytes()?;
+        let label_map = next()
+            .into_map()?
+            .into_iter()
+            .map(|(k, v)| {
+                Ok((
+                    format!("c14n{}", k.into_unsigned()?),
+                    format!("b{}", v.into_unsigned()?),
+                ))
+            })
+            .collect::<Result<_, Error>>()?;
+        let mandatory_indexes = indexes_from_cbor(next())?;
+        let selective_indexes = indexes_from_cbor(next())?;
+        let presentation_header = next().into_bytes()?;
+
+        Ok(Self {
+            bbs_proof,
+            label_map,
+            mandatory_indexes,
+            selective_indexes,
+            presentation_header,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{shuffled_label_map, BaseProof, DerivedProof, HMAC_KEY_LENGTH};
+    use crate::{
+        bbsplus::ciphersuites::Bls12381Sha256, keys::pair::KeyPair, schemes::algorithms::BBSplus,
+    };
+    use alloc::collections::BTreeMap;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    const PROOF_CONFIG: &str = "_:c14n0 <http://purl.org/dc/terms/created> \"2023-08-15T23:36:38Z\"^^<http://www.w3.org/2001/XMLSchema#dateTime> .\n_:c14n0 <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <https://w3id.org/security#DataIntegrityProof> .\n_:c14n0 <https://w3id.org/security#cryptosuite> \"bbs-2023\"^^<https://w3id.org/security#cryptosuiteString> .\n";
+    const HMAC_KEY: [u8; HMAC_KEY_LENGTH] = [0x42; HMAC_KEY_LENGTH];
+    const MANDATORY_INDEXES: [usize; 2] = [0, 3];
+
+    fn statements() -> Vec<String> {
+        [
+            "<did:example:1234> <https://schema.org/issuer> <did:example:issuer> .\n",
+            "_:b0 <https://schema.org/name> \"Alice\" .\n",
+            "_:b0 <https://schema.org/birthDate> \"1990-01-01\" .\n",
+            "_:b1 <https://schema.org/credentialSubject> _:b0 .\n",
+            "_:b1 <https://schema.org/nationality> \"IT\" .\n",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect()
+    }
+
+    fn base_proof() -> BaseProof {
+        let keypair =
+            KeyPair::<BBSplus<Bls12381Sha256>>::random_with_rng(&mut StdRng::seed_from_u64(1))
+                .unwrap();
+        BaseProof::create(
+            keypair.private_key(),
+            keypair.public_key(),
+            &HMAC_KEY,
+            PROOF_CONFIG,
+            &statements(),
+            &MANDATORY_INDEXES,
+            vec!["/issuer".to_owned(), "/credentialSubject".to_owned()],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn shuffled_labels() {
+        let labels = ["c14n0", "c14n1", "c14n2"];
+        let label_map = shuffled_label_map(&HMAC_KEY, &labels);
+        assert_eq!(label_map, shuffled_label_map(&HMAC_KEY, &labels));
+        let mut shuffled: Vec<&String> = label_map.values().collect();
+        shuffled.sort();
+        assert_eq!(shuffled, ["b0", "b1", "b2"]);
+        assert_ne!(
+            label_map,
+            shuffled_label_map(&[0x43; HMAC_KEY_LENGTH], &labels)
+        );
+    }
+
+    #[test]
+    fn base_proof_value() {
+        let base_proof = base_proof();
+        base_proof
+            .verify(PROOF_CONFIG, &statements(), &MANDATORY_INDEXES)
+            .unwrap();
+        assert!(base_proof
+            .verify(PROOF_CONFIG, &statements(), &[0])
+            .is_err());
+
+        let proof_value = base_proof.to_proof_value();
+        // Tag 0x5d02 followed by an array of 5 components, the first one an 80 bytes signature
+        assert!(proof_value.starts_with("u2V0ChVhQ"));
+        assert_eq!(
+            BaseProof::from_proof_value(&proof_value).unwrap(),
+            base_proof
+        );
+        assert!(!format!("{:?}", base_proof).contains(&format!("{:?}", HMAC_KEY)));
+        assert!(DerivedProof::from_proof_value(&proof_value).is_err());
+    }
+
+    #[test]
+    fn derived_proof_value() {
+        let base_proof = BaseProof::from_proof_value(&base_proof().to_proof_value()).unwrap();
+        let statements = statements();
+        // The Holder reveals the nationality together with the mandatory statements
+        let selective_indexes = [3, 4];
+        let label_map = BTreeMap::from([
+            ("c14n0".to_owned(), "b1".to_owned()),
+            ("c14n1".to_owned(), "b0".to_owned()),
+        ]);
+        let derived_proof = DerivedProof::create_with_rng(
+            &base_proof,
+            &statements,
+            &MANDATORY_INDEXES,
+            &selective_indexes,
+            label_map,
+            Some(b"nonce"),
+            &mut StdRng::seed_from_u64(2),
+        )
+        .unwrap();
+        assert_eq!(derived_proof.mandatory_indexes, [0, 1]);
+        assert_eq!(derived_proof.selective_indexes, [2]);
+
+        let proof_value = derived_proof.to_proof_value().unwrap();
+        assert!(proof_value.starts_with("u2V0D"));
+        let received = DerivedProof::from_proof_value(&proof_value).unwrap();
+        assert_eq!(received, derived_proof);
+
+        let revealed: Vec<String> = [0, 3, 4].iter().map(|&i| statements[i].clone()).collect();
+        received
+            .verify(&base_proof.public_key, PROOF_CONFIG, &revealed)
+            .unwrap();
+
+        let mut tampered = revealed.clone();
+        tampered[2] = "_:b1 <https://schema.org/nationality> \"FR\" .\n".to_owned();
+        assert!(received
+            .verify(&base_proof.public_key, PROOF_CONFIG, &tampered)
+            .is_err());
+        let mut other_nonce = received.clone();
+        other_nonce.presentation_header = b"other".to_vec();
+        assert!(other_nonce
+            .verify(&base_proof.public_key, PROOF_CONFIG, &revealed)
+            .is_err());
+    }
+}
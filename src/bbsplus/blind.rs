@@ -15,6 +15,7 @@
 use super::{
     commitment::BlindFactor,
     keys::{BBSplusPublicKey, BBSplusSecretKey},
+    pseudonym::ProverNym,
     signature::{core_verify, BBSplusSignature},
 };
 use crate::{
@@ -29,6 +30,7 @@ use crate::{
         util::bbsplus_utils::{calculate_domain, hash_to_scalar, ScalarExt},
     },
 };
+use alloc::{borrow::ToOwned, vec::Vec};
 use bls12_381_plus::{G1Projective, Scalar};
 use elliptic_curve::hash2curve::ExpandMsg;
 
@@ -38,6 +40,8 @@ impl<CS: BbsCiphersuite> BlindSignature<BBSplus<CS>> {
     /// # Description
     /// This operation returns a BBS blind signature from a secret key (SK), over a header, a set of messages and optionally a commitment value. If supplied, the commitment value must be accompanied by its proof of correctness (commitment_with_proof). The issuer can also further randomize the supplied commitment, by supplying a random scalar (signer_blind)
     ///
+    /// For pseudonym issuance, the commitment MUST contain the `prover_nym` as the last committed message (see [`Commitment::commit`]) and the Signer supplies a random scalar (`signer_nym_entropy`) that is added to it. The resulting `nym_secret` (see [`ProverNym::nym_secret`]) is signed without being known by the Signer. In that case the [`BbsCiphersuite::API_ID_BLIND_NYM`] api_id is used.
+    ///
     /// # Inputs:
     /// * `sk` (REQUIRED), a secret key
     /// * `pk` (REQUIRED), a public key
@@ -45,6 +49,7 @@ impl<CS: BbsCiphersuite> BlindSignature<BBSplus<CS>> {
     /// * `header` (OPTIONAL), an octet string containing context and application specific information.
     /// * `messages` (OPTIONAL), a vector of octet strings. If not supplied, it defaults to the empty array.
     /// * `signer_blind` (OPTIONAL), a random scalar value ([`BlindFactor`]) to further randomize the supplied commitment.
+    /// * `signer_nym_entropy` (OPTIONAL), a random scalar value ([`BlindFactor`]) added to the committed `prover_nym`.
     ///
     /// # Output:
     /// a [`BlindSignature::BBSplus`] or [`Error`].
@@ -56,7 +61,68 @@ impl<CS: BbsCiphersuite> BlindSignature<BBSplus<CS>> {
         header: Option<&[u8]>,
         messages: Option<&[Vec<u8>]>,
         signer_blind: Option<&BlindFactor>,
+        signer_nym_entropy: Option<&BlindFactor>,
+    ) -> Result<Self, Error> {
+        Self::blind_sign_internal(
+            sk,
+            pk,
+            commitment_with_proof,
+            header,
+            messages,
+            signer_blind,
+            signer_nym_entropy,
+            None,
+        )
+    }
+
+    /// # Description
+    /// Same as [`Self::blind_sign`], but using prebuilt generators (e.g., obtained from a [`crate::bbsplus::generators::GeneratorsCache`]) instead of creating them at each call.
+    ///
+    /// # Inputs:
+    /// * `generators` (REQUIRED), at least L+1 [`Generators`] created with `api_id` equal to [`BbsCiphersuite::API_ID_BLIND`] (or [`BbsCiphersuite::API_ID_BLIND_NYM`] if `signer_nym_entropy` is supplied), where L is the number of messages.
+    /// * `blind_generators` (REQUIRED), at least M+1 [`Generators`] created with `api_id` equal to "BLIND_" followed by the `api_id` of `generators`, where M is the number of committed messages.
+    /// * the other inputs are the same as [`Self::blind_sign`].
+    ///
+    /// # Output:
+    /// a [`BlindSignature::BBSplus`] or [`Error`].
+    ///
+    pub fn blind_sign_with_generators(
+        sk: &BBSplusSecretKey,
+        pk: &BBSplusPublicKey,
+        commitment_with_proof: Option<&[u8]>,
+        header: Option<&[u8]>,
+        messages: Option<&[Vec<u8>]>,
+        signer_blind: Option<&BlindFactor>,
+        signer_nym_entropy: Option<&BlindFactor>,
+        generators: &Generators,
+        blind_generators: &Generators,
+    ) -> Result<Self, Error> {
+        Self::blind_sign_internal(
+            sk,
+            pk,
+            commitment_with_proof,
+            header,
+            messages,
+            signer_blind,
+            signer_nym_entropy,
+            Some((generators, blind_generators)),
+        )
+    }
+
+    fn blind_sign_internal(
+        sk: &BBSplusSecretKey,
+        pk: &BBSplusPublicKey,
+        commitment_with_proof: Option<&[u8]>,
+        header: Option<&[u8]>,
+        messages: Option<&[Vec<u8>]>,
+        signer_blind: Option<&BlindFactor>,
+        signer_nym_entropy: Option<&BlindFactor>,
+        generators: Option<(&Generators, &Generators)>,
     ) -> Result<Self, Error> {
+        let api_id = match signer_nym_entropy {
+            Some(_) => CS::API_ID_BLIND_NYM,
+            None => CS::API_ID_BLIND,
+        };
         let messages = messages.unwrap_or(&[]);
         let L = messages.len();
         let commitment_with_proof = commitment_with_proof.unwrap_or(&[]);
@@ -74,11 +140,19 @@ impl<CS: BbsCiphersuite> BlindSignature<BBSplus<CS>> {
                 .ok_or(Error::InvalidCommitmentProof)?;
         }
 
-        let generators = Generators::create::<CS>(L + 1, Some(CS::API_ID_BLIND));
-        let blind_generators =
-            Generators::create::<CS>(M + 1, Some(&[b"BLIND_", CS::API_ID_BLIND].concat()));
+        let blind_api_id = [b"BLIND_", api_id].concat();
+        let (generators, blind_generators) = match generators {
+            Some((generators, blind_generators)) => (
+                generators.first::<CS>(L + 1, api_id)?,
+                blind_generators.first::<CS>(M + 1, &blind_api_id)?,
+            ),
+            None => (
+                Generators::create::<CS>(L + 1, Some(api_id)),
+                Generators::create::<CS>(M + 1, Some(&blind_api_id)),
+            ),
+        };
 
-        let message_scalars = BBSplusMessage::messages_to_scalar::<CS>(messages, CS::API_ID_BLIND)?;
+        let message_scalars = BBSplusMessage::messages_to_scalar::<CS>(messages, api_id)?;
 
         let blind_sig = core_blind_sign::<CS>(
             sk,
@@ -89,7 +163,8 @@ impl<CS: BbsCiphersuite> BlindSignature<BBSplus<CS>> {
             header,
             &message_scalars,
             signer_blind,
-            Some(CS::API_ID_BLIND),
+            signer_nym_entropy,
+            Some(api_id),
         )?;
 
         Ok(Self::BBSplus(blind_sig))
@@ -109,6 +184,7 @@ impl<CS: BbsCiphersuite> BlindSignature<BBSplus<CS>> {
     /// * `committed_messages` (OPTIONAL), a vector of octet strings messages committed by the Prover.
     /// * `secret_prover_blind` (OPTIONAL), a scalar value ([`BlindFactor`]).
     /// * `signer_blind` (OPTIONAL), a scalar value ([`BlindFactor`]).
+    /// * `nym_secret` (OPTIONAL), the `nym_secret` ([`ProverNym::nym_secret`]) signed as the last committed message, for signatures issued with a `signer_nym_entropy`.
     ///
     /// # Output:
     /// a result: [`Ok`] or [`Error`].
@@ -121,16 +197,72 @@ impl<CS: BbsCiphersuite> BlindSignature<BBSplus<CS>> {
         committed_messages: Option<&[Vec<u8>]>,
         secret_prover_blind: Option<&BlindFactor>,
         signer_blind: Option<&BlindFactor>,
+        nym_secret: Option<&ProverNym>,
     ) -> Result<(), Error> {
-        let messages = messages.unwrap_or(&[]);
-        let committed_messages = committed_messages.unwrap_or(&[]);
-        let api_id = CS::API_ID_BLIND;
+        self.verify_internal(
+            pk,
+            header,
+            messages,
+            committed_messages,
+            secret_prover_blind,
+            signer_blind,
+            nym_secret,
+            None,
+        )
+    }
 
-        let L = messages.len();
-        let M = committed_messages.len();
+    /// # Description
+    /// Same as [`Self::verify`], but using prebuilt generators (e.g., obtained from a [`crate::bbsplus::generators::GeneratorsCache`]) instead of creating them at each call.
+    ///
+    /// # Inputs:
+    /// * `generators` (REQUIRED), at least L+1 [`Generators`] created with `api_id` equal to [`BbsCiphersuite::API_ID_BLIND`] (or [`BbsCiphersuite::API_ID_BLIND_NYM`] if `nym_secret` is supplied), where L is the number of messages.
+    /// * `blind_generators` (REQUIRED), at least M+1 [`Generators`] created with `api_id` equal to "BLIND_" followed by the `api_id` of `generators`, where M is the number of committed messages.
+    /// * the other inputs are the same as [`Self::verify`].
+    ///
+    /// # Output:
+    /// a result: [`Ok`] or [`Error`].
+    ///
+    pub fn verify_with_generators(
+        &self,
+        pk: &BBSplusPublicKey,
+        header: Option<&[u8]>,
+        messages: Option<&[Vec<u8>]>,
+        committed_messages: Option<&[Vec<u8>]>,
+        secret_prover_blind: Option<&BlindFactor>,
+        signer_blind: Option<&BlindFactor>,
+        nym_secret: Option<&ProverNym>,
+        generators: &Generators,
+        blind_generators: &Generators,
+    ) -> Result<(), Error> {
+        self.verify_internal(
+            pk,
+            header,
+            messages,
+            committed_messages,
+            secret_prover_blind,
+            signer_blind,
+            nym_secret,
+            Some((generators, blind_generators)),
+        )
+    }
 
-        let generators = Generators::create::<CS>(L + 1, Some(api_id));
-        let blind_generators = Generators::create::<CS>(M + 1, Some(&[b"BLIND_", api_id].concat()));
+    fn verify_internal(
+        &self,
+        pk: &BBSplusPublicKey,
+        header: Option<&[u8]>,
+        messages: Option<&[Vec<u8>]>,
+        committed_messages: Option<&[Vec<u8>]>,
+        secret_prover_blind: Option<&BlindFactor>,
+        signer_blind: Option<&BlindFactor>,
+        nym_secret: Option<&ProverNym>,
+        generators: Option<(&Generators, &Generators)>,
+    ) -> Result<(), Error> {
+        let messages = messages.unwrap_or(&[]);
+        let committed_messages = committed_messages.unwrap_or(&[]);
+        let api_id = match nym_secret {
+            Some(_) => CS::API_ID_BLIND_NYM,
+            None => CS::API_ID_BLIND,
+        };
 
         let message_scalars = BBSplusMessage::messages_to_scalar::<CS>(messages, api_id)?;
 
@@ -138,8 +270,26 @@ impl<CS: BbsCiphersuite> BlindSignature<BBSplus<CS>> {
             secret_prover_blind.map_or(Scalar::ZERO, |b| b.0)
                 + signer_blind.map_or(Scalar::ZERO, |b| b.0),
         );
-        let committed_message_scalars =
+        let mut committed_message_scalars =
             BBSplusMessage::messages_to_scalar::<CS>(committed_messages, api_id)?;
+        if let Some(nym_secret) = nym_secret {
+            committed_message_scalars.push(BBSplusMessage::new(nym_secret.0));
+        }
+
+        let L = message_scalars.len();
+        let M = committed_message_scalars.len();
+
+        let blind_api_id = [b"BLIND_", api_id].concat();
+        let (generators, blind_generators) = match generators {
+            Some((generators, blind_generators)) => (
+                generators.first::<CS>(L + 1, api_id)?,
+                blind_generators.first::<CS>(M + 1, &blind_api_id)?,
+            ),
+            None => (
+                Generators::create::<CS>(L + 1, Some(api_id)),
+                Generators::create::<CS>(M + 1, Some(&blind_api_id)),
+            ),
+        };
 
         let tmp_messages = [
             &*message_scalars,
@@ -201,6 +351,7 @@ impl<CS: BbsCiphersuite> BlindSignature<BBSplus<CS>> {
 /// * `header` (OPTIONAL), an octet string containing context and application specific information.
 /// * `messages` (REQUIRED), a vector of scalar values ([`BBSplusMessage`]). It could be empty.
 /// * `signer_blind` (OPTIONAL), a random scalar value ([`BlindFactor`]).
+/// * `signer_nym_entropy` (OPTIONAL), a random scalar value ([`BlindFactor`]) added to the last committed message (the `prover_nym`).
 /// * `api_id` (OPTIONAL), an octet string ([`BbsCiphersuite::API_ID_BLIND`])
 ///
 /// # Output:
@@ -215,13 +366,14 @@ fn core_blind_sign<CS>(
     header: Option<&[u8]>,
     messages: &[BBSplusMessage],
     signer_blind: Option<&BlindFactor>,
+    signer_nym_entropy: Option<&BlindFactor>,
     api_id: Option<&[u8]>,
 ) -> Result<BBSplusSignature, Error>
 where
     CS: BbsCiphersuite,
     CS::Expander: for<'a> ExpandMsg<'a>,
 {
-    let signer_blind = signer_blind.unwrap_or(&BlindFactor(Scalar::ZERO));
+    let signer_blind = signer_blind.map_or(Scalar::ZERO, |b| b.0);
     let api_id = api_id.unwrap_or(b"");
     let signature_dst = [api_id, CS::H2S].concat();
 
@@ -251,7 +403,7 @@ where
     let tmp_generators = [
         &generators.values[1..],
         core::slice::from_ref(&Q2),
-        &blind_generators
+        blind_generators
             .values
             .get(1..blind_generators.values.len() - 1)
             .unwrap_or_default(),
@@ -262,8 +414,11 @@ where
     let mut e_octs: Vec<u8> = Vec::new();
     e_octs.extend_from_slice(&sk.to_bytes());
     e_octs.extend_from_slice(commitment_with_proof);
-    if signer_blind.0 != Scalar::ZERO {
-        e_octs.extend_from_slice(&signer_blind.to_bytes());
+    if signer_blind != Scalar::ZERO {
+        e_octs.extend_from_slice(&signer_blind.to_be_bytes());
+    }
+    if let Some(signer_nym_entropy) = signer_nym_entropy {
+        e_octs.extend_from_slice(&signer_nym_entropy.to_bytes());
     }
     messages
         .iter()
@@ -272,8 +427,23 @@ where
     e_octs.extend_from_slice(&domain.to_bytes_be());
 
     let e = hash_to_scalar::<CS>(&e_octs, &signature_dst)?; //TODO: Not sure where the Signature DST ("BBS_BLS12381G1_XMD:SHA-256_SSWU_RO_H2G_HM2S_SIGNATURE_MOCK_RANDOM_SCALARS_DST_") in the fixtures is used
-    if signer_blind.0 != Scalar::ZERO {
-        commit += Q2 * signer_blind.0;
+    if signer_blind != Scalar::ZERO {
+        commit += Q2 * signer_blind;
+    }
+    if let Some(signer_nym_entropy) = signer_nym_entropy {
+        // the prover_nym is the last committed message, blind_generators also contains an implied generator (see above)
+        if commitment_with_proof.is_empty() {
+            return Err(Error::BlindSignError(
+                "A commitment to the prover_nym is required".to_owned(),
+            ));
+        }
+        let J_nym = blind_generators
+            .values
+            .len()
+            .checked_sub(2)
+            .and_then(|i| blind_generators.values.get(i))
+            .ok_or(Error::NotEnoughGenerators)?;
+        commit += J_nym * signer_nym_entropy.0;
     }
 
     let mut B = generators.g1_base_point + Q1 * domain;
@@ -296,13 +466,16 @@ mod tests {
         bbsplus::{
             ciphersuites::BbsCiphersuite,
             commitment::BlindFactor,
+            generators::GeneratorsCache,
             keys::{BBSplusPublicKey, BBSplusSecretKey},
         },
+        errors::Error,
         schemes::{
             algorithms::{BBSplus, BbsBls12381Sha256, BbsBls12381Shake256, Scheme},
             generics::BlindSignature,
         },
     };
+    use bls12_381_plus::{G1Projective, Scalar};
     use elliptic_curve::hash2curve::ExpandMsg;
     use std::fs;
 
@@ -360,10 +533,8 @@ mod tests {
             .as_str()
             .map(|c| hex::decode(c).unwrap());
 
-        let committed_messages: Option<Vec<Vec<u8>>> = match committed_messages {
-            Some(cm) => Some(cm.iter().map(|m| hex::decode(m).unwrap()).collect()),
-            None => None,
-        };
+        let committed_messages: Option<Vec<Vec<u8>>> =
+            committed_messages.map(|cm| cm.iter().map(|m| hex::decode(m).unwrap()).collect());
 
         let signer_blind: Option<[u8; 32]> = match proof_json["signerBlind"] {
             serde_json::Value::Null => None,
@@ -391,12 +562,13 @@ mod tests {
             Some(&header),
             Some(&messages),
             signer_blind.as_ref(),
+            None,
         )
         .unwrap();
         let expected_signature = proof_json["signature"].as_str().unwrap();
         let signature_oct = signature.to_bytes();
 
-        assert_eq!(hex::encode(&signature_oct), expected_signature);
+        assert_eq!(hex::encode(signature_oct), expected_signature);
 
         let result = signature
             .verify(
@@ -406,11 +578,63 @@ mod tests {
                 committed_messages.as_deref(),
                 prover_blind.as_ref(),
                 signer_blind.as_ref(),
+                None,
             )
             .is_ok();
 
         let expected_result = proof_json["result"]["valid"].as_bool().unwrap();
 
         assert_eq!(result, expected_result);
+
+        // prebuilt generators
+        let api_id = <S::Ciphersuite as BbsCiphersuite>::API_ID_BLIND;
+        let cache = GeneratorsCache::<S::Ciphersuite>::new();
+        // number of committed messages, computed as in `blind_sign`
+        let M = commitment_with_proof.as_ref().map_or(0, |c| {
+            (c.len() - G1Projective::COMPRESSED_BYTES - Scalar::BYTES) / Scalar::BYTES
+        });
+        let generators = cache.get(messages.len() + 1, Some(api_id));
+        let blind_generators = cache.get(M + 1, Some(&[b"BLIND_", api_id].concat()));
+
+        let sign_with_generators = |generators, blind_generators| {
+            BlindSignature::<BBSplus<S::Ciphersuite>>::blind_sign_with_generators(
+                &sk,
+                &pk,
+                commitment_with_proof.as_deref(),
+                Some(&header),
+                Some(&messages),
+                signer_blind.as_ref(),
+                None,
+                generators,
+                blind_generators,
+            )
+        };
+        let signature = sign_with_generators(&generators, &blind_generators).unwrap();
+        assert_eq!(hex::encode(signature.to_bytes()), expected_signature);
+
+        let result = signature
+            .verify_with_generators(
+                &pk,
+                Some(&header),
+                Some(&messages),
+                committed_messages.as_deref(),
+                prover_blind.as_ref(),
+                signer_blind.as_ref(),
+                None,
+                &generators,
+                &blind_generators,
+            )
+            .is_ok();
+        assert_eq!(result, expected_result);
+
+        // generators created for another api_id are rejected
+        let other_generators = cache.get(
+            messages.len() + 1,
+            Some(<S::Ciphersuite as BbsCiphersuite>::API_ID),
+        );
+        assert!(matches!(
+            sign_with_generators(&other_generators, &blind_generators),
+            Err(Error::CiphersuiteMismatch)
+        ));
     }
 }
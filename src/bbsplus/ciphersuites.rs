@@ -22,6 +22,8 @@ pub trait BbsCiphersuite: Eq + 'static + Ciphersuite {
     const ID: &'static [u8];
     const API_ID: &'static [u8]; // ciphersuite_id || "H2G_HM2S_"
     const API_ID_BLIND: &'static [u8];
+    const API_ID_NYM: &'static [u8]; // ciphersuite_id || "H2G_HM2S_PSEUDONYM_"
+    const API_ID_BLIND_NYM: &'static [u8]; // ciphersuite_id || "BLIND_H2G_HM2S_PSEUDONYM_"
     const COMMIT_DST: &'static [u8];
     const BLIND_PROOF_DST: &'static [u8];
     const KEYGEN_DST: &'static [u8] = b"KEYGEN_DST_";
@@ -30,6 +32,9 @@ pub trait BbsCiphersuite: Eq + 'static + Ciphersuite {
     const GENERATOR_DST: &'static [u8] = b"SIG_GENERATOR_DST_";
     const MAP_MSG_SCALAR: &'static [u8] = b"MAP_MSG_TO_SCALAR_AS_HASH_";
     const H2S: &'static [u8] = b"H2S_";
+    const NYM_OP_DST: &'static [u8] = b"OP_";
+    const RANGE_PROOF_DST: &'static [u8] = b"RANGE_PROOF_";
+    const POP_DST: &'static [u8] = b"POP_";
     const MOCKED_SCALAR_DST: &'static [u8];
     const SEED_MOCKED_SCALAR: &'static [u8] = b"3.141592653589793238462643383279";
 
@@ -52,6 +57,9 @@ impl BbsCiphersuite for Bls12381Shake256 {
     const MOCKED_SCALAR_DST: &'static [u8] =
         b"BBS_BLS12381G1_XOF:SHAKE-256_SSWU_RO_H2G_HM2S_MOCK_RANDOM_SCALARS_DST_";
     const API_ID_BLIND: &'static [u8] = b"BBS_BLS12381G1_XOF:SHAKE-256_SSWU_RO_BLIND_H2G_HM2S_";
+    const API_ID_NYM: &'static [u8] = b"BBS_BLS12381G1_XOF:SHAKE-256_SSWU_RO_H2G_HM2S_PSEUDONYM_";
+    const API_ID_BLIND_NYM: &'static [u8] =
+        b"BBS_BLS12381G1_XOF:SHAKE-256_SSWU_RO_BLIND_H2G_HM2S_PSEUDONYM_";
     const COMMIT_DST: &'static [u8] =
         b"BBS_BLS12381G1_XOF:SHAKE-256_SSWU_RO_H2G_HM2S_COMMIT_MOCK_RANDOM_SCALARS_DST_";
     const BLIND_PROOF_DST: &'static [u8] =
@@ -68,6 +76,9 @@ impl BbsCiphersuite for Bls12381Sha256 {
     const MOCKED_SCALAR_DST: &'static [u8] =
         b"BBS_BLS12381G1_XMD:SHA-256_SSWU_RO_H2G_HM2S_MOCK_RANDOM_SCALARS_DST_";
     const API_ID_BLIND: &'static [u8] = b"BBS_BLS12381G1_XMD:SHA-256_SSWU_RO_BLIND_H2G_HM2S_";
+    const API_ID_NYM: &'static [u8] = b"BBS_BLS12381G1_XMD:SHA-256_SSWU_RO_H2G_HM2S_PSEUDONYM_";
+    const API_ID_BLIND_NYM: &'static [u8] =
+        b"BBS_BLS12381G1_XMD:SHA-256_SSWU_RO_BLIND_H2G_HM2S_PSEUDONYM_";
     const COMMIT_DST: &'static [u8] =
         b"BBS_BLS12381G1_XMD:SHA-256_SSWU_RO_H2G_HM2S_COMMIT_MOCK_RANDOM_SCALARS_DST_";
     const BLIND_PROOF_DST: &'static [u8] =
@@ -12,27 +12,31 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use super::proof::BBSplusZKPoK;
+use super::{proof::BBSplusZKPoK, pseudonym::ProverNym};
 use crate::{
     bbsplus::{ciphersuites::BbsCiphersuite, generators::Generators},
     errors::Error,
     schemes::{algorithms::BBSplus, generics::Commitment},
     utils::{
+        cbor::{decode_with_ciphersuite, encode_with_ciphersuite},
         message::bbsplus_message::BBSplusMessage,
         util::bbsplus_utils::{
-            calculate_blind_challenge, get_random, parse_g1_projective, ScalarExt,
+            calculate_blind_challenge, get_random_with_rng, parse_g1_projective, ScalarExt,
         },
     },
 };
+use alloc::vec::Vec;
 use bls12_381_plus::group::Curve;
 use bls12_381_plus::{G1Projective, Scalar};
+use core::fmt;
 use elliptic_curve::hash2curve::ExpandMsg;
+#[cfg(feature = "std")]
+use rand::thread_rng;
+use rand::{CryptoRng, RngCore};
 use serde::{Deserialize, Serialize};
+use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
 
-#[cfg(not(test))]
-use crate::utils::util::bbsplus_utils::calculate_random_scalars;
-#[cfg(test)]
-use crate::utils::util::bbsplus_utils::seeded_random_scalars;
+use crate::utils::util::bbsplus_utils::Randomness;
 
 #[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
 pub struct BBSplusCommitment {
@@ -50,6 +54,9 @@ impl BBSplusCommitment {
     }
 
     pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        if bytes.len() < G1Projective::COMPRESSED_BYTES {
+            return Err(Error::InvalidCommitment);
+        }
         let commitment = parse_g1_projective(&bytes[0..G1Projective::COMPRESSED_BYTES])
             .map_err(|_| Error::InvalidCommitment)?;
         let proof = BBSplusZKPoK::from_bytes(&bytes[G1Projective::COMPRESSED_BYTES..])
@@ -63,18 +70,91 @@ impl<CS: BbsCiphersuite> Commitment<BBSplus<CS>> {
     /// # Description
     /// This operation is used by the Prover to create a commitment to a set of messages (committed_messages), that they intend to include to the blind signature. Note that this operation returns both the serialized combination of the commitment and its proof of correctness (commitment_with_proof), as well as the random scalar used to blind the commitment (secret_prover_blind).
     ///
+    /// If a `prover_nym` is supplied, it is committed as the last committed message, so that the Signer never learns the secret used to compute the pseudonyms of the Prover (see [`BlindSignature::blind_sign`](crate::schemes::generics::BlindSignature::blind_sign)). In that case the [`BbsCiphersuite::API_ID_BLIND_NYM`] api_id is used.
+    ///
     /// # Inputs:
     /// * `committed_messages` (OPTIONAL), a vector of octet strings. If not supplied it defaults to the empty array.
+    /// * `prover_nym` (OPTIONAL), the Prover secret ([`ProverNym`]).
+    ///
+    /// # Output:
+    /// ([`Commitment::BBSplus`], [`BlindFactor`]), a tuple (**`commitment_with_proof`**, **`secret_prover_blind`**) or [`Error`].
+    ///
+    #[cfg(feature = "std")]
+    pub fn commit(
+        committed_messages: Option<&[Vec<u8>]>,
+        prover_nym: Option<&ProverNym>,
+    ) -> Result<(Self, BlindFactor), Error>
+    where
+        CS::Expander: for<'a> ExpandMsg<'a>,
+    {
+        Self::commit_with_rng(committed_messages, prover_nym, &mut thread_rng())
+    }
+
+    /// # Description
+    /// Same as [`Self::commit`], drawing the `secret_prover_blind` and the blindings of the proof of correctness from `rng`.
+    ///
+    /// # Inputs:
+    /// * `rng` (REQUIRED), a cryptographically secure random number generator.
+    /// * the other inputs are the same as [`Self::commit`].
+    ///
+    /// # Output:
+    /// ([`Commitment::BBSplus`], [`BlindFactor`]), a tuple (**`commitment_with_proof`**, **`secret_prover_blind`**) or [`Error`].
+    ///
+    pub fn commit_with_rng<R: RngCore + CryptoRng>(
+        committed_messages: Option<&[Vec<u8>]>,
+        prover_nym: Option<&ProverNym>,
+        rng: &mut R,
+    ) -> Result<(Self, BlindFactor), Error>
+    where
+        CS::Expander: for<'a> ExpandMsg<'a>,
+    {
+        let api_id = match prover_nym {
+            Some(_) => CS::API_ID_BLIND_NYM,
+            None => CS::API_ID_BLIND,
+        };
+        let (commitment_with_proof, secret) = commit::<CS, _>(
+            committed_messages,
+            prover_nym,
+            Some(api_id),
+            Randomness::Rng(rng),
+        )?;
+        Ok((Self::BBSplus(commitment_with_proof), secret))
+    }
+
+    /// # Description
+    /// Same as [`Self::commit`], but the random scalars are replaced by the mocked random scalars computed from `seed` and `dst`, as done to produce the fixtures of the draft. The resulting `secret_prover_blind` is predictable, so this MUST only be used for interoperability testing.
+    ///
+    /// # Inputs:
+    /// * `seed` (OPTIONAL), an octet string. If not supplied it defaults to [`BbsCiphersuite::SEED_MOCKED_SCALAR`].
+    /// * `dst` (OPTIONAL), an octet string. If not supplied it defaults to [`BbsCiphersuite::COMMIT_DST`].
+    /// * the other inputs are the same as [`Self::commit`].
     ///
     /// # Output:
     /// ([`Commitment::BBSplus`], [`BlindFactor`]), a tuple (**`commitment_with_proof`**, **`secret_prover_blind`**) or [`Error`].
     ///
-    pub fn commit(committed_messages: Option<&[Vec<u8>]>) -> Result<(Self, BlindFactor), Error>
+    #[cfg(any(test, feature = "test-vectors"))]
+    pub fn commit_with_mocked_scalars(
+        committed_messages: Option<&[Vec<u8>]>,
+        prover_nym: Option<&ProverNym>,
+        seed: Option<&[u8]>,
+        dst: Option<&[u8]>,
+    ) -> Result<(Self, BlindFactor), Error>
     where
         CS::Expander: for<'a> ExpandMsg<'a>,
     {
-        let (commitment_with_proof, secret) =
-            commit::<CS>(committed_messages, Some(CS::API_ID_BLIND))?;
+        let api_id = match prover_nym {
+            Some(_) => CS::API_ID_BLIND_NYM,
+            None => CS::API_ID_BLIND,
+        };
+        let (commitment_with_proof, secret) = commit::<CS, rand::rngs::ThreadRng>(
+            committed_messages,
+            prover_nym,
+            Some(api_id),
+            Randomness::Mocked {
+                seed: seed.unwrap_or(CS::SEED_MOCKED_SCALAR),
+                dst: dst.unwrap_or(CS::COMMIT_DST),
+            },
+        )?;
         Ok((Self::BBSplus(commitment_with_proof), secret))
     }
 
@@ -130,14 +210,30 @@ impl<CS: BbsCiphersuite> Commitment<BBSplus<CS>> {
     pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
         Ok(Self::BBSplus(BBSplusCommitment::from_bytes(bytes)?))
     }
+
+    /// Encodes the commitment with its proof as the CBOR array `[ciphersuite_id, commitment_with_proof]`, with the `ciphersuite_id` of `CS` as a text string and the commitment octets as a byte string
+    pub fn to_cbor(&self) -> Vec<u8> {
+        encode_with_ciphersuite(CS::ID, &self.to_bytes())
+    }
+
+    /// Decodes a commitment encoded with [`Self::to_cbor`], failing with [`Error::CiphersuiteMismatch`] if it was encoded for another ciphersuite
+    pub fn from_cbor(bytes: &[u8]) -> Result<Self, Error> {
+        Self::from_bytes(&decode_with_ciphersuite(CS::ID, bytes)?)
+    }
 }
 
-#[derive(Debug)]
+/// Secret blinding factor (`secret_prover_blind` or `signer_blind`).
 pub struct BlindFactor(pub(crate) Scalar);
 
 impl BlindFactor {
+    #[cfg(feature = "std")]
     pub fn random() -> Self {
-        Self(get_random())
+        Self::random_with_rng(&mut thread_rng())
+    }
+
+    /// Returns a random `secret_prover_blind` drawn from `rng`
+    pub fn random_with_rng<R: RngCore + CryptoRng>(rng: &mut R) -> Self {
+        Self(get_random_with_rng(rng))
     }
 
     pub fn to_bytes(&self) -> [u8; 32] {
@@ -149,6 +245,26 @@ impl BlindFactor {
     }
 }
 
+impl Zeroize for BlindFactor {
+    fn zeroize(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl Drop for BlindFactor {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for BlindFactor {}
+
+impl fmt::Debug for BlindFactor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("BlindFactor(<redacted>)")
+    }
+}
+
 /// https://datatracker.ietf.org/doc/html/draft-kalos-bbs-blind-signatures-01#name-commitment-computation
 ///
 /// # Description
@@ -156,36 +272,40 @@ impl BlindFactor {
 ///
 /// # Inputs:
 /// * `committed_messages` (OPTIONAL), a vector of octet strings. If not supplied it defaults to the empty array.
+/// * `prover_nym` (OPTIONAL), the Prover secret ([`ProverNym`]), committed after the `committed_messages`.
 /// * `api_id` (OPTIONAL), octet string. If not supplied it defaults to the empty octet string.
+/// * `randomness` (REQUIRED), the source of the random scalars ([`Randomness`]).
 ///
 /// # Output:
 /// ([`BBSplusCommitment`], [`BlindFactor`]), a tuple (commitment + proof, secret_prover_blind) or [`Error`].
 ///
-fn commit<CS>(
+fn commit<CS, R>(
     committed_messages: Option<&[Vec<u8>]>,
+    prover_nym: Option<&ProverNym>,
     api_id: Option<&[u8]>,
+    mut randomness: Randomness<R>,
 ) -> Result<(BBSplusCommitment, BlindFactor), Error>
 where
     CS: BbsCiphersuite,
+    R: RngCore + CryptoRng,
     CS::Expander: for<'a> ExpandMsg<'a>,
 {
     let committed_messages = committed_messages.unwrap_or(&[]);
     let api_id = api_id.unwrap_or(b"");
 
-    let M = committed_messages.len();
+    let mut commited_message_scalars =
+        BBSplusMessage::messages_to_scalar::<CS>(committed_messages, api_id)?;
+    if let Some(prover_nym) = prover_nym {
+        commited_message_scalars.push(BBSplusMessage::new(prover_nym.0));
+    }
+
+    let M = commited_message_scalars.len();
     let generators = Generators::create::<CS>(M + 1, Some(&[b"BLIND_", api_id].concat())).values;
 
     let Q2 = generators[0];
     let Js = &generators[1..M + 1];
 
-    let commited_message_scalars =
-        BBSplusMessage::messages_to_scalar::<CS>(committed_messages, api_id)?;
-
-    #[cfg(not(test))]
-    let random_scalars = calculate_random_scalars(M + 2);
-
-    #[cfg(test)]
-    let random_scalars = seeded_random_scalars::<CS>(M + 2, CS::SEED_MOCKED_SCALAR, CS::COMMIT_DST);
+    let random_scalars = Zeroizing::new(randomness.scalars::<CS>(M + 2));
 
     let secret_prover_blind = random_scalars[0];
     let s_tilde = random_scalars[1];
@@ -256,8 +376,8 @@ where
     let G2 = blind_generators[0];
     let Js = &blind_generators[1..];
     let mut Cbar = G2 * commitment_proof.s_cap;
-    for i in 0..M {
-        Cbar += Js[i] * commitment_proof.m_cap[i];
+    for (J, m_cap) in core::iter::zip(Js, &commitment_proof.m_cap) {
+        Cbar += J * m_cap;
     }
 
     Cbar += commitment * (-commitment_proof.challenge);
@@ -276,6 +396,7 @@ mod tests {
     use std::fs;
 
     use elliptic_curve::hash2curve::ExpandMsg;
+    use rand::{rngs::StdRng, SeedableRng};
 
     use crate::{
         bbsplus::{ciphersuites::BbsCiphersuite, generators::Generators},
@@ -330,7 +451,13 @@ mod tests {
         let expected_result = proof_json["result"]["valid"].as_bool().unwrap();
 
         let (commitment_with_proof_result, secret) =
-            Commitment::<BBSplus<S::Ciphersuite>>::commit(Some(&committed_messages)).unwrap();
+            Commitment::<BBSplus<S::Ciphersuite>>::commit_with_mocked_scalars(
+                Some(&committed_messages),
+                None,
+                None,
+                None,
+            )
+            .unwrap();
 
         let commitment_with_proof_result_oct = commitment_with_proof_result.to_bytes();
         assert_eq!(
@@ -340,6 +467,15 @@ mod tests {
 
         assert_eq!(hex::encode(secret.to_bytes()), prover_blind);
 
+        let (rng_commitment_with_proof, rng_secret) =
+            Commitment::<BBSplus<S::Ciphersuite>>::commit_with_rng(
+                Some(&committed_messages),
+                None,
+                &mut StdRng::seed_from_u64(1),
+            )
+            .unwrap();
+        assert_ne!(rng_secret.to_bytes(), secret.to_bytes());
+
         let blind_generators = Generators::create::<S::Ciphersuite>(
             committed_messages.len() + 1,
             Some(&[b"BLIND_", <S::Ciphersuite as BbsCiphersuite>::API_ID_BLIND].concat()),
@@ -353,5 +489,14 @@ mod tests {
         .is_ok();
 
         assert_eq!(result, expected_result);
+
+        assert!(
+            Commitment::<BBSplus<S::Ciphersuite>>::deserialize_and_validate_commit(
+                Some(&rng_commitment_with_proof.to_bytes()),
+                &blind_generators,
+                Some(<S::Ciphersuite as BbsCiphersuite>::API_ID_BLIND),
+            )
+            .is_ok()
+        );
     }
 }
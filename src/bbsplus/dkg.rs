@@ -0,0 +1,754 @@
+// Copyright 2023 Fondazione LINKS
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Distributed generation of BBS issuer keys (Pedersen DKG with Feldman verifiable secret sharing), without a trusted dealer.
+//!
+//! Each party `i` of `n` picks a random polynomial `f_i` of degree `t - 1` and:
+//! 1. broadcasts the [`DkgCommitment`] `C_i,k = BP2 * a_i,k` to its coefficients and privately sends [`DkgShare`] `f_i(j)` to every party `j`
+//!    ([`DkgParticipant::new`]);
+//! 2. checks every received share against the commitment of its sender ([`DkgParticipant::receive_share`]), broadcasting a
+//!    [`DkgComplaint`] for each invalid or missing ([`DkgParticipant::missing_shares`]) share;
+//! 3. answers the complaints against itself by broadcasting the disputed share ([`DkgParticipant::answer_complaint`]). A party whose
+//!    answer is missing or invalid is disqualified by everyone ([`DkgParticipant::resolve_complaint`]);
+//! 4. computes its [`SecretKeyShare`] `SK_j = sum f_i(j)` and the joint public key `PK = sum C_i,0` over the qualified parties
+//!    ([`DkgParticipant::finalize`]). The DKG fails if the commitment of any party has not been received.
+//!
+//! The resulting key shares can be used with [`super::threshold`], whose presignatures are generated by the parties as well, so that neither the
+//! key nor the signing material is ever held by a single party. [`simulate_dkg`] runs all the parties in-process, with in-memory transports.
+
+use super::{
+    keys::BBSplusPublicKey,
+    threshold::{check_threshold, evaluate_polynomial, SecretKeyShare},
+};
+use crate::{errors::Error, utils::util::bbsplus_utils::get_random_with_rng};
+use alloc::{
+    borrow::ToOwned,
+    collections::{BTreeMap, BTreeSet},
+    format,
+    vec::Vec,
+};
+use bls12_381_plus::{G2Projective, Scalar};
+use core::fmt;
+#[cfg(feature = "std")]
+use rand::thread_rng;
+use rand::{CryptoRng, RngCore};
+use serde::{Deserialize, Serialize};
+use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
+
+/// Broadcast commitment `C_k = BP2 * a_k` to the coefficients of the sharing polynomial of a party
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct DkgCommitment {
+    sender: usize,
+    commitments: Vec<G2Projective>,
+}
+
+impl DkgCommitment {
+    pub fn sender(&self) -> usize {
+        self.sender
+    }
+
+    /// Checks `BP2 * share == C_0 + C_1 * receiver + ... + C_t-1 * receiver^(t-1)`
+    fn verify_share(&self, share: &DkgShare) -> bool {
+        share.sender == self.sender
+            && G2Projective::GENERATOR * share.value
+                == evaluate_commitments(&self.commitments, share.receiver)
+    }
+}
+
+/// Share `f_sender(receiver)` of the polynomial of `sender`. It is sent privately, unless disputed by a [`DkgComplaint`].
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DkgShare {
+    sender: usize,
+    receiver: usize,
+    value: Scalar,
+}
+
+impl DkgShare {
+    pub fn sender(&self) -> usize {
+        self.sender
+    }
+
+    pub fn receiver(&self) -> usize {
+        self.receiver
+    }
+}
+
+impl Zeroize for DkgShare {
+    fn zeroize(&mut self) {
+        self.value.zeroize();
+    }
+}
+
+impl Drop for DkgShare {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for DkgShare {}
+
+impl fmt::Debug for DkgShare {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DkgShare")
+            .field("sender", &self.sender)
+            .field("receiver", &self.receiver)
+            .field("value", &"<redacted>")
+            .finish()
+    }
+}
+
+/// Broadcast complaint of `accuser` about the share received from `accused`
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct DkgComplaint {
+    accuser: usize,
+    accused: usize,
+}
+
+impl DkgComplaint {
+    pub fn accuser(&self) -> usize {
+        self.accuser
+    }
+
+    pub fn accused(&self) -> usize {
+        self.accused
+    }
+}
+
+/// Result of the DKG for a party
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DkgOutput {
+    key_share: SecretKeyShare,
+    public_key: BBSplusPublicKey,
+    commitments: Vec<G2Projective>,
+    qualified: Vec<usize>,
+}
+
+impl DkgOutput {
+    pub fn key_share(&self) -> &SecretKeyShare {
+        &self.key_share
+    }
+
+    /// The joint public key
+    pub fn public_key(&self) -> &BBSplusPublicKey {
+        &self.public_key
+    }
+
+    /// Indexes of the parties whose polynomials make up the joint key
+    pub fn qualified(&self) -> &[usize] {
+        &self.qualified
+    }
+
+    /// Returns the public key share `BP2 * SK_index` of any party, e.g. to check its contributions.
+    pub fn public_key_share(&self, index: usize) -> BBSplusPublicKey {
+        BBSplusPublicKey(evaluate_commitments(&self.commitments, index))
+    }
+}
+
+/// State of a party during the DKG
+pub struct DkgParticipant {
+    index: usize,
+    threshold: usize,
+    participants: usize,
+    polynomial: Zeroizing<Vec<Scalar>>,
+    commitments: BTreeMap<usize, DkgCommitment>,
+    shares: BTreeMap<usize, DkgShare>,
+    invalid_shares: BTreeSet<usize>,
+    disqualified: BTreeSet<usize>,
+}
+
+impl DkgParticipant {
+    /// # Description
+    /// Starts the DKG for the party `index`: picks a random sharing polynomial of degree `threshold - 1`.
+    ///
+    /// # Inputs:
+    /// * `index` (REQUIRED), the index of the party, from 1 to `participants`.
+    /// * `threshold` (REQUIRED), the number of parties needed to sign, `1 <= threshold <= participants`.
+    /// * `participants` (REQUIRED), the number of parties.
+    ///
+    /// # Output:
+    /// the state of the party, the [`DkgCommitment`] to broadcast and the [`DkgShare`]s to send privately to each of the other parties, or [`Error`].
+    ///
+    #[cfg(feature = "std")]
+    pub fn new(
+        index: usize,
+        threshold: usize,
+        participants: usize,
+    ) -> Result<(Self, DkgCommitment, Vec<DkgShare>), Error> {
+        Self::new_with_rng(index, threshold, participants, &mut thread_rng())
+    }
+
+    /// # Description
+    /// Same as [`Self::new`], drawing the coefficients of the sharing polynomial from `rng`.
+    ///
+    /// # Inputs:
+    /// * `rng` (REQUIRED), a cryptographically secure random number generator.
+    /// * the other inputs are the same as [`Self::new`].
+    ///
+    /// # Output:
+    /// the state of the party, the [`DkgCommitment`] to broadcast and the [`DkgShare`]s to send privately to each of the other parties, or [`Error`].
+    ///
+    pub fn new_with_rng<R: RngCore + CryptoRng>(
+        index: usize,
+        threshold: usize,
+        participants: usize,
+        rng: &mut R,
+    ) -> Result<(Self, DkgCommitment, Vec<DkgShare>), Error> {
+        check_threshold(threshold, participants).map_err(Error::KeyGenError)?;
+        if index == 0 || index > participants {
+            return Err(Error::KeyGenError(format!("Invalid party index {}", index)));
+        }
+
+        let polynomial: Zeroizing<Vec<Scalar>> =
+            Zeroizing::new((0..threshold).map(|_| get_random_with_rng(rng)).collect());
+        let commitment = DkgCommitment {
+            sender: index,
+            commitments: polynomial
+                .iter()
+                .map(|a| G2Projective::GENERATOR * a)
+                .collect(),
+        };
+
+        let mut participant = Self {
+            index,
+            threshold,
+            participants,
+            polynomial,
+            commitments: BTreeMap::new(),
+            shares: BTreeMap::new(),
+            invalid_shares: BTreeSet::new(),
+            disqualified: BTreeSet::new(),
+        };
+
+        let own_share = participant.share_for(index);
+        participant.commitments.insert(index, commitment.clone());
+        participant.shares.insert(index, own_share);
+
+        let shares = (1..=participants)
+            .filter(|&j| j != index)
+            .map(|j| participant.share_for(j))
+            .collect();
+
+        Ok((participant, commitment, shares))
+    }
+
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    fn share_for(&self, receiver: usize) -> DkgShare {
+        DkgShare {
+            sender: self.index,
+            receiver,
+            value: evaluate_polynomial(&self.polynomial, Scalar::from(receiver as u64)),
+        }
+    }
+
+    /// # Description
+    /// Stores the [`DkgCommitment`] broadcast by another party. It MUST be received before the shares of that party.
+    ///
+    /// # Inputs:
+    /// * `commitment` (REQUIRED), the [`DkgCommitment`] of another party.
+    ///
+    /// # Output:
+    /// a result: [`Ok`] or [`Error`].
+    ///
+    pub fn receive_commitment(&mut self, commitment: DkgCommitment) -> Result<(), Error> {
+        if commitment.sender == 0 || commitment.sender > self.participants {
+            return Err(Error::KeyGenError(format!(
+                "Invalid party index {}",
+                commitment.sender
+            )));
+        }
+        if commitment.commitments.len() != self.threshold {
+            return Err(Error::KeyGenError(format!(
+                "Invalid commitment of party {}",
+                commitment.sender
+            )));
+        }
+        if self.commitments.contains_key(&commitment.sender) {
+            return Err(Error::KeyGenError(format!(
+                "Duplicate commitment of party {}",
+                commitment.sender
+            )));
+        }
+        self.commitments.insert(commitment.sender, commitment);
+        Ok(())
+    }
+
+    /// # Description
+    /// Checks a [`DkgShare`] sent by another party against its commitment. Each party MUST send a single share.
+    ///
+    /// # Inputs:
+    /// * `share` (REQUIRED), a [`DkgShare`] addressed to this party.
+    ///
+    /// # Output:
+    /// [`None`] if the share is valid, otherwise the [`DkgComplaint`] to broadcast, or [`Error`].
+    ///
+    pub fn receive_share(&mut self, share: DkgShare) -> Result<Option<DkgComplaint>, Error> {
+        if share.receiver != self.index {
+            return Err(Error::KeyGenError(format!(
+                "Share addressed to party {}",
+                share.receiver
+            )));
+        }
+        let commitment = self.commitments.get(&share.sender).ok_or_else(|| {
+            Error::KeyGenError(format!("Missing commitment of party {}", share.sender))
+        })?;
+        if self.shares.contains_key(&share.sender) || self.invalid_shares.contains(&share.sender) {
+            return Err(Error::KeyGenError(format!(
+                "Duplicate share of party {}",
+                share.sender
+            )));
+        }
+
+        if !commitment.verify_share(&share) {
+            self.invalid_shares.insert(share.sender);
+            return Ok(Some(DkgComplaint {
+                accuser: self.index,
+                accused: share.sender,
+            }));
+        }
+        self.shares.insert(share.sender, share);
+        Ok(None)
+    }
+
+    /// # Description
+    /// Lists the complaints against the parties that broadcast a commitment but did not send a valid share to this party.
+    ///
+    /// # Output:
+    /// the [`DkgComplaint`]s to broadcast.
+    ///
+    pub fn missing_shares(&self) -> Vec<DkgComplaint> {
+        self.commitments
+            .keys()
+            .filter(|sender| !self.shares.contains_key(sender))
+            .map(|&accused| DkgComplaint {
+                accuser: self.index,
+                accused,
+            })
+            .collect()
+    }
+
+    /// # Description
+    /// Answers a complaint against this party by revealing the disputed share, to be broadcast.
+    ///
+    /// # Inputs:
+    /// * `complaint` (REQUIRED), a broadcast [`DkgComplaint`].
+    ///
+    /// # Output:
+    /// the disputed [`DkgShare`] if the complaint is against this party, [`None`] otherwise.
+    ///
+    pub fn answer_complaint(&self, complaint: &DkgComplaint) -> Option<DkgShare> {
+        if complaint.accused != self.index
+            || complaint.accuser == 0
+            || complaint.accuser > self.participants
+        {
+            return None;
+        }
+        Some(self.share_for(complaint.accuser))
+    }
+
+    /// # Description
+    /// Resolves a broadcast complaint with the answer of the accused party: if the revealed share is valid, the accuser adopts it,
+    /// otherwise (or if there is no answer) the accused party is disqualified. All the parties MUST resolve the same complaints in the same order,
+    /// so that they agree on the qualified parties. Complaints of parties that are not qualified are rejected.
+    ///
+    /// # Inputs:
+    /// * `complaint` (REQUIRED), a broadcast [`DkgComplaint`].
+    /// * `answer` (OPTIONAL), the [`DkgShare`] revealed by the accused party.
+    ///
+    /// # Output:
+    /// a result: [`Ok`] or [`Error`].
+    ///
+    pub fn resolve_complaint(
+        &mut self,
+        complaint: &DkgComplaint,
+        answer: Option<&DkgShare>,
+    ) -> Result<(), Error> {
+        if !self.commitments.contains_key(&complaint.accuser)
+            || self.disqualified.contains(&complaint.accuser)
+        {
+            return Err(Error::KeyGenError(format!(
+                "Complaint of party {}, which is not qualified",
+                complaint.accuser
+            )));
+        }
+        let commitment = self.commitments.get(&complaint.accused).ok_or_else(|| {
+            Error::KeyGenError(format!("Missing commitment of party {}", complaint.accused))
+        })?;
+
+        match answer {
+            Some(share)
+                if share.receiver == complaint.accuser && commitment.verify_share(share) =>
+            {
+                if complaint.accuser == self.index {
+                    self.shares.insert(complaint.accused, share.clone());
+                }
+            }
+            _ => {
+                self.disqualified.insert(complaint.accused);
+            }
+        }
+        Ok(())
+    }
+
+    /// # Description
+    /// Completes the DKG, once the commitments of all the parties have been received and all the complaints have been resolved.
+    ///
+    /// # Output:
+    /// the [`DkgOutput`] of the party or [`Error`].
+    ///
+    pub fn finalize(self) -> Result<DkgOutput, Error> {
+        if let Some(sender) = (1..=self.participants).find(|i| !self.commitments.contains_key(i)) {
+            return Err(Error::KeyGenError(format!(
+                "Missing commitment of party {}",
+                sender
+            )));
+        }
+        let qualified: Vec<usize> = self
+            .commitments
+            .keys()
+            .filter(|sender| !self.disqualified.contains(sender))
+            .copied()
+            .collect();
+        if qualified.len() < self.threshold {
+            return Err(Error::KeyGenError(format!(
+                "{} qualified parties, at least {} needed",
+                qualified.len(),
+                self.threshold
+            )));
+        }
+        if !qualified.contains(&self.index) {
+            return Err(Error::KeyGenError("Party has been disqualified".to_owned()));
+        }
+
+        let mut value = Scalar::ZERO;
+        let mut commitments = alloc::vec![G2Projective::IDENTITY; self.threshold];
+        for sender in &qualified {
+            let share = self
+                .shares
+                .get(sender)
+                .ok_or_else(|| Error::KeyGenError(format!("Missing share of party {}", sender)))?;
+            value += share.value;
+            for (sum, c) in commitments
+                .iter_mut()
+                .zip(&self.commitments[sender].commitments)
+            {
+                *sum += c;
+            }
+        }
+
+        let public_key = BBSplusPublicKey(commitments[0]);
+        public_key.validate()?;
+
+        Ok(DkgOutput {
+            key_share: SecretKeyShare::new(self.index, self.threshold, self.participants, value),
+            public_key,
+            commitments,
+            qualified,
+        })
+    }
+}
+
+impl fmt::Debug for DkgParticipant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DkgParticipant")
+            .field("index", &self.index)
+            .field("threshold", &self.threshold)
+            .field("participants", &self.participants)
+            .field("polynomial", &"<redacted>")
+            .field("commitments", &self.commitments)
+            .field("shares", &self.shares)
+            .field("invalid_shares", &self.invalid_shares)
+            .field("disqualified", &self.disqualified)
+            .finish()
+    }
+}
+
+/// # Description
+/// Runs the DKG in-process for all the parties, delivering the messages through in-memory queues.
+///
+/// # Inputs:
+/// * `threshold` (REQUIRED), the number of parties needed to sign, `1 <= threshold <= participants`.
+/// * `participants` (REQUIRED), the number of parties.
+///
+/// # Output:
+/// the [`DkgOutput`]s of the parties, ordered by index, or [`Error`].
+///
+#[cfg(feature = "std")]
+pub fn simulate_dkg(threshold: usize, participants: usize) -> Result<Vec<DkgOutput>, Error> {
+    simulate_dkg_with_rng(threshold, participants, &mut thread_rng())
+}
+
+/// # Description
+/// Same as [`simulate_dkg`], drawing the random values from `rng`.
+///
+/// # Inputs:
+/// * `rng` (REQUIRED), a cryptographically secure random number generator.
+/// * the other inputs are the same as [`simulate_dkg`].
+///
+/// # Output:
+/// the [`DkgOutput`]s of the parties, ordered by index, or [`Error`].
+///
+pub fn simulate_dkg_with_rng<R: RngCore + CryptoRng>(
+    threshold: usize,
+    participants: usize,
+    rng: &mut R,
+) -> Result<Vec<DkgOutput>, Error> {
+    let mut parties = Vec::with_capacity(participants);
+    let mut broadcast = Vec::with_capacity(participants);
+    let mut inboxes: Vec<Vec<DkgShare>> = (0..participants).map(|_| Vec::new()).collect();
+
+    for index in 1..=participants {
+        let (party, commitment, shares) =
+            DkgParticipant::new_with_rng(index, threshold, participants, rng)?;
+        parties.push(party);
+        broadcast.push(commitment);
+        for share in shares {
+            inboxes[share.receiver - 1].push(share);
+        }
+    }
+
+    let mut complaints = Vec::new();
+    for (party, inbox) in parties.iter_mut().zip(inboxes) {
+        let index = party.index;
+        for commitment in broadcast.iter().filter(|c| c.sender != index) {
+            party.receive_commitment(commitment.clone())?;
+        }
+        for share in inbox {
+            complaints.extend(party.receive_share(share)?);
+        }
+        complaints.extend(party.missing_shares());
+    }
+
+    let answers: Vec<Option<DkgShare>> = complaints
+        .iter()
+        .map(|complaint| {
+            parties
+                .iter()
+                .find_map(|party| party.answer_complaint(complaint))
+        })
+        .collect();
+    for party in parties.iter_mut() {
+        for (complaint, answer) in complaints.iter().zip(&answers) {
+            party.resolve_complaint(complaint, answer.as_ref())?;
+        }
+    }
+
+    parties.into_iter().map(DkgParticipant::finalize).collect()
+}
+
+/// `C_0 + C_1 * x + ... + C_t-1 * x^(t-1)` with the Horner method
+fn evaluate_commitments(commitments: &[G2Projective], x: usize) -> G2Projective {
+    let x = Scalar::from(x as u64);
+    commitments
+        .iter()
+        .rev()
+        .fold(G2Projective::IDENTITY, |acc, c| acc * x + c)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{simulate_dkg_with_rng, DkgComplaint, DkgParticipant, DkgShare};
+    use crate::{
+        bbsplus::{
+            ciphersuites::Bls12381Sha256,
+            threshold::{simulate_presignatures_with_rng, simulate_threshold_sign},
+        },
+        errors::Error,
+    };
+    use bls12_381_plus::Scalar;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn dkg_threshold_sign() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let outputs = simulate_dkg_with_rng(3, 5, &mut rng).unwrap();
+
+        let pk = outputs[0].public_key();
+        for output in &outputs {
+            assert_eq!(output.public_key(), pk);
+            assert_eq!(output.qualified(), &[1, 2, 3, 4, 5]);
+            assert_eq!(
+                output.key_share().public_key_share(),
+                outputs[0].public_key_share(output.key_share().index())
+            );
+        }
+
+        let messages = vec![b"first".to_vec(), b"second".to_vec()];
+        let key_shares: Vec<_> = [1, 3, 4]
+            .iter()
+            .map(|&i| outputs[i].key_share().clone())
+            .collect();
+        // no dealer for the presignatures either
+        let presignatures = simulate_presignatures_with_rng(3, 5, &mut rng).unwrap();
+        let signature = simulate_threshold_sign::<Bls12381Sha256>(
+            pk,
+            &key_shares,
+            presignatures,
+            None,
+            Some(&messages),
+        )
+        .unwrap();
+        assert!(signature.verify(pk, Some(&messages), None).is_ok());
+    }
+
+    /// Runs a 2-of-3 DKG where party 1 sends a wrong share to party 2
+    fn dkg_with_complaint(answer: bool) -> Vec<Result<super::DkgOutput, Error>> {
+        let mut rng = StdRng::seed_from_u64(2);
+        let (mut parties, mut broadcast, mut inboxes) = (vec![], vec![], vec![]);
+        for i in 1..=3 {
+            let (party, commitment, shares) =
+                DkgParticipant::new_with_rng(i, 2, 3, &mut rng).unwrap();
+            parties.push(party);
+            broadcast.push(commitment);
+            inboxes.extend(shares);
+        }
+
+        let mut complaints = vec![];
+        for party in parties.iter_mut() {
+            let index = party.index();
+            for commitment in broadcast.iter().filter(|c| c.sender() != index) {
+                party.receive_commitment(commitment.clone()).unwrap();
+            }
+            for share in inboxes.iter().filter(|s| s.receiver() == index) {
+                let mut share = share.clone();
+                if share.sender() == 1 && share.receiver() == 2 {
+                    share.value += Scalar::ONE;
+                }
+                complaints.extend(party.receive_share(share).unwrap());
+            }
+        }
+        assert_eq!(
+            complaints,
+            vec![DkgComplaint {
+                accuser: 2,
+                accused: 1
+            }]
+        );
+
+        let answer: Option<DkgShare> = if answer {
+            parties[0].answer_complaint(&complaints[0])
+        } else {
+            None
+        };
+        assert!(parties[1].answer_complaint(&complaints[0]).is_none());
+        for party in parties.iter_mut() {
+            party
+                .resolve_complaint(&complaints[0], answer.as_ref())
+                .unwrap();
+        }
+
+        parties.into_iter().map(DkgParticipant::finalize).collect()
+    }
+
+    #[test]
+    fn dkg_complaint_answered() {
+        let outputs: Vec<_> = dkg_with_complaint(true)
+            .into_iter()
+            .map(Result::unwrap)
+            .collect();
+        for output in &outputs {
+            assert_eq!(output.qualified(), &[1, 2, 3]);
+            assert_eq!(output.public_key(), outputs[0].public_key());
+            assert_eq!(
+                output.key_share().public_key_share(),
+                outputs[0].public_key_share(output.key_share().index())
+            );
+        }
+    }
+
+    #[test]
+    fn dkg_complaint_unanswered() {
+        let outputs = dkg_with_complaint(false);
+        assert!(matches!(outputs[0], Err(Error::KeyGenError(_))));
+        let outputs: Vec<_> = outputs[1..].iter().map(|o| o.as_ref().unwrap()).collect();
+        for output in &outputs {
+            assert_eq!(output.qualified(), &[2, 3]);
+            assert_eq!(output.public_key(), outputs[0].public_key());
+            assert_eq!(
+                output.key_share().public_key_share(),
+                outputs[0].public_key_share(output.key_share().index())
+            );
+        }
+    }
+
+    #[test]
+    fn dkg_invalid_parameters() {
+        let mut rng = StdRng::seed_from_u64(3);
+        assert!(DkgParticipant::new_with_rng(0, 2, 3, &mut rng).is_err());
+        assert!(DkgParticipant::new_with_rng(4, 2, 3, &mut rng).is_err());
+        assert!(DkgParticipant::new_with_rng(1, 4, 3, &mut rng).is_err());
+
+        let (mut party, _, _) = DkgParticipant::new_with_rng(1, 2, 3, &mut rng).unwrap();
+        let (_, commitment, shares) = DkgParticipant::new_with_rng(2, 2, 3, &mut rng).unwrap();
+        let share = shares.into_iter().find(|s| s.receiver() == 1).unwrap();
+        assert!(party.receive_share(share.clone()).is_err());
+        party.receive_commitment(commitment.clone()).unwrap();
+        assert!(party.receive_commitment(commitment).is_err());
+        assert_eq!(party.missing_shares().len(), 1);
+        assert_eq!(party.receive_share(share.clone()).unwrap(), None);
+        assert!(party.missing_shares().is_empty());
+        assert!(party.receive_share(share).is_err());
+
+        // complaints of unknown or disqualified parties are rejected
+        for accuser in [0, 3, 4] {
+            assert!(party
+                .resolve_complaint(
+                    &DkgComplaint {
+                        accuser,
+                        accused: 2
+                    },
+                    None
+                )
+                .is_err());
+        }
+        party
+            .resolve_complaint(
+                &DkgComplaint {
+                    accuser: 1,
+                    accused: 2,
+                },
+                None,
+            )
+            .unwrap();
+        assert!(party
+            .resolve_complaint(
+                &DkgComplaint {
+                    accuser: 2,
+                    accused: 1
+                },
+                None
+            )
+            .is_err());
+
+        // party 3 never broadcast its commitment
+        assert!(matches!(party.finalize(), Err(Error::KeyGenError(_))));
+    }
+
+    #[test]
+    fn dkg_duplicate_invalid_share() {
+        let mut rng = StdRng::seed_from_u64(4);
+        let (mut party, _, _) = DkgParticipant::new_with_rng(1, 2, 2, &mut rng).unwrap();
+        let (_, commitment, shares) = DkgParticipant::new_with_rng(2, 2, 2, &mut rng).unwrap();
+        party.receive_commitment(commitment).unwrap();
+
+        let share = shares.into_iter().next().unwrap();
+        let mut wrong_share = share.clone();
+        wrong_share.value += Scalar::ONE;
+        assert!(party.receive_share(wrong_share).unwrap().is_some());
+        // the dealer cannot replace a share it has been accused for
+        assert!(party.receive_share(share).is_err());
+    }
+}
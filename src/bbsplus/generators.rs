@@ -13,17 +13,25 @@
 // limitations under the License.
 
 use super::ciphersuites::BbsCiphersuite;
+use crate::errors::Error;
 use crate::utils::util::bbsplus_utils::i2osp;
+use alloc::{string::String, vec::Vec};
 use bls12_381_plus::G1Projective;
+#[cfg(feature = "std")]
+use core::marker::PhantomData;
 use elliptic_curve::group::Curve;
 use elliptic_curve::hash2curve::{ExpandMsg, Expander};
+use serde::de::{Deserializer, Error as _};
 use serde::ser::{SerializeStruct, Serializer};
 use serde::{Deserialize, Serialize};
+#[cfg(feature = "std")]
+use std::{collections::HashMap, sync::RwLock};
 
-#[derive(Clone, PartialEq, Eq, Debug, Deserialize)]
+#[derive(Clone, PartialEq, Eq, Debug)]
 pub struct Generators {
     pub g1_base_point: G1Projective,
     pub values: Vec<G1Projective>,
+    api_id: Vec<u8>,
 }
 
 impl Serialize for Generators {
@@ -44,11 +52,50 @@ impl Serialize for Generators {
         )?;
 
         state.serialize_field("Generators", &result)?;
+        state.serialize_field("api_id", &hex::encode(&self.api_id))?;
         state.end()
     }
 }
 
+impl<'de> Deserialize<'de> for Generators {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        struct Encoded {
+            #[serde(rename = "BP")]
+            g1_base_point: String,
+            #[serde(rename = "Generators")]
+            values: Vec<String>,
+            #[serde(default)]
+            api_id: String,
+        }
+
+        fn decode_point<E: serde::de::Error>(hex: &str) -> Result<G1Projective, E> {
+            Option::from(G1Projective::from_compressed_hex(hex))
+                .ok_or_else(|| E::custom("invalid G1 point"))
+        }
+
+        let encoded = Encoded::deserialize(deserializer)?;
+        Ok(Self {
+            g1_base_point: decode_point(&encoded.g1_base_point)?,
+            values: encoded
+                .values
+                .iter()
+                .map(|v| decode_point(v))
+                .collect::<Result<_, _>>()?,
+            api_id: hex::decode(&encoded.api_id).map_err(D::Error::custom)?,
+        })
+    }
+}
+
 impl Generators {
+    /// Returns the `api_id` the generators have been created with
+    pub fn api_id(&self) -> &[u8] {
+        &self.api_id
+    }
+
     /// # Description
     /// Create Generators an P1 (A fixed point in the G1 subgroup)
     ///
@@ -69,10 +116,43 @@ impl Generators {
         Self {
             g1_base_point: G1Projective::from_compressed_hex(CS::P1).unwrap(),
             values: generators[0..].to_vec(),
+            api_id: api_id.unwrap_or(&[]).to_vec(),
         }
     }
 
-    /// Utility to append one list of Generators to another.
+    /// # Description
+    /// Returns the first `count` generators, checking that they have been created for the ciphersuite `CS` and the operation using them. Used by the APIs accepting prebuilt [`Generators`].
+    ///
+    /// # Inputs:
+    /// * `count` (REQUIRED), unsigned integer. Number of generators needed.
+    /// * `api_id` (REQUIRED), octet string. The `api_id` of the operation using the generators.
+    ///
+    /// # Output:
+    /// * [`Generators`], [`Error::CiphersuiteMismatch`] or [`Error::NotEnoughGenerators`]
+    ///
+    pub(crate) fn first<CS>(&self, count: usize, api_id: &[u8]) -> Result<Generators, Error>
+    where
+        CS: BbsCiphersuite,
+    {
+        let P1 = G1Projective::from_compressed_hex(CS::P1).unwrap();
+        if self.g1_base_point != P1 || self.api_id != api_id {
+            return Err(Error::CiphersuiteMismatch);
+        }
+
+        let values = self
+            .values
+            .get(..count)
+            .ok_or(Error::NotEnoughGenerators)?
+            .to_vec();
+
+        Ok(Self {
+            g1_base_point: self.g1_base_point,
+            values,
+            api_id: self.api_id.clone(),
+        })
+    }
+
+    /// Utility to append one list of Generators to another. The result keeps the `api_id` of `self`.
     /// # Panics
     /// Panics if the Generators have different base points.
     pub(crate) fn append(mut self, other: Self) -> Self {
@@ -98,37 +178,144 @@ where
     CS: BbsCiphersuite,
     CS::Expander: for<'a> ExpandMsg<'a>,
 {
-    let api_id = api_id.unwrap_or(&[]);
-
-    let seed_dst = [api_id, CS::GENERATOR_SEED_DST].concat();
-    let generator_dst = [api_id, CS::GENERATOR_DST].concat();
-    let generator_seed = [api_id, CS::GENERATOR_SEED].concat();
-
-    let mut v = vec![0u8; CS::EXPAND_LEN];
-    CS::Expander::expand_message(&[&generator_seed], &[&seed_dst], CS::EXPAND_LEN)
-        .unwrap()
-        .fill_bytes(&mut v);
-
-    let mut buffer = vec![0u8; CS::EXPAND_LEN];
-    let mut generators = Vec::new();
-    for i in 1..count + 1 {
-        v = [&*v, &i2osp::<8>(i)].concat();
-        CS::Expander::expand_message(&[&v], &[&seed_dst], CS::EXPAND_LEN)
+    let mut state = GeneratorsState::new::<CS>(api_id);
+    state.extend::<CS>(count, api_id);
+    state.values
+}
+
+/// State of the generators calculation, that can be resumed to create more generators without recomputing the previous ones
+#[derive(Clone, Debug)]
+struct GeneratorsState {
+    v: Vec<u8>,
+    values: Vec<G1Projective>,
+}
+
+impl GeneratorsState {
+    fn new<CS>(api_id: Option<&[u8]>) -> Self
+    where
+        CS: BbsCiphersuite,
+        CS::Expander: for<'a> ExpandMsg<'a>,
+    {
+        let api_id = api_id.unwrap_or(&[]);
+
+        let seed_dst = [api_id, CS::GENERATOR_SEED_DST].concat();
+        let generator_seed = [api_id, CS::GENERATOR_SEED].concat();
+
+        let mut v = vec![0u8; CS::EXPAND_LEN];
+        CS::Expander::expand_message(&[&generator_seed], &[&seed_dst], CS::EXPAND_LEN)
             .unwrap()
-            .fill_bytes(&mut buffer);
-        v = buffer.clone();
-        let generator = G1Projective::hash::<CS::Expander>(&v, &generator_dst);
-        generators.push(generator);
+            .fill_bytes(&mut v);
+
+        Self {
+            v,
+            values: Vec::new(),
+        }
+    }
+
+    fn extend<CS>(&mut self, count: usize, api_id: Option<&[u8]>)
+    where
+        CS: BbsCiphersuite,
+        CS::Expander: for<'a> ExpandMsg<'a>,
+    {
+        let api_id = api_id.unwrap_or(&[]);
+
+        let seed_dst = [api_id, CS::GENERATOR_SEED_DST].concat();
+        let generator_dst = [api_id, CS::GENERATOR_DST].concat();
+
+        let mut buffer = vec![0u8; CS::EXPAND_LEN];
+        for i in self.values.len() + 1..count + 1 {
+            let v = [&*self.v, &i2osp::<8>(i)].concat();
+            CS::Expander::expand_message(&[&v], &[&seed_dst], CS::EXPAND_LEN)
+                .unwrap()
+                .fill_bytes(&mut buffer);
+            self.v = buffer.clone();
+            let generator = G1Projective::hash::<CS::Expander>(&self.v, &generator_dst);
+            self.values.push(generator);
+        }
+    }
+}
+
+/// # Description
+/// A thread-safe cache of [`Generators`] for the ciphersuite `CS`. For each `api_id`, the generators are created lazily and the list grows when more generators are requested, without recomputing the ones already created.
+/// The [`Generators`] returned can be used with the APIs accepting prebuilt generators (e.g., [`crate::schemes::generics::Signature::sign_with_generators`]).
+/// Requires the `std` feature.
+#[cfg(feature = "std")]
+pub struct GeneratorsCache<CS: BbsCiphersuite> {
+    entries: RwLock<HashMap<Vec<u8>, GeneratorsState>>,
+    _ciphersuite: PhantomData<fn() -> CS>,
+}
+
+#[cfg(feature = "std")]
+impl<CS: BbsCiphersuite> Default for GeneratorsCache<CS> {
+    fn default() -> Self {
+        Self {
+            entries: RwLock::new(HashMap::new()),
+            _ciphersuite: PhantomData,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<CS: BbsCiphersuite> GeneratorsCache<CS> {
+    pub fn new() -> Self {
+        Self::default()
     }
 
-    generators
+    /// # Description
+    /// Returns the first `count` generators for `api_id`, creating only the ones not already in the cache
+    ///
+    /// # Inputs:
+    /// * `count` (REQUIRED), unsigned integer. Number of generators to return.
+    /// * `api_id` (OPTIONAL), octet string. If not supplied it defaults to the empty octet string ("").
+    ///
+    /// # Output:
+    /// * [`Generators`], containing an array of generators and P1
+    ///
+    pub fn get(&self, count: usize, api_id: Option<&[u8]>) -> Generators
+    where
+        CS::Expander: for<'a> ExpandMsg<'a>,
+    {
+        let key = api_id.unwrap_or(&[]);
+        let g1_base_point = G1Projective::from_compressed_hex(CS::P1).unwrap();
+
+        {
+            let entries = self.entries.read().unwrap_or_else(|e| e.into_inner());
+            if let Some(state) = entries.get(key).filter(|s| s.values.len() >= count) {
+                return Generators {
+                    g1_base_point,
+                    values: state.values[..count].to_vec(),
+                    api_id: key.to_vec(),
+                };
+            }
+        }
+
+        let mut entries = self.entries.write().unwrap_or_else(|e| e.into_inner());
+        let state = entries
+            .entry(key.to_vec())
+            .or_insert_with(|| GeneratorsState::new::<CS>(api_id));
+        state.extend::<CS>(count, api_id);
+
+        Generators {
+            g1_base_point,
+            values: state.values[..count].to_vec(),
+            api_id: key.to_vec(),
+        }
+    }
+
+    /// Removes all the generators from the cache.
+    pub fn clear(&self) {
+        self.entries
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .clear();
+    }
 }
 
 #[cfg(test)]
 mod tests {
 
     use crate::bbsplus::ciphersuites::BbsCiphersuite;
-    use crate::bbsplus::generators::Generators;
+    use crate::bbsplus::generators::{Generators, GeneratorsCache};
     use crate::schemes::algorithms::Scheme;
     use crate::schemes::algorithms::{BbsBls12381Sha256, BbsBls12381Shake256};
     use elliptic_curve::{group::Curve, hash2curve::ExpandMsg};
@@ -149,6 +336,89 @@ mod tests {
         );
     }
 
+    //GENERATORS CACHE
+
+    #[test]
+    fn generators_cache_sha256() {
+        generators_cache::<BbsBls12381Sha256>();
+    }
+
+    #[test]
+    fn generators_cache_shake256() {
+        generators_cache::<BbsBls12381Shake256>();
+    }
+
+    fn generators_cache<S: Scheme>()
+    where
+        S::Ciphersuite: BbsCiphersuite,
+        <S::Ciphersuite as BbsCiphersuite>::Expander: for<'a> ExpandMsg<'a>,
+    {
+        let api_id = <S::Ciphersuite as BbsCiphersuite>::API_ID;
+        let blind_api_id = <S::Ciphersuite as BbsCiphersuite>::API_ID_BLIND;
+        let cache = GeneratorsCache::<S::Ciphersuite>::new();
+
+        // the cache grows lazily
+        for count in [3, 1, 11, 7] {
+            assert_eq!(
+                cache.get(count, Some(api_id)),
+                Generators::create::<S::Ciphersuite>(count, Some(api_id))
+            );
+        }
+
+        // each api_id has its own generators
+        assert_eq!(
+            cache.get(5, Some(blind_api_id)),
+            Generators::create::<S::Ciphersuite>(5, Some(blind_api_id))
+        );
+        assert_ne!(cache.get(5, Some(api_id)), cache.get(5, Some(blind_api_id)));
+
+        // the cache can be shared between threads
+        let expected = Generators::create::<S::Ciphersuite>(20, Some(api_id));
+        cache.clear();
+        std::thread::scope(|s| {
+            for count in [20, 13, 17, 20] {
+                let (cache, expected) = (&cache, &expected);
+                s.spawn(move || {
+                    assert_eq!(
+                        cache.get(count, Some(api_id)).values,
+                        expected.values[..count]
+                    );
+                });
+            }
+        });
+    }
+
+    //SERDE
+
+    #[test]
+    fn serde_round_trip_sha256() {
+        serde_round_trip::<BbsBls12381Sha256>();
+    }
+
+    #[test]
+    fn serde_round_trip_shake256() {
+        serde_round_trip::<BbsBls12381Shake256>();
+    }
+
+    fn serde_round_trip<S: Scheme>()
+    where
+        S::Ciphersuite: BbsCiphersuite,
+        <S::Ciphersuite as BbsCiphersuite>::Expander: for<'a> ExpandMsg<'a>,
+    {
+        let api_id = <S::Ciphersuite as BbsCiphersuite>::API_ID;
+        let generators = Generators::create::<S::Ciphersuite>(5, Some(api_id));
+
+        let encoded = serde_json::to_string(&generators).unwrap();
+        let decoded: Generators = serde_json::from_str(&encoded).unwrap();
+
+        assert_eq!(decoded, generators);
+        assert_eq!(decoded.api_id(), api_id);
+        assert_eq!(
+            decoded.first::<S::Ciphersuite>(3, api_id).unwrap(),
+            Generators::create::<S::Ciphersuite>(3, Some(api_id))
+        );
+    }
+
     fn message_generators<S: Scheme>(filename: &str)
     where
         S::Ciphersuite: BbsCiphersuite,
@@ -181,7 +451,7 @@ mod tests {
         let mut result = BP == expected_BP;
         // println!("{}", result);
 
-        if result == false {
+        if !result {
             eprintln!("{}", result);
             eprintln!("  GENERATOR BP: {}", result);
             eprintln!("  Expected: {}", expected_BP);
@@ -217,6 +487,6 @@ mod tests {
                 }
             });
 
-        assert_eq!(result, true);
+        assert!(result);
     }
 }
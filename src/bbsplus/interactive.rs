@@ -0,0 +1,571 @@
+// Copyright 2023 Fondazione LINKS
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Interactive (three-move) proof of knowledge of a BBS signature.
+//!
+//! Instead of computing the challenge with the Fiat-Shamir heuristic, as [`crate::schemes::generics::PoKSignature::proof_gen`] does,
+//! the Prover sends a [`ProofCommitment`], the Verifier replies with a random [`ProofChallenge`] and the Prover answers with a [`ProofResponse`]:
+//! 1. [`InteractiveProver::commit`] → [`ProofCommitment`]
+//! 2. [`InteractiveVerifier::challenge`] → [`ProofChallenge`]
+//! 3. [`InteractiveProver::respond`] → [`ProofResponse`], checked by [`InteractiveVerifier::verify`]
+//!
+//! The Prover state is consumed when responding, so the same commitment can never be used to answer two different challenges.
+
+use super::{
+    ciphersuites::BbsCiphersuite,
+    generators::Generators,
+    keys::BBSplusPublicKey,
+    proof::{
+        check_proof_pairing, proof_finalize, proof_init, proof_verify_init, BBSplusPoKSignature,
+        ProofInitResult,
+    },
+    signature::BBSplusSignature,
+};
+use crate::{
+    errors::Error,
+    utils::{
+        message::bbsplus_message::BBSplusMessage,
+        util::{
+            bbsplus_utils::{
+                calculate_random_scalars_with_rng, get_messages, get_random_with_rng,
+                parse_g1_projective, ScalarExt,
+            },
+            get_remaining_indexes,
+        },
+    },
+};
+use alloc::{borrow::ToOwned, format, vec::Vec};
+use bls12_381_plus::{G1Projective, Scalar};
+use core::{fmt, marker::PhantomData};
+use elliptic_curve::{group::Curve, hash2curve::ExpandMsg};
+#[cfg(feature = "std")]
+use rand::thread_rng;
+use rand::{CryptoRng, RngCore};
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroizing;
+
+/// First message of the Prover: the randomized signature (`Abar`, `Bbar`, `D`) and the commitments `T1`, `T2` to the random scalars
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct ProofCommitment {
+    Abar: G1Projective,
+    Bbar: G1Projective,
+    D: G1Projective,
+    T1: G1Projective,
+    T2: G1Projective,
+}
+
+impl ProofCommitment {
+    pub const BYTES: usize = 5 * G1Projective::COMPRESSED_BYTES;
+
+    pub fn to_bytes(&self) -> [u8; Self::BYTES] {
+        let mut bytes = [0u8; Self::BYTES];
+        for (chunk, point) in bytes
+            .chunks_exact_mut(G1Projective::COMPRESSED_BYTES)
+            .zip([self.Abar, self.Bbar, self.D, self.T1, self.T2])
+        {
+            chunk.copy_from_slice(&point.to_affine().to_compressed());
+        }
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8; Self::BYTES]) -> Result<Self, Error> {
+        let mut points = bytes
+            .chunks_exact(G1Projective::COMPRESSED_BYTES)
+            .map(|chunk| {
+                parse_g1_projective(chunk).map_err(|_| Error::InvalidProofOfKnowledgeSignature)
+            });
+        let mut next = || points.next().unwrap_or(Err(Error::UnespectedError));
+        Ok(Self {
+            Abar: next()?,
+            Bbar: next()?,
+            D: next()?,
+            T1: next()?,
+            T2: next()?,
+        })
+    }
+}
+
+/// Random challenge chosen by the Verifier
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct ProofChallenge(Scalar);
+
+impl ProofChallenge {
+    pub fn to_bytes(&self) -> [u8; Scalar::BYTES] {
+        self.0.to_bytes_be()
+    }
+
+    pub fn from_bytes(bytes: &[u8; Scalar::BYTES]) -> Result<Self, Error> {
+        Ok(Self(Scalar::from_bytes_be(bytes)?))
+    }
+}
+
+/// Last message of the Prover: the responses to the [`ProofChallenge`]
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct ProofResponse {
+    e_cap: Scalar,
+    r1_cap: Scalar,
+    r3_cap: Scalar,
+    m_cap: Vec<Scalar>,
+}
+
+impl ProofResponse {
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity((3 + self.m_cap.len()) * Scalar::BYTES);
+        for s in [self.e_cap, self.r1_cap, self.r3_cap]
+            .iter()
+            .chain(&self.m_cap)
+        {
+            bytes.extend_from_slice(&s.to_bytes_be());
+        }
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        if bytes.len() < 3 * Scalar::BYTES || bytes.len() % Scalar::BYTES != 0 {
+            return Err(Error::InvalidProofOfKnowledgeSignature);
+        }
+        let mut scalars = bytes
+            .chunks_exact(Scalar::BYTES)
+            .map(Scalar::from_bytes_be)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| Error::InvalidProofOfKnowledgeSignature)?;
+        let m_cap = scalars.split_off(3);
+        Ok(Self {
+            e_cap: scalars[0],
+            r1_cap: scalars[1],
+            r3_cap: scalars[2],
+            m_cap,
+        })
+    }
+}
+
+/// Prover state between the [`ProofCommitment`] and the [`ProofResponse`].
+pub struct InteractiveProver<CS: BbsCiphersuite> {
+    init_res: ProofInitResult,
+    e: Scalar,
+    random_scalars: Zeroizing<Vec<Scalar>>,
+    undisclosed_messages: Zeroizing<Vec<BBSplusMessage>>,
+    _ciphersuite: PhantomData<CS>,
+}
+
+impl<CS: BbsCiphersuite> InteractiveProver<CS> {
+    /// # Description
+    /// First move of the interactive proof: the Prover randomizes the signature and commits to the random scalars, as in the proof initialization of [`crate::schemes::generics::PoKSignature::proof_gen`].
+    ///
+    /// # Inputs:
+    /// * `pk` (REQUIRED), the Signer public key.
+    /// * `signature` (REQUIRED), an octet string.
+    /// * `header` (OPTIONAL), an octet string containing context and application.
+    /// * `messages` (OPTIONAL), a vector of octet strings representing the signed messages.
+    /// * `disclosed_indexes` (OPTIONAL), vector of unsigned integers. Indexes of disclosed messages.
+    ///
+    /// # Output:
+    /// the Prover state and the [`ProofCommitment`] to send to the Verifier, or [`Error`].
+    ///
+    #[cfg(feature = "std")]
+    pub fn commit(
+        pk: &BBSplusPublicKey,
+        signature: &[u8],
+        header: Option<&[u8]>,
+        messages: Option<&[Vec<u8>]>,
+        disclosed_indexes: Option<&[usize]>,
+    ) -> Result<(Self, ProofCommitment), Error>
+    where
+        CS::Expander: for<'a> ExpandMsg<'a>,
+    {
+        Self::commit_with_rng(
+            pk,
+            signature,
+            header,
+            messages,
+            disclosed_indexes,
+            &mut thread_rng(),
+        )
+    }
+
+    /// # Description
+    /// Same as [`Self::commit`], drawing the random scalars from `rng`.
+    ///
+    /// # Inputs:
+    /// * `rng` (REQUIRED), a cryptographically secure random number generator.
+    /// * the other inputs are the same as [`Self::commit`].
+    ///
+    /// # Output:
+    /// the Prover state and the [`ProofCommitment`] to send to the Verifier, or [`Error`].
+    ///
+    pub fn commit_with_rng<R: RngCore + CryptoRng>(
+        pk: &BBSplusPublicKey,
+        signature: &[u8],
+        header: Option<&[u8]>,
+        messages: Option<&[Vec<u8>]>,
+        disclosed_indexes: Option<&[usize]>,
+        rng: &mut R,
+    ) -> Result<(Self, ProofCommitment), Error>
+    where
+        CS::Expander: for<'a> ExpandMsg<'a>,
+    {
+        let signature = BBSplusSignature::from_bytes(
+            signature.try_into().map_err(|_| Error::InvalidSignature)?,
+        )?;
+        let messages =
+            BBSplusMessage::messages_to_scalar::<CS>(messages.unwrap_or(&[]), CS::API_ID)?;
+        let L = messages.len();
+
+        let mut disclosed_indexes = disclosed_indexes.unwrap_or(&[]).to_vec();
+        disclosed_indexes.sort();
+        disclosed_indexes.dedup();
+        if let Some(invalid_index) = disclosed_indexes.iter().find(|&&i| i >= L) {
+            return Err(Error::ProofGenError(format!(
+                "Invalid disclosed index: {}",
+                invalid_index
+            )));
+        }
+        let undisclosed_indexes = get_remaining_indexes(L, &disclosed_indexes);
+        let U = undisclosed_indexes.len();
+
+        let random_scalars = Zeroizing::new(calculate_random_scalars_with_rng(5 + U, rng));
+        let generators = Generators::create::<CS>(L + 1, Some(CS::API_ID));
+
+        let init_res = proof_init::<CS>(
+            pk,
+            &signature,
+            &generators,
+            &random_scalars,
+            header,
+            &messages,
+            &undisclosed_indexes,
+            Some(CS::API_ID),
+        )?;
+
+        let commitment = ProofCommitment {
+            Abar: init_res.Abar,
+            Bbar: init_res.Bbar,
+            D: init_res.D,
+            T1: init_res.T1,
+            T2: init_res.T2,
+        };
+
+        let prover = Self {
+            init_res,
+            e: signature.e,
+            random_scalars,
+            undisclosed_messages: Zeroizing::new(get_messages(&messages, &undisclosed_indexes)),
+            _ciphersuite: PhantomData,
+        };
+
+        Ok((prover, commitment))
+    }
+
+    /// # Description
+    /// Third move of the interactive proof: the Prover answers the challenge of the Verifier, as in the proof finalization of [`crate::schemes::generics::PoKSignature::proof_gen`].
+    ///
+    /// # Inputs:
+    /// * `challenge` (REQUIRED), the [`ProofChallenge`] received from the Verifier.
+    ///
+    /// # Output:
+    /// the [`ProofResponse`] to send to the Verifier, or [`Error`].
+    ///
+    pub fn respond(self, challenge: &ProofChallenge) -> Result<ProofResponse, Error> {
+        let proof = proof_finalize(
+            &self.init_res,
+            challenge.0,
+            self.e,
+            &self.random_scalars,
+            &self.undisclosed_messages,
+        )?;
+
+        Ok(ProofResponse {
+            e_cap: proof.e_cap,
+            r1_cap: proof.r1_cap,
+            r3_cap: proof.r3_cap,
+            m_cap: proof.m_cap,
+        })
+    }
+}
+
+impl<CS: BbsCiphersuite> fmt::Debug for InteractiveProver<CS> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InteractiveProver")
+            .field("init_res", &self.init_res)
+            .field("e", &self.e)
+            .field("random_scalars", &"<redacted>")
+            .field("undisclosed_messages", &"<redacted>")
+            .finish()
+    }
+}
+
+/// Verifier state between the [`ProofChallenge`] and the [`ProofResponse`]
+#[derive(Clone, Debug)]
+pub struct InteractiveVerifier<'a, CS: BbsCiphersuite> {
+    pk: &'a BBSplusPublicKey,
+    header: Option<&'a [u8]>,
+    disclosed_messages: Vec<BBSplusMessage>,
+    disclosed_indexes: Vec<usize>,
+    commitment: ProofCommitment,
+    challenge: ProofChallenge,
+    _ciphersuite: PhantomData<CS>,
+}
+
+impl<'a, CS: BbsCiphersuite> InteractiveVerifier<'a, CS> {
+    /// # Description
+    /// Second move of the interactive proof: the Verifier receives the [`ProofCommitment`] and picks a random challenge.
+    ///
+    /// # Inputs:
+    /// * `pk` (REQUIRED), the Signer public key.
+    /// * `header` (OPTIONAL), an octet string containing context and application.
+    /// * `disclosed_messages` (OPTIONAL), a vector of octet strings representing the disclosed messages.
+    /// * `disclosed_indexes` (OPTIONAL), vector of unsigned integers. Indexes of disclosed messages.
+    /// * `commitment` (REQUIRED), the [`ProofCommitment`] received from the Prover.
+    ///
+    /// # Output:
+    /// the Verifier state and the [`ProofChallenge`] to send to the Prover, or [`Error`].
+    ///
+    #[cfg(feature = "std")]
+    pub fn challenge(
+        pk: &'a BBSplusPublicKey,
+        header: Option<&'a [u8]>,
+        disclosed_messages: Option<&[Vec<u8>]>,
+        disclosed_indexes: Option<&[usize]>,
+        commitment: &ProofCommitment,
+    ) -> Result<(Self, ProofChallenge), Error>
+    where
+        CS::Expander: for<'b> ExpandMsg<'b>,
+    {
+        Self::challenge_with_rng(
+            pk,
+            header,
+            disclosed_messages,
+            disclosed_indexes,
+            commitment,
+            &mut thread_rng(),
+        )
+    }
+
+    /// # Description
+    /// Same as [`Self::challenge`], drawing the challenge from `rng`.
+    ///
+    /// # Inputs:
+    /// * `rng` (REQUIRED), a cryptographically secure random number generator.
+    /// * the other inputs are the same as [`Self::challenge`].
+    ///
+    /// # Output:
+    /// the Verifier state and the [`ProofChallenge`] to send to the Prover, or [`Error`].
+    ///
+    pub fn challenge_with_rng<R: RngCore + CryptoRng>(
+        pk: &'a BBSplusPublicKey,
+        header: Option<&'a [u8]>,
+        disclosed_messages: Option<&[Vec<u8>]>,
+        disclosed_indexes: Option<&[usize]>,
+        commitment: &ProofCommitment,
+        rng: &mut R,
+    ) -> Result<(Self, ProofChallenge), Error>
+    where
+        CS::Expander: for<'b> ExpandMsg<'b>,
+    {
+        let mut disclosed_indexes = disclosed_indexes.unwrap_or(&[]).to_vec();
+        disclosed_indexes.sort();
+        disclosed_indexes.dedup();
+        let disclosed_messages = BBSplusMessage::messages_to_scalar::<CS>(
+            disclosed_messages.unwrap_or(&[]),
+            CS::API_ID,
+        )?;
+        if disclosed_messages.len() != disclosed_indexes.len() {
+            return Err(Error::PoKSVerificationError(
+                "len messages != len indexes".to_owned(),
+            ));
+        }
+
+        let challenge = ProofChallenge(get_random_with_rng(rng));
+        let verifier = Self {
+            pk,
+            header,
+            disclosed_messages,
+            disclosed_indexes,
+            commitment: commitment.clone(),
+            challenge,
+            _ciphersuite: PhantomData,
+        };
+
+        Ok((verifier, challenge))
+    }
+
+    /// # Description
+    /// Checks the [`ProofResponse`] of the Prover: the commitments `T1`, `T2` recomputed from the responses and the challenge must be the ones received in the first move, and the randomized signature must pass the pairing check.
+    ///
+    /// # Inputs:
+    /// * `response` (REQUIRED), the [`ProofResponse`] received from the Prover.
+    ///
+    /// # Output:
+    /// a result: [`Ok`] or [`Error`].
+    ///
+    pub fn verify(self, response: &ProofResponse) -> Result<(), Error> {
+        let proof = BBSplusPoKSignature {
+            Abar: self.commitment.Abar,
+            Bbar: self.commitment.Bbar,
+            D: self.commitment.D,
+            e_cap: response.e_cap,
+            r1_cap: response.r1_cap,
+            r3_cap: response.r3_cap,
+            m_cap: response.m_cap.clone(),
+            challenge: self.challenge.0,
+        };
+
+        let L = proof.m_cap.len() + self.disclosed_indexes.len();
+        let generators = Generators::create::<CS>(L + 1, Some(CS::API_ID));
+
+        let init_res = proof_verify_init::<CS>(
+            self.pk,
+            &proof,
+            &generators,
+            self.header,
+            &self.disclosed_messages,
+            &self.disclosed_indexes,
+            Some(CS::API_ID),
+        )?;
+
+        if init_res.T1 != self.commitment.T1 || init_res.T2 != self.commitment.T2 {
+            return Err(Error::PoKSVerificationError("Invalid response".to_owned()));
+        }
+
+        check_proof_pairing(self.pk, &proof)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        InteractiveProver, InteractiveVerifier, ProofChallenge, ProofCommitment, ProofResponse,
+    };
+    use crate::{
+        bbsplus::ciphersuites::{BbsCiphersuite, Bls12381Sha256, Bls12381Shake256},
+        keys::pair::KeyPair,
+        schemes::{algorithms::BBSplus, generics::Signature},
+    };
+    use elliptic_curve::hash2curve::ExpandMsg;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    fn messages() -> Vec<Vec<u8>> {
+        vec![b"first".to_vec(), b"second".to_vec(), b"third".to_vec()]
+    }
+
+    fn interactive_run<CS: BbsCiphersuite>(disclosed_messages: &[Vec<u8>], cheat: bool) -> bool
+    where
+        CS::Expander: for<'a> ExpandMsg<'a>,
+    {
+        let mut rng = StdRng::seed_from_u64(42);
+        let keypair = KeyPair::<BBSplus<CS>>::random_with_rng(&mut rng).unwrap();
+        let header = b"header";
+        let messages = messages();
+        let signature = Signature::<BBSplus<CS>>::sign(
+            Some(&messages),
+            keypair.private_key(),
+            keypair.public_key(),
+            Some(header),
+        )
+        .unwrap();
+
+        let disclosed_indexes = [0usize, 2];
+        let (prover, commitment) = InteractiveProver::<CS>::commit_with_rng(
+            keypair.public_key(),
+            &signature.to_bytes(),
+            Some(header),
+            Some(&messages),
+            Some(&disclosed_indexes),
+            &mut rng,
+        )
+        .unwrap();
+
+        let (verifier, challenge) = InteractiveVerifier::<CS>::challenge_with_rng(
+            keypair.public_key(),
+            Some(header),
+            Some(disclosed_messages),
+            Some(&disclosed_indexes),
+            &commitment,
+            &mut rng,
+        )
+        .unwrap();
+
+        let response = if cheat {
+            let other = ProofChallenge::from_bytes(&[1u8; 32]).unwrap();
+            prover.respond(&other).unwrap()
+        } else {
+            prover.respond(&challenge).unwrap()
+        };
+
+        verifier.verify(&response).is_ok()
+    }
+
+    #[test]
+    fn interactive_proof_sha256() {
+        let messages = messages();
+        let disclosed = [messages[0].clone(), messages[2].clone()];
+        assert!(interactive_run::<Bls12381Sha256>(&disclosed, false));
+        assert!(!interactive_run::<Bls12381Sha256>(&disclosed, true));
+        let tampered = [messages[0].clone(), b"tampered".to_vec()];
+        assert!(!interactive_run::<Bls12381Sha256>(&tampered, false));
+    }
+
+    #[test]
+    fn interactive_proof_shake256() {
+        let messages = messages();
+        let disclosed = [messages[0].clone(), messages[2].clone()];
+        assert!(interactive_run::<Bls12381Shake256>(&disclosed, false));
+        assert!(!interactive_run::<Bls12381Shake256>(&disclosed, true));
+    }
+
+    #[test]
+    fn interactive_messages_bytes() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let keypair = KeyPair::<BBSplus<Bls12381Sha256>>::random_with_rng(&mut rng).unwrap();
+        let messages = messages();
+        let signature = Signature::<BBSplus<Bls12381Sha256>>::sign(
+            Some(&messages),
+            keypair.private_key(),
+            keypair.public_key(),
+            None,
+        )
+        .unwrap();
+
+        let (prover, commitment) = InteractiveProver::<Bls12381Sha256>::commit_with_rng(
+            keypair.public_key(),
+            &signature.to_bytes(),
+            None,
+            Some(&messages),
+            Some(&[1]),
+            &mut rng,
+        )
+        .unwrap();
+        let commitment = ProofCommitment::from_bytes(&commitment.to_bytes()).unwrap();
+
+        let (verifier, challenge) = InteractiveVerifier::<Bls12381Sha256>::challenge_with_rng(
+            keypair.public_key(),
+            None,
+            Some(&messages[1..2]),
+            Some(&[1]),
+            &commitment,
+            &mut rng,
+        )
+        .unwrap();
+        let challenge = ProofChallenge::from_bytes(&challenge.to_bytes()).unwrap();
+
+        let response = prover.respond(&challenge).unwrap();
+        let bytes = response.to_bytes();
+        assert_eq!(bytes.len(), 5 * 32);
+        let response = ProofResponse::from_bytes(&bytes).unwrap();
+        assert!(verifier.verify(&response).is_ok());
+
+        assert!(ProofResponse::from_bytes(&bytes[..64]).is_err());
+        assert!(ProofCommitment::from_bytes(&[0u8; ProofCommitment::BYTES]).is_err());
+    }
+}
@@ -0,0 +1,591 @@
+// Copyright 2023 Fondazione LINKS
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! [JSON Web Proofs](https://datatracker.ietf.org/doc/draft-ietf-jose-json-web-proof/) with the BBS
+//! [JSON Proof Algorithms](https://datatracker.ietf.org/doc/draft-ietf-jose-json-proof-algorithms/).
+//!
+//! The octets of the issuer protected header are the BBS `header` and the payloads are the signed `messages`.
+//! In the presented form the octets of the presentation protected header are the presentation header (`ph`)
+//! and the disclosed payloads are the ones at the `disclosed_indexes`.
+//!
+//! Compact serialization: payloads are separated by `~`, an undisclosed payload is empty and a zero-length payload is `_`.
+
+use super::{
+    ciphersuites::{BbsCiphersuite, Bls12381Sha256, Bls12381Shake256},
+    keys::{BBSplusPublicKey, BBSplusSecretKey},
+    signature::BBSplusSignature,
+};
+use crate::{
+    errors::Error,
+    schemes::{
+        algorithms::BBSplus,
+        generics::{PoKSignature, Signature},
+    },
+    utils::encoding::{base64url_decode, base64url_encode},
+};
+use elliptic_curve::hash2curve::ExpandMsg;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// Separator of the payloads in the compact serialization
+const PAYLOAD_SEPARATOR: char = '~';
+/// Compact serialization of a zero-length payload
+const EMPTY_PAYLOAD: &str = "_";
+
+/// BBS ciphersuite registered as a JSON Proof Algorithm
+pub trait JpaAlgorithm: BbsCiphersuite {
+    /// Value of the `alg` header parameter
+    const ALG: &'static str;
+}
+
+impl JpaAlgorithm for Bls12381Sha256 {
+    const ALG: &'static str = "BBS";
+}
+
+impl JpaAlgorithm for Bls12381Shake256 {
+    const ALG: &'static str = "BBS-SHAKE256";
+}
+
+/// JWP Issuer Protected Header
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct IssuerProtectedHeader {
+    pub alg: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kid: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub typ: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cid: Option<String>,
+    /// Names of the claims in the payloads, in the same order
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub claims: Option<Vec<String>>,
+    /// Any other header parameter
+    #[serde(flatten)]
+    pub custom: Map<String, Value>,
+}
+
+impl IssuerProtectedHeader {
+    pub fn new<CS: JpaAlgorithm>() -> Self {
+        Self {
+            alg: CS::ALG.to_owned(),
+            kid: None,
+            typ: None,
+            cid: None,
+            claims: None,
+            custom: Map::new(),
+        }
+    }
+}
+
+/// JWP Presentation Protected Header
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct PresentationProtectedHeader {
+    pub alg: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kid: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub aud: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<String>,
+    /// Any other header parameter
+    #[serde(flatten)]
+    pub custom: Map<String, Value>,
+}
+
+impl PresentationProtectedHeader {
+    pub fn new<CS: JpaAlgorithm>() -> Self {
+        Self {
+            alg: CS::ALG.to_owned(),
+            kid: None,
+            aud: None,
+            nonce: None,
+            custom: Map::new(),
+        }
+    }
+}
+
+/// JWP in the issued form, carrying a BBS signature over all the payloads
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct JwpIssued {
+    issuer_header: Vec<u8>,
+    payloads: Vec<Vec<u8>>,
+    proof: Vec<u8>,
+}
+
+/// JWP in the presented form, carrying a BBS proof of knowledge of the signature disclosing some of the payloads
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct JwpPresented {
+    presentation_header: Vec<u8>,
+    issuer_header: Vec<u8>,
+    payloads: Vec<Option<Vec<u8>>>,
+    proof: Vec<u8>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct JwpIssuedJson {
+    issuer: String,
+    payloads: Vec<String>,
+    proof: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct JwpPresentedJson {
+    presentation: String,
+    issuer: String,
+    payloads: Vec<Option<String>>,
+    proof: Vec<String>,
+}
+
+fn invalid(msg: &str) -> Error {
+    Error::DeserializationError(["JWP: ", msg].concat())
+}
+
+fn decode_segment(segment: &str) -> Result<Vec<u8>, Error> {
+    base64url_decode(segment).ok_or_else(|| invalid("invalid base64url"))
+}
+
+/// Checks that the `alg` of a protected header is the one of `CS`
+fn check_alg<CS: JpaAlgorithm>(header: &[u8]) -> Result<(), Error> {
+    #[derive(Deserialize)]
+    struct Alg {
+        alg: String,
+    }
+    let header: Alg =
+        serde_json::from_slice(header).map_err(|_| invalid("invalid protected header"))?;
+    if header.alg != CS::ALG {
+        return Err(Error::CiphersuiteMismatch);
+    }
+    Ok(())
+}
+
+fn encode_compact_payload(payload: Option<&[u8]>) -> String {
+    match payload {
+        None => String::new(),
+        Some([]) => EMPTY_PAYLOAD.to_owned(),
+        Some(payload) => base64url_encode(payload),
+    }
+}
+
+fn decode_compact_payload(segment: &str) -> Result<Option<Vec<u8>>, Error> {
+    match segment {
+        "" => Ok(None),
+        EMPTY_PAYLOAD => Ok(Some(Vec::new())),
+        segment => decode_segment(segment).map(Some),
+    }
+}
+
+fn encode_compact_payloads(payloads: impl Iterator<Item = String>) -> String {
+    payloads
+        .collect::<Vec<_>>()
+        .join(&PAYLOAD_SEPARATOR.to_string())
+}
+
+fn decode_compact_payloads(segment: &str) -> Result<Vec<Option<Vec<u8>>>, Error> {
+    if segment.is_empty() {
+        return Ok(Vec::new());
+    }
+    segment
+        .split(PAYLOAD_SEPARATOR)
+        .map(decode_compact_payload)
+        .collect()
+}
+
+fn decode_json_proof(proof: &[String]) -> Result<Vec<u8>, Error> {
+    match proof {
+        [proof] => decode_segment(proof),
+        _ => Err(invalid("expected a single proof part")),
+    }
+}
+
+impl JwpIssued {
+    /// # Description
+    /// Issues a JWP signing the payloads, with the issuer protected header as the BBS header
+    ///
+    /// # Inputs:
+    /// * `header` (REQUIRED), the issuer protected header, whose `alg` must be the one of `CS`.
+    /// * `payloads` (REQUIRED), the payloads (messages) to sign.
+    /// * `sk` (REQUIRED), the Signer secret key.
+    /// * `pk` (REQUIRED), the Signer public key.
+    ///
+    /// # Output:
+    /// * a [`JwpIssued`] or [`Error`]
+    pub fn sign<CS: JpaAlgorithm>(
+        header: &IssuerProtectedHeader,
+        payloads: &[Vec<u8>],
+        sk: &BBSplusSecretKey,
+        pk: &BBSplusPublicKey,
+    ) -> Result<Self, Error>
+    where
+        CS::Expander: for<'a> ExpandMsg<'a>,
+    {
+        if header.alg != CS::ALG {
+            return Err(Error::CiphersuiteMismatch);
+        }
+        let issuer_header = serde_json::to_vec(header)
+            .map_err(|e| Error::SignatureGenerationError(e.to_string()))?;
+        let signature =
+            Signature::<BBSplus<CS>>::sign(Some(payloads), sk, pk, Some(&issuer_header))?;
+        Ok(Self {
+            issuer_header,
+            payloads: payloads.to_vec(),
+            proof: signature.to_bytes().to_vec(),
+        })
+    }
+
+    /// # Description
+    /// Verifies the BBS signature of the JWP
+    ///
+    /// # Inputs:
+    /// * `pk` (REQUIRED), the Signer public key.
+    ///
+    /// # Output:
+    /// * [`Error::CiphersuiteMismatch`] if the `alg` is not the one of `CS`, another [`Error`] if the signature is not valid
+    pub fn verify<CS: JpaAlgorithm>(&self, pk: &BBSplusPublicKey) -> Result<(), Error>
+    where
+        CS::Expander: for<'a> ExpandMsg<'a>,
+    {
+        check_alg::<CS>(&self.issuer_header)?;
+        self.signature::<CS>()?
+            .verify(pk, Some(&self.payloads), Some(&self.issuer_header))
+    }
+
+    /// # Description
+    /// Derives the presented form of the JWP, disclosing the payloads at `disclosed_indexes`
+    ///
+    /// # Inputs:
+    /// * `pk` (REQUIRED), the Signer public key.
+    /// * `header` (REQUIRED), the presentation protected header (e.g., with the `aud` and `nonce` of the Verifier), whose `alg` must be the one of `CS`.
+    /// * `disclosed_indexes` (REQUIRED), indexes of the payloads to disclose.
+    ///
+    /// # Output:
+    /// * a [`JwpPresented`] or [`Error`]
+    pub fn present<CS: JpaAlgorithm>(
+        &self,
+        pk: &BBSplusPublicKey,
+        header: &PresentationProtectedHeader,
+        disclosed_indexes: &[usize],
+    ) -> Result<JwpPresented, Error>
+    where
+        CS::Expander: for<'a> ExpandMsg<'a>,
+    {
+        check_alg::<CS>(&self.issuer_header)?;
+        if header.alg != CS::ALG {
+            return Err(Error::CiphersuiteMismatch);
+        }
+        let presentation_header =
+            serde_json::to_vec(header).map_err(|e| Error::ProofGenError(e.to_string()))?;
+        let proof = PoKSignature::<BBSplus<CS>>::proof_gen(
+            pk,
+            &self.proof,
+            Some(&self.issuer_header),
+            Some(&presentation_header),
+            Some(&self.payloads),
+            Some(disclosed_indexes),
+        )?;
+        let payloads = self
+            .payloads
+            .iter()
+            .enumerate()
+            .map(|(i, payload)| disclosed_indexes.contains(&i).then(|| payload.clone()))
+            .collect();
+        Ok(JwpPresented {
+            presentation_header,
+            issuer_header: self.issuer_header.clone(),
+            payloads,
+            proof: proof.to_bytes(),
+        })
+    }
+
+    pub fn issuer_protected_header(&self) -> Result<IssuerProtectedHeader, Error> {
+        serde_json::from_slice(&self.issuer_header)
+            .map_err(|_| invalid("invalid issuer protected header"))
+    }
+
+    pub fn payloads(&self) -> &[Vec<u8>] {
+        &self.payloads
+    }
+
+    pub fn to_compact(&self) -> String {
+        [
+            base64url_encode(&self.issuer_header),
+            encode_compact_payloads(
+                self.payloads
+                    .iter()
+                    .map(|p| encode_compact_payload(Some(p))),
+            ),
+            base64url_encode(&self.proof),
+        ]
+        .join(".")
+    }
+
+    pub fn from_compact(jwp: &str) -> Result<Self, Error> {
+        let [issuer_header, payloads, proof]: [&str; 3] = jwp
+            .split('.')
+            .collect::<Vec<_>>()
+            .try_into()
+            .map_err(|_| invalid("expected 3 parts in the issued form"))?;
+        let payloads = decode_compact_payloads(payloads)?
+            .into_iter()
+            .map(|p| p.ok_or_else(|| invalid("undisclosed payload in the issued form")))
+            .collect::<Result<_, _>>()?;
+        Ok(Self {
+            issuer_header: decode_segment(issuer_header)?,
+            payloads,
+            proof: decode_segment(proof)?,
+        })
+    }
+
+    pub fn to_json(&self) -> String {
+        let jwp = JwpIssuedJson {
+            issuer: base64url_encode(&self.issuer_header),
+            payloads: self.payloads.iter().map(|p| base64url_encode(p)).collect(),
+            proof: vec![base64url_encode(&self.proof)],
+        };
+        serde_json::to_string(&jwp).expect("serializable")
+    }
+
+    pub fn from_json(jwp: &str) -> Result<Self, Error> {
+        let jwp: JwpIssuedJson =
+            serde_json::from_str(jwp).map_err(|_| invalid("invalid JSON serialization"))?;
+        Ok(Self {
+            issuer_header: decode_segment(&jwp.issuer)?,
+            payloads: jwp
+                .payloads
+                .iter()
+                .map(|p| decode_segment(p))
+                .collect::<Result<_, _>>()?,
+            proof: decode_json_proof(&jwp.proof)?,
+        })
+    }
+
+    fn signature<CS: JpaAlgorithm>(&self) -> Result<Signature<BBSplus<CS>>, Error> {
+        let bytes: &[u8; BBSplusSignature::BYTES] = self
+            .proof
+            .as_slice()
+            .try_into()
+            .map_err(|_| Error::InvalidSignature)?;
+        Signature::<BBSplus<CS>>::from_bytes(bytes)
+    }
+}
+
+impl JwpPresented {
+    /// # Description
+    /// Verifies the BBS proof of the JWP against the disclosed payloads and both the protected headers
+    ///
+    /// # Inputs:
+    /// * `pk` (REQUIRED), the Signer public key.
+    ///
+    /// # Output:
+    /// * [`Error::CiphersuiteMismatch`] if an `alg` is not the one of `CS`, another [`Error`] if the proof is not valid
+    pub fn verify<CS: JpaAlgorithm>(&self, pk: &BBSplusPublicKey) -> Result<(), Error>
+    where
+        CS::Expander: for<'a> ExpandMsg<'a>,
+    {
+        check_alg::<CS>(&self.issuer_header)?;
+        check_alg::<CS>(&self.presentation_header)?;
+        let (disclosed_indexes, disclosed_payloads): (Vec<usize>, Vec<Vec<u8>>) = self
+            .payloads
+            .iter()
+            .enumerate()
+            .filter_map(|(i, payload)| payload.clone().map(|p| (i, p)))
+            .unzip();
+        PoKSignature::<BBSplus<CS>>::from_bytes(&self.proof)?.proof_verify(
+            pk,
+            Some(&disclosed_payloads),
+            Some(&disclosed_indexes),
+            Some(&self.issuer_header),
+            Some(&self.presentation_header),
+        )
+    }
+
+    pub fn issuer_protected_header(&self) -> Result<IssuerProtectedHeader, Error> {
+        serde_json::from_slice(&self.issuer_header)
+            .map_err(|_| invalid("invalid issuer protected header"))
+    }
+
+    pub fn presentation_protected_header(&self) -> Result<PresentationProtectedHeader, Error> {
+        serde_json::from_slice(&self.presentation_header)
+            .map_err(|_| invalid("invalid presentation protected header"))
+    }
+
+    /// All the payloads, `None` for the undisclosed ones
+    pub fn payloads(&self) -> &[Option<Vec<u8>>] {
+        &self.payloads
+    }
+
+    pub fn to_compact(&self) -> String {
+        [
+            base64url_encode(&self.presentation_header),
+            base64url_encode(&self.issuer_header),
+            encode_compact_payloads(
+                self.payloads
+                    .iter()
+                    .map(|p| encode_compact_payload(p.as_deref())),
+            ),
+            base64url_encode(&self.proof),
+        ]
+        .join(".")
+    }
+
+    pub fn from_compact(jwp: &str) -> Result<Self, Error> {
+        let [presentation_header, issuer_header, payloads, proof]: [&str; 4] = jwp
+            .split('.')
+            .collect::<Vec<_>>()
+            .try_into()
+            .map_err(|_| invalid("expected 4 parts in the presented form"))?;
+        Ok(Self {
+            presentation_header: decode_segment(presentation_header)?,
+            issuer_header: decode_segment(issuer_header)?,
+            payloads: decode_compact_payloads(payloads)?,
+            proof: decode_segment(proof)?,
+        })
+    }
+
+    pub fn to_json(&self) -> String {
+        let jwp = JwpPresentedJson {
+            presentation: base64url_encode(&self.presentation_header),
+            issuer: base64url_encode(&self.issuer_header),
+            payloads: self
+                .payloads
+                .iter()
+                .map(|p| p.as_deref().map(base64url_encode))
+                .collect(),
+            proof: vec![base64url_encode(&self.proof)],
+        };
+        serde_json::to_string(&jwp).expect("serializable")
+    }
+
+    pub fn from_json(jwp: &str) -> Result<Self, Error> {
+        let jwp: JwpPresentedJson =
+            serde_json::from_str(jwp).map_err(|_| invalid("invalid JSON serialization"))?;
+        Ok(Self {
+            presentation_header: decode_segment(&jwp.presentation)?,
+            issuer_header: decode_segment(&jwp.issuer)?,
+            payloads: jwp
+                .payloads
+                .iter()
+                .map(|p| p.as_deref().map(decode_segment).transpose())
+                .collect::<Result<_, _>>()?,
+            proof: decode_json_proof(&jwp.proof)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{IssuerProtectedHeader, JwpIssued, JwpPresented, PresentationProtectedHeader};
+    use crate::{
+        bbsplus::ciphersuites::{Bls12381Sha256, Bls12381Shake256},
+        errors::Error,
+        keys::pair::KeyPair,
+        schemes::algorithms::BBSplus,
+    };
+
+    fn issued() -> (KeyPair<BBSplus<Bls12381Sha256>>, JwpIssued) {
+        let keypair = KeyPair::<BBSplus<Bls12381Sha256>>::random().unwrap();
+        let mut header = IssuerProtectedHeader::new::<Bls12381Sha256>();
+        header.kid = Some("issuer-key".to_owned());
+        header.claims = Some(vec!["name".into(), "age".into(), "nickname".into()]);
+        let payloads = [b"\"Alice\"".to_vec(), b"42".to_vec(), Vec::new()];
+        let jwp = JwpIssued::sign::<Bls12381Sha256>(
+            &header,
+            &payloads,
+            keypair.private_key(),
+            keypair.public_key(),
+        )
+        .unwrap();
+        (keypair, jwp)
+    }
+
+    #[test]
+    fn issued_form() {
+        let (keypair, jwp) = issued();
+        let pk = keypair.public_key();
+        jwp.verify::<Bls12381Sha256>(pk).unwrap();
+        assert!(matches!(
+            jwp.verify::<Bls12381Shake256>(pk),
+            Err(Error::CiphersuiteMismatch)
+        ));
+        assert_eq!(
+            jwp.issuer_protected_header().unwrap().kid.as_deref(),
+            Some("issuer-key")
+        );
+
+        let compact = jwp.to_compact();
+        // The zero-length payload is encoded as "_"
+        assert_eq!(compact.split('.').nth(1).unwrap(), "IkFsaWNlIg~NDI~_");
+        assert_eq!(JwpIssued::from_compact(&compact).unwrap(), jwp);
+        assert_eq!(JwpIssued::from_json(&jwp.to_json()).unwrap(), jwp);
+
+        let tampered = compact.replace("NDI", "NDM");
+        assert!(JwpIssued::from_compact(&tampered)
+            .unwrap()
+            .verify::<Bls12381Sha256>(pk)
+            .is_err());
+        assert!(JwpIssued::from_compact("a.b").is_err());
+        assert!(JwpIssued::from_compact(&compact.replace("NDI", "")).is_err());
+    }
+
+    #[test]
+    fn presented_form() {
+        let (keypair, jwp) = issued();
+        let pk = keypair.public_key();
+        let mut header = PresentationProtectedHeader::new::<Bls12381Sha256>();
+        header.aud = Some("https://verifier.example".to_owned());
+        header.nonce = Some("wrmBRkKtXjQ".to_owned());
+
+        let presented = jwp.present::<Bls12381Sha256>(pk, &header, &[0, 2]).unwrap();
+        assert_eq!(
+            presented.payloads(),
+            [Some(b"\"Alice\"".to_vec()), None, Some(Vec::new())]
+        );
+        presented.verify::<Bls12381Sha256>(pk).unwrap();
+        assert_eq!(presented.presentation_protected_header().unwrap(), header);
+
+        let compact = presented.to_compact();
+        assert_eq!(compact.split('.').nth(2).unwrap(), "IkFsaWNlIg~~_");
+        let received = JwpPresented::from_compact(&compact).unwrap();
+        assert_eq!(received, presented);
+        received.verify::<Bls12381Sha256>(pk).unwrap();
+
+        let json = presented.to_json();
+        assert!(json.contains(r#""payloads":["IkFsaWNlIg",null,""]"#));
+        assert_eq!(JwpPresented::from_json(&json).unwrap(), presented);
+
+        // Disclosing a payload which was not presented, or replaying the proof for another Verifier, fails
+        let [presentation, issuer, _, proof]: [&str; 4] =
+            compact.split('.').collect::<Vec<_>>().try_into().unwrap();
+        let forged = [presentation, issuer, "IkFsaWNlIg~NDI~_", proof].join(".");
+        assert!(JwpPresented::from_compact(&forged)
+            .unwrap()
+            .verify::<Bls12381Sha256>(pk)
+            .is_err());
+        header.aud = Some("https://other.example".to_owned());
+        let other_presentation = super::base64url_encode(&serde_json::to_vec(&header).unwrap());
+        let replayed = [other_presentation.as_str(), issuer, "IkFsaWNlIg~~_", proof].join(".");
+        assert!(JwpPresented::from_compact(&replayed)
+            .unwrap()
+            .verify::<Bls12381Sha256>(pk)
+            .is_err());
+
+        assert!(matches!(
+            jwp.present::<Bls12381Sha256>(
+                pk,
+                &PresentationProtectedHeader::new::<Bls12381Shake256>(),
+                &[0]
+            ),
+            Err(Error::CiphersuiteMismatch)
+        ));
+    }
+}
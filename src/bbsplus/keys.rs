@@ -20,14 +20,29 @@ use crate::{
         traits::{PrivateKey, PublicKey},
     },
     schemes::algorithms::BBSplus,
-    utils::util::bbsplus_utils::{
-        generate_random_secret, hash_to_scalar, i2osp, parse_g2_projective_compressed,
-        parse_g2_projective_uncompressed,
+    utils::{
+        cbor::{decode_with_ciphersuite, encode_with_ciphersuite},
+        encoding::{
+            base58btc_decode, base58btc_encode, base64url_decode, base64url_encode,
+            MULTIBASE_BASE58BTC,
+        },
+        util::bbsplus_utils::{
+            generate_random_secret_with_rng, hash_to_scalar, i2osp, parse_g1_projective,
+            parse_g2_projective_compressed, parse_g2_projective_uncompressed,
+        },
     },
 };
-use bls12_381_plus::{G2Affine, G2Projective, Scalar};
-use elliptic_curve::{group::Curve, hash2curve::ExpandMsg};
+use alloc::{borrow::ToOwned, format, string::String, vec::Vec};
+use bls12_381_plus::{
+    multi_miller_loop, G1Affine, G1Projective, G2Affine, G2Prepared, G2Projective, Scalar,
+};
+use core::fmt;
+use elliptic_curve::{group::Curve, hash2curve::ExpandMsg, Group};
+#[cfg(feature = "std")]
+use rand::thread_rng;
+use rand::{CryptoRng, RngCore};
 use serde::{Deserialize, Serialize};
+use zeroize::{Zeroize, ZeroizeOnDrop};
 
 #[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
 pub struct BBSplusPublicKey(pub G2Projective);
@@ -41,7 +56,7 @@ impl BBSplusPublicKey {
         let mut x = [0u8; G2Affine::UNCOMPRESSED_BYTES / 2];
         let mut y = [0u8; G2Affine::UNCOMPRESSED_BYTES / 2];
         let mid_index = G2Affine::UNCOMPRESSED_BYTES / 2;
-        let (first, second) = uncompressed.split_at(mid_index).try_into().unwrap();
+        let (first, second) = uncompressed.split_at(mid_index);
         x.copy_from_slice(first);
         y.copy_from_slice(second);
         (x, y)
@@ -77,20 +92,104 @@ impl BBSplusPublicKey {
     }
 
     pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
-        let g2 = parse_g2_projective_compressed(&bytes[0..G2Affine::COMPRESSED_BYTES])
-            .map_err(|_| Error::KeyDeserializationError)?;
+        let bytes = bytes
+            .get(0..G2Affine::COMPRESSED_BYTES)
+            .ok_or(Error::KeyDeserializationError)?;
+        let g2 =
+            parse_g2_projective_compressed(bytes).map_err(|_| Error::KeyDeserializationError)?;
+        Ok(Self(g2))
+    }
+
+    /// https://datatracker.ietf.org/doc/html/draft-irtf-cfrg-bls-signature-05#name-keyvalidate
+    ///
+    /// # Description
+    /// This operation checks that the public key is a valid point of G2, i.e. it is not the identity and it belongs to the prime order subgroup. Keys obtained from untrusted sources (e.g., an issuer registry) SHOULD be validated before use.
+    ///
+    /// # Output:
+    /// a result: [`Ok`] or [`Error::InvalidPublicKey`].
+    ///
+    pub fn validate(&self) -> Result<(), Error> {
+        let point = self.0.to_affine();
+        if point.is_identity().into()
+            || !bool::from(point.is_on_curve())
+            || !bool::from(point.is_torsion_free())
+        {
+            return Err(Error::InvalidPublicKey);
+        }
+        Ok(())
+    }
+
+    /// Multicodec prefix of a `bls12_381-g2-pub` key (0xeb as unsigned varint)
+    pub const MULTICODEC: [u8; 2] = [0xeb, 0x01];
+
+    /// Encodes the public key as a [Multikey](https://www.w3.org/TR/cid-1.0/#Multikey) `publicKeyMultibase`
+    /// value: the multicodec `bls12_381-g2-pub` prefix followed by the compressed key, in base58btc.
+    pub fn to_multikey(&self) -> String {
+        encode_multikey(&Self::MULTICODEC, &self.to_bytes())
+    }
+
+    pub fn from_multikey(multikey: &str) -> Result<Self, Error> {
+        let bytes = decode_multikey(&Self::MULTICODEC, multikey)?;
+        Self::from_compressed_slice(&bytes)
+    }
+
+    /// Encodes the public key as a [did:key](https://w3c-ccg.github.io/did-method-key/) identifier
+    pub fn to_did_key(&self) -> String {
+        format!("{}{}", DID_KEY_PREFIX, self.to_multikey())
+    }
+
+    /// Decodes a did:key identifier, or a did:key URL with a fragment
+    pub fn from_did_key(did: &str) -> Result<Self, Error> {
+        let multikey = did
+            .strip_prefix(DID_KEY_PREFIX)
+            .ok_or(Error::KeyDeserializationError)?;
+        let multikey = multikey.split('#').next().unwrap_or(multikey);
+        Self::from_multikey(multikey)
+    }
+
+    /// Encodes the public key as a JWK with `kty` "OKP", `crv` "BLS12381G2" and the compressed key as `x`
+    pub fn to_jwk(&self) -> BBSplusJwk {
+        BBSplusJwk {
+            kty: JWK_KTY.to_owned(),
+            crv: JWK_CRV.to_owned(),
+            x: base64url_encode(&self.to_bytes()),
+            d: None,
+        }
+    }
+
+    pub fn from_jwk(jwk: &BBSplusJwk) -> Result<Self, Error> {
+        if jwk.kty != JWK_KTY || jwk.crv != JWK_CRV {
+            return Err(Error::KeyDeserializationError);
+        }
+        let bytes = base64url_decode(&jwk.x).ok_or(Error::KeyDeserializationError)?;
+        Self::from_compressed_slice(&bytes)
+    }
+
+    /// Encodes the public key as the CBOR array `[ciphersuite_id, public_key]`, binding the key to the ciphersuite `CS` it is used with
+    pub fn to_cbor<CS: BbsCiphersuite>(&self) -> Vec<u8> {
+        encode_with_ciphersuite(CS::ID, &self.to_bytes())
+    }
+
+    /// Decodes a public key encoded with [`Self::to_cbor`], failing with [`Error::CiphersuiteMismatch`] if it was encoded for another ciphersuite
+    pub fn from_cbor<CS: BbsCiphersuite>(bytes: &[u8]) -> Result<Self, Error> {
+        Self::from_compressed_slice(&decode_with_ciphersuite(CS::ID, bytes)?)
+    }
+
+    fn from_compressed_slice(bytes: &[u8]) -> Result<Self, Error> {
+        let g2 =
+            parse_g2_projective_compressed(bytes).map_err(|_| Error::KeyDeserializationError)?;
         Ok(Self(g2))
     }
 }
 
-#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+/// BBS secret key.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct BBSplusSecretKey(pub Scalar);
 
 impl BBSplusSecretKey {
     /// In Big Endian order
     pub fn to_bytes(&self) -> [u8; Scalar::BYTES] {
-        let bytes = self.0.to_be_bytes();
-        bytes
+        self.0.to_be_bytes()
     }
 
     pub fn encode(&self) -> String {
@@ -114,6 +213,200 @@ impl BBSplusSecretKey {
 
         Ok(Self(s.unwrap()))
     }
+
+    /// Multicodec prefix of a `bls12_381-g2-priv` key (0x130a as unsigned varint)
+    pub const MULTICODEC: [u8; 2] = [0x8a, 0x26];
+
+    /// Encodes the secret key as a Multikey `secretKeyMultibase` value: the multicodec
+    /// `bls12_381-g2-priv` prefix followed by the big endian secret key, in base58btc.
+    pub fn to_multikey(&self) -> String {
+        encode_multikey(&Self::MULTICODEC, &self.to_bytes())
+    }
+
+    pub fn from_multikey(multikey: &str) -> Result<Self, Error> {
+        let mut bytes = decode_multikey(&Self::MULTICODEC, multikey)?;
+        let sk = Self::from_bytes(&bytes);
+        bytes.zeroize();
+        sk
+    }
+
+    /// Encodes the secret key as a JWK carrying both the public key (`x`) and the secret key (`d`)
+    pub fn to_jwk(&self) -> BBSplusJwk {
+        let mut jwk = self.public_key().to_jwk();
+        jwk.d = Some(base64url_encode(&self.to_bytes()));
+        jwk
+    }
+
+    /// Decodes the secret key of a JWK, checking that `x` is the corresponding public key
+    pub fn from_jwk(jwk: &BBSplusJwk) -> Result<Self, Error> {
+        let pk = BBSplusPublicKey::from_jwk(jwk)?;
+        let mut bytes = base64url_decode(jwk.d.as_ref().ok_or(Error::KeyDeserializationError)?)
+            .ok_or(Error::KeyDeserializationError)?;
+        let sk = Self::from_bytes(&bytes);
+        bytes.zeroize();
+        let sk = sk?;
+        if sk.public_key() != pk {
+            return Err(Error::KeyDeserializationError);
+        }
+        Ok(sk)
+    }
+}
+
+impl Zeroize for BBSplusSecretKey {
+    fn zeroize(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl Drop for BBSplusSecretKey {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for BBSplusSecretKey {}
+
+impl fmt::Debug for BBSplusSecretKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("BBSplusSecretKey(<redacted>)")
+    }
+}
+
+/// Proof of possession of a [`BBSplusSecretKey`]: a BLS signature, with the secret key, on the encoding of the corresponding [`BBSplusPublicKey`], binding the public key to someone who knows its secret key (e.g., to reject rogue keys when onboarding an issuer).
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct BBSplusProofOfPossession(G1Projective);
+
+impl BBSplusProofOfPossession {
+    pub const BYTES: usize = G1Affine::COMPRESSED_BYTES;
+
+    /// https://datatracker.ietf.org/doc/html/draft-irtf-cfrg-bls-signature-05#name-popprove
+    ///
+    /// # Description
+    /// This operation generates the proof of possession of `sk`, i.e. `proof = SK * hash_to_curve_g1(PK, pop_dst)`, where `pop_dst` is [`BbsCiphersuite::ID`] || [`BbsCiphersuite::POP_DST`].
+    ///
+    /// # Inputs:
+    /// * `sk` (REQUIRED), the secret key.
+    ///
+    /// # Output:
+    /// a [`BBSplusProofOfPossession`].
+    ///
+    pub fn generate<CS: BbsCiphersuite>(sk: &BBSplusSecretKey) -> Self
+    where
+        CS::Expander: for<'a> ExpandMsg<'a>,
+    {
+        Self(pop_point::<CS>(&sk.public_key()) * sk.0)
+    }
+
+    /// https://datatracker.ietf.org/doc/html/draft-irtf-cfrg-bls-signature-05#name-popverify
+    ///
+    /// # Description
+    /// This operation checks the proof of possession against a public key, i.e. `e(proof, BP2) == e(hash_to_curve_g1(PK, pop_dst), PK)`. The public key is validated as well (see [`BBSplusPublicKey::validate`]).
+    ///
+    /// # Inputs:
+    /// * `pk` (REQUIRED), the public key.
+    ///
+    /// # Output:
+    /// a result: [`Ok`] or [`Error`].
+    ///
+    pub fn verify<CS: BbsCiphersuite>(&self, pk: &BBSplusPublicKey) -> Result<(), Error>
+    where
+        CS::Expander: for<'a> ExpandMsg<'a>,
+    {
+        pk.validate()?;
+
+        let proof = self.0.to_affine();
+        if proof.is_identity().into() {
+            return Err(Error::InvalidProofOfPossession);
+        }
+
+        let BP2 = G2Projective::GENERATOR;
+        let term1 = (&proof, &G2Prepared::from(-BP2.to_affine()));
+        let term2 = (
+            &pop_point::<CS>(pk).to_affine(),
+            &G2Prepared::from(pk.0.to_affine()),
+        );
+
+        let pairing = multi_miller_loop(&[term1, term2]).final_exponentiation();
+
+        if pairing.is_identity().into() {
+            Ok(())
+        } else {
+            Err(Error::InvalidProofOfPossession)
+        }
+    }
+
+    pub fn to_bytes(&self) -> [u8; Self::BYTES] {
+        self.0.to_affine().to_compressed()
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        let point = parse_g1_projective(bytes).map_err(|_| Error::InvalidProofOfPossession)?;
+        Ok(Self(point))
+    }
+}
+
+/// `hash_to_curve_g1(PK, ciphersuite_id || "POP_")`
+fn pop_point<CS: BbsCiphersuite>(pk: &BBSplusPublicKey) -> G1Projective
+where
+    CS::Expander: for<'a> ExpandMsg<'a>,
+{
+    let pop_dst = [CS::ID, CS::POP_DST].concat();
+    G1Projective::hash::<CS::Expander>(&pk.to_bytes(), &pop_dst)
+}
+
+const DID_KEY_PREFIX: &str = "did:key:";
+const JWK_KTY: &str = "OKP";
+const JWK_CRV: &str = "BLS12381G2";
+
+fn encode_multikey(multicodec: &[u8; 2], key: &[u8]) -> String {
+    let mut bytes = Vec::with_capacity(multicodec.len() + key.len());
+    bytes.extend_from_slice(multicodec);
+    bytes.extend_from_slice(key);
+    let mut multikey = String::from(MULTIBASE_BASE58BTC);
+    multikey.push_str(&base58btc_encode(&bytes));
+    bytes.zeroize();
+    multikey
+}
+
+fn decode_multikey(multicodec: &[u8; 2], multikey: &str) -> Result<Vec<u8>, Error> {
+    let encoded = multikey
+        .strip_prefix(MULTIBASE_BASE58BTC)
+        .ok_or(Error::KeyDeserializationError)?;
+    let mut bytes = base58btc_decode(encoded).ok_or(Error::KeyDeserializationError)?;
+    if !bytes.starts_with(multicodec) {
+        bytes.zeroize();
+        return Err(Error::KeyDeserializationError);
+    }
+    bytes.drain(..multicodec.len());
+    Ok(bytes)
+}
+
+/// JSON Web Key of a BLS12-381 G2 key, with `kty` "OKP" and `crv` "BLS12381G2".
+/// `x` is the base64url encoded compressed public key, `d` the base64url encoded secret key (only in the JWK of a secret key).
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BBSplusJwk {
+    pub kty: String,
+    pub crv: String,
+    pub x: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub d: Option<String>,
+}
+
+impl Drop for BBSplusJwk {
+    fn drop(&mut self) {
+        self.d.zeroize();
+    }
+}
+
+impl fmt::Debug for BBSplusJwk {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BBSplusJwk")
+            .field("kty", &self.kty)
+            .field("crv", &self.crv)
+            .field("x", &self.x)
+            .field("d", &self.d.as_ref().map(|_| "<redacted>"))
+            .finish()
+    }
 }
 
 impl PublicKey for BBSplusPublicKey {
@@ -176,11 +469,27 @@ impl<CS: BbsCiphersuite> KeyPair<BBSplus<CS>> {
     ///
     /// # Output:
     /// * a keypair [`KeyPair`]
+    #[cfg(feature = "std")]
     pub fn random() -> Result<Self, Error>
     where
         CS::Expander: for<'a> ExpandMsg<'a>,
     {
-        let key_material = generate_random_secret(64);
+        Self::random_with_rng(&mut thread_rng())
+    }
+
+    /// # Description
+    /// This operation generates a random keypair (SK, PK), drawing the key material from `rng`
+    ///
+    /// # Inputs:
+    /// * `rng` (REQUIRED), a cryptographically secure random number generator.
+    ///
+    /// # Output:
+    /// * a keypair [`KeyPair`]
+    pub fn random_with_rng<R: RngCore + CryptoRng>(rng: &mut R) -> Result<Self, Error>
+    where
+        CS::Expander: for<'a> ExpandMsg<'a>,
+    {
+        let key_material = generate_random_secret_with_rng(64, rng);
         let sk = key_gen::<CS>(&key_material, None, None)?;
 
         let pk = sk_to_pk(sk);
@@ -200,10 +509,10 @@ impl<CS: BbsCiphersuite> KeyPair<BBSplus<CS>> {
 /// # Inputs:
 /// * `key_material` (REQUIRED), a secret octet string. MUST be at least 32 Bytes.
 /// * `key_info` (OPTIONAL), an octet string. Defaults to an empty string if
-/// not supplied.
+///   not supplied.
 /// * `key_dst` (OPTIONAL), an octet string representing the domain separation
-/// tag. Defaults to the octet string
-/// ciphersuite_id || "KEYGEN_DST_" if not supplied.
+///   tag. Defaults to the octet string
+///   ciphersuite_id || "KEYGEN_DST_" if not supplied.
 /// # Output:
 /// * SK, a [`Scalar`]
 ///  
@@ -251,18 +560,22 @@ where
 ///
 fn sk_to_pk(sk: Scalar) -> G2Projective {
     // W = SK * BP2
-    let pk = G2Affine::generator() * sk;
-    pk
+    G2Affine::generator() * sk
 }
 
 #[cfg(test)]
 mod tests {
 
-    use crate::bbsplus::ciphersuites::BbsCiphersuite;
+    use super::{BBSplusJwk, BBSplusProofOfPossession, BBSplusPublicKey, BBSplusSecretKey};
+    use crate::bbsplus::ciphersuites::{BbsCiphersuite, Bls12381Sha256, Bls12381Shake256};
+    use crate::errors::Error;
     use crate::schemes::algorithms::Scheme;
     use crate::schemes::algorithms::{BbsBls12381Sha256, BbsBls12381Shake256};
     use crate::{keys::pair::KeyPair, schemes::algorithms::BBSplus};
+    use bls12_381_plus::{G2Affine, G2Projective, Scalar};
+    use rand::{rngs::StdRng, SeedableRng};
     use std::fs;
+    use zeroize::Zeroize;
 
     //KEYPAIR - SHA256
 
@@ -278,6 +591,162 @@ mod tests {
         key_pair_gen::<BbsBls12381Shake256>("./fixture_data/bls12-381-shake-256/keypair.json");
     }
 
+    //SECRET KEY - zeroize and redacted Debug
+
+    #[test]
+    fn secret_key_zeroize() {
+        let keypair =
+            KeyPair::<BBSplus<<BbsBls12381Sha256 as Scheme>::Ciphersuite>>::random().unwrap();
+        let mut sk = keypair.private_key().clone();
+        assert_eq!(format!("{:?}", sk), "BBSplusSecretKey(<redacted>)");
+        assert!(!format!("{:?}", keypair).contains(&sk.encode()));
+
+        sk.zeroize();
+        assert_eq!(sk.0, Scalar::ZERO);
+    }
+
+    //KEY VALIDATION AND PROOF OF POSSESSION
+
+    #[test]
+    fn public_key_validate() {
+        let keypair =
+            KeyPair::<BBSplus<Bls12381Sha256>>::random_with_rng(&mut StdRng::seed_from_u64(3))
+                .unwrap();
+        assert!(keypair.public_key().validate().is_ok());
+
+        let identity = BBSplusPublicKey(G2Projective::IDENTITY);
+        assert!(matches!(identity.validate(), Err(Error::InvalidPublicKey)));
+
+        // a point on the curve outside of the prime order subgroup
+        let outside_subgroup = (1u8..)
+            .find_map(|x| {
+                let mut bytes = [0u8; G2Affine::COMPRESSED_BYTES];
+                bytes[0] = 0x80;
+                bytes[G2Affine::COMPRESSED_BYTES - 1] = x;
+                Option::<G2Affine>::from(G2Affine::from_compressed_unchecked(&bytes))
+                    .filter(|p| !bool::from(p.is_torsion_free()))
+            })
+            .unwrap();
+        let rogue = BBSplusPublicKey(outside_subgroup.into());
+        assert!(matches!(rogue.validate(), Err(Error::InvalidPublicKey)));
+        assert!(BBSplusPublicKey::from_bytes(&rogue.to_bytes()).is_err());
+    }
+
+    #[test]
+    fn proof_of_possession() {
+        let mut rng = StdRng::seed_from_u64(5);
+        let keypair = KeyPair::<BBSplus<Bls12381Sha256>>::random_with_rng(&mut rng).unwrap();
+        let other = KeyPair::<BBSplus<Bls12381Sha256>>::random_with_rng(&mut rng).unwrap();
+        let (sk, pk) = (keypair.private_key(), keypair.public_key());
+
+        let pop = BBSplusProofOfPossession::generate::<Bls12381Sha256>(sk);
+        assert!(pop.verify::<Bls12381Sha256>(pk).is_ok());
+        assert!(matches!(
+            pop.verify::<Bls12381Sha256>(other.public_key()),
+            Err(Error::InvalidProofOfPossession)
+        ));
+        assert!(pop.verify::<Bls12381Shake256>(pk).is_err());
+
+        // rogue key PK' = PK_attacker - PK_victim, whose secret key is unknown
+        let rogue = BBSplusPublicKey(other.public_key().0 - pk.0);
+        assert!(pop.verify::<Bls12381Sha256>(&rogue).is_err());
+
+        let decoded = BBSplusProofOfPossession::from_bytes(&pop.to_bytes()).unwrap();
+        assert_eq!(decoded, pop);
+        assert!(BBSplusProofOfPossession::from_bytes(&pop.to_bytes()[1..]).is_err());
+
+        let pop = BBSplusProofOfPossession::generate::<Bls12381Shake256>(sk);
+        assert!(pop.verify::<Bls12381Shake256>(pk).is_ok());
+    }
+
+    //MULTIKEY, DID:KEY, JWK
+
+    #[test]
+    fn did_key_vector() {
+        // BLS12-381 G2 example of the did:key specification
+        const DID: &str = "did:key:zUC7EK3ZakmukHhuncwkbySmomv3FmrkmS36E4Ks5rsb6VQSRpoCrx6Hb8e2Nk6UvJFSdyw9NK1scFXJp21gNNYFjVWNgaqyGnkyhtagagCpQb5B7tagJu3HDbjQ8h5ypoHjwBb";
+        let pk = BBSplusPublicKey::from_did_key(DID).unwrap();
+        assert_eq!(pk.to_did_key(), DID);
+        let multikey = DID.strip_prefix("did:key:").unwrap();
+        assert_eq!(pk.to_multikey(), multikey);
+        assert_eq!(
+            BBSplusPublicKey::from_did_key(&format!("{}#{}", DID, multikey)).unwrap(),
+            pk
+        );
+    }
+
+    #[test]
+    fn multikey_and_jwk_round_trip() {
+        let keypair =
+            KeyPair::<BBSplus<<BbsBls12381Sha256 as Scheme>::Ciphersuite>>::random_with_rng(
+                &mut StdRng::seed_from_u64(7),
+            )
+            .unwrap();
+        let (sk, pk) = (keypair.private_key(), keypair.public_key());
+
+        let pk_multikey = pk.to_multikey();
+        assert!(pk_multikey.starts_with("zUC7"));
+        assert_eq!(&BBSplusPublicKey::from_multikey(&pk_multikey).unwrap(), pk);
+        assert_eq!(
+            &BBSplusSecretKey::from_multikey(&sk.to_multikey()).unwrap(),
+            sk
+        );
+        assert!(BBSplusSecretKey::from_multikey(&pk_multikey).is_err());
+        assert!(BBSplusPublicKey::from_multikey(&sk.to_multikey()).is_err());
+        assert!(BBSplusPublicKey::from_multikey(&pk_multikey[1..]).is_err());
+
+        let pk_jwk = serde_json::to_value(pk.to_jwk()).unwrap();
+        assert_eq!(pk_jwk["kty"], "OKP");
+        assert_eq!(pk_jwk["crv"], "BLS12381G2");
+        assert!(pk_jwk.get("d").is_none());
+        let pk_jwk: BBSplusJwk = serde_json::from_value(pk_jwk).unwrap();
+        assert_eq!(&BBSplusPublicKey::from_jwk(&pk_jwk).unwrap(), pk);
+        assert!(BBSplusSecretKey::from_jwk(&pk_jwk).is_err());
+
+        let sk_jwk = sk.to_jwk();
+        assert!(!format!("{:?}", sk_jwk).contains(sk_jwk.d.as_ref().unwrap()));
+        assert_eq!(&BBSplusSecretKey::from_jwk(&sk_jwk).unwrap(), sk);
+        assert_eq!(&BBSplusPublicKey::from_jwk(&sk_jwk).unwrap(), pk);
+
+        let mut wrong_x = sk_jwk.clone();
+        wrong_x.x = KeyPair::<BBSplus<<BbsBls12381Sha256 as Scheme>::Ciphersuite>>::random()
+            .unwrap()
+            .public_key()
+            .to_jwk()
+            .x
+            .clone();
+        assert!(BBSplusSecretKey::from_jwk(&wrong_x).is_err());
+        let mut wrong_crv = pk_jwk.clone();
+        wrong_crv.crv = "BLS12381G1".to_owned();
+        assert!(BBSplusPublicKey::from_jwk(&wrong_crv).is_err());
+    }
+
+    //KEYPAIR - random with rng
+
+    #[test]
+    fn random_keypair_with_rng() {
+        let random = |seed| {
+            KeyPair::<BBSplus<<BbsBls12381Sha256 as Scheme>::Ciphersuite>>::random_with_rng(
+                &mut StdRng::seed_from_u64(seed),
+            )
+            .unwrap()
+        };
+
+        let (keypair, same_keypair, other_keypair) = (random(1), random(1), random(2));
+        assert_eq!(
+            keypair.private_key().encode(),
+            same_keypair.private_key().encode()
+        );
+        assert_eq!(
+            keypair.public_key().encode(),
+            same_keypair.public_key().encode()
+        );
+        assert_ne!(
+            keypair.private_key().encode(),
+            other_keypair.private_key().encode()
+        );
+    }
+
     fn key_pair_gen<S: Scheme>(filename: &str)
     where
         S::Ciphersuite: BbsCiphersuite,
@@ -303,7 +772,7 @@ mod tests {
 
         let result1 = sk == SK_expected;
 
-        if result1 == false {
+        if !result1 {
             println!("      keyGen:        {}", result1);
             println!("      Expected key:  {}", SK_expected);
             println!("      Generated key: {}", sk);
@@ -311,7 +780,7 @@ mod tests {
 
         let result2 = pk == PK_expected;
 
-        if result2 == false {
+        if !result2 {
             println!("      skToPk:        {}", result2);
             println!("      Expected key:  {}", PK_expected);
             println!("      Generated key: {}", pk);
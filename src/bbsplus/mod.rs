@@ -12,10 +12,20 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+#[cfg(feature = "bbs-2023")]
+pub mod bbs2023;
 pub mod blind;
 pub mod ciphersuites;
 pub mod commitment;
+pub mod dkg;
 pub mod generators;
+pub mod interactive;
+#[cfg(feature = "jwp")]
+pub mod jwp;
 pub mod keys;
+pub mod multi_proof;
 pub mod proof;
+pub mod pseudonym;
+pub mod range_proof;
 pub mod signature;
+pub mod threshold;
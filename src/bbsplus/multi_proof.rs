@@ -0,0 +1,613 @@
+// Copyright 2023 Fondazione LINKS
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::{
+    ciphersuites::BbsCiphersuite,
+    generators::Generators,
+    keys::BBSplusPublicKey,
+    proof::{
+        challenge_octets, proof_finalize, proof_init, proof_verify_init, BBSplusPoKSignature,
+        ProofInitResult,
+    },
+    signature::BBSplusSignature,
+};
+use crate::{
+    errors::Error,
+    utils::{
+        message::bbsplus_message::BBSplusMessage,
+        util::{
+            bbsplus_utils::{get_messages, hash_to_scalar, i2osp},
+            get_remaining_indexes,
+        },
+    },
+};
+use alloc::{borrow::ToOwned, vec::Vec};
+use bls12_381_plus::{multi_miller_loop, G2Prepared, G2Projective, Scalar};
+use elliptic_curve::{group::Curve, hash2curve::ExpandMsg, Group};
+#[cfg(feature = "std")]
+use rand::thread_rng;
+use rand::{CryptoRng, RngCore};
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroizing;
+
+use crate::utils::util::bbsplus_utils::calculate_random_scalars_with_rng;
+
+/// A credential presented by the Prover, i.e. a signature together with the signed messages and the indexes of the messages to disclose
+#[derive(Clone, Debug)]
+pub struct ProverCredential<'a> {
+    pub pk: &'a BBSplusPublicKey,
+    pub signature: &'a [u8],
+    pub header: Option<&'a [u8]>,
+    pub messages: Option<&'a [Vec<u8>]>,
+    pub disclosed_indexes: Option<&'a [usize]>,
+}
+
+/// A credential received by the Verifier, i.e. the disclosed messages together with their indexes
+#[derive(Clone, Debug)]
+pub struct VerifierCredential<'a> {
+    pub pk: &'a BBSplusPublicKey,
+    pub header: Option<&'a [u8]>,
+    pub disclosed_messages: Option<&'a [Vec<u8>]>,
+    pub disclosed_indexes: Option<&'a [usize]>,
+}
+
+/// Proof of knowledge of multiple signatures, computed with a single joint challenge, showing that some undisclosed messages are equal across the credentials.
+/// The equalities are expressed as vectors of `(credential index, message index)` couples, where all the messages of a vector are proven equal.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct MultiPoKSignature {
+    proofs: Vec<BBSplusPoKSignature>,
+}
+
+impl MultiPoKSignature {
+    /// # Description
+    /// This operation creates a proof of knowledge of multiple BBS signatures, optionally disclosing any subset of the messages of each signature. The undisclosed messages in the same equality class share the same `m~` random scalar and all the proofs share the same challenge, so that the Verifier is convinced that those messages are equal without learning them.
+    ///
+    /// # Inputs:
+    /// * `credentials` (REQUIRED), a vector of [`ProverCredential`].
+    /// * `equalities` (OPTIONAL), a vector of equality classes, each one a vector of `(credential index, message index)` couples referring to undisclosed messages. Equality classes MUST be disjoint.
+    /// * `ph` (OPTIONAL), an octet string containing the presentation header.
+    ///
+    /// # Output:
+    /// a [`MultiPoKSignature`] or [`Error`].
+    ///
+    #[cfg(feature = "std")]
+    pub fn proof_gen<CS>(
+        credentials: &[ProverCredential],
+        equalities: &[Vec<(usize, usize)>],
+        ph: Option<&[u8]>,
+    ) -> Result<Self, Error>
+    where
+        CS: BbsCiphersuite,
+        CS::Expander: for<'a> ExpandMsg<'a>,
+    {
+        Self::proof_gen_with_rng::<CS, _>(credentials, equalities, ph, &mut thread_rng())
+    }
+
+    /// # Description
+    /// Same as [`Self::proof_gen`], drawing the random scalars of every proof from `rng`.
+    ///
+    /// # Inputs:
+    /// * `rng` (REQUIRED), a cryptographically secure random number generator.
+    /// * the other inputs are the same as [`Self::proof_gen`].
+    ///
+    /// # Output:
+    /// a [`MultiPoKSignature`] or [`Error`].
+    ///
+    pub fn proof_gen_with_rng<CS, R: RngCore + CryptoRng>(
+        credentials: &[ProverCredential],
+        equalities: &[Vec<(usize, usize)>],
+        ph: Option<&[u8]>,
+        rng: &mut R,
+    ) -> Result<Self, Error>
+    where
+        CS: BbsCiphersuite,
+        CS::Expander: for<'a> ExpandMsg<'a>,
+    {
+        let api_id = CS::API_ID;
+
+        struct ProverState {
+            signature: BBSplusSignature,
+            generators: Generators,
+            messages: Vec<BBSplusMessage>,
+            disclosed_indexes: Vec<usize>,
+            undisclosed_indexes: Vec<usize>,
+            random_scalars: Zeroizing<Vec<Scalar>>,
+        }
+
+        let mut states = Vec::new();
+        for credential in credentials {
+            let signature = BBSplusSignature::from_bytes(
+                credential
+                    .signature
+                    .try_into()
+                    .map_err(|_| Error::InvalidSignature)?,
+            )?;
+            let messages = credential.messages.unwrap_or(&[]);
+            let messages = BBSplusMessage::messages_to_scalar::<CS>(messages, api_id)?;
+            let L = messages.len();
+
+            let mut disclosed_indexes = credential.disclosed_indexes.unwrap_or(&[]).to_vec();
+            disclosed_indexes.sort();
+            disclosed_indexes.dedup();
+            if let Some(invalid_index) = disclosed_indexes.iter().find(|&&i| i >= L) {
+                return Err(Error::ProofGenError(format!(
+                    "Invalid disclosed index: {}",
+                    invalid_index
+                )));
+            }
+
+            let undisclosed_indexes = get_remaining_indexes(L, &disclosed_indexes);
+            let U = undisclosed_indexes.len();
+
+            let random_scalars = calculate_random_scalars_with_rng(5 + U, rng);
+
+            states.push(ProverState {
+                signature,
+                generators: Generators::create::<CS>(L + 1, Some(api_id)),
+                messages,
+                disclosed_indexes,
+                undisclosed_indexes,
+                random_scalars: Zeroizing::new(random_scalars),
+            });
+        }
+
+        // the messages of an equality class share the m~ of the first one
+        let mut linked: Vec<(usize, usize)> = Vec::new();
+        for class in equalities {
+            let mut shared: Option<(Scalar, BBSplusMessage)> = None;
+            for &(k, i) in class {
+                if linked.contains(&(k, i)) {
+                    return Err(Error::ProofGenError(
+                        "Equality classes must be disjoint".to_owned(),
+                    ));
+                }
+                linked.push((k, i));
+
+                let state = states.get_mut(k).ok_or_else(|| {
+                    Error::ProofGenError(format!("Invalid credential index: {}", k))
+                })?;
+                let j = state
+                    .undisclosed_indexes
+                    .iter()
+                    .position(|&u| u == i)
+                    .ok_or_else(|| {
+                        Error::ProofGenError(format!(
+                            "Message {} of credential {} must be undisclosed",
+                            i, k
+                        ))
+                    })?;
+
+                match shared {
+                    None => shared = Some((state.random_scalars[5 + j], state.messages[i])),
+                    Some((m_tilde, message)) => {
+                        if state.messages[i] != message {
+                            return Err(Error::ProofGenError(format!(
+                                "Message {} of credential {} is different from the others in its equality class",
+                                i, k
+                            )));
+                        }
+                        state.random_scalars[5 + j] = m_tilde;
+                    }
+                }
+            }
+        }
+
+        let mut init_results = Vec::new();
+        for (state, credential) in core::iter::zip(&states, credentials) {
+            init_results.push(proof_init::<CS>(
+                credential.pk,
+                &state.signature,
+                &state.generators,
+                &state.random_scalars,
+                credential.header,
+                &state.messages,
+                &state.undisclosed_indexes,
+                Some(api_id),
+            )?);
+        }
+
+        let disclosed_messages: Vec<Vec<BBSplusMessage>> = states
+            .iter()
+            .map(|s| get_messages(&s.messages, &s.disclosed_indexes))
+            .collect();
+
+        let challenge = joint_challenge_calculate::<CS>(
+            &init_results,
+            states.iter().map(|s| s.disclosed_indexes.as_slice()),
+            &disclosed_messages,
+            ph,
+            Some(api_id),
+        )?;
+
+        let mut proofs = Vec::new();
+        for (state, init_res) in core::iter::zip(&states, &init_results) {
+            let undisclosed_messages = get_messages(&state.messages, &state.undisclosed_indexes);
+            proofs.push(proof_finalize(
+                init_res,
+                challenge,
+                state.signature.e,
+                &state.random_scalars,
+                &undisclosed_messages,
+            )?);
+        }
+
+        Ok(Self { proofs })
+    }
+
+    /// # Description
+    /// This operation validates a [`MultiPoKSignature`], given the disclosed messages of each credential and the equality classes of the undisclosed messages.
+    ///
+    /// # Inputs:
+    /// * `self`, a multi-credential proof.
+    /// * `credentials` (REQUIRED), a vector of [`VerifierCredential`], in the same order used to generate the proof.
+    /// * `equalities` (OPTIONAL), a vector of equality classes, each one a vector of `(credential index, message index)` couples referring to undisclosed messages.
+    /// * `ph` (OPTIONAL), an octet string containing the presentation header.
+    ///
+    /// # Output:
+    /// a result: [`Ok`] or [`Error`].
+    ///
+    pub fn proof_verify<CS>(
+        &self,
+        credentials: &[VerifierCredential],
+        equalities: &[Vec<(usize, usize)>],
+        ph: Option<&[u8]>,
+    ) -> Result<(), Error>
+    where
+        CS: BbsCiphersuite,
+        CS::Expander: for<'a> ExpandMsg<'a>,
+    {
+        let api_id = CS::API_ID;
+
+        if self.proofs.len() != credentials.len() {
+            return Err(Error::PoKSVerificationError(
+                "Number of proofs different from number of credentials".to_owned(),
+            ));
+        }
+
+        let challenge = self
+            .proofs
+            .first()
+            .ok_or_else(|| Error::PoKSVerificationError("No proofs".to_owned()))?
+            .challenge;
+        if self.proofs.iter().any(|p| p.challenge != challenge) {
+            return Err(Error::PoKSVerificationError("invalid challenge".to_owned()));
+        }
+
+        let mut disclosed_indexes_list = Vec::new();
+        let mut disclosed_messages_list = Vec::new();
+        let mut init_results = Vec::new();
+        for (proof, credential) in core::iter::zip(&self.proofs, credentials) {
+            let mut disclosed_indexes = credential.disclosed_indexes.unwrap_or(&[]).to_vec();
+            disclosed_indexes.sort();
+            disclosed_indexes.dedup();
+            let disclosed_messages = BBSplusMessage::messages_to_scalar::<CS>(
+                credential.disclosed_messages.unwrap_or(&[]),
+                api_id,
+            )?;
+
+            let U = proof.m_cap.len();
+            let R = disclosed_indexes.len();
+            let generators = Generators::create::<CS>(U + R + 1, Some(api_id));
+
+            init_results.push(proof_verify_init::<CS>(
+                credential.pk,
+                proof,
+                &generators,
+                credential.header,
+                &disclosed_messages,
+                &disclosed_indexes,
+                Some(api_id),
+            )?);
+            disclosed_indexes_list.push(disclosed_indexes);
+            disclosed_messages_list.push(disclosed_messages);
+        }
+
+        // the messages of an equality class must have the same m^
+        for class in equalities {
+            let mut shared: Option<Scalar> = None;
+            for &(k, i) in class {
+                let proof = self.proofs.get(k).ok_or_else(|| {
+                    Error::PoKSVerificationError(format!("Invalid credential index: {}", k))
+                })?;
+                let L = proof.m_cap.len() + disclosed_indexes_list[k].len();
+                let m_cap = get_remaining_indexes(L, &disclosed_indexes_list[k])
+                    .iter()
+                    .position(|&u| u == i)
+                    .map(|j| proof.m_cap[j])
+                    .ok_or_else(|| {
+                        Error::PoKSVerificationError(format!(
+                            "Message {} of credential {} must be undisclosed",
+                            i, k
+                        ))
+                    })?;
+
+                if *shared.get_or_insert(m_cap) != m_cap {
+                    return Err(Error::PoKSVerificationError(
+                        "Equality of undisclosed messages not satisfied".to_owned(),
+                    ));
+                }
+            }
+        }
+
+        let expected_challenge = joint_challenge_calculate::<CS>(
+            &init_results,
+            disclosed_indexes_list.iter().map(|d| d.as_slice()),
+            &disclosed_messages_list,
+            ph,
+            Some(api_id),
+        )?;
+
+        if challenge != expected_challenge {
+            return Err(Error::PoKSVerificationError("invalid challenge".to_owned()));
+        }
+
+        let BP2 = G2Projective::GENERATOR;
+        for (proof, credential) in core::iter::zip(&self.proofs, credentials) {
+            let term1 = (
+                &proof.Abar.to_affine(),
+                &G2Prepared::from(credential.pk.0.to_affine()),
+            );
+            let term2 = (&proof.Bbar.to_affine(), &G2Prepared::from(-BP2.to_affine()));
+
+            let pairing = multi_miller_loop(&[term1, term2]).final_exponentiation();
+            if !bool::from(pairing.is_identity()) {
+                return Err(Error::PoKSVerificationError("Invalid Proof".to_owned()));
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn proofs(&self) -> &[BBSplusPoKSignature] {
+        &self.proofs
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes: Vec<u8> = Vec::new();
+        bytes.extend_from_slice(&i2osp::<8>(self.proofs.len()));
+        for proof in &self.proofs {
+            let proof_bytes = proof.to_bytes();
+            bytes.extend_from_slice(&i2osp::<8>(proof_bytes.len()));
+            bytes.extend_from_slice(&proof_bytes);
+        }
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        let read_len = |offset: usize| -> Result<usize, Error> {
+            let len = bytes
+                .get(offset..offset + 8)
+                .and_then(|b| <[u8; 8]>::try_from(b).ok())
+                .ok_or(Error::InvalidProofOfKnowledgeSignature)?;
+            usize::try_from(u64::from_be_bytes(len))
+                .map_err(|_| Error::InvalidProofOfKnowledgeSignature)
+        };
+
+        let count = read_len(0)?;
+        let mut offset = 8;
+        let mut proofs = Vec::new();
+        for _ in 0..count {
+            let len = read_len(offset)?;
+            offset += 8;
+            let proof_bytes = bytes
+                .get(offset..offset.saturating_add(len))
+                .ok_or(Error::InvalidProofOfKnowledgeSignature)?;
+            proofs.push(BBSplusPoKSignature::from_bytes(proof_bytes)?);
+            offset += len;
+        }
+
+        if offset != bytes.len() {
+            return Err(Error::InvalidProofOfKnowledgeSignature);
+        }
+
+        Ok(Self { proofs })
+    }
+}
+
+/// # Description
+/// This operation calculates the challenge shared by all the proofs of a [`MultiPoKSignature`], hashing the challenge inputs of each proof (see [`challenge_octets`]) together with the presentation header.
+///
+/// # Inputs:
+/// * `init_results` (REQUIRED), a vector of [`ProofInitResult`], one for each proof.
+/// * `disclosed_indexes` (REQUIRED), the indexes of the disclosed messages of each proof.
+/// * `disclosed_messages` (REQUIRED), the disclosed messages of each proof.
+/// * `ph` (OPTIONAL), an octet string containing the presentation header.
+/// * `api_id` (OPTIONAL), an octet string.
+///
+/// # Output:
+/// a challenge ([`Scalar`]) or [`Error`].
+///
+fn joint_challenge_calculate<'a, CS>(
+    init_results: &[ProofInitResult],
+    disclosed_indexes: impl Iterator<Item = &'a [usize]>,
+    disclosed_messages: &[Vec<BBSplusMessage>],
+    ph: Option<&[u8]>,
+    api_id: Option<&[u8]>,
+) -> Result<Scalar, Error>
+where
+    CS: BbsCiphersuite,
+    CS::Expander: for<'b> ExpandMsg<'b>,
+{
+    let api_id = api_id.unwrap_or(b"");
+    let challenge_dst = [api_id, CS::H2S].concat();
+    let ph = ph.unwrap_or(b"");
+
+    let mut c_arr: Vec<u8> = Vec::new();
+    c_arr.extend_from_slice(&i2osp::<8>(init_results.len()));
+    for ((init_res, indexes), messages) in init_results
+        .iter()
+        .zip(disclosed_indexes)
+        .zip(disclosed_messages)
+    {
+        c_arr.extend(challenge_octets(init_res, None, &[], indexes, messages)?);
+    }
+    c_arr.extend_from_slice(&i2osp::<8>(ph.len()));
+    c_arr.extend_from_slice(ph);
+
+    hash_to_scalar::<CS>(&c_arr, &challenge_dst)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MultiPoKSignature, ProverCredential, VerifierCredential};
+    use crate::{
+        bbsplus::{
+            ciphersuites::BbsCiphersuite,
+            keys::{BBSplusPublicKey, BBSplusSecretKey},
+        },
+        schemes::{
+            algorithms::{BBSplus, BbsBls12381Sha256, BbsBls12381Shake256, Scheme},
+            generics::Signature,
+        },
+        utils::util::bbsplus_utils::get_messages_vec,
+    };
+    use elliptic_curve::hash2curve::ExpandMsg;
+    use std::fs;
+
+    macro_rules! multi_proof_tests {
+        ( $( ($t:ident, $p:literal): { $( ($n:ident, $f:literal), )+ },)+ ) => { $($(
+            #[test] fn $n() { multi_proof_check::<$t>($p, $f); }
+        )+)+ }
+    }
+
+    multi_proof_tests! {
+        (BbsBls12381Sha256, "./fixture_data/bls12-381-sha-256/"): {
+            (multi_proof_check_sha256_1, "./fixture_data_multi/multiProof001.json"),
+            (multi_proof_check_sha256_2, "./fixture_data_multi/multiProof002.json"),
+            (multi_proof_check_sha256_3, "./fixture_data_multi/multiProof003.json"),
+            (multi_proof_check_sha256_4, "./fixture_data_multi/multiProof004.json"),
+            (multi_proof_check_sha256_5, "./fixture_data_multi/multiProof005.json"),
+        },
+        (BbsBls12381Shake256, "./fixture_data/bls12-381-shake-256/"): {
+            (multi_proof_check_shake256_1, "./fixture_data_multi/multiProof001.json"),
+            (multi_proof_check_shake256_2, "./fixture_data_multi/multiProof002.json"),
+            (multi_proof_check_shake256_3, "./fixture_data_multi/multiProof003.json"),
+            (multi_proof_check_shake256_4, "./fixture_data_multi/multiProof004.json"),
+            (multi_proof_check_shake256_5, "./fixture_data_multi/multiProof005.json"),
+        },
+    }
+
+    fn get_hex_array(json: &serde_json::Value) -> Vec<Vec<u8>> {
+        json.as_array()
+            .unwrap()
+            .iter()
+            .map(|m| hex::decode(m.as_str().unwrap()).unwrap())
+            .collect()
+    }
+
+    fn get_equalities(json: &serde_json::Value) -> Vec<Vec<(usize, usize)>> {
+        serde_json::from_value(json.clone()).unwrap()
+    }
+
+    fn multi_proof_check<S: Scheme>(pathname: &str, filename: &str)
+    where
+        S::Ciphersuite: BbsCiphersuite,
+        <S::Ciphersuite as BbsCiphersuite>::Expander: for<'a> ExpandMsg<'a>,
+    {
+        let keypair_data =
+            fs::read_to_string([pathname, "keypair.json"].concat()).expect("Unable to read file");
+        let keypair_json: serde_json::Value =
+            serde_json::from_str(&keypair_data).expect("Unable to parse");
+        let data = fs::read_to_string(filename).expect("Unable to read file");
+        let json: serde_json::Value = serde_json::from_str(&data).expect("Unable to parse");
+        eprintln!("{}", json["caseName"]);
+
+        let SK = BBSplusSecretKey::from_bytes(
+            &hex::decode(keypair_json["keyPair"]["secretKey"].as_str().unwrap()).unwrap(),
+        )
+        .unwrap();
+        let PK = BBSplusPublicKey::from_bytes(
+            &hex::decode(keypair_json["keyPair"]["publicKey"].as_str().unwrap()).unwrap(),
+        )
+        .unwrap();
+
+        let ph = hex::decode(json["presentationHeader"].as_str().unwrap()).unwrap();
+        let prover_equalities = get_equalities(&json["proverEqualities"]);
+        let verifier_equalities = get_equalities(&json["verifierEqualities"]);
+        let result_expected = json["result"]["valid"].as_bool().unwrap();
+
+        let mut inputs = Vec::new();
+        for credential in json["credentials"].as_array().unwrap() {
+            let header = hex::decode(credential["header"].as_str().unwrap()).unwrap();
+            let messages = get_hex_array(&credential["messages"]);
+            let disclosed_indexes: Vec<usize> =
+                serde_json::from_value(credential["disclosedIndexes"].clone()).unwrap();
+            let signature = Signature::<BBSplus<S::Ciphersuite>>::sign(
+                Some(&messages),
+                &SK,
+                &PK,
+                Some(&header),
+            )
+            .unwrap()
+            .to_bytes();
+            let disclosed_messages = get_messages_vec(&messages, &disclosed_indexes);
+
+            inputs.push((
+                header,
+                messages,
+                disclosed_indexes,
+                signature,
+                disclosed_messages,
+            ));
+        }
+
+        let prover_credentials: Vec<ProverCredential> = inputs
+            .iter()
+            .map(
+                |(header, messages, disclosed_indexes, signature, _)| ProverCredential {
+                    pk: &PK,
+                    signature,
+                    header: Some(header),
+                    messages: Some(messages),
+                    disclosed_indexes: Some(disclosed_indexes),
+                },
+            )
+            .collect();
+
+        let proof = match MultiPoKSignature::proof_gen::<S::Ciphersuite>(
+            &prover_credentials,
+            &prover_equalities,
+            Some(&ph),
+        ) {
+            Ok(proof) => proof,
+            Err(e) => {
+                eprintln!("  multiProofGen: {:?}", e);
+                assert!(!result_expected, "failed");
+                return;
+            }
+        };
+
+        let proof = MultiPoKSignature::from_bytes(&proof.to_bytes()).unwrap();
+
+        let verifier_credentials: Vec<VerifierCredential> = inputs
+            .iter()
+            .map(
+                |(header, _, disclosed_indexes, _, disclosed_messages)| VerifierCredential {
+                    pk: &PK,
+                    header: Some(header),
+                    disclosed_messages: Some(disclosed_messages),
+                    disclosed_indexes: Some(disclosed_indexes),
+                },
+            )
+            .collect();
+
+        let result = proof.proof_verify::<S::Ciphersuite>(
+            &verifier_credentials,
+            &verifier_equalities,
+            Some(&ph),
+        );
+        eprintln!("  multiProofVerify: {:?}", result);
+        assert_eq!(result.is_ok(), result_expected, "failed");
+    }
+}
@@ -20,35 +20,38 @@ use crate::{
     errors::Error,
     schemes::{algorithms::BBSplus, generics::PoKSignature},
     utils::{
+        cbor::{decode_with_ciphersuite, encode_with_ciphersuite},
         message::bbsplus_message::BBSplusMessage,
         util::{
             bbsplus_utils::{
-                calculate_domain, get_messages, hash_to_scalar, i2osp, parse_g1_projective,
-                ScalarExt,
+                calculate_domain, get_messages, get_random_with_rng, hash_to_scalar, i2osp,
+                parse_g1_projective, ScalarExt,
             },
             get_remaining_indexes,
         },
     },
 };
-use bls12_381_plus::{multi_miller_loop, G1Projective, G2Prepared, G2Projective, Scalar};
+use alloc::{borrow::ToOwned, string::ToString, vec::Vec};
+use bls12_381_plus::{multi_miller_loop, G1Affine, G1Projective, G2Prepared, G2Projective, Scalar};
 use elliptic_curve::{group::Curve, hash2curve::ExpandMsg, Group};
+#[cfg(feature = "std")]
+use rand::thread_rng;
+use rand::{CryptoRng, RngCore};
 use serde::{Deserialize, Serialize};
+use zeroize::Zeroizing;
 
-#[cfg(not(test))]
-use crate::utils::util::bbsplus_utils::calculate_random_scalars;
-#[cfg(test)]
-use crate::utils::util::bbsplus_utils::seeded_random_scalars;
+use crate::utils::util::bbsplus_utils::Randomness;
 
 #[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
 pub struct BBSplusPoKSignature {
-    Abar: G1Projective,
-    Bbar: G1Projective,
-    D: G1Projective,
-    e_cap: Scalar,
-    r1_cap: Scalar,
-    r3_cap: Scalar,
-    m_cap: Vec<Scalar>,
-    challenge: Scalar,
+    pub(crate) Abar: G1Projective,
+    pub(crate) Bbar: G1Projective,
+    pub(crate) D: G1Projective,
+    pub(crate) e_cap: Scalar,
+    pub(crate) r1_cap: Scalar,
+    pub(crate) r3_cap: Scalar,
+    pub(crate) m_cap: Vec<Scalar>,
+    pub(crate) challenge: Scalar,
 }
 
 impl BBSplusPoKSignature {
@@ -69,6 +72,10 @@ impl BBSplusPoKSignature {
     }
 
     pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        // Abar, Bbar, D, e_cap, r1_cap, r3_cap and at least the challenge
+        if bytes.len() < 272 || (bytes.len() - 240) % 32 != 0 {
+            return Err(Error::InvalidProofOfKnowledgeSignature);
+        }
         let Abar = parse_g1_projective(&bytes[0..48])
             .map_err(|_| Error::InvalidProofOfKnowledgeSignature)?;
         let Bbar = parse_g1_projective(&bytes[48..96])
@@ -107,6 +114,17 @@ impl BBSplusPoKSignature {
     }
 }
 
+/// A proof to be verified with [`PoKSignature::batch_proof_verify`], together with the inputs of [`PoKSignature::proof_verify`]
+#[derive(Clone, Debug)]
+pub struct ProofBatchItem<'a, CS: BbsCiphersuite> {
+    pub proof: &'a PoKSignature<BBSplus<CS>>,
+    pub pk: &'a BBSplusPublicKey,
+    pub disclosed_messages: Option<&'a [Vec<u8>]>,
+    pub disclosed_indexes: Option<&'a [usize]>,
+    pub header: Option<&'a [u8]>,
+    pub ph: Option<&'a [u8]>,
+}
+
 impl<CS: BbsCiphersuite> PoKSignature<BBSplus<CS>> {
     /// https://datatracker.ietf.org/doc/html/draft-irtf-cfrg-bbs-signatures-07#name-proof-generation-proofgen
     ///
@@ -124,6 +142,7 @@ impl<CS: BbsCiphersuite> PoKSignature<BBSplus<CS>> {
     /// # Output:
     /// a PoK of a Signature [`PoKSignature::BBSplus`] or [`Error`].
     ///
+    #[cfg(feature = "std")]
     pub fn proof_gen(
         pk: &BBSplusPublicKey,
         signature: &[u8],
@@ -132,6 +151,101 @@ impl<CS: BbsCiphersuite> PoKSignature<BBSplus<CS>> {
         messages: Option<&[Vec<u8>]>,
         disclosed_indexes: Option<&[usize]>,
     ) -> Result<Self, Error>
+    where
+        CS::Expander: for<'a> ExpandMsg<'a>,
+    {
+        Self::proof_gen_with_rng(
+            pk,
+            signature,
+            header,
+            ph,
+            messages,
+            disclosed_indexes,
+            &mut thread_rng(),
+        )
+    }
+
+    /// # Description
+    /// Same as [`Self::proof_gen`], but the random scalars of the proof are drawn from the supplied `rng`.
+    ///
+    /// # Inputs:
+    /// * `rng` (REQUIRED), a cryptographically secure random number generator.
+    /// * the other inputs are the same as [`Self::proof_gen`].
+    ///
+    /// # Output:
+    /// a PoK of a Signature [`PoKSignature::BBSplus`] or [`Error`].
+    ///
+    pub fn proof_gen_with_rng<R: RngCore + CryptoRng>(
+        pk: &BBSplusPublicKey,
+        signature: &[u8],
+        header: Option<&[u8]>,
+        ph: Option<&[u8]>,
+        messages: Option<&[Vec<u8>]>,
+        disclosed_indexes: Option<&[usize]>,
+        rng: &mut R,
+    ) -> Result<Self, Error>
+    where
+        CS::Expander: for<'a> ExpandMsg<'a>,
+    {
+        Self::proof_gen_internal(
+            pk,
+            signature,
+            header,
+            ph,
+            messages,
+            disclosed_indexes,
+            Randomness::Rng(rng),
+        )
+    }
+
+    /// # Description
+    /// Same as [`Self::proof_gen`], but the random scalars are replaced by the mocked random scalars computed from `seed` and `dst`, as done to produce the fixtures of the draft. The resulting proofs are deterministic, so this MUST only be used for interoperability testing.
+    ///
+    /// # Inputs:
+    /// * `seed` (OPTIONAL), an octet string. If not supplied it defaults to [`BbsCiphersuite::SEED_MOCKED_SCALAR`].
+    /// * `dst` (OPTIONAL), an octet string. If not supplied it defaults to [`BbsCiphersuite::MOCKED_SCALAR_DST`].
+    /// * the other inputs are the same as [`Self::proof_gen`].
+    ///
+    /// # Output:
+    /// a PoK of a Signature [`PoKSignature::BBSplus`] or [`Error`].
+    ///
+    #[cfg(any(test, feature = "test-vectors"))]
+    pub fn proof_gen_with_mocked_scalars(
+        pk: &BBSplusPublicKey,
+        signature: &[u8],
+        header: Option<&[u8]>,
+        ph: Option<&[u8]>,
+        messages: Option<&[Vec<u8>]>,
+        disclosed_indexes: Option<&[usize]>,
+        seed: Option<&[u8]>,
+        dst: Option<&[u8]>,
+    ) -> Result<Self, Error>
+    where
+        CS::Expander: for<'a> ExpandMsg<'a>,
+    {
+        Self::proof_gen_internal::<rand::rngs::ThreadRng>(
+            pk,
+            signature,
+            header,
+            ph,
+            messages,
+            disclosed_indexes,
+            Randomness::Mocked {
+                seed: seed.unwrap_or(CS::SEED_MOCKED_SCALAR),
+                dst: dst.unwrap_or(CS::MOCKED_SCALAR_DST),
+            },
+        )
+    }
+
+    fn proof_gen_internal<R: RngCore + CryptoRng>(
+        pk: &BBSplusPublicKey,
+        signature: &[u8],
+        header: Option<&[u8]>,
+        ph: Option<&[u8]>,
+        messages: Option<&[Vec<u8>]>,
+        disclosed_indexes: Option<&[usize]>,
+        randomness: Randomness<R>,
+    ) -> Result<Self, Error>
     where
         CS::Expander: for<'a> ExpandMsg<'a>,
     {
@@ -144,7 +258,7 @@ impl<CS: BbsCiphersuite> PoKSignature<BBSplus<CS>> {
         let message_scalars = BBSplusMessage::messages_to_scalar::<CS>(messages, CS::API_ID)?;
         let generators = Generators::create::<CS>(messages.len() + 1, Some(CS::API_ID));
 
-        let proof = core_proof_gen::<CS>(
+        let proof = core_proof_gen::<CS, _>(
             pk,
             &signature,
             &generators,
@@ -153,8 +267,9 @@ impl<CS: BbsCiphersuite> PoKSignature<BBSplus<CS>> {
             header,
             ph,
             Some(CS::API_ID),
-            &CS::SEED_MOCKED_SCALAR,
-            &CS::MOCKED_SCALAR_DST,
+            None,
+            None,
+            randomness,
         )?;
 
         Ok(Self::BBSplus(proof))
@@ -181,6 +296,7 @@ impl<CS: BbsCiphersuite> PoKSignature<BBSplus<CS>> {
     /// # Output:
     /// [`PoKSignature::BBSplus`] or [`Error`]: a PoK of a Signature, a vector of octet strings representing all the disclosed messages and their indexes.
     ///
+    #[cfg(feature = "std")]
     pub fn blind_proof_gen(
         pk: &BBSplusPublicKey,
         signature: &[u8],
@@ -193,6 +309,218 @@ impl<CS: BbsCiphersuite> PoKSignature<BBSplus<CS>> {
         secret_prover_blind: Option<&BlindFactor>,
         signer_blind: Option<&BlindFactor>,
     ) -> Result<Self, Error>
+    where
+        CS::Expander: for<'a> ExpandMsg<'a>,
+    {
+        Self::blind_proof_gen_with_rng(
+            pk,
+            signature,
+            header,
+            ph,
+            messages,
+            committed_messages,
+            disclosed_indexes,
+            disclosed_commitment_indexes,
+            secret_prover_blind,
+            signer_blind,
+            &mut thread_rng(),
+        )
+    }
+
+    /// # Description
+    /// Same as [`Self::blind_proof_gen`], drawing the random scalars of the proof from `rng`.
+    ///
+    /// # Inputs:
+    /// * `rng` (REQUIRED), a cryptographically secure random number generator.
+    /// * the other inputs are the same as [`Self::blind_proof_gen`].
+    ///
+    /// # Output:
+    /// [`PoKSignature::BBSplus`] or [`Error`].
+    ///
+    pub fn blind_proof_gen_with_rng<R: RngCore + CryptoRng>(
+        pk: &BBSplusPublicKey,
+        signature: &[u8],
+        header: Option<&[u8]>,
+        ph: Option<&[u8]>,
+        messages: Option<&[Vec<u8>]>,
+        committed_messages: Option<&[Vec<u8>]>,
+        disclosed_indexes: Option<&[usize]>,
+        disclosed_commitment_indexes: Option<&[usize]>,
+        secret_prover_blind: Option<&BlindFactor>,
+        signer_blind: Option<&BlindFactor>,
+        rng: &mut R,
+    ) -> Result<Self, Error>
+    where
+        CS::Expander: for<'a> ExpandMsg<'a>,
+    {
+        Self::blind_proof_gen_internal(
+            pk,
+            signature,
+            header,
+            ph,
+            messages,
+            committed_messages,
+            disclosed_indexes,
+            disclosed_commitment_indexes,
+            secret_prover_blind,
+            signer_blind,
+            None,
+            Randomness::Rng(rng),
+        )
+    }
+
+    /// # Description
+    /// Same as [`Self::blind_proof_gen`], but using prebuilt generators (e.g., obtained from a [`crate::bbsplus::generators::GeneratorsCache`]) instead of creating them at each call.
+    ///
+    /// # Inputs:
+    /// * `generators` (REQUIRED), at least L+1 [`Generators`] created with `api_id` equal to [`BbsCiphersuite::API_ID_BLIND`], where L is the number of messages.
+    /// * `blind_generators` (REQUIRED), at least M+1 [`Generators`] created with `api_id` equal to "BLIND_" followed by [`BbsCiphersuite::API_ID_BLIND`], where M is the number of committed messages.
+    /// * the other inputs are the same as [`Self::blind_proof_gen`].
+    ///
+    /// # Output:
+    /// [`PoKSignature::BBSplus`] or [`Error`].
+    ///
+    #[cfg(feature = "std")]
+    pub fn blind_proof_gen_with_generators(
+        pk: &BBSplusPublicKey,
+        signature: &[u8],
+        header: Option<&[u8]>,
+        ph: Option<&[u8]>,
+        messages: Option<&[Vec<u8>]>,
+        committed_messages: Option<&[Vec<u8>]>,
+        disclosed_indexes: Option<&[usize]>,
+        disclosed_commitment_indexes: Option<&[usize]>,
+        secret_prover_blind: Option<&BlindFactor>,
+        signer_blind: Option<&BlindFactor>,
+        generators: &Generators,
+        blind_generators: &Generators,
+    ) -> Result<Self, Error>
+    where
+        CS::Expander: for<'a> ExpandMsg<'a>,
+    {
+        Self::blind_proof_gen_with_generators_and_rng(
+            pk,
+            signature,
+            header,
+            ph,
+            messages,
+            committed_messages,
+            disclosed_indexes,
+            disclosed_commitment_indexes,
+            secret_prover_blind,
+            signer_blind,
+            generators,
+            blind_generators,
+            &mut thread_rng(),
+        )
+    }
+
+    /// # Description
+    /// Same as [`Self::blind_proof_gen_with_generators`], drawing the random scalars of the proof from `rng`.
+    ///
+    /// # Inputs:
+    /// * `rng` (REQUIRED), a cryptographically secure random number generator.
+    /// * the other inputs are the same as [`Self::blind_proof_gen_with_generators`].
+    ///
+    /// # Output:
+    /// [`PoKSignature::BBSplus`] or [`Error`].
+    ///
+    pub fn blind_proof_gen_with_generators_and_rng<R: RngCore + CryptoRng>(
+        pk: &BBSplusPublicKey,
+        signature: &[u8],
+        header: Option<&[u8]>,
+        ph: Option<&[u8]>,
+        messages: Option<&[Vec<u8>]>,
+        committed_messages: Option<&[Vec<u8>]>,
+        disclosed_indexes: Option<&[usize]>,
+        disclosed_commitment_indexes: Option<&[usize]>,
+        secret_prover_blind: Option<&BlindFactor>,
+        signer_blind: Option<&BlindFactor>,
+        generators: &Generators,
+        blind_generators: &Generators,
+        rng: &mut R,
+    ) -> Result<Self, Error>
+    where
+        CS::Expander: for<'a> ExpandMsg<'a>,
+    {
+        Self::blind_proof_gen_internal(
+            pk,
+            signature,
+            header,
+            ph,
+            messages,
+            committed_messages,
+            disclosed_indexes,
+            disclosed_commitment_indexes,
+            secret_prover_blind,
+            signer_blind,
+            Some((generators, blind_generators)),
+            Randomness::Rng(rng),
+        )
+    }
+
+    /// # Description
+    /// Same as [`Self::blind_proof_gen`], but the random scalars are replaced by the mocked random scalars computed from `seed` and `dst`, as done to produce the fixtures of the draft. The resulting proofs are deterministic, so this MUST only be used for interoperability testing.
+    ///
+    /// # Inputs:
+    /// * `seed` (OPTIONAL), an octet string. If not supplied it defaults to [`BbsCiphersuite::SEED_MOCKED_SCALAR`].
+    /// * `dst` (OPTIONAL), an octet string. If not supplied it defaults to [`BbsCiphersuite::BLIND_PROOF_DST`].
+    /// * the other inputs are the same as [`Self::blind_proof_gen`].
+    ///
+    /// # Output:
+    /// [`PoKSignature::BBSplus`] or [`Error`].
+    ///
+    #[cfg(any(test, feature = "test-vectors"))]
+    pub fn blind_proof_gen_with_mocked_scalars(
+        pk: &BBSplusPublicKey,
+        signature: &[u8],
+        header: Option<&[u8]>,
+        ph: Option<&[u8]>,
+        messages: Option<&[Vec<u8>]>,
+        committed_messages: Option<&[Vec<u8>]>,
+        disclosed_indexes: Option<&[usize]>,
+        disclosed_commitment_indexes: Option<&[usize]>,
+        secret_prover_blind: Option<&BlindFactor>,
+        signer_blind: Option<&BlindFactor>,
+        seed: Option<&[u8]>,
+        dst: Option<&[u8]>,
+    ) -> Result<Self, Error>
+    where
+        CS::Expander: for<'a> ExpandMsg<'a>,
+    {
+        Self::blind_proof_gen_internal::<rand::rngs::ThreadRng>(
+            pk,
+            signature,
+            header,
+            ph,
+            messages,
+            committed_messages,
+            disclosed_indexes,
+            disclosed_commitment_indexes,
+            secret_prover_blind,
+            signer_blind,
+            None,
+            Randomness::Mocked {
+                seed: seed.unwrap_or(CS::SEED_MOCKED_SCALAR),
+                dst: dst.unwrap_or(CS::BLIND_PROOF_DST),
+            },
+        )
+    }
+
+    fn blind_proof_gen_internal<R: RngCore + CryptoRng>(
+        pk: &BBSplusPublicKey,
+        signature: &[u8],
+        header: Option<&[u8]>,
+        ph: Option<&[u8]>,
+        messages: Option<&[Vec<u8>]>,
+        committed_messages: Option<&[Vec<u8>]>,
+        disclosed_indexes: Option<&[usize]>,
+        disclosed_commitment_indexes: Option<&[usize]>,
+        secret_prover_blind: Option<&BlindFactor>,
+        signer_blind: Option<&BlindFactor>,
+        generators: Option<(&Generators, &Generators)>,
+        randomness: Randomness<R>,
+    ) -> Result<Self, Error>
     where
         CS::Expander: for<'a> ExpandMsg<'a>,
     {
@@ -224,8 +552,17 @@ impl<CS: BbsCiphersuite> PoKSignature<BBSplus<CS>> {
             ));
         }
 
-        let generators = Generators::create::<CS>(L + 1, Some(api_id));
-        let blind_generators = Generators::create::<CS>(M + 1, Some(&[b"BLIND_", api_id].concat()));
+        let blind_api_id = [b"BLIND_", api_id].concat();
+        let (generators, blind_generators) = match generators {
+            Some((generators, blind_generators)) => (
+                generators.first::<CS>(L + 1, api_id)?,
+                blind_generators.first::<CS>(M + 1, &blind_api_id)?,
+            ),
+            None => (
+                Generators::create::<CS>(L + 1, Some(api_id)),
+                Generators::create::<CS>(M + 1, Some(&blind_api_id)),
+            ),
+        };
 
         let message_scalars = BBSplusMessage::messages_to_scalar::<CS>(messages, api_id)?;
         let blind_factor = BBSplusMessage::new(
@@ -248,7 +585,7 @@ impl<CS: BbsCiphersuite> PoKSignature<BBSplus<CS>> {
         ]
         .concat();
 
-        let proof = core_proof_gen::<CS>(
+        let proof = core_proof_gen::<CS, _>(
             pk,
             &signature,
             &generators.append(blind_generators),
@@ -257,8 +594,9 @@ impl<CS: BbsCiphersuite> PoKSignature<BBSplus<CS>> {
             header,
             ph,
             Some(api_id),
-            CS::SEED_MOCKED_SCALAR,
-            &CS::BLIND_PROOF_DST,
+            None,
+            None,
+            randomness,
         )?;
 
         Ok(Self::BBSplus(proof))
@@ -306,7 +644,7 @@ impl<CS: BbsCiphersuite> PoKSignature<BBSplus<CS>> {
 
         let generators = Generators::create::<CS>(U + R + 1, Some(CS::API_ID));
 
-        let result = core_proof_verify::<CS>(
+        core_proof_verify::<CS>(
             pk,
             proof,
             &generators,
@@ -315,9 +653,153 @@ impl<CS: BbsCiphersuite> PoKSignature<BBSplus<CS>> {
             &disclosed_message_scalars,
             &disclosed_indexes,
             Some(CS::API_ID),
-        );
+            None,
+            None,
+        )
+    }
 
-        result
+    /// # Description
+    /// Same as [`Self::proof_gen`], but using prebuilt generators (e.g., obtained from a [`crate::bbsplus::generators::GeneratorsCache`]) instead of creating them at each call.
+    ///
+    /// # Inputs:
+    /// * `pk` (REQUIRED), the Signer public key.
+    /// * `signature` (REQUIRED), an octet string.
+    /// * `header` (OPTIONAL), an octet string containing context and application.
+    /// * `ph` (OPTIONAL), an octet string containing the presentation header.
+    /// * `messages` (OPTIONAL), a vector of octet strings representing the signed messages.
+    /// * `disclosed_indexes` (OPTIONAL), vector of unsigned integers in ascending order. Indexes of disclosed messages.
+    /// * `generators` (REQUIRED), at least L+1 [`Generators`] created with `api_id` equal to [`BbsCiphersuite::API_ID`], where L is the number of messages.
+    ///
+    /// # Output:
+    /// a PoK of a Signature [`PoKSignature::BBSplus`] or [`Error`].
+    ///
+    #[cfg(feature = "std")]
+    pub fn proof_gen_with_generators(
+        pk: &BBSplusPublicKey,
+        signature: &[u8],
+        header: Option<&[u8]>,
+        ph: Option<&[u8]>,
+        messages: Option<&[Vec<u8>]>,
+        disclosed_indexes: Option<&[usize]>,
+        generators: &Generators,
+    ) -> Result<Self, Error>
+    where
+        CS::Expander: for<'a> ExpandMsg<'a>,
+    {
+        Self::proof_gen_with_generators_and_rng(
+            pk,
+            signature,
+            header,
+            ph,
+            messages,
+            disclosed_indexes,
+            generators,
+            &mut thread_rng(),
+        )
+    }
+
+    /// # Description
+    /// Same as [`Self::proof_gen_with_generators`], with the proof randomness taken from `rng`.
+    ///
+    /// # Inputs:
+    /// * `rng` (REQUIRED), a cryptographically secure random number generator.
+    /// * the other inputs are the same as [`Self::proof_gen_with_generators`].
+    ///
+    /// # Output:
+    /// a PoK of a Signature [`PoKSignature::BBSplus`] or [`Error`].
+    ///
+    pub fn proof_gen_with_generators_and_rng<R: RngCore + CryptoRng>(
+        pk: &BBSplusPublicKey,
+        signature: &[u8],
+        header: Option<&[u8]>,
+        ph: Option<&[u8]>,
+        messages: Option<&[Vec<u8>]>,
+        disclosed_indexes: Option<&[usize]>,
+        generators: &Generators,
+        rng: &mut R,
+    ) -> Result<Self, Error>
+    where
+        CS::Expander: for<'a> ExpandMsg<'a>,
+    {
+        let signature = BBSplusSignature::from_bytes(
+            signature.try_into().map_err(|_| Error::InvalidSignature)?,
+        )?;
+        let messages = messages.unwrap_or(&[]);
+        let disclosed_indexes = disclosed_indexes.unwrap_or(&[]);
+
+        let message_scalars = BBSplusMessage::messages_to_scalar::<CS>(messages, CS::API_ID)?;
+        let generators = generators.first::<CS>(messages.len() + 1, CS::API_ID)?;
+
+        let proof = core_proof_gen::<CS, _>(
+            pk,
+            &signature,
+            &generators,
+            &message_scalars,
+            disclosed_indexes,
+            header,
+            ph,
+            Some(CS::API_ID),
+            None,
+            None,
+            Randomness::Rng(rng),
+        )?;
+
+        Ok(Self::BBSplus(proof))
+    }
+
+    /// # Description
+    /// Same as [`Self::proof_verify`], but using prebuilt generators (e.g., obtained from a [`crate::bbsplus::generators::GeneratorsCache`]) instead of creating them at each call.
+    ///
+    /// # Inputs:
+    /// * `self`, a proof.
+    /// * `pk` (REQUIRED), the Signer public key.
+    /// * `disclosed_messages` (OPTIONAL), a vector of octet strings representing the messages disclosed to the Verifier.
+    /// * `disclosed_indexes` (OPTIONAL), vector of unsigned integers in ascending order. Indexes of disclosed messages.
+    /// * `header` (OPTIONAL), an octet string containing context and application.
+    /// * `ph` (OPTIONAL), an octet string containing the presentation header.
+    /// * `generators` (REQUIRED), at least L+1 [`Generators`] created with `api_id` equal to [`BbsCiphersuite::API_ID`], where L is the number of signed messages.
+    ///
+    /// # Output:
+    /// a result: [`Ok`] or [`Error`].
+    ///
+    pub fn proof_verify_with_generators(
+        &self,
+        pk: &BBSplusPublicKey,
+        disclosed_messages: Option<&[Vec<u8>]>,
+        disclosed_indexes: Option<&[usize]>,
+        header: Option<&[u8]>,
+        ph: Option<&[u8]>,
+        generators: &Generators,
+    ) -> Result<(), Error>
+    where
+        CS::Expander: for<'a> ExpandMsg<'a>,
+    {
+        let proof = self.to_bbsplus_proof();
+        let disclosed_messages = disclosed_messages.unwrap_or(&[]);
+        let mut disclosed_indexes = disclosed_indexes.unwrap_or(&[]).to_vec();
+        disclosed_indexes.sort();
+        disclosed_indexes.dedup();
+
+        let U = proof.m_cap.len();
+        let R = disclosed_indexes.len();
+
+        let disclosed_message_scalars =
+            BBSplusMessage::messages_to_scalar::<CS>(disclosed_messages, CS::API_ID)?;
+
+        let generators = generators.first::<CS>(U + R + 1, CS::API_ID)?;
+
+        core_proof_verify::<CS>(
+            pk,
+            proof,
+            &generators,
+            header,
+            ph,
+            &disclosed_message_scalars,
+            &disclosed_indexes,
+            Some(CS::API_ID),
+            None,
+            None,
+        )
     }
 
     /// https://datatracker.ietf.org/doc/html/draft-kalos-bbs-blind-signatures-01#name-proof-verification
@@ -355,6 +837,74 @@ impl<CS: BbsCiphersuite> PoKSignature<BBSplus<CS>> {
         disclosed_indexes: Option<&[usize]>,
         disclosed_commitment_indexes: Option<&[usize]>,
     ) -> Result<(), Error>
+    where
+        CS::Expander: for<'a> ExpandMsg<'a>,
+    {
+        self.blind_proof_verify_internal(
+            pk,
+            header,
+            ph,
+            L,
+            disclosed_messages,
+            disclosed_committed_messages,
+            disclosed_indexes,
+            disclosed_commitment_indexes,
+            None,
+        )
+    }
+
+    /// # Description
+    /// Same as [`Self::blind_proof_verify`], but using prebuilt generators (e.g., obtained from a [`crate::bbsplus::generators::GeneratorsCache`]) instead of creating them at each call.
+    ///
+    /// # Inputs:
+    /// * `generators` (REQUIRED), at least L+1 [`Generators`] created with `api_id` equal to [`BbsCiphersuite::API_ID_BLIND`].
+    /// * `blind_generators` (REQUIRED), at least M+1 [`Generators`] created with `api_id` equal to "BLIND_" followed by [`BbsCiphersuite::API_ID_BLIND`], where M is the number of committed messages.
+    /// * the other inputs are the same as [`Self::blind_proof_verify`].
+    ///
+    /// # Output:
+    /// a result: [`Ok`] or [`Error`].
+    ///
+    pub fn blind_proof_verify_with_generators(
+        &self,
+        pk: &BBSplusPublicKey,
+        header: Option<&[u8]>,
+        ph: Option<&[u8]>,
+        L: Option<usize>,
+        disclosed_messages: Option<&[Vec<u8>]>,
+        disclosed_committed_messages: Option<&[Vec<u8>]>,
+        disclosed_indexes: Option<&[usize]>,
+        disclosed_commitment_indexes: Option<&[usize]>,
+        generators: &Generators,
+        blind_generators: &Generators,
+    ) -> Result<(), Error>
+    where
+        CS::Expander: for<'a> ExpandMsg<'a>,
+    {
+        self.blind_proof_verify_internal(
+            pk,
+            header,
+            ph,
+            L,
+            disclosed_messages,
+            disclosed_committed_messages,
+            disclosed_indexes,
+            disclosed_commitment_indexes,
+            Some((generators, blind_generators)),
+        )
+    }
+
+    fn blind_proof_verify_internal(
+        &self,
+        pk: &BBSplusPublicKey,
+        header: Option<&[u8]>,
+        ph: Option<&[u8]>,
+        L: Option<usize>,
+        disclosed_messages: Option<&[Vec<u8>]>,
+        disclosed_committed_messages: Option<&[Vec<u8>]>,
+        disclosed_indexes: Option<&[usize]>,
+        disclosed_commitment_indexes: Option<&[usize]>,
+        generators: Option<(&Generators, &Generators)>,
+    ) -> Result<(), Error>
     where
         CS::Expander: for<'a> ExpandMsg<'a>,
     {
@@ -374,8 +924,17 @@ impl<CS: BbsCiphersuite> PoKSignature<BBSplus<CS>> {
         let U = proof.m_cap.len();
         let M = disclosed_indexes.len() + disclosed_commitment_indexes.len() + U - 1 - L;
 
-        let generators = Generators::create::<CS>(L + 1, Some(api_id));
-        let blind_generators = Generators::create::<CS>(M + 1, Some(&[b"BLIND_", api_id].concat()));
+        let blind_api_id = [b"BLIND_", api_id].concat();
+        let (generators, blind_generators) = match generators {
+            Some((generators, blind_generators)) => (
+                generators.first::<CS>(L + 1, api_id)?,
+                blind_generators.first::<CS>(M + 1, &blind_api_id)?,
+            ),
+            None => (
+                Generators::create::<CS>(L + 1, Some(api_id)),
+                Generators::create::<CS>(M + 1, Some(&blind_api_id)),
+            ),
+        };
 
         let message_scalars = [
             BBSplusMessage::messages_to_scalar::<CS>(disclosed_messages, api_id)?,
@@ -398,9 +957,131 @@ impl<CS: BbsCiphersuite> PoKSignature<BBSplus<CS>> {
             &message_scalars,
             &indexes,
             Some(api_id),
+            None,
+            None,
         )
     }
 
+    /// # Description
+    /// The `batch_proof_verify` operation validates many BBS proofs at once. The challenge of each proof is checked as in [`Self::proof_verify`], stopping at the first invalid one, while the pairing equations of the proofs before it are combined with random weights, so that a single multi-pairing and a single final exponentiation are computed. If the combined check fails, the pairing equations are checked one by one, so that the reported index is the one of the first invalid proof, whichever check it fails.
+    ///
+    /// # Inputs:
+    /// * `items` (REQUIRED), a vector of [`ProofBatchItem`], each one containing the proof and the inputs of [`Self::proof_verify`].
+    ///
+    /// # Output:
+    /// a result: [`Ok`] or [`Error::BatchVerificationError`] with the index of the first invalid proof.
+    ///
+    #[cfg(feature = "std")]
+    pub fn batch_proof_verify(items: &[ProofBatchItem<CS>]) -> Result<(), Error>
+    where
+        CS::Expander: for<'a> ExpandMsg<'a>,
+    {
+        Self::batch_proof_verify_with_rng(items, &mut thread_rng())
+    }
+
+    /// # Description
+    /// Same as [`Self::batch_proof_verify`], but the random weights used to combine the pairing equations are drawn from `rng`.
+    ///
+    /// # Inputs:
+    /// * `items` (REQUIRED), a vector of [`ProofBatchItem`].
+    /// * `rng` (REQUIRED), a cryptographically secure random number generator.
+    ///
+    /// # Output:
+    /// a result: [`Ok`] or [`Error::BatchVerificationError`] with the index of the first invalid proof.
+    ///
+    pub fn batch_proof_verify_with_rng<R: RngCore + CryptoRng>(
+        items: &[ProofBatchItem<CS>],
+        rng: &mut R,
+    ) -> Result<(), Error>
+    where
+        CS::Expander: for<'a> ExpandMsg<'a>,
+    {
+        // e(Abar, PK) * e(Bbar, -BP2) = 1
+        // The first terms are grouped by public key, the second ones are all merged together
+        let mut pk_terms: Vec<(G2Projective, G1Projective)> = Vec::new();
+        let mut bp2_term = G1Projective::IDENTITY;
+        let mut first_invalid_challenge = None;
+
+        for (i, item) in items.iter().enumerate() {
+            let proof = item.proof.to_bbsplus_proof();
+            let disclosed_messages = item.disclosed_messages.unwrap_or(&[]);
+            let mut disclosed_indexes = item.disclosed_indexes.unwrap_or(&[]).to_vec();
+            disclosed_indexes.sort();
+            disclosed_indexes.dedup();
+
+            let generators = Generators::create::<CS>(
+                proof.m_cap.len() + disclosed_indexes.len() + 1,
+                Some(CS::API_ID),
+            );
+
+            let challenge_check =
+                BBSplusMessage::messages_to_scalar::<CS>(disclosed_messages, CS::API_ID).and_then(
+                    |disclosed_message_scalars| {
+                        check_proof_challenge::<CS>(
+                            item.pk,
+                            proof,
+                            &generators,
+                            item.header,
+                            item.ph,
+                            &disclosed_message_scalars,
+                            &disclosed_indexes,
+                            Some(CS::API_ID),
+                            None,
+                            None,
+                        )
+                    },
+                );
+            if challenge_check.is_err() {
+                first_invalid_challenge = Some(i);
+                break;
+            }
+
+            let r = get_random_with_rng(rng);
+            let rAbar = proof.Abar * r;
+            match pk_terms.iter_mut().find(|(pk, _)| *pk == item.pk.0) {
+                Some((_, Abar_sum)) => *Abar_sum += rAbar,
+                None => pk_terms.push((item.pk.0, rAbar)),
+            }
+            bp2_term -= proof.Bbar * r;
+        }
+
+        let g1_points: Vec<G1Affine> = pk_terms
+            .iter()
+            .map(|(_, Abar_sum)| Abar_sum.to_affine())
+            .chain(core::iter::once(bp2_term.to_affine()))
+            .collect();
+        let g2_points: Vec<G2Prepared> = pk_terms
+            .iter()
+            .map(|(pk, _)| G2Prepared::from(pk.to_affine()))
+            .chain(core::iter::once(G2Prepared::from(
+                G2Projective::GENERATOR.to_affine(),
+            )))
+            .collect();
+        let terms: Vec<(&G1Affine, &G2Prepared)> =
+            core::iter::zip(&g1_points, &g2_points).collect();
+
+        if multi_miller_loop(&terms)
+            .final_exponentiation()
+            .is_identity()
+            .into()
+        {
+            return match first_invalid_challenge {
+                Some(i) => Err(Error::BatchVerificationError(i)),
+                None => Ok(()),
+            };
+        }
+
+        // The proofs before the first invalid challenge are the ones in the combined check
+        let checked = first_invalid_challenge.unwrap_or(items.len());
+        let invalid_index = items[..checked]
+            .iter()
+            .position(|item| check_proof_pairing(item.pk, item.proof.to_bbsplus_proof()).is_err())
+            .or(first_invalid_challenge)
+            .ok_or_else(|| Error::PoKSVerificationError("Invalid Proof".to_owned()))?;
+
+        Err(Error::BatchVerificationError(invalid_index))
+    }
+
     pub fn to_bytes(&self) -> Vec<u8> {
         self.to_bbsplus_proof().to_bytes()
     }
@@ -409,6 +1090,16 @@ impl<CS: BbsCiphersuite> PoKSignature<BBSplus<CS>> {
         Ok(Self::BBSplus(BBSplusPoKSignature::from_bytes(bytes)?))
     }
 
+    /// Encodes the proof as the CBOR array `[ciphersuite_id, proof]`, with the `ciphersuite_id` of `CS` as a text string and the proof octets as a byte string
+    pub fn to_cbor(&self) -> Vec<u8> {
+        encode_with_ciphersuite(CS::ID, &self.to_bytes())
+    }
+
+    /// Decodes a proof encoded with [`Self::to_cbor`], failing with [`Error::CiphersuiteMismatch`] if it was encoded for another ciphersuite
+    pub fn from_cbor(bytes: &[u8]) -> Result<Self, Error> {
+        Self::from_bytes(&decode_with_ciphersuite(CS::ID, bytes)?)
+    }
+
     pub fn to_bbsplus_proof(&self) -> &BBSplusPoKSignature {
         match self {
             Self::BBSplus(inner) => inner,
@@ -431,11 +1122,14 @@ impl<CS: BbsCiphersuite> PoKSignature<BBSplus<CS>> {
 /// * `messages` (OPTIONAL), a vector of scalars ([`BBSplusMessage`]) representing the signed messages.
 /// * `disclosed_indexes` (OPTIONAL), vector of usize in ascending order. Indexes of disclosed messages.
 /// * `api_id` (OPTIONAL), an octet string.
+/// * `nym` (OPTIONAL), a [`NymBinding`]. If supplied, the last message MUST be the undisclosed `nym_secret` and the proof will also show that the pseudonym is computed from it.
+/// * `commitments` (OPTIONAL), a [`CommitmentBinding`]. If supplied, the proof will also show that the commitments open to the corresponding undisclosed messages.
+/// * `randomness` (REQUIRED), the source of the random scalars ([`Randomness`]).
 ///
 /// # Output:
 /// a PoK of a Signature [`BBSplusPoKSignature`] or [`Error`].
 ///
-fn core_proof_gen<CS>(
+pub(super) fn core_proof_gen<CS, R>(
     pk: &BBSplusPublicKey,
     signature: &BBSplusSignature,
     generators: &Generators,
@@ -444,12 +1138,14 @@ fn core_proof_gen<CS>(
     header: Option<&[u8]>,
     ph: Option<&[u8]>,
     api_id: Option<&[u8]>,
-    _seed: &[u8],
-    _dst: &[u8],
+    nym: Option<&NymBinding>,
+    commitments: Option<&CommitmentBinding>,
+    mut randomness: Randomness<R>,
 ) -> Result<BBSplusPoKSignature, Error>
 where
     CS: BbsCiphersuite,
     CS::Expander: for<'a> ExpandMsg<'a>,
+    R: RngCore + CryptoRng,
 {
     let L = messages.len();
     if L > generators.values.len() - 1 {
@@ -473,16 +1169,18 @@ where
         )));
     }
 
+    if nym.is_some() && (L == 0 || disclosed_indexes.contains(&(L - 1))) {
+        return Err(Error::ProofGenError(
+            "The nym_secret must be the last undisclosed message".to_owned(),
+        ));
+    }
+
     let undisclosed_indexes: Vec<usize> = get_remaining_indexes(L, &disclosed_indexes);
 
     let disclosed_messages = get_messages(messages, &disclosed_indexes);
-    let undisclosed_messages = get_messages(messages, &undisclosed_indexes);
+    let undisclosed_messages = Zeroizing::new(get_messages(messages, &undisclosed_indexes));
 
-    #[cfg(not(test))]
-    let random_scalars = calculate_random_scalars(5 + U);
-
-    #[cfg(test)]
-    let random_scalars = seeded_random_scalars::<CS>(5 + U, _seed, _dst);
+    let random_scalars = Zeroizing::new(randomness.scalars::<CS>(5 + U));
 
     let init_res = proof_init::<CS>(
         pk,
@@ -495,8 +1193,18 @@ where
         api_id,
     )?;
 
+    // the nym_secret is the last undisclosed message, so it shares the last m~ with the pseudonym proof
+    let nym_init = nym.map(|n| n.proof_init(random_scalars[4 + U]));
+
+    let commitments_init = match commitments {
+        Some(c) => c.proof_init(&random_scalars[5..], &undisclosed_indexes)?,
+        None => Vec::new(),
+    };
+
     let challenge = proof_challenge_calculate::<CS>(
         &init_res,
+        nym_init.as_ref(),
+        &commitments_init,
         &disclosed_indexes,
         &disclosed_messages,
         ph,
@@ -514,14 +1222,103 @@ where
     Ok(proof)
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
-struct ProofInitResult {
-    Abar: G1Projective,
-    Bbar: G1Projective,
-    D: G1Projective,
-    T1: G1Projective,
-    T2: G1Projective,
-    domain: Scalar,
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub(super) struct ProofInitResult {
+    pub(super) Abar: G1Projective,
+    pub(super) Bbar: G1Projective,
+    pub(super) D: G1Projective,
+    pub(super) T1: G1Projective,
+    pub(super) T2: G1Projective,
+    pub(super) domain: Scalar,
+}
+
+/// Binds a proof to a pseudonym, i.e. `pseudonym = OP * nym_secret`, where `OP` is derived from the verifier id
+#[derive(Clone, Debug)]
+pub(super) struct NymBinding {
+    pub(super) OP: G1Projective,
+    pub(super) pseudonym: G1Projective,
+}
+
+#[derive(Clone, Debug)]
+pub(super) struct NymInitResult {
+    pseudonym: G1Projective,
+    OP: G1Projective,
+    U: G1Projective,
+}
+
+impl NymBinding {
+    /// `U = OP * nym_secret~`
+    fn proof_init(&self, nym_secret_tilde: Scalar) -> NymInitResult {
+        NymInitResult {
+            pseudonym: self.pseudonym,
+            OP: self.OP,
+            U: self.OP * nym_secret_tilde,
+        }
+    }
+
+    /// `U = OP * nym_secret^ - pseudonym * challenge`
+    fn verify_init(&self, nym_secret_cap: Scalar, challenge: Scalar) -> NymInitResult {
+        NymInitResult {
+            pseudonym: self.pseudonym,
+            OP: self.OP,
+            U: self.OP * nym_secret_cap - self.pseudonym * challenge,
+        }
+    }
+}
+
+/// Binds a proof to Pedersen commitments of some undisclosed messages, i.e. `C = G * m + H * s`, so that other proofs (e.g. range proofs) can be built over the commitments.
+/// The proof of opening of each commitment shares the `m~` random scalar with the proof of knowledge of the signature.
+#[derive(Clone, Debug)]
+pub(crate) struct CommitmentBinding {
+    pub(crate) G: G1Projective,
+    pub(crate) H: G1Projective,
+    /// index of the committed message, commitment `C` and either `s~` (proof generation) or `s^` (proof verification)
+    pub(crate) commitments: Vec<(usize, G1Projective, Scalar)>,
+    /// additional points to include in the challenge calculation
+    pub(crate) points: Vec<G1Projective>,
+}
+
+impl CommitmentBinding {
+    fn undisclosed_position(index: usize, undisclosed_indexes: &[usize]) -> Result<usize, Error> {
+        undisclosed_indexes
+            .iter()
+            .position(|&i| i == index)
+            .ok_or_else(|| Error::ProofGenError(format!("message {} must be undisclosed", index)))
+    }
+
+    /// `T = G * m~ + H * s~`, for each commitment
+    fn proof_init(
+        &self,
+        m_tilde: &[Scalar],
+        undisclosed_indexes: &[usize],
+    ) -> Result<Vec<G1Projective>, Error> {
+        let mut points = Vec::new();
+        for (index, C, s_tilde) in &self.commitments {
+            let j = Self::undisclosed_position(*index, undisclosed_indexes)?;
+            points.push(*C);
+            points.push(self.G * m_tilde[j] + self.H * s_tilde);
+        }
+        points.extend_from_slice(&self.points);
+        Ok(points)
+    }
+
+    /// `T = G * m^ + H * s^ - C * challenge`, for each commitment
+    fn verify_init(
+        &self,
+        m_cap: &[Scalar],
+        challenge: Scalar,
+        undisclosed_indexes: &[usize],
+    ) -> Result<Vec<G1Projective>, Error> {
+        let mut points = Vec::new();
+        for (index, C, s_cap) in &self.commitments {
+            let j = Self::undisclosed_position(*index, undisclosed_indexes)
+                .map_err(|e| Error::PoKSVerificationError(e.to_string()))?;
+            points.push(*C);
+            points.push(self.G * m_cap[j] + self.H * s_cap - C * challenge);
+        }
+        points.extend_from_slice(&self.points);
+        Ok(points)
+    }
 }
 
 /// https://datatracker.ietf.org/doc/html/draft-irtf-cfrg-bbs-signatures-07#name-proof-initialization
@@ -543,7 +1340,7 @@ struct ProofInitResult {
 /// # Output:
 /// a [`ProofInitResult`] or [`Error`].
 ///
-fn proof_init<CS>(
+pub(super) fn proof_init<CS>(
     pk: &BBSplusPublicKey,
     signature: &BBSplusSignature,
     generators: &Generators,
@@ -575,25 +1372,25 @@ where
 
     let mut B = generators.g1_base_point + Q1 * domain;
     for i in 0..L {
-        B = B + H_points[i] * messages[i].value;
+        B += H_points[i] * messages[i].value;
     }
 
-    let r1 = random_scalars[0];
-    let r2 = random_scalars[1];
-    let e_tilde = random_scalars[2];
-    let r1_tilde = random_scalars[3];
-    let r3_tilde = random_scalars[4];
+    let r1 = Zeroizing::new(random_scalars[0]);
+    let r2 = Zeroizing::new(random_scalars[1]);
+    let e_tilde = Zeroizing::new(random_scalars[2]);
+    let r1_tilde = Zeroizing::new(random_scalars[3]);
+    let r3_tilde = Zeroizing::new(random_scalars[4]);
     let m_tilde = &random_scalars[5..(5 + U)];
 
-    let D = B * r2;
-    let Abar = signature.A * (r1 * r2);
-    let Bbar = D * r1 - Abar * signature.e;
+    let D = B * *r2;
+    let Abar = signature.A * (*r1 * *r2);
+    let Bbar = D * *r1 - Abar * signature.e;
 
-    let T1 = Abar * e_tilde + D * r1_tilde;
-    let mut T2 = D * r3_tilde;
+    let T1 = Abar * *e_tilde + D * *r1_tilde;
+    let mut T2 = D * *r3_tilde;
 
     for idx in 0..U {
-        T2 = T2 + H_points[undisclosed_indexes[idx]] * m_tilde[idx];
+        T2 += H_points[undisclosed_indexes[idx]] * m_tilde[idx];
     }
 
     Ok(ProofInitResult {
@@ -613,6 +1410,8 @@ where
 ///
 /// # Inputs:
 /// * `init_res` (REQUIRED), [`ProofInitResult`] returned after initializing the proof generation or verification operations, consisting of 5 points of G1 and a scalar value, in that order.
+/// * `nym_init` (OPTIONAL), [`NymInitResult`] returned after initializing the pseudonym proof, consisting of 3 points of G1.
+/// * `commitments_init` (OPTIONAL), points of G1 returned after initializing the [`CommitmentBinding`]. It could be empty.
 /// * `header` (OPTIONAL), an octet string containing context and application.
 /// * `dsclosed_messages` (OPTIONAL), a vector of scalars ([`BBSplusMessage`]) representing the disclosed messages to the Verifier.
 /// * `disclosed_indexes` (OPTIONAL), vector of usize in ascending order. Indexes of disclosed messages.
@@ -624,6 +1423,8 @@ where
 ///
 fn proof_challenge_calculate<CS>(
     init_res: &ProofInitResult,
+    nym_init: Option<&NymInitResult>,
+    commitments_init: &[G1Projective],
     disclosed_indexes: &[usize],
     disclosed_messages: &[BBSplusMessage],
     ph: Option<&[u8]>,
@@ -632,6 +1433,47 @@ fn proof_challenge_calculate<CS>(
 where
     CS: BbsCiphersuite,
 {
+    let mut c_arr = challenge_octets(
+        init_res,
+        nym_init,
+        commitments_init,
+        disclosed_indexes,
+        disclosed_messages,
+    )?;
+
+    let api_id = api_id.unwrap_or(b"");
+    let challenge_dst = [api_id, CS::H2S].concat();
+
+    let ph = ph.unwrap_or(b"");
+
+    let ph_i2osp = i2osp::<8>(ph.len());
+
+    c_arr.extend_from_slice(&ph_i2osp);
+    c_arr.extend_from_slice(ph);
+
+    hash_to_scalar::<CS>(&c_arr, &challenge_dst)
+}
+
+/// # Description
+/// Serializes the part of the challenge input that depends on a single proof, i.e. the disclosed messages, the [`ProofInitResult`] and, if present, the pseudonym and commitments initialization results. Used by the [`proof_challenge_calculate`] operation and to compute joint challenges over multiple proofs.
+///
+/// # Inputs:
+/// * `init_res` (REQUIRED), [`ProofInitResult`] returned after initializing the proof generation or verification operations.
+/// * `nym_init` (OPTIONAL), [`NymInitResult`] returned after initializing the pseudonym proof.
+/// * `commitments_init` (OPTIONAL), points of G1 returned after initializing the [`CommitmentBinding`]. It could be empty.
+/// * `disclosed_indexes` (OPTIONAL), vector of usize in ascending order. Indexes of disclosed messages.
+/// * `dsclosed_messages` (OPTIONAL), a vector of scalars ([`BBSplusMessage`]) representing the disclosed messages to the Verifier.
+///
+/// # Output:
+/// an octet string or [`Error`].
+///
+pub(super) fn challenge_octets(
+    init_res: &ProofInitResult,
+    nym_init: Option<&NymInitResult>,
+    commitments_init: &[G1Projective],
+    disclosed_indexes: &[usize],
+    disclosed_messages: &[BBSplusMessage],
+) -> Result<Vec<u8>, Error> {
     let R = disclosed_indexes.len();
 
     if disclosed_messages.len() != R {
@@ -640,11 +1482,6 @@ where
         ));
     }
 
-    let api_id = api_id.unwrap_or(b"");
-    let challenge_dst = [api_id, CS::H2S].concat();
-
-    let ph = ph.unwrap_or(b"");
-
     let mut c_arr: Vec<u8> = Vec::new();
     c_arr.extend_from_slice(&i2osp::<8>(R));
     for (i, m) in core::iter::zip(disclosed_indexes, disclosed_messages) {
@@ -656,14 +1493,17 @@ where
     c_arr.extend_from_slice(&init_res.D.to_affine().to_compressed());
     c_arr.extend_from_slice(&init_res.T1.to_affine().to_compressed());
     c_arr.extend_from_slice(&init_res.T2.to_affine().to_compressed());
+    if let Some(nym_init) = nym_init {
+        c_arr.extend_from_slice(&nym_init.pseudonym.to_affine().to_compressed());
+        c_arr.extend_from_slice(&nym_init.OP.to_affine().to_compressed());
+        c_arr.extend_from_slice(&nym_init.U.to_affine().to_compressed());
+    }
+    for p in commitments_init {
+        c_arr.extend_from_slice(&p.to_affine().to_compressed());
+    }
     c_arr.extend_from_slice(&init_res.domain.to_bytes_be());
 
-    let ph_i2osp = i2osp::<8>(ph.len());
-
-    c_arr.extend_from_slice(&ph_i2osp);
-    c_arr.extend_from_slice(ph);
-
-    hash_to_scalar::<CS>(&c_arr, &challenge_dst)
+    Ok(c_arr)
 }
 
 /// https://datatracker.ietf.org/doc/html/draft-irtf-cfrg-bbs-signatures-07#name-proof-finalization
@@ -681,7 +1521,7 @@ where
 /// # Output:
 /// a proof ([`BBSplusPoKSignature`]) or [`Error`].
 ///
-fn proof_finalize(
+pub(super) fn proof_finalize(
     init_res: &ProofInitResult,
     challenge: Scalar,
     e: Scalar,
@@ -737,11 +1577,13 @@ fn proof_finalize(
 /// * `disclosed_messages` (OPTIONAL), a vector of scalars ([`BBSplusMessage`]) representing the messages disclosed to the Verifier.
 /// * `disclosed_indexes` (OPTIONAL), vector of unsigned integers in ascending order. Indexes of disclosed messages.
 /// * `api_id` (OPTIONAL), an octet string.
+/// * `nym` (OPTIONAL), a [`NymBinding`]. If supplied, the last undisclosed message is checked to be the `nym_secret` of the pseudonym.
+/// * `commitments` (OPTIONAL), a [`CommitmentBinding`]. If supplied, the commitments are checked to open to the corresponding undisclosed messages.
 ///
 /// # Output:
 /// a result: [`Ok`] or [`Error`].
 ///
-fn core_proof_verify<CS>(
+pub(super) fn core_proof_verify<CS>(
     pk: &BBSplusPublicKey,
     proof: &BBSplusPoKSignature,
     generators: &Generators,
@@ -750,32 +1592,42 @@ fn core_proof_verify<CS>(
     disclosed_messages: &[BBSplusMessage],
     disclosed_indexes: &[usize],
     api_id: Option<&[u8]>,
+    nym: Option<&NymBinding>,
+    commitments: Option<&CommitmentBinding>,
 ) -> Result<(), Error>
 where
     CS: BbsCiphersuite,
 {
-    let init_res = proof_verify_init::<CS>(
+    check_proof_challenge::<CS>(
         pk,
         proof,
         generators,
         header,
+        ph,
         disclosed_messages,
         disclosed_indexes,
         api_id,
+        nym,
+        commitments,
     )?;
 
-    let challenge = proof_challenge_calculate::<CS>(
-        &init_res,
-        disclosed_indexes,
-        disclosed_messages,
-        ph,
-        api_id,
-    )?;
-
-    if proof.challenge != challenge {
-        return Err(Error::PoKSVerificationError("invalid challenge".to_owned()));
-    }
+    check_proof_pairing(pk, proof)
+}
 
+/// # Description
+/// This operation checks that `e(Abar, pk) * e(Bbar, -BP2) == Identity_GT`, i.e. the pairing check of the [`core_proof_verify`] operation.
+///
+/// # Inputs:
+/// * `pk` (REQUIRED), the Signer public key.
+/// * `proof` (REQUIRED), a [`BBSplusPoKSignature`].
+///
+/// # Output:
+/// a result: [`Ok`] or [`Error`].
+///
+pub(super) fn check_proof_pairing(
+    pk: &BBSplusPublicKey,
+    proof: &BBSplusPoKSignature,
+) -> Result<(), Error> {
     let BP2 = G2Projective::GENERATOR;
 
     let term1 = (&proof.Abar.to_affine(), &G2Prepared::from(pk.0.to_affine()));
@@ -790,6 +1642,90 @@ where
     }
 }
 
+/// # Description
+/// This operation recomputes the challenge of a proof and checks it against the one in the proof. Together with the pairing check, it makes up the [`core_proof_verify`] operation.
+///
+/// # Inputs:
+/// * `pk` (REQUIRED), the Signer public key.
+/// * `proof` (REQUIRED), a [`BBSplusPoKSignature`].
+/// * `generators` (REQUIRED), vector of pseudo-random points in G1.
+/// * `header` (OPTIONAL), an octet string containing context and application.
+/// * `ph` (OPTIONAL), an octet string containing the presentation header.
+/// * `disclosed_messages` (OPTIONAL), a vector of scalars ([`BBSplusMessage`]) representing the messages disclosed to the Verifier.
+/// * `disclosed_indexes` (OPTIONAL), vector of unsigned integers in ascending order. Indexes of disclosed messages.
+/// * `api_id` (OPTIONAL), an octet string.
+/// * `nym` (OPTIONAL), a [`NymBinding`].
+/// * `commitments` (OPTIONAL), a [`CommitmentBinding`].
+///
+/// # Output:
+/// a result: [`Ok`] or [`Error`].
+///
+fn check_proof_challenge<CS>(
+    pk: &BBSplusPublicKey,
+    proof: &BBSplusPoKSignature,
+    generators: &Generators,
+    header: Option<&[u8]>,
+    ph: Option<&[u8]>,
+    disclosed_messages: &[BBSplusMessage],
+    disclosed_indexes: &[usize],
+    api_id: Option<&[u8]>,
+    nym: Option<&NymBinding>,
+    commitments: Option<&CommitmentBinding>,
+) -> Result<(), Error>
+where
+    CS: BbsCiphersuite,
+{
+    let nym_init = match nym {
+        Some(nym) => {
+            let L = proof.m_cap.len() + disclosed_indexes.len();
+            let m_cap = proof
+                .m_cap
+                .last()
+                .filter(|_| !disclosed_indexes.contains(&(L - 1)));
+            let m_cap = m_cap.ok_or_else(|| {
+                Error::PoKSVerificationError("nym_secret must be undisclosed".to_owned())
+            })?;
+            Some(nym.verify_init(*m_cap, proof.challenge))
+        }
+        None => None,
+    };
+
+    let commitments_init = match commitments {
+        Some(c) => {
+            let L = proof.m_cap.len() + disclosed_indexes.len();
+            let undisclosed_indexes = get_remaining_indexes(L, disclosed_indexes);
+            c.verify_init(&proof.m_cap, proof.challenge, &undisclosed_indexes)?
+        }
+        None => Vec::new(),
+    };
+
+    let init_res = proof_verify_init::<CS>(
+        pk,
+        proof,
+        generators,
+        header,
+        disclosed_messages,
+        disclosed_indexes,
+        api_id,
+    )?;
+
+    let challenge = proof_challenge_calculate::<CS>(
+        &init_res,
+        nym_init.as_ref(),
+        &commitments_init,
+        disclosed_indexes,
+        disclosed_messages,
+        ph,
+        api_id,
+    )?;
+
+    if proof.challenge != challenge {
+        return Err(Error::PoKSVerificationError("invalid challenge".to_owned()));
+    }
+
+    Ok(())
+}
+
 /// https://datatracker.ietf.org/doc/html/draft-irtf-cfrg-bbs-signatures-07#name-proof-verification-initiali
 ///
 /// # Description
@@ -807,7 +1743,7 @@ where
 /// # Output:
 /// a [`ProofInitResult`] or [`Error`].
 ///
-fn proof_verify_init<CS>(
+pub(super) fn proof_verify_init<CS>(
     pk: &BBSplusPublicKey,
     proof: &BBSplusPoKSignature,
     generators: &Generators,
@@ -901,6 +1837,10 @@ impl BBSplusZKPoK {
     }
 
     pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        // s_cap and at least the challenge
+        if bytes.len() < 64 || bytes.len() % 32 != 0 {
+            return Err(Error::InvalidProofOfKnowledgeSignature);
+        }
         let s_cap = Scalar::from_bytes_be(
             &<[u8; 32]>::try_from(&bytes[0..32])
                 .map_err(|_| Error::InvalidProofOfKnowledgeSignature)?,
@@ -928,16 +1868,18 @@ impl BBSplusZKPoK {
 mod tests {
     use crate::{
         bbsplus::{
-            ciphersuites::BbsCiphersuite, commitment::BlindFactor, keys::BBSplusPublicKey,
-            proof::seeded_random_scalars, signature::BBSplusSignature,
+            ciphersuites::BbsCiphersuite, commitment::BlindFactor, generators::GeneratorsCache,
+            keys::BBSplusPublicKey, proof::ProofBatchItem, signature::BBSplusSignature,
         },
+        errors::Error,
         schemes::{
             algorithms::{BBSplus, BbsBls12381Sha256, BbsBls12381Shake256, Scheme},
             generics::{PoKSignature, Signature},
         },
-        utils::util::bbsplus_utils::{get_messages_vec, ScalarExt},
+        utils::util::bbsplus_utils::{get_messages_vec, seeded_random_scalars, ScalarExt},
     };
     use elliptic_curve::hash2curve::ExpandMsg;
+    use rand::{rngs::StdRng, SeedableRng};
 
     //mocked_rng - SHA256 - UPDATED
     #[test]
@@ -996,6 +1938,53 @@ mod tests {
         },
     }
 
+    // PROOF WITH PREBUILT GENERATORS
+
+    #[test]
+    fn proof_with_generators_sha256() {
+        proof_with_generators::<BbsBls12381Sha256>(
+            "./fixture_data/bls12-381-sha-256/",
+            &[
+                "proof/proof001.json",
+                "proof/proof003.json",
+                "proof/proof014.json",
+            ],
+        );
+    }
+
+    #[test]
+    fn proof_with_generators_shake256() {
+        proof_with_generators::<BbsBls12381Shake256>(
+            "./fixture_data/bls12-381-shake-256/",
+            &[
+                "proof/proof001.json",
+                "proof/proof003.json",
+                "proof/proof014.json",
+            ],
+        );
+    }
+
+    // BATCH PROOF VERIFY
+
+    macro_rules! batch_proof_verify_tests {
+        ( $( ($t:ident, $p:literal): { $( ($n:ident, [$($f:literal),+], $e:expr), )+ },)+ ) => { $($(
+            #[test] fn $n() { batch_proof_verify::<$t>($p, &[$($f),+], $e); }
+        )+)+ }
+    }
+
+    batch_proof_verify_tests! {
+        (BbsBls12381Sha256, "./fixture_data/bls12-381-sha-256/"): {
+            (batch_proof_verify_sha256_1, ["proof/proof001.json", "proof/proof002.json", "proof/proof003.json", "proof/proof014.json", "proof/proof015.json"], None),
+            (batch_proof_verify_sha256_2, ["proof/proof001.json", "proof/proof002.json", "proof/proof005.json", "proof/proof003.json"], Some(2)),
+            (batch_proof_verify_sha256_3, ["proof/proof003.json", "proof/proof012.json"], Some(1)),
+        },
+        (BbsBls12381Shake256, "./fixture_data/bls12-381-shake-256/"): {
+            (batch_proof_verify_shake256_1, ["proof/proof001.json", "proof/proof002.json", "proof/proof003.json", "proof/proof014.json", "proof/proof015.json"], None),
+            (batch_proof_verify_shake256_2, ["proof/proof001.json", "proof/proof002.json", "proof/proof005.json", "proof/proof003.json"], Some(2)),
+            (batch_proof_verify_shake256_3, ["proof/proof003.json", "proof/proof012.json"], Some(1)),
+        },
+    }
+
     // BLIND PROOF OF KNOWLEDGE OF A SIGNATURE
 
     macro_rules! blind_proof_tests {
@@ -1058,7 +2047,7 @@ mod tests {
             let scalar_expected = mocked_scalars_hex[i];
 
             if scalar_hex != scalar_expected {
-                if results == true {
+                if results {
                     results = false
                 }
                 eprintln!(" count: {}", i);
@@ -1121,19 +2110,63 @@ mod tests {
             .map(|m| hex::decode(m).unwrap())
             .collect();
 
-        let proof = PoKSignature::<BBSplus<S::Ciphersuite>>::proof_gen(
+        let proof = PoKSignature::<BBSplus<S::Ciphersuite>>::proof_gen_with_mocked_scalars(
+            &PK,
+            &signature.to_bytes(),
+            Some(&header),
+            Some(&ph),
+            Some(&msgs),
+            Some(&revealed_message_indexes),
+            None,
+            None,
+        )
+        .unwrap();
+        let my_encoded_proof = hex::encode(proof.to_bytes());
+
+        let reseeded_proof =
+            PoKSignature::<BBSplus<S::Ciphersuite>>::proof_gen_with_mocked_scalars(
+                &PK,
+                &signature.to_bytes(),
+                Some(&header),
+                Some(&ph),
+                Some(&msgs),
+                Some(&revealed_message_indexes),
+                Some(b"another seed"),
+                None,
+            )
+            .unwrap();
+        assert_ne!(reseeded_proof.to_bytes(), proof.to_bytes());
+
+        let rng_proof = PoKSignature::<BBSplus<S::Ciphersuite>>::proof_gen_with_rng(
             &PK,
             &signature.to_bytes(),
             Some(&header),
             Some(&ph),
             Some(&msgs),
             Some(&revealed_message_indexes),
+            &mut StdRng::seed_from_u64(1),
         )
         .unwrap();
-        let my_encoded_proof = hex::encode(&proof.to_bytes());
+        assert_ne!(rng_proof.to_bytes(), proof.to_bytes());
+
+        for other_proof in [&reseeded_proof, &rng_proof] {
+            assert!(
+                !result_expected
+                    || other_proof
+                        .proof_verify(
+                            &PK,
+                            Some(&get_messages_vec(&msgs, &revealed_message_indexes)),
+                            Some(&revealed_message_indexes),
+                            Some(&header),
+                            Some(&ph),
+                        )
+                        .is_ok()
+            );
+        }
+
         let result0 = proof_expected == my_encoded_proof;
         let result1 = result0 == result_expected;
-        if result1 == false {
+        if !result1 {
             println!("  proofGen: {}", result1);
             println!("  Expected: {}", proof_expected);
             println!("  Computed: {}", my_encoded_proof);
@@ -1170,7 +2203,7 @@ mod tests {
             eprintln!("  proofVerify: {}", result3);
             eprintln!("  Expected: {}", result_expected);
             eprintln!("  Computed: {}", result2);
-            if result_expected == false {
+            if !result_expected {
                 eprintln!(
                     "{} ({})",
                     result3,
@@ -1180,6 +2213,248 @@ mod tests {
         }
     }
 
+    fn proof_with_generators<S: Scheme>(pathname: &str, proof_filenames: &[&str])
+    where
+        S::Ciphersuite: BbsCiphersuite,
+        <S::Ciphersuite as BbsCiphersuite>::Expander: for<'a> ExpandMsg<'a>,
+    {
+        let api_id = <S::Ciphersuite as BbsCiphersuite>::API_ID;
+        let cache = GeneratorsCache::<S::Ciphersuite>::new();
+
+        for proof_filename in proof_filenames {
+            let data = std::fs::read_to_string([pathname, proof_filename].concat())
+                .expect("Unable to read file");
+            let proof_json: serde_json::Value =
+                serde_json::from_str(&data).expect("Unable to parse");
+
+            let PK = BBSplusPublicKey::from_bytes(
+                &hex::decode(proof_json["signerPublicKey"].as_str().unwrap()).unwrap(),
+            )
+            .unwrap();
+            let signature = hex::decode(proof_json["signature"].as_str().unwrap()).unwrap();
+            let header = hex::decode(proof_json["header"].as_str().unwrap()).unwrap();
+            let ph = hex::decode(proof_json["presentationHeader"].as_str().unwrap()).unwrap();
+            let msgs: Vec<Vec<u8>> = proof_json["messages"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|m| hex::decode(m.as_str().unwrap()).unwrap())
+                .collect();
+            let disclosed_indexes: Vec<usize> = proof_json["disclosedIndexes"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|m| serde_json::from_value(m.clone()).unwrap())
+                .collect();
+            let disclosed_messages = get_messages_vec(&msgs, &disclosed_indexes);
+            let proof_expected = proof_json["proof"].as_str().unwrap();
+
+            let generators = cache.get(msgs.len() + 1, Some(api_id));
+
+            let proof = PoKSignature::<BBSplus<S::Ciphersuite>>::proof_gen_with_generators_and_rng(
+                &PK,
+                &signature,
+                Some(&header),
+                Some(&ph),
+                Some(&msgs),
+                Some(&disclosed_indexes),
+                &generators,
+                &mut StdRng::seed_from_u64(1),
+            )
+            .unwrap();
+            let created_generators_proof =
+                PoKSignature::<BBSplus<S::Ciphersuite>>::proof_gen_with_rng(
+                    &PK,
+                    &signature,
+                    Some(&header),
+                    Some(&ph),
+                    Some(&msgs),
+                    Some(&disclosed_indexes),
+                    &mut StdRng::seed_from_u64(1),
+                )
+                .unwrap();
+            assert_eq!(proof.to_bytes(), created_generators_proof.to_bytes());
+
+            let fixture_proof = PoKSignature::<BBSplus<S::Ciphersuite>>::from_bytes(
+                &hex::decode(proof_expected).unwrap(),
+            )
+            .unwrap();
+            assert!(fixture_proof
+                .proof_verify_with_generators(
+                    &PK,
+                    Some(&disclosed_messages),
+                    Some(&disclosed_indexes),
+                    Some(&header),
+                    Some(&ph),
+                    &generators,
+                )
+                .is_ok());
+
+            assert!(proof
+                .proof_verify_with_generators(
+                    &PK,
+                    Some(&disclosed_messages),
+                    Some(&disclosed_indexes),
+                    Some(&header),
+                    Some(&ph),
+                    &generators,
+                )
+                .is_ok());
+        }
+    }
+
+    #[test]
+    fn batch_proof_verify_first_invalid_sha256() {
+        batch_proof_verify_first_invalid::<BbsBls12381Sha256>("./fixture_data/bls12-381-sha-256/");
+    }
+
+    #[test]
+    fn batch_proof_verify_first_invalid_shake256() {
+        batch_proof_verify_first_invalid::<BbsBls12381Shake256>(
+            "./fixture_data/bls12-381-shake-256/",
+        );
+    }
+
+    // A proof with a valid challenge but an invalid pairing equation, followed by one with an invalid challenge
+    fn batch_proof_verify_first_invalid<S: Scheme>(pathname: &str)
+    where
+        S::Ciphersuite: BbsCiphersuite,
+        <S::Ciphersuite as BbsCiphersuite>::Expander: for<'a> ExpandMsg<'a>,
+    {
+        let load = |filename: &str| -> serde_json::Value {
+            let data = std::fs::read_to_string([pathname, filename].concat())
+                .expect("Unable to read file");
+            serde_json::from_str(&data).expect("Unable to parse")
+        };
+        let hex_field =
+            |json: &serde_json::Value, key: &str| hex::decode(json[key].as_str().unwrap()).unwrap();
+
+        let proof_json = load("proof/proof003.json");
+        let pk = BBSplusPublicKey::from_bytes(&hex_field(&proof_json, "signerPublicKey")).unwrap();
+        let signature = hex_field(&proof_json, "signature");
+        let header = hex_field(&proof_json, "header");
+        let ph = hex_field(&proof_json, "presentationHeader");
+        let disclosed_indexes: Vec<usize> =
+            serde_json::from_value(proof_json["disclosedIndexes"].clone()).unwrap();
+
+        // The signature does not sign these messages, so only the pairing equation fails
+        let forged_messages: Vec<Vec<u8>> = proof_json["messages"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|m| {
+                let mut m = hex::decode(m.as_str().unwrap()).unwrap();
+                m.push(0);
+                m
+            })
+            .collect();
+        let forged_proof = PoKSignature::<BBSplus<S::Ciphersuite>>::proof_gen(
+            &pk,
+            &signature,
+            Some(&header),
+            Some(&ph),
+            Some(&forged_messages),
+            Some(&disclosed_indexes),
+        )
+        .unwrap();
+        let forged_disclosed = get_messages_vec(&forged_messages, &disclosed_indexes);
+
+        let wrong_ph = [ph.as_slice(), b"wrong"].concat();
+        let items = [
+            ProofBatchItem {
+                proof: &forged_proof,
+                pk: &pk,
+                disclosed_messages: Some(&forged_disclosed),
+                disclosed_indexes: Some(&disclosed_indexes),
+                header: Some(&header),
+                ph: Some(&ph),
+            },
+            ProofBatchItem {
+                proof: &forged_proof,
+                pk: &pk,
+                disclosed_messages: Some(&forged_disclosed),
+                disclosed_indexes: Some(&disclosed_indexes),
+                header: Some(&header),
+                ph: Some(&wrong_ph),
+            },
+        ];
+
+        let result = PoKSignature::<BBSplus<S::Ciphersuite>>::batch_proof_verify(&items);
+        assert!(
+            matches!(result, Err(Error::BatchVerificationError(0))),
+            "failed: {:?}",
+            result
+        );
+    }
+
+    fn batch_proof_verify<S: Scheme>(
+        pathname: &str,
+        proof_filenames: &[&str],
+        invalid_index: Option<usize>,
+    ) where
+        S::Ciphersuite: BbsCiphersuite,
+        <S::Ciphersuite as BbsCiphersuite>::Expander: for<'a> ExpandMsg<'a>,
+    {
+        let mut inputs = Vec::new();
+        for proof_filename in proof_filenames {
+            let data = std::fs::read_to_string([pathname, proof_filename].concat())
+                .expect("Unable to read file");
+            let proof_json: serde_json::Value =
+                serde_json::from_str(&data).expect("Unable to parse");
+
+            let PK = BBSplusPublicKey::from_bytes(
+                &hex::decode(proof_json["signerPublicKey"].as_str().unwrap()).unwrap(),
+            )
+            .unwrap();
+            let header = hex::decode(proof_json["header"].as_str().unwrap()).unwrap();
+            let ph = hex::decode(proof_json["presentationHeader"].as_str().unwrap()).unwrap();
+            let msgs: Vec<Vec<u8>> = proof_json["messages"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|m| hex::decode(m.as_str().unwrap()).unwrap())
+                .collect();
+            let disclosed_indexes: Vec<usize> = proof_json["disclosedIndexes"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|m| serde_json::from_value(m.clone()).unwrap())
+                .collect();
+            let disclosed_messages = get_messages_vec(&msgs, &disclosed_indexes);
+            let proof = PoKSignature::<BBSplus<S::Ciphersuite>>::from_bytes(
+                &hex::decode(proof_json["proof"].as_str().unwrap()).unwrap(),
+            )
+            .unwrap();
+
+            inputs.push((proof, PK, disclosed_messages, disclosed_indexes, header, ph));
+        }
+
+        let items: Vec<ProofBatchItem<S::Ciphersuite>> = inputs
+            .iter()
+            .map(
+                |(proof, pk, disclosed_messages, disclosed_indexes, header, ph)| ProofBatchItem {
+                    proof,
+                    pk,
+                    disclosed_messages: Some(disclosed_messages),
+                    disclosed_indexes: Some(disclosed_indexes),
+                    header: Some(header),
+                    ph: Some(ph),
+                },
+            )
+            .collect();
+
+        let result = PoKSignature::<BBSplus<S::Ciphersuite>>::batch_proof_verify(&items);
+
+        match invalid_index {
+            None => assert!(result.is_ok(), "failed: {:?}", result),
+            Some(index) => assert!(
+                matches!(result, Err(Error::BatchVerificationError(i)) if i == index),
+                "failed: {:?}",
+                result
+            ),
+        }
+    }
+
     fn blind_proof_check<S: Scheme>(pathname: &str, proof_filename: &str, messages_path: &str)
     where
         S::Ciphersuite: BbsCiphersuite,
@@ -1207,20 +2482,16 @@ mod tests {
                     .map(|m| serde_json::from_value(m.clone()).unwrap())
                     .collect()
             });
-        let committed_messages: Option<Vec<Vec<u8>>> = match committed_messages {
-            Some(cm) => Some(cm.iter().map(|m| hex::decode(m).unwrap()).collect()),
-            None => None,
-        };
+        let committed_messages: Option<Vec<Vec<u8>>> =
+            committed_messages.map(|cm| cm.iter().map(|m| hex::decode(m).unwrap()).collect());
 
         let messages: Option<Vec<String>> = messages_json["messages"].as_array().and_then(|cm| {
             cm.iter()
                 .map(|m| serde_json::from_value(m.clone()).unwrap())
                 .collect()
         });
-        let messages: Option<Vec<Vec<u8>>> = match messages {
-            Some(m) => Some(m.iter().map(|m| hex::decode(m).unwrap()).collect()),
-            None => None,
-        };
+        let messages: Option<Vec<Vec<u8>>> =
+            messages.map(|m| m.iter().map(|m| hex::decode(m).unwrap()).collect());
 
         let secret_prover_blind = proof_json["proverBlind"].as_str().map(|b| {
             BlindFactor::from_bytes(&hex::decode(b).unwrap().try_into().unwrap()).unwrap()
@@ -1275,7 +2546,7 @@ mod tests {
             None
         };
 
-        let proof = PoKSignature::<BBSplus<S::Ciphersuite>>::blind_proof_gen(
+        let proof = PoKSignature::<BBSplus<S::Ciphersuite>>::blind_proof_gen_with_mocked_scalars(
             &pk,
             &signature.to_bytes(),
             Some(&header),
@@ -1286,6 +2557,8 @@ mod tests {
             disclosed_commitment_indexes.as_deref(),
             secret_prover_blind.as_ref(),
             signer_blind.as_ref(),
+            None,
+            None,
         )
         .unwrap();
 
@@ -1309,5 +2582,59 @@ mod tests {
         let expected_result = proof_json["result"]["valid"].as_bool().unwrap();
 
         assert_eq!(result, expected_result);
+
+        // prebuilt generators
+        let api_id = <S::Ciphersuite as BbsCiphersuite>::API_ID_BLIND;
+        let cache = GeneratorsCache::<S::Ciphersuite>::new();
+        let L = messages.as_ref().map_or(0, Vec::len);
+        let M = used_committed_messages.as_ref().map_or(0, Vec::len);
+        let generators = cache.get(L + 1, Some(api_id));
+        let blind_generators = cache.get(M + 1, Some(&[b"BLIND_", api_id].concat()));
+
+        let verify_with_generators =
+            |proof: &PoKSignature<BBSplus<S::Ciphersuite>>, generators, blind_generators| {
+                proof.blind_proof_verify_with_generators(
+                    &pk,
+                    Some(&header),
+                    Some(&ph),
+                    Some(L),
+                    disclosed_messages.as_deref(),
+                    disclosed_committed_messages.as_deref(),
+                    disclosed_indexes.as_deref(),
+                    disclosed_commitment_indexes.as_deref(),
+                    generators,
+                    blind_generators,
+                )
+            };
+        assert_eq!(
+            verify_with_generators(&proof, &generators, &blind_generators).is_ok(),
+            expected_result
+        );
+
+        // generators created for another api_id are rejected
+        let other_generators = cache.get(L + 1, Some(<S::Ciphersuite as BbsCiphersuite>::API_ID));
+        assert!(matches!(
+            verify_with_generators(&proof, &other_generators, &blind_generators),
+            Err(Error::CiphersuiteMismatch)
+        ));
+
+        if expected_result {
+            let proof = PoKSignature::<BBSplus<S::Ciphersuite>>::blind_proof_gen_with_generators(
+                &pk,
+                &signature.to_bytes(),
+                Some(&header),
+                Some(&ph),
+                messages.as_deref(),
+                used_committed_messages.as_deref(),
+                disclosed_indexes.as_deref(),
+                disclosed_commitment_indexes.as_deref(),
+                secret_prover_blind.as_ref(),
+                signer_blind.as_ref(),
+                &generators,
+                &blind_generators,
+            )
+            .unwrap();
+            assert!(verify_with_generators(&proof, &generators, &blind_generators).is_ok());
+        }
     }
 }
@@ -0,0 +1,1224 @@
+// Copyright 2023 Fondazione LINKS
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::{
+    ciphersuites::BbsCiphersuite,
+    commitment::BlindFactor,
+    generators::Generators,
+    keys::{BBSplusPublicKey, BBSplusSecretKey},
+    proof::{core_proof_gen, core_proof_verify, NymBinding},
+    signature::{core_sign, core_verify, BBSplusSignature},
+};
+use crate::{
+    errors::Error,
+    schemes::{
+        algorithms::BBSplus,
+        generics::{PoKSignature, Signature},
+    },
+    utils::{
+        message::bbsplus_message::BBSplusMessage,
+        util::bbsplus_utils::{get_random_with_rng, parse_g1_projective, Randomness, ScalarExt},
+    },
+};
+use alloc::{borrow::ToOwned, vec::Vec};
+use bls12_381_plus::{G1Projective, Scalar};
+use core::fmt;
+use elliptic_curve::{group::Curve, hash2curve::ExpandMsg};
+#[cfg(feature = "std")]
+use rand::thread_rng;
+use rand::{CryptoRng, RngCore};
+use serde::{Deserialize, Serialize};
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+/// Secret value of the Prover (prover_nym), signed as the last message and used to compute the pseudonyms.
+pub struct ProverNym(pub(crate) Scalar);
+
+impl ProverNym {
+    #[cfg(feature = "std")]
+    pub fn random() -> Self {
+        Self::random_with_rng(&mut thread_rng())
+    }
+
+    /// Returns a random `prover_nym` drawn from `rng`
+    pub fn random_with_rng<R: RngCore + CryptoRng>(rng: &mut R) -> Self {
+        Self(get_random_with_rng(rng))
+    }
+
+    /// Returns the `nym_secret` (prover_nym + signer_nym_entropy) signed by a blind signature issued with a `signer_nym_entropy`
+    pub fn nym_secret(&self, signer_nym_entropy: &BlindFactor) -> Self {
+        Self(self.0 + signer_nym_entropy.0)
+    }
+
+    pub fn to_bytes(&self) -> [u8; 32] {
+        self.0.to_be_bytes()
+    }
+
+    pub fn from_bytes(bytes: &[u8; 32]) -> Result<Self, Error> {
+        Ok(Self(Scalar::from_bytes_be(bytes)?))
+    }
+}
+
+impl Zeroize for ProverNym {
+    fn zeroize(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl Drop for ProverNym {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for ProverNym {}
+
+impl fmt::Debug for ProverNym {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ProverNym(<redacted>)")
+    }
+}
+
+/// Verifier-scoped pseudonym of a Prover. Pseudonyms computed for different verifier ids are unlinkable.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct Pseudonym(pub G1Projective);
+
+impl Pseudonym {
+    /// # Description
+    /// Computes the pseudonym of a Prover for a specific Verifier as `OP * nym_secret`, where `OP` is the hash to curve of the `verifier_id`.
+    /// This is the pseudonym returned by [`PoKSignature::nym_proof_gen`].
+    ///
+    /// # Inputs:
+    /// * `verifier_id` (REQUIRED), an octet string identifying the Verifier.
+    /// * `prover_nym` (REQUIRED), the Prover secret ([`ProverNym`]).
+    ///
+    /// # Output:
+    /// * a [`Pseudonym`] or [`Error`].
+    ///
+    pub fn new<CS>(verifier_id: &[u8], prover_nym: &ProverNym) -> Result<Self, Error>
+    where
+        CS: BbsCiphersuite,
+        CS::Expander: for<'a> ExpandMsg<'a>,
+    {
+        calculate_pseudonym::<CS>(verifier_id, prover_nym.0, CS::API_ID_NYM)
+    }
+
+    /// # Description
+    /// Computes the pseudonym of a Prover for a specific Verifier in the blind flow, where `nym_secret` is the
+    /// [`ProverNym`] combined with the Signer entropy. This is the pseudonym returned by [`PoKSignature::blind_nym_proof_gen`].
+    ///
+    /// # Inputs:
+    /// * `verifier_id` (REQUIRED), an octet string identifying the Verifier.
+    /// * `nym_secret` (REQUIRED), the Prover secret combined with the Signer entropy ([`ProverNym::nym_secret`]).
+    ///
+    /// # Output:
+    /// * a [`Pseudonym`] or [`Error`].
+    ///
+    pub fn new_blind<CS>(verifier_id: &[u8], nym_secret: &ProverNym) -> Result<Self, Error>
+    where
+        CS: BbsCiphersuite,
+        CS::Expander: for<'a> ExpandMsg<'a>,
+    {
+        calculate_pseudonym::<CS>(verifier_id, nym_secret.0, CS::API_ID_BLIND_NYM)
+    }
+
+    pub fn to_bytes(&self) -> [u8; G1Projective::COMPRESSED_BYTES] {
+        self.0.to_affine().to_compressed()
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        let point = parse_g1_projective(bytes).map_err(|_| Error::InvalidPseudonym)?;
+        if point == G1Projective::IDENTITY {
+            return Err(Error::InvalidPseudonym);
+        }
+        Ok(Self(point))
+    }
+}
+
+impl<CS: BbsCiphersuite> Signature<BBSplus<CS>> {
+    /// # Description
+    /// The `nym_sign` API returns a BBS signature over a header, a set of messages and the secret `prover_nym` of the Prover, which is signed as an additional last message. The signature can then be used to compute verifier-scoped pseudonyms ([`PoKSignature::nym_proof_gen`]).
+    ///
+    /// # Inputs:
+    /// * `messages` (OPTIONAL), a vector of octet strings representing the messages, it could be an empty vector.
+    /// * `sk` (REQUIRED), a secret key
+    /// * `pk` (REQUIRED), a public key
+    /// * `header` (OPTIONAL), an octet string containing context and application specific information.
+    /// * `prover_nym` (REQUIRED), the Prover secret ([`ProverNym`]).
+    ///
+    /// # Output:
+    /// * new [`Signature::BBSplus`] or [`Error`]
+    pub fn nym_sign(
+        messages: Option<&[Vec<u8>]>,
+        sk: &BBSplusSecretKey,
+        pk: &BBSplusPublicKey,
+        header: Option<&[u8]>,
+        prover_nym: &ProverNym,
+    ) -> Result<Self, Error>
+    where
+        CS::Expander: for<'a> ExpandMsg<'a>,
+    {
+        let messages = messages.unwrap_or(&[]);
+        let api_id = CS::API_ID_NYM;
+        let mut message_scalars = BBSplusMessage::messages_to_scalar::<CS>(messages, api_id)?;
+        message_scalars.push(BBSplusMessage::new(prover_nym.0));
+
+        let generators = Generators::create::<CS>(message_scalars.len() + 1, Some(api_id));
+        let signature =
+            core_sign::<CS>(sk, pk, generators, header, &message_scalars, Some(api_id))?;
+
+        Ok(Self::BBSplus(signature))
+    }
+
+    /// # Description
+    /// The `nym_verify` API validates a BBS signature generated with [`Self::nym_sign`], given a public key (PK), a header, a set of messages and the Prover secret `prover_nym`.
+    ///
+    /// # Inputs:
+    /// * `self`, the signature
+    /// * `pk` (REQUIRED), a public key
+    /// * `messages` (OPTIONAL), a vector of octet strings representing the messages, it could be an empty vector.
+    /// * `header` (OPTIONAL), an octet string containing context and application specific information.
+    /// * `prover_nym` (REQUIRED), the Prover secret ([`ProverNym`]).
+    ///
+    /// # Output:
+    /// * a result either [`Ok()`] or [`Error`]
+    pub fn nym_verify(
+        &self,
+        pk: &BBSplusPublicKey,
+        messages: Option<&[Vec<u8>]>,
+        header: Option<&[u8]>,
+        prover_nym: &ProverNym,
+    ) -> Result<(), Error>
+    where
+        CS::Expander: for<'a> ExpandMsg<'a>,
+    {
+        let messages = messages.unwrap_or(&[]);
+        let api_id = CS::API_ID_NYM;
+        let mut message_scalars = BBSplusMessage::messages_to_scalar::<CS>(messages, api_id)?;
+        message_scalars.push(BBSplusMessage::new(prover_nym.0));
+
+        let generators = Generators::create::<CS>(message_scalars.len() + 1, Some(api_id));
+
+        core_verify::<CS>(
+            pk,
+            self.bbsPlusSignature(),
+            &message_scalars,
+            generators,
+            header,
+            Some(api_id),
+        )
+    }
+}
+
+impl<CS: BbsCiphersuite> PoKSignature<BBSplus<CS>> {
+    /// # Description
+    /// This operation creates a BBS proof of a signature generated with [`Signature::nym_sign`], together with the pseudonym of the Prover for the Verifier identified by `verifier_id`. The proof shows that the pseudonym is computed from the `prover_nym` signed by the Signer, without revealing it.
+    ///
+    /// # Inputs:
+    /// * `pk` (REQUIRED), the Signer public key.
+    /// * `signature` (REQUIRED), an octet string.
+    /// * `header` (OPTIONAL), an octet string containing context and application.
+    /// * `ph` (OPTIONAL), an octet string containing the presentation header.
+    /// * `messages` (OPTIONAL), a vector of octet strings representing the signed messages.
+    /// * `disclosed_indexes` (OPTIONAL), vector of unsigned integers in ascending order. Indexes of disclosed messages.
+    /// * `verifier_id` (REQUIRED), an octet string identifying the Verifier.
+    /// * `prover_nym` (REQUIRED), the Prover secret ([`ProverNym`]).
+    ///
+    /// # Output:
+    /// a tuple ([`PoKSignature::BBSplus`], [`Pseudonym`]) or [`Error`].
+    ///
+    #[cfg(feature = "std")]
+    pub fn nym_proof_gen(
+        pk: &BBSplusPublicKey,
+        signature: &[u8],
+        header: Option<&[u8]>,
+        ph: Option<&[u8]>,
+        messages: Option<&[Vec<u8>]>,
+        disclosed_indexes: Option<&[usize]>,
+        verifier_id: &[u8],
+        prover_nym: &ProverNym,
+    ) -> Result<(Self, Pseudonym), Error>
+    where
+        CS::Expander: for<'a> ExpandMsg<'a>,
+    {
+        Self::nym_proof_gen_with_rng(
+            pk,
+            signature,
+            header,
+            ph,
+            messages,
+            disclosed_indexes,
+            verifier_id,
+            prover_nym,
+            &mut thread_rng(),
+        )
+    }
+
+    /// # Description
+    /// Same as [`Self::nym_proof_gen`], but the random scalars of the proof are drawn from `rng`.
+    ///
+    /// # Inputs:
+    /// * `rng` (REQUIRED), a cryptographically secure random number generator.
+    /// * the other inputs are the same as [`Self::nym_proof_gen`].
+    ///
+    /// # Output:
+    /// a tuple ([`PoKSignature::BBSplus`], [`Pseudonym`]) or [`Error`].
+    ///
+    pub fn nym_proof_gen_with_rng<R: RngCore + CryptoRng>(
+        pk: &BBSplusPublicKey,
+        signature: &[u8],
+        header: Option<&[u8]>,
+        ph: Option<&[u8]>,
+        messages: Option<&[Vec<u8>]>,
+        disclosed_indexes: Option<&[usize]>,
+        verifier_id: &[u8],
+        prover_nym: &ProverNym,
+        rng: &mut R,
+    ) -> Result<(Self, Pseudonym), Error>
+    where
+        CS::Expander: for<'a> ExpandMsg<'a>,
+    {
+        Self::nym_proof_gen_internal(
+            pk,
+            signature,
+            header,
+            ph,
+            messages,
+            disclosed_indexes,
+            verifier_id,
+            prover_nym,
+            Randomness::Rng(rng),
+        )
+    }
+
+    /// # Description
+    /// Same as [`Self::nym_proof_gen`], but the random scalars are replaced by the mocked random scalars computed from `seed` and `dst`. The resulting proofs are deterministic, so this MUST only be used for testing.
+    ///
+    /// # Inputs:
+    /// * `seed` (OPTIONAL), an octet string. If not supplied it defaults to [`BbsCiphersuite::SEED_MOCKED_SCALAR`].
+    /// * `dst` (OPTIONAL), an octet string. If not supplied it defaults to [`BbsCiphersuite::MOCKED_SCALAR_DST`].
+    /// * the other inputs are the same as [`Self::nym_proof_gen`].
+    ///
+    /// # Output:
+    /// a tuple ([`PoKSignature::BBSplus`], [`Pseudonym`]) or [`Error`].
+    ///
+    #[cfg(any(test, feature = "test-vectors"))]
+    pub fn nym_proof_gen_with_mocked_scalars(
+        pk: &BBSplusPublicKey,
+        signature: &[u8],
+        header: Option<&[u8]>,
+        ph: Option<&[u8]>,
+        messages: Option<&[Vec<u8>]>,
+        disclosed_indexes: Option<&[usize]>,
+        verifier_id: &[u8],
+        prover_nym: &ProverNym,
+        seed: Option<&[u8]>,
+        dst: Option<&[u8]>,
+    ) -> Result<(Self, Pseudonym), Error>
+    where
+        CS::Expander: for<'a> ExpandMsg<'a>,
+    {
+        Self::nym_proof_gen_internal::<rand::rngs::ThreadRng>(
+            pk,
+            signature,
+            header,
+            ph,
+            messages,
+            disclosed_indexes,
+            verifier_id,
+            prover_nym,
+            Randomness::Mocked {
+                seed: seed.unwrap_or(CS::SEED_MOCKED_SCALAR),
+                dst: dst.unwrap_or(CS::MOCKED_SCALAR_DST),
+            },
+        )
+    }
+
+    fn nym_proof_gen_internal<R: RngCore + CryptoRng>(
+        pk: &BBSplusPublicKey,
+        signature: &[u8],
+        header: Option<&[u8]>,
+        ph: Option<&[u8]>,
+        messages: Option<&[Vec<u8>]>,
+        disclosed_indexes: Option<&[usize]>,
+        verifier_id: &[u8],
+        prover_nym: &ProverNym,
+        randomness: Randomness<R>,
+    ) -> Result<(Self, Pseudonym), Error>
+    where
+        CS::Expander: for<'a> ExpandMsg<'a>,
+    {
+        let signature = BBSplusSignature::from_bytes(
+            signature.try_into().map_err(|_| Error::InvalidSignature)?,
+        )?;
+        let messages = messages.unwrap_or(&[]);
+        let disclosed_indexes = disclosed_indexes.unwrap_or(&[]);
+        let api_id = CS::API_ID_NYM;
+
+        if disclosed_indexes.iter().any(|&i| i >= messages.len()) {
+            return Err(Error::ProofGenError(
+                "disclosed index out of range".to_owned(),
+            ));
+        }
+
+        let mut message_scalars = BBSplusMessage::messages_to_scalar::<CS>(messages, api_id)?;
+        message_scalars.push(BBSplusMessage::new(prover_nym.0));
+
+        let generators = Generators::create::<CS>(message_scalars.len() + 1, Some(api_id));
+
+        let pseudonym = calculate_pseudonym::<CS>(verifier_id, prover_nym.0, api_id)?;
+        let nym = NymBinding {
+            OP: calculate_op::<CS>(verifier_id, api_id)?,
+            pseudonym: pseudonym.0,
+        };
+
+        let proof = core_proof_gen::<CS, _>(
+            pk,
+            &signature,
+            &generators,
+            &message_scalars,
+            disclosed_indexes,
+            header,
+            ph,
+            Some(api_id),
+            Some(&nym),
+            None,
+            randomness,
+        )?;
+
+        Ok((Self::BBSplus(proof), pseudonym))
+    }
+
+    /// # Description
+    /// The `nym_proof_verify` operation validates a BBS proof generated with [`Self::nym_proof_gen`], given the Signer's public key (PK), the pseudonym of the Prover, the verifier id, a header and presentation header values, the disclosed messages and their indexes.
+    ///
+    /// # Inputs:
+    /// * `self`, a proof.
+    /// * `pk` (REQUIRED), the Signer public key.
+    /// * `pseudonym` (REQUIRED), the [`Pseudonym`] of the Prover.
+    /// * `verifier_id` (REQUIRED), an octet string identifying the Verifier.
+    /// * `disclosed_messages` (OPTIONAL), a vector of octet string representing the messages disclosed to the Verifier.
+    /// * `disclosed_indexes` (OPTIONAL), vector of usize in ascending order. Indexes of disclosed messages.
+    /// * `header` (OPTIONAL), an octet string containing context and application.
+    /// * `ph` (OPTIONAL), an octet string containing the presentation header.
+    ///
+    /// # Output:
+    /// a result: [`Ok`] or [`Error`].
+    ///
+    pub fn nym_proof_verify(
+        &self,
+        pk: &BBSplusPublicKey,
+        pseudonym: &Pseudonym,
+        verifier_id: &[u8],
+        disclosed_messages: Option<&[Vec<u8>]>,
+        disclosed_indexes: Option<&[usize]>,
+        header: Option<&[u8]>,
+        ph: Option<&[u8]>,
+    ) -> Result<(), Error>
+    where
+        CS::Expander: for<'a> ExpandMsg<'a>,
+    {
+        let proof = self.to_bbsplus_proof();
+        let disclosed_messages = disclosed_messages.unwrap_or(&[]);
+        let mut disclosed_indexes = disclosed_indexes.unwrap_or(&[]).to_vec();
+        disclosed_indexes.sort();
+        disclosed_indexes.dedup();
+        let api_id = CS::API_ID_NYM;
+
+        let U = proof.m_cap.len();
+        let R = disclosed_indexes.len();
+
+        let disclosed_message_scalars =
+            BBSplusMessage::messages_to_scalar::<CS>(disclosed_messages, api_id)?;
+
+        let generators = Generators::create::<CS>(U + R + 1, Some(api_id));
+
+        let nym = NymBinding {
+            OP: calculate_op::<CS>(verifier_id, api_id)?,
+            pseudonym: pseudonym.0,
+        };
+
+        core_proof_verify::<CS>(
+            pk,
+            proof,
+            &generators,
+            header,
+            ph,
+            &disclosed_message_scalars,
+            &disclosed_indexes,
+            Some(api_id),
+            Some(&nym),
+            None,
+        )
+    }
+
+    /// # Description
+    /// This operation creates a BBS proof of a blind signature issued with a `signer_nym_entropy` (see [`BlindSignature::blind_sign`](crate::schemes::generics::BlindSignature::blind_sign)), together with the pseudonym of the Prover for the Verifier identified by `verifier_id`. As in [`Self::blind_proof_gen`], the messages known to the Signer and the messages committed by the Prover are supplied separately, while the `nym_secret` is never disclosed.
+    ///
+    /// # Inputs:
+    /// * `pk` (REQUIRED), the Signer public key.
+    /// * `signature` (REQUIRED), an octet string.
+    /// * `header` (OPTIONAL), an octet string containing context and application.
+    /// * `ph` (OPTIONAL), an octet string containing the presentation header.
+    /// * `messages` (OPTIONAL), a vector of octet strings messages supplied by the Signer.  If not supplied, it defaults to the empty array.
+    /// * `committed_messages` (OPTIONAL), a vector of octet strings messages committed by the Prover.
+    /// * `disclosed_indexes` (OPTIONAL), vector of unsigned integers in ascending order. Indexes of disclosed messages.
+    /// * `disclosed_commitment_indexes` (OPTIONAL), vector of unsigned integers in ascending order. Indexes of disclosed committed messages.
+    /// * `secret_prover_blind` (OPTIONAL), a scalar value ([`BlindFactor`]).
+    /// * `signer_blind` (OPTIONAL), a scalar value ([`BlindFactor`]).
+    /// * `verifier_id` (REQUIRED), an octet string identifying the Verifier.
+    /// * `nym_secret` (REQUIRED), the signed `nym_secret` ([`ProverNym::nym_secret`]).
+    ///
+    /// # Output:
+    /// a tuple ([`PoKSignature::BBSplus`], [`Pseudonym`]) or [`Error`].
+    ///
+    #[cfg(feature = "std")]
+    pub fn blind_nym_proof_gen(
+        pk: &BBSplusPublicKey,
+        signature: &[u8],
+        header: Option<&[u8]>,
+        ph: Option<&[u8]>,
+        messages: Option<&[Vec<u8>]>,
+        committed_messages: Option<&[Vec<u8>]>,
+        disclosed_indexes: Option<&[usize]>,
+        disclosed_commitment_indexes: Option<&[usize]>,
+        secret_prover_blind: Option<&BlindFactor>,
+        signer_blind: Option<&BlindFactor>,
+        verifier_id: &[u8],
+        nym_secret: &ProverNym,
+    ) -> Result<(Self, Pseudonym), Error>
+    where
+        CS::Expander: for<'a> ExpandMsg<'a>,
+    {
+        Self::blind_nym_proof_gen_with_rng(
+            pk,
+            signature,
+            header,
+            ph,
+            messages,
+            committed_messages,
+            disclosed_indexes,
+            disclosed_commitment_indexes,
+            secret_prover_blind,
+            signer_blind,
+            verifier_id,
+            nym_secret,
+            &mut thread_rng(),
+        )
+    }
+
+    /// # Description
+    /// Same as [`Self::blind_nym_proof_gen`], with the proof randomness taken from `rng`.
+    ///
+    /// # Inputs:
+    /// * `rng` (REQUIRED), a cryptographically secure random number generator.
+    /// * the other inputs are the same as [`Self::blind_nym_proof_gen`].
+    ///
+    /// # Output:
+    /// a tuple ([`PoKSignature::BBSplus`], [`Pseudonym`]) or [`Error`].
+    ///
+    pub fn blind_nym_proof_gen_with_rng<R: RngCore + CryptoRng>(
+        pk: &BBSplusPublicKey,
+        signature: &[u8],
+        header: Option<&[u8]>,
+        ph: Option<&[u8]>,
+        messages: Option<&[Vec<u8>]>,
+        committed_messages: Option<&[Vec<u8>]>,
+        disclosed_indexes: Option<&[usize]>,
+        disclosed_commitment_indexes: Option<&[usize]>,
+        secret_prover_blind: Option<&BlindFactor>,
+        signer_blind: Option<&BlindFactor>,
+        verifier_id: &[u8],
+        nym_secret: &ProverNym,
+        rng: &mut R,
+    ) -> Result<(Self, Pseudonym), Error>
+    where
+        CS::Expander: for<'a> ExpandMsg<'a>,
+    {
+        Self::blind_nym_proof_gen_internal(
+            pk,
+            signature,
+            header,
+            ph,
+            messages,
+            committed_messages,
+            disclosed_indexes,
+            disclosed_commitment_indexes,
+            secret_prover_blind,
+            signer_blind,
+            verifier_id,
+            nym_secret,
+            Randomness::Rng(rng),
+        )
+    }
+
+    /// # Description
+    /// Same as [`Self::blind_nym_proof_gen`], but the random scalars are replaced by the mocked random scalars computed from `seed` and `dst`. The resulting proofs are deterministic, so this MUST only be used for testing.
+    ///
+    /// # Inputs:
+    /// * `seed` (OPTIONAL), an octet string. If not supplied it defaults to [`BbsCiphersuite::SEED_MOCKED_SCALAR`].
+    /// * `dst` (OPTIONAL), an octet string. If not supplied it defaults to [`BbsCiphersuite::BLIND_PROOF_DST`].
+    /// * the other inputs are the same as [`Self::blind_nym_proof_gen`].
+    ///
+    /// # Output:
+    /// a tuple ([`PoKSignature::BBSplus`], [`Pseudonym`]) or [`Error`].
+    ///
+    #[cfg(any(test, feature = "test-vectors"))]
+    pub fn blind_nym_proof_gen_with_mocked_scalars(
+        pk: &BBSplusPublicKey,
+        signature: &[u8],
+        header: Option<&[u8]>,
+        ph: Option<&[u8]>,
+        messages: Option<&[Vec<u8>]>,
+        committed_messages: Option<&[Vec<u8>]>,
+        disclosed_indexes: Option<&[usize]>,
+        disclosed_commitment_indexes: Option<&[usize]>,
+        secret_prover_blind: Option<&BlindFactor>,
+        signer_blind: Option<&BlindFactor>,
+        verifier_id: &[u8],
+        nym_secret: &ProverNym,
+        seed: Option<&[u8]>,
+        dst: Option<&[u8]>,
+    ) -> Result<(Self, Pseudonym), Error>
+    where
+        CS::Expander: for<'a> ExpandMsg<'a>,
+    {
+        Self::blind_nym_proof_gen_internal::<rand::rngs::ThreadRng>(
+            pk,
+            signature,
+            header,
+            ph,
+            messages,
+            committed_messages,
+            disclosed_indexes,
+            disclosed_commitment_indexes,
+            secret_prover_blind,
+            signer_blind,
+            verifier_id,
+            nym_secret,
+            Randomness::Mocked {
+                seed: seed.unwrap_or(CS::SEED_MOCKED_SCALAR),
+                dst: dst.unwrap_or(CS::BLIND_PROOF_DST),
+            },
+        )
+    }
+
+    fn blind_nym_proof_gen_internal<R: RngCore + CryptoRng>(
+        pk: &BBSplusPublicKey,
+        signature: &[u8],
+        header: Option<&[u8]>,
+        ph: Option<&[u8]>,
+        messages: Option<&[Vec<u8>]>,
+        committed_messages: Option<&[Vec<u8>]>,
+        disclosed_indexes: Option<&[usize]>,
+        disclosed_commitment_indexes: Option<&[usize]>,
+        secret_prover_blind: Option<&BlindFactor>,
+        signer_blind: Option<&BlindFactor>,
+        verifier_id: &[u8],
+        nym_secret: &ProverNym,
+        randomness: Randomness<R>,
+    ) -> Result<(Self, Pseudonym), Error>
+    where
+        CS::Expander: for<'a> ExpandMsg<'a>,
+    {
+        let signature = BBSplusSignature::from_bytes(
+            signature.try_into().map_err(|_| Error::InvalidSignature)?,
+        )?;
+        let api_id = CS::API_ID_BLIND_NYM;
+        let messages = messages.unwrap_or(&[]);
+        let committed_messages = committed_messages.unwrap_or(&[]);
+        let disclosed_indexes = disclosed_indexes.unwrap_or(&[]);
+        let disclosed_commitment_indexes = disclosed_commitment_indexes.unwrap_or(&[]);
+        let L = messages.len();
+        let M = committed_messages.len();
+
+        if disclosed_indexes.iter().any(|&i| i >= L) {
+            return Err(Error::BlindProofGenError(
+                "disclosed index out of range".to_owned(),
+            ));
+        } else if disclosed_commitment_indexes.iter().any(|&i| i >= M) {
+            return Err(Error::BlindProofGenError(
+                "commitment disclosed index out of range".to_owned(),
+            ));
+        }
+
+        let generators = Generators::create::<CS>(L + 1, Some(api_id));
+        let blind_generators = Generators::create::<CS>(M + 2, Some(&[b"BLIND_", api_id].concat()));
+
+        let message_scalars = BBSplusMessage::messages_to_scalar::<CS>(messages, api_id)?;
+        let blind_factor = BBSplusMessage::new(
+            secret_prover_blind.map_or(Scalar::ZERO, |b| b.0)
+                + signer_blind.map_or(Scalar::ZERO, |b| b.0),
+        );
+        let committed_message_scalars =
+            BBSplusMessage::messages_to_scalar::<CS>(committed_messages, api_id)?;
+
+        let indexes = disclosed_indexes
+            .iter()
+            .copied()
+            .chain(disclosed_commitment_indexes.iter().map(|&j| j + L + 1))
+            .collect::<Vec<_>>();
+
+        let tmp_messages = [
+            &*message_scalars,
+            core::slice::from_ref(&blind_factor),
+            &*committed_message_scalars,
+            &[BBSplusMessage::new(nym_secret.0)],
+        ]
+        .concat();
+
+        let pseudonym = calculate_pseudonym::<CS>(verifier_id, nym_secret.0, api_id)?;
+        let nym = NymBinding {
+            OP: calculate_op::<CS>(verifier_id, api_id)?,
+            pseudonym: pseudonym.0,
+        };
+
+        let proof = core_proof_gen::<CS, _>(
+            pk,
+            &signature,
+            &generators.append(blind_generators),
+            &tmp_messages,
+            &indexes,
+            header,
+            ph,
+            Some(api_id),
+            Some(&nym),
+            None,
+            randomness,
+        )?;
+
+        Ok((Self::BBSplus(proof), pseudonym))
+    }
+
+    /// # Description
+    /// The `blind_nym_proof_verify` operation validates a BBS proof generated with [`Self::blind_nym_proof_gen`], given the Signer's public key (PK), the pseudonym of the Prover, the verifier id, a header and presentation header values, the disclosed messages known to the Signer and the disclosed committed messages with their indexes, and the total number of Signer known messages `L`.
+    ///
+    /// # Inputs:
+    /// * `self`, a proof.
+    /// * `pk` (REQUIRED), the Signer public key.
+    /// * `pseudonym` (REQUIRED), the [`Pseudonym`] of the Prover.
+    /// * `verifier_id` (REQUIRED), an octet string identifying the Verifier.
+    /// * `header` (OPTIONAL), an octet string containing context and application.
+    /// * `ph` (OPTIONAL), an octet string containing the presentation header.
+    /// * `L` (OPTIONAL), an integer, representing the total number of Signer known messages if not supplied it defaults to 0.
+    /// * `disclosed_messages` (OPTIONAL), a vector of octet string representing the messages disclosed to the Verifier.
+    /// * `disclosed_committed_messages` (OPTIONAL), a vector of octet string representing the committed messages disclosed to the Verifier.
+    /// * `disclosed_indexes` (OPTIONAL), vector of usize in ascending order. Indexes of disclosed messages.
+    /// * `disclosed_commitment_indexes` (OPTIONAL), vector of usize in ascending order. Indexes of disclosed committed messages.
+    ///
+    /// # Output:
+    /// a result: [`Ok`] or [`Error`].
+    ///
+    pub fn blind_nym_proof_verify(
+        &self,
+        pk: &BBSplusPublicKey,
+        pseudonym: &Pseudonym,
+        verifier_id: &[u8],
+        header: Option<&[u8]>,
+        ph: Option<&[u8]>,
+        L: Option<usize>,
+        disclosed_messages: Option<&[Vec<u8>]>,
+        disclosed_committed_messages: Option<&[Vec<u8>]>,
+        disclosed_indexes: Option<&[usize]>,
+        disclosed_commitment_indexes: Option<&[usize]>,
+    ) -> Result<(), Error>
+    where
+        CS::Expander: for<'a> ExpandMsg<'a>,
+    {
+        let proof = self.to_bbsplus_proof();
+        let L = L.unwrap_or(0);
+        let disclosed_messages = disclosed_messages.unwrap_or(&[]);
+        let disclosed_committed_messages = disclosed_committed_messages.unwrap_or(&[]);
+        let mut disclosed_indexes = disclosed_indexes.unwrap_or(&[]).to_vec();
+        disclosed_indexes.sort();
+        disclosed_indexes.dedup();
+        let mut disclosed_commitment_indexes = disclosed_commitment_indexes.unwrap_or(&[]).to_vec();
+        disclosed_commitment_indexes.sort();
+        disclosed_commitment_indexes.dedup();
+
+        let api_id = CS::API_ID_BLIND_NYM;
+
+        // M counts the committed messages and the nym_secret
+        let U = proof.m_cap.len();
+        let M = (disclosed_indexes.len() + disclosed_commitment_indexes.len() + U)
+            .checked_sub(L + 1)
+            .ok_or_else(|| Error::PoKSVerificationError("Invalid L".to_owned()))?;
+
+        let generators = Generators::create::<CS>(L + 1, Some(api_id));
+        let blind_generators = Generators::create::<CS>(M + 1, Some(&[b"BLIND_", api_id].concat()));
+
+        let message_scalars = [
+            BBSplusMessage::messages_to_scalar::<CS>(disclosed_messages, api_id)?,
+            BBSplusMessage::messages_to_scalar::<CS>(disclosed_committed_messages, api_id)?,
+        ]
+        .concat();
+
+        let indexes = disclosed_indexes
+            .iter()
+            .copied()
+            .chain(disclosed_commitment_indexes.iter().map(|j| j + L + 1))
+            .collect::<Vec<_>>();
+
+        let nym = NymBinding {
+            OP: calculate_op::<CS>(verifier_id, api_id)?,
+            pseudonym: pseudonym.0,
+        };
+
+        core_proof_verify::<CS>(
+            pk,
+            proof,
+            &generators.append(blind_generators),
+            header,
+            ph,
+            &message_scalars,
+            &indexes,
+            Some(api_id),
+            Some(&nym),
+            None,
+        )
+    }
+}
+
+/// # Description
+/// Maps the `verifier_id` to a point of G1 (OP), used as base for the pseudonym
+///
+/// # Inputs:
+/// * `verifier_id` (REQUIRED), an octet string identifying the Verifier.
+/// * `api_id` (REQUIRED), an octet string.
+///
+/// # Output:
+/// * a [`G1Projective`] point or [`Error`].
+///
+fn calculate_op<CS>(verifier_id: &[u8], api_id: &[u8]) -> Result<G1Projective, Error>
+where
+    CS: BbsCiphersuite,
+    CS::Expander: for<'a> ExpandMsg<'a>,
+{
+    let op_dst = [api_id, CS::NYM_OP_DST].concat();
+    let OP = G1Projective::hash::<CS::Expander>(verifier_id, &op_dst);
+
+    if OP == G1Projective::IDENTITY {
+        return Err(Error::InvalidPseudonym);
+    }
+
+    Ok(OP)
+}
+
+/// # Description
+/// Computes the pseudonym as `OP * nym_secret`
+///
+/// # Inputs:
+/// * `verifier_id` (REQUIRED), an octet string identifying the Verifier.
+/// * `nym_secret` (REQUIRED), a scalar value.
+/// * `api_id` (REQUIRED), an octet string.
+///
+/// # Output:
+/// * a [`Pseudonym`] or [`Error`].
+///
+pub(super) fn calculate_pseudonym<CS>(
+    verifier_id: &[u8],
+    nym_secret: Scalar,
+    api_id: &[u8],
+) -> Result<Pseudonym, Error>
+where
+    CS: BbsCiphersuite,
+    CS::Expander: for<'a> ExpandMsg<'a>,
+{
+    let OP = calculate_op::<CS>(verifier_id, api_id)?;
+    let pseudonym = OP * nym_secret;
+
+    if pseudonym == G1Projective::IDENTITY {
+        return Err(Error::InvalidPseudonym);
+    }
+
+    Ok(Pseudonym(pseudonym))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ProverNym;
+    use crate::{
+        bbsplus::{
+            ciphersuites::BbsCiphersuite,
+            commitment::BlindFactor,
+            keys::{BBSplusPublicKey, BBSplusSecretKey},
+            pseudonym::Pseudonym,
+        },
+        schemes::{
+            algorithms::{BBSplus, BbsBls12381Sha256, BbsBls12381Shake256, Scheme},
+            generics::{BlindSignature, Commitment, PoKSignature, Signature},
+        },
+        utils::util::bbsplus_utils::get_messages_vec,
+    };
+    use elliptic_curve::hash2curve::ExpandMsg;
+
+    //NYM SIGNATURE
+
+    macro_rules! nym_sign_tests {
+        ( $( ($t:ident, $p:literal): { $( ($n:ident, $f:literal), )+ },)+ ) => { $($(
+            #[test] fn $n() { nym_sign::<$t>($p, $f); }
+        )+)+ }
+    }
+
+    nym_sign_tests! {
+        (BbsBls12381Sha256, "./regression_data_nym/bls12-381-sha-256/"): {
+            (nym_sign_sha256_1, "signature/signature001.json"),
+            (nym_sign_sha256_2, "signature/signature002.json"),
+        },
+        (BbsBls12381Shake256, "./regression_data_nym/bls12-381-shake-256/"): {
+            (nym_sign_shake256_1, "signature/signature001.json"),
+            (nym_sign_shake256_2, "signature/signature002.json"),
+        },
+    }
+
+    //NYM PROOF
+
+    macro_rules! nym_proof_tests {
+        ( $( ($t:ident, $p:literal): { $( ($n:ident, $f:literal), )+ },)+ ) => { $($(
+            #[test] fn $n() { nym_proof_check::<$t>($p, $f); }
+        )+)+ }
+    }
+
+    nym_proof_tests! {
+        (BbsBls12381Sha256, "./regression_data_nym/bls12-381-sha-256/"): {
+            (nym_proof_check_sha256_1, "proof/proof001.json"),
+            (nym_proof_check_sha256_2, "proof/proof002.json"),
+            (nym_proof_check_sha256_3, "proof/proof003.json"),
+            (nym_proof_check_sha256_4, "proof/proof004.json"),
+        },
+        (BbsBls12381Shake256, "./regression_data_nym/bls12-381-shake-256/"): {
+            (nym_proof_check_shake256_1, "proof/proof001.json"),
+            (nym_proof_check_shake256_2, "proof/proof002.json"),
+            (nym_proof_check_shake256_3, "proof/proof003.json"),
+            (nym_proof_check_shake256_4, "proof/proof004.json"),
+        },
+    }
+
+    //BLIND NYM PROOF
+
+    macro_rules! blind_nym_proof_tests {
+        ( $( ($t:ident, $p:literal): { $( ($n:ident, $f:literal), )+ },)+ ) => { $($(
+            #[test] fn $n() { blind_nym_proof_check::<$t>($p, $f); }
+        )+)+ }
+    }
+
+    blind_nym_proof_tests! {
+        (BbsBls12381Sha256, "./regression_data_nym/bls12-381-sha-256/"): {
+            (blind_nym_proof_check_sha256_1, "blind_proof/proof001.json"),
+            (blind_nym_proof_check_sha256_2, "blind_proof/proof002.json"),
+            (blind_nym_proof_check_sha256_3, "blind_proof/proof003.json"),
+        },
+        (BbsBls12381Shake256, "./regression_data_nym/bls12-381-shake-256/"): {
+            (blind_nym_proof_check_shake256_1, "blind_proof/proof001.json"),
+            (blind_nym_proof_check_shake256_2, "blind_proof/proof002.json"),
+            (blind_nym_proof_check_shake256_3, "blind_proof/proof003.json"),
+        },
+    }
+
+    fn get_hex_messages(data: &serde_json::Value) -> Vec<Vec<u8>> {
+        get_hex_array(data, "messages")
+    }
+
+    fn get_hex_array(data: &serde_json::Value, key: &str) -> Vec<Vec<u8>> {
+        data[key]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|m| hex::decode(m.as_str().unwrap()).unwrap())
+            .collect()
+    }
+
+    fn get_indexes(data: &serde_json::Value, key: &str) -> Vec<usize> {
+        data[key]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|i| i.as_u64().unwrap() as usize)
+            .collect()
+    }
+
+    fn get_blind_factor(data: &serde_json::Value, key: &str) -> BlindFactor {
+        BlindFactor::from_bytes(
+            &hex::decode(data[key].as_str().unwrap())
+                .unwrap()
+                .try_into()
+                .unwrap(),
+        )
+        .unwrap()
+    }
+
+    fn get_prover_nym(data: &serde_json::Value) -> ProverNym {
+        ProverNym::from_bytes(
+            &hex::decode(data["proverNym"].as_str().unwrap())
+                .unwrap()
+                .try_into()
+                .unwrap(),
+        )
+        .unwrap()
+    }
+
+    fn nym_sign<S: Scheme>(pathname: &str, filename: &str)
+    where
+        S::Ciphersuite: BbsCiphersuite,
+        <S::Ciphersuite as BbsCiphersuite>::Expander: for<'a> ExpandMsg<'a>,
+    {
+        let data =
+            std::fs::read_to_string([pathname, filename].concat()).expect("Unable to read file");
+        let data_json: serde_json::Value = serde_json::from_str(&data).expect("Unable to parse");
+        eprintln!("{}", data_json["caseName"]);
+
+        let sk = BBSplusSecretKey::from_bytes(
+            &hex::decode(data_json["signerKeyPair"]["secretKey"].as_str().unwrap()).unwrap(),
+        )
+        .unwrap();
+        let pk = BBSplusPublicKey::from_bytes(
+            &hex::decode(data_json["signerKeyPair"]["publicKey"].as_str().unwrap()).unwrap(),
+        )
+        .unwrap();
+        let header = hex::decode(data_json["header"].as_str().unwrap()).unwrap();
+        let messages = get_hex_messages(&data_json);
+        let prover_nym = get_prover_nym(&data_json);
+        let signature_expected = data_json["signature"].as_str().unwrap();
+        let result_expected = data_json["result"]["valid"].as_bool().unwrap();
+
+        let signature = Signature::<BBSplus<S::Ciphersuite>>::nym_sign(
+            Some(&messages),
+            &sk,
+            &pk,
+            Some(&header),
+            &prover_nym,
+        )
+        .unwrap();
+
+        let result0 = hex::encode(signature.to_bytes()) == signature_expected;
+        assert_eq!(result0, result_expected, "nym_sign");
+
+        let signature = Signature::<BBSplus<S::Ciphersuite>>::from_bytes(
+            &hex::decode(signature_expected).unwrap().try_into().unwrap(),
+        )
+        .unwrap();
+
+        let result1 = signature
+            .nym_verify(&pk, Some(&messages), Some(&header), &prover_nym)
+            .is_ok();
+        assert_eq!(result1, result_expected, "nym_verify");
+    }
+
+    fn nym_proof_check<S: Scheme>(pathname: &str, filename: &str)
+    where
+        S::Ciphersuite: BbsCiphersuite,
+        <S::Ciphersuite as BbsCiphersuite>::Expander: for<'a> ExpandMsg<'a>,
+    {
+        let data =
+            std::fs::read_to_string([pathname, filename].concat()).expect("Unable to read file");
+        let proof_json: serde_json::Value = serde_json::from_str(&data).expect("Unable to parse");
+        eprintln!("{}", proof_json["caseName"]);
+
+        let pk = BBSplusPublicKey::from_bytes(
+            &hex::decode(proof_json["signerPublicKey"].as_str().unwrap()).unwrap(),
+        )
+        .unwrap();
+        let signature = hex::decode(proof_json["signature"].as_str().unwrap()).unwrap();
+        let header = hex::decode(proof_json["header"].as_str().unwrap()).unwrap();
+        let ph = hex::decode(proof_json["presentationHeader"].as_str().unwrap()).unwrap();
+        let messages = get_hex_messages(&proof_json);
+        let disclosed_indexes = get_indexes(&proof_json, "disclosedIndexes");
+        let prover_nym = get_prover_nym(&proof_json);
+        let verifier_id = hex::decode(proof_json["verifierId"].as_str().unwrap()).unwrap();
+        let pseudonym_expected = proof_json["pseudonym"].as_str().unwrap();
+        let proof_expected = proof_json["proof"].as_str().unwrap();
+        let result_expected = proof_json["result"]["valid"].as_bool().unwrap();
+
+        let (proof, pseudonym) =
+            PoKSignature::<BBSplus<S::Ciphersuite>>::nym_proof_gen_with_mocked_scalars(
+                &pk,
+                &signature,
+                Some(&header),
+                Some(&ph),
+                Some(&messages),
+                Some(&disclosed_indexes),
+                &verifier_id,
+                &prover_nym,
+                None,
+                None,
+            )
+            .unwrap();
+
+        assert_eq!(
+            pseudonym,
+            Pseudonym::new::<S::Ciphersuite>(&verifier_id, &prover_nym).unwrap()
+        );
+
+        let result0 = hex::encode(pseudonym.to_bytes()) == pseudonym_expected
+            && hex::encode(proof.to_bytes()) == proof_expected;
+        assert_eq!(result0, result_expected, "nym_proof_gen");
+
+        let disclosed_messages = get_messages_vec(&messages, &disclosed_indexes);
+        let proof = PoKSignature::<BBSplus<S::Ciphersuite>>::from_bytes(
+            &hex::decode(proof_expected).unwrap(),
+        )
+        .unwrap();
+        let pseudonym = Pseudonym::from_bytes(&hex::decode(pseudonym_expected).unwrap()).unwrap();
+
+        let result1 = proof
+            .nym_proof_verify(
+                &pk,
+                &pseudonym,
+                &verifier_id,
+                Some(&disclosed_messages),
+                Some(&disclosed_indexes),
+                Some(&header),
+                Some(&ph),
+            )
+            .is_ok();
+        assert_eq!(result1, result_expected, "nym_proof_verify");
+    }
+
+    fn blind_nym_proof_check<S: Scheme>(pathname: &str, filename: &str)
+    where
+        S::Ciphersuite: BbsCiphersuite,
+        <S::Ciphersuite as BbsCiphersuite>::Expander: for<'a> ExpandMsg<'a>,
+    {
+        let data =
+            std::fs::read_to_string([pathname, filename].concat()).expect("Unable to read file");
+        let proof_json: serde_json::Value = serde_json::from_str(&data).expect("Unable to parse");
+        eprintln!("{}", proof_json["caseName"]);
+
+        let sk = BBSplusSecretKey::from_bytes(
+            &hex::decode(proof_json["signerKeyPair"]["secretKey"].as_str().unwrap()).unwrap(),
+        )
+        .unwrap();
+        let pk = BBSplusPublicKey::from_bytes(
+            &hex::decode(proof_json["signerKeyPair"]["publicKey"].as_str().unwrap()).unwrap(),
+        )
+        .unwrap();
+        let header = hex::decode(proof_json["header"].as_str().unwrap()).unwrap();
+        let ph = hex::decode(proof_json["presentationHeader"].as_str().unwrap()).unwrap();
+        let messages = get_hex_messages(&proof_json);
+        let committed_messages = get_hex_array(&proof_json, "committedMessages");
+        let prover_nym = get_prover_nym(&proof_json);
+        let signer_blind = get_blind_factor(&proof_json, "signerBlind");
+        let signer_nym_entropy = get_blind_factor(&proof_json, "signerNymEntropy");
+        let disclosed_indexes = get_indexes(&proof_json, "disclosedIndexes");
+        let disclosed_commitment_indexes = get_indexes(&proof_json, "disclosedCommitmentIndexes");
+        let verifier_id = hex::decode(proof_json["verifierId"].as_str().unwrap()).unwrap();
+        let result_expected = proof_json["result"]["valid"].as_bool().unwrap();
+
+        // Prover commits to the committed messages and to the prover_nym
+        let (commitment_with_proof, prover_blind) =
+            Commitment::<BBSplus<S::Ciphersuite>>::commit_with_mocked_scalars(
+                Some(&committed_messages),
+                Some(&prover_nym),
+                None,
+                None,
+            )
+            .unwrap();
+        assert_eq!(
+            hex::encode(commitment_with_proof.to_bytes()),
+            proof_json["commitmentWithProof"].as_str().unwrap()
+        );
+        assert_eq!(
+            hex::encode(prover_blind.to_bytes()),
+            proof_json["proverBlind"].as_str().unwrap()
+        );
+
+        // Signer adds its entropy to the prover_nym
+        let signature = BlindSignature::<BBSplus<S::Ciphersuite>>::blind_sign(
+            &sk,
+            &pk,
+            Some(&commitment_with_proof.to_bytes()),
+            Some(&header),
+            Some(&messages),
+            Some(&signer_blind),
+            Some(&signer_nym_entropy),
+        )
+        .unwrap();
+        assert_eq!(
+            hex::encode(signature.to_bytes()),
+            proof_json["signature"].as_str().unwrap()
+        );
+
+        let nym_secret = prover_nym.nym_secret(&signer_nym_entropy);
+        signature
+            .verify(
+                &pk,
+                Some(&header),
+                Some(&messages),
+                Some(&committed_messages),
+                Some(&prover_blind),
+                Some(&signer_blind),
+                Some(&nym_secret),
+            )
+            .unwrap();
+
+        let pseudonym_expected = proof_json["pseudonym"].as_str().unwrap();
+        let proof_expected = proof_json["proof"].as_str().unwrap();
+
+        let (proof, pseudonym) =
+            PoKSignature::<BBSplus<S::Ciphersuite>>::blind_nym_proof_gen_with_mocked_scalars(
+                &pk,
+                &signature.to_bytes(),
+                Some(&header),
+                Some(&ph),
+                Some(&messages),
+                Some(&committed_messages),
+                Some(&disclosed_indexes),
+                Some(&disclosed_commitment_indexes),
+                Some(&prover_blind),
+                Some(&signer_blind),
+                &verifier_id,
+                &nym_secret,
+                None,
+                None,
+            )
+            .unwrap();
+
+        assert_eq!(
+            pseudonym,
+            Pseudonym::new_blind::<S::Ciphersuite>(&verifier_id, &nym_secret).unwrap()
+        );
+        assert_ne!(
+            pseudonym,
+            Pseudonym::new::<S::Ciphersuite>(&verifier_id, &nym_secret).unwrap()
+        );
+
+        let result0 = hex::encode(pseudonym.to_bytes()) == pseudonym_expected
+            && hex::encode(proof.to_bytes()) == proof_expected;
+        assert_eq!(result0, result_expected, "blind_nym_proof_gen");
+
+        let disclosed_messages = get_messages_vec(&messages, &disclosed_indexes);
+        let disclosed_committed_messages =
+            get_messages_vec(&committed_messages, &disclosed_commitment_indexes);
+        let proof = PoKSignature::<BBSplus<S::Ciphersuite>>::from_bytes(
+            &hex::decode(proof_expected).unwrap(),
+        )
+        .unwrap();
+        let pseudonym = Pseudonym::from_bytes(&hex::decode(pseudonym_expected).unwrap()).unwrap();
+
+        let result1 = proof
+            .blind_nym_proof_verify(
+                &pk,
+                &pseudonym,
+                &verifier_id,
+                Some(&header),
+                Some(&ph),
+                Some(messages.len()),
+                Some(&disclosed_messages),
+                Some(&disclosed_committed_messages),
+                Some(&disclosed_indexes),
+                Some(&disclosed_commitment_indexes),
+            )
+            .is_ok();
+        assert_eq!(result1, result_expected, "blind_nym_proof_verify");
+    }
+}
@@ -0,0 +1,805 @@
+// Copyright 2023 Fondazione LINKS
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::{
+    ciphersuites::BbsCiphersuite,
+    generators::Generators,
+    keys::{BBSplusPublicKey, BBSplusSecretKey},
+    proof::{core_proof_gen, core_proof_verify, CommitmentBinding},
+    signature::{core_sign, core_verify, BBSplusSignature},
+};
+use crate::{
+    errors::Error,
+    schemes::{
+        algorithms::BBSplus,
+        generics::{PoKSignature, Signature},
+    },
+    utils::{
+        message::bbsplus_message::BBSplusMessage,
+        util::bbsplus_utils::{
+            get_random_with_rng, i2osp, parse_g1_projective, Randomness, ScalarExt,
+        },
+    },
+};
+use alloc::{borrow::ToOwned, string::ToString, vec::Vec};
+use bls12_381_plus::{G1Projective, Scalar};
+use elliptic_curve::{group::Curve, hash2curve::ExpandMsg};
+#[cfg(feature = "std")]
+use rand::thread_rng;
+use rand::{CryptoRng, RngCore};
+use serde::{Deserialize, Serialize};
+
+/// Predicate `lower <= m <= upper` over a signed integer message `m` (see [`BBSplusMessage::from_integer`])
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct RangePredicate {
+    pub index: usize,
+    pub lower: u64,
+    pub upper: u64,
+}
+
+impl RangePredicate {
+    pub fn new(index: usize, lower: u64, upper: u64) -> Self {
+        Self {
+            index,
+            lower,
+            upper,
+        }
+    }
+
+    /// Number of bits `n` such that `upper - lower < 2^n`
+    fn bits(&self) -> Result<usize, Error> {
+        let width = self
+            .upper
+            .checked_sub(self.lower)
+            .ok_or_else(|| Error::RangeProofGenError("lower > upper".to_owned()))?;
+        Ok((64 - width.leading_zeros() as usize).max(1))
+    }
+}
+
+/// Proof that a commitment `D = G * b + H * t` commits to a bit `b`, as an OR proof of `D = H * t` or `D - G = H * t`
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+struct BitProof {
+    D: G1Projective,
+    c0: Scalar,
+    z0: Scalar,
+    z1: Scalar,
+}
+
+/// Proof that the message committed in `C = G * m + H * s` satisfies a [`RangePredicate`]. The value `m - lower` and `upper - m` are decomposed in bits, whose commitments sum up to `C - G * lower` and `G * upper - C` respectively.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+struct PredicateProof {
+    C: G1Projective,
+    s_cap: Scalar,
+    lower_bits: Vec<BitProof>,
+    upper_bits: Vec<BitProof>,
+}
+
+/// Range proofs over some undisclosed integer messages, linked to a proof of knowledge of a signature ([`PoKSignature`]). The two proofs share the same challenge and can only be verified together.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct RangeProof {
+    proofs: Vec<PredicateProof>,
+}
+
+impl RangeProof {
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes: Vec<u8> = Vec::new();
+        bytes.extend_from_slice(&i2osp::<8>(self.proofs.len()));
+        for p in &self.proofs {
+            bytes.extend_from_slice(&p.C.to_affine().to_compressed());
+            bytes.extend_from_slice(&p.s_cap.to_bytes_be());
+            bytes.extend_from_slice(&i2osp::<8>(p.lower_bits.len()));
+            for b in p.lower_bits.iter().chain(&p.upper_bits) {
+                bytes.extend_from_slice(&b.D.to_affine().to_compressed());
+                bytes.extend_from_slice(&b.c0.to_bytes_be());
+                bytes.extend_from_slice(&b.z0.to_bytes_be());
+                bytes.extend_from_slice(&b.z1.to_bytes_be());
+            }
+        }
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        let mut offset = 0;
+        let mut next = |len: usize| -> Result<&[u8], Error> {
+            let chunk = bytes
+                .get(offset..offset + len)
+                .ok_or(Error::InvalidRangeProof)?;
+            offset += len;
+            Ok(chunk)
+        };
+        let read_len = |chunk: &[u8]| -> Result<usize, Error> {
+            let len = u64::from_be_bytes(chunk.try_into().map_err(|_| Error::InvalidRangeProof)?);
+            usize::try_from(len).map_err(|_| Error::InvalidRangeProof)
+        };
+        let read_g1 =
+            |chunk: &[u8]| parse_g1_projective(chunk).map_err(|_| Error::InvalidRangeProof);
+        let read_scalar =
+            |chunk: &[u8]| Scalar::from_bytes_be(chunk).map_err(|_| Error::InvalidRangeProof);
+
+        let count = read_len(next(8)?)?;
+        let mut proofs = Vec::new();
+        for _ in 0..count {
+            let C = read_g1(next(48)?)?;
+            let s_cap = read_scalar(next(32)?)?;
+            let n = read_len(next(8)?)?;
+            if n == 0 || n > 64 {
+                return Err(Error::InvalidRangeProof);
+            }
+            let mut bits = Vec::new();
+            for _ in 0..2 * n {
+                bits.push(BitProof {
+                    D: read_g1(next(48)?)?,
+                    c0: read_scalar(next(32)?)?,
+                    z0: read_scalar(next(32)?)?,
+                    z1: read_scalar(next(32)?)?,
+                });
+            }
+            let upper_bits = bits.split_off(n);
+            proofs.push(PredicateProof {
+                C,
+                s_cap,
+                lower_bits: bits,
+                upper_bits,
+            });
+        }
+
+        if offset != bytes.len() {
+            return Err(Error::InvalidRangeProof);
+        }
+
+        Ok(Self { proofs })
+    }
+}
+
+impl<CS: BbsCiphersuite> Signature<BBSplus<CS>> {
+    /// # Description
+    /// The `sign_with_integers` API returns a BBS signature over a header and a set of messages, where the messages at `integer_indexes` are integers mapped directly to scalars (see [`BBSplusMessage::messages_to_scalar_with_integers`]). Range proofs can then be computed over those messages ([`PoKSignature::range_proof_gen`]).
+    ///
+    /// # Inputs:
+    /// * `messages` (OPTIONAL), a vector of octet strings representing the messages, it could be an empty vector. Integer messages are 8 octets long (`u64` in big-endian order).
+    /// * `integer_indexes` (OPTIONAL), vector of unsigned integers. Indexes of the integer messages.
+    /// * `sk` (REQUIRED), a secret key
+    /// * `pk` (REQUIRED), a public key
+    /// * `header` (OPTIONAL), an octet string containing context and application specific information.
+    ///
+    /// # Output:
+    /// * new [`Signature::BBSplus`] or [`Error`]
+    pub fn sign_with_integers(
+        messages: Option<&[Vec<u8>]>,
+        integer_indexes: Option<&[usize]>,
+        sk: &BBSplusSecretKey,
+        pk: &BBSplusPublicKey,
+        header: Option<&[u8]>,
+    ) -> Result<Self, Error>
+    where
+        CS::Expander: for<'a> ExpandMsg<'a>,
+    {
+        let messages = messages.unwrap_or(&[]);
+        let integer_indexes = integer_indexes.unwrap_or(&[]);
+        let message_scalars = BBSplusMessage::messages_to_scalar_with_integers::<CS>(
+            messages,
+            integer_indexes,
+            CS::API_ID,
+        )?;
+        let generators = Generators::create::<CS>(messages.len() + 1, Some(CS::API_ID));
+        let signature = core_sign::<CS>(
+            sk,
+            pk,
+            generators,
+            header,
+            &message_scalars,
+            Some(CS::API_ID),
+        )?;
+
+        Ok(Self::BBSplus(signature))
+    }
+
+    /// # Description
+    /// The `verify_with_integers` API validates a BBS signature generated with [`Self::sign_with_integers`].
+    ///
+    /// # Inputs:
+    /// * `self`, the signature
+    /// * `pk` (REQUIRED), a public key
+    /// * `messages` (OPTIONAL), a vector of octet strings representing the messages, it could be an empty vector.
+    /// * `integer_indexes` (OPTIONAL), vector of unsigned integers. Indexes of the integer messages.
+    /// * `header` (OPTIONAL), an octet string containing context and application specific information.
+    ///
+    /// # Output:
+    /// * a result either [`Ok()`] or [`Error`]
+    pub fn verify_with_integers(
+        &self,
+        pk: &BBSplusPublicKey,
+        messages: Option<&[Vec<u8>]>,
+        integer_indexes: Option<&[usize]>,
+        header: Option<&[u8]>,
+    ) -> Result<(), Error>
+    where
+        CS::Expander: for<'a> ExpandMsg<'a>,
+    {
+        let messages = messages.unwrap_or(&[]);
+        let integer_indexes = integer_indexes.unwrap_or(&[]);
+        let message_scalars = BBSplusMessage::messages_to_scalar_with_integers::<CS>(
+            messages,
+            integer_indexes,
+            CS::API_ID,
+        )?;
+        let generators = Generators::create::<CS>(messages.len() + 1, Some(CS::API_ID));
+
+        core_verify::<CS>(
+            pk,
+            self.bbsPlusSignature(),
+            &message_scalars,
+            generators,
+            header,
+            Some(CS::API_ID),
+        )
+    }
+}
+
+impl<CS: BbsCiphersuite> PoKSignature<BBSplus<CS>> {
+    /// # Description
+    /// This operation creates a BBS proof (see [`Self::proof_gen`]) together with a [`RangeProof`], showing that some undisclosed integer messages satisfy the supplied [`RangePredicate`]s (e.g., `age >= 18`), without revealing them.
+    /// Each message is committed in a Pedersen commitment whose opening is proven with the same `m~` random scalar of the BBS proof, so that the range proofs are bound to the signed messages.
+    ///
+    /// # Inputs:
+    /// * `pk` (REQUIRED), the Signer public key.
+    /// * `signature` (REQUIRED), an octet string, a signature generated with [`Signature::sign_with_integers`].
+    /// * `header` (OPTIONAL), an octet string containing context and application.
+    /// * `ph` (OPTIONAL), an octet string containing the presentation header.
+    /// * `messages` (OPTIONAL), a vector of octet strings representing the signed messages.
+    /// * `integer_indexes` (OPTIONAL), vector of unsigned integers. Indexes of the integer messages.
+    /// * `disclosed_indexes` (OPTIONAL), vector of unsigned integers in ascending order. Indexes of disclosed messages.
+    /// * `predicates` (REQUIRED), a vector of [`RangePredicate`] over undisclosed integer messages.
+    ///
+    /// # Output:
+    /// a PoK of a Signature [`PoKSignature::BBSplus`] and a [`RangeProof`] or [`Error`].
+    ///
+    #[cfg(feature = "std")]
+    pub fn range_proof_gen(
+        pk: &BBSplusPublicKey,
+        signature: &[u8],
+        header: Option<&[u8]>,
+        ph: Option<&[u8]>,
+        messages: Option<&[Vec<u8>]>,
+        integer_indexes: Option<&[usize]>,
+        disclosed_indexes: Option<&[usize]>,
+        predicates: &[RangePredicate],
+    ) -> Result<(Self, RangeProof), Error>
+    where
+        CS::Expander: for<'a> ExpandMsg<'a>,
+    {
+        Self::range_proof_gen_with_rng(
+            pk,
+            signature,
+            header,
+            ph,
+            messages,
+            integer_indexes,
+            disclosed_indexes,
+            predicates,
+            &mut thread_rng(),
+        )
+    }
+
+    /// # Description
+    /// Same as [`Self::range_proof_gen`], drawing the blindings of the BBS proof and of the range proofs from `rng`.
+    ///
+    /// # Inputs:
+    /// * `rng` (REQUIRED), a cryptographically secure random number generator.
+    /// * the other inputs are the same as [`Self::range_proof_gen`].
+    ///
+    /// # Output:
+    /// a PoK of a Signature [`PoKSignature::BBSplus`] and a [`RangeProof`] or [`Error`].
+    ///
+    pub fn range_proof_gen_with_rng<R: RngCore + CryptoRng>(
+        pk: &BBSplusPublicKey,
+        signature: &[u8],
+        header: Option<&[u8]>,
+        ph: Option<&[u8]>,
+        messages: Option<&[Vec<u8>]>,
+        integer_indexes: Option<&[usize]>,
+        disclosed_indexes: Option<&[usize]>,
+        predicates: &[RangePredicate],
+        rng: &mut R,
+    ) -> Result<(Self, RangeProof), Error>
+    where
+        CS::Expander: for<'a> ExpandMsg<'a>,
+    {
+        let signature = BBSplusSignature::from_bytes(
+            signature.try_into().map_err(|_| Error::InvalidSignature)?,
+        )?;
+        let messages = messages.unwrap_or(&[]);
+        let integer_indexes = integer_indexes.unwrap_or(&[]);
+        let disclosed_indexes = disclosed_indexes.unwrap_or(&[]);
+
+        let message_scalars = BBSplusMessage::messages_to_scalar_with_integers::<CS>(
+            messages,
+            integer_indexes,
+            CS::API_ID,
+        )?;
+        let generators = Generators::create::<CS>(messages.len() + 1, Some(CS::API_ID));
+        let (G, H) = range_generators::<CS>();
+
+        let mut commitments = Vec::new();
+        let mut provers = Vec::new();
+        for predicate in predicates {
+            if !integer_indexes.contains(&predicate.index) {
+                return Err(Error::RangeProofGenError(format!(
+                    "message {} is not an integer",
+                    predicate.index
+                )));
+            }
+            if disclosed_indexes.contains(&predicate.index) {
+                return Err(Error::RangeProofGenError(format!(
+                    "message {} must be undisclosed",
+                    predicate.index
+                )));
+            }
+            let n = predicate.bits()?;
+            let value = message_scalars
+                .get(predicate.index)
+                .and_then(|m| m.to_integer())
+                .filter(|v| (predicate.lower..=predicate.upper).contains(v))
+                .ok_or_else(|| {
+                    Error::RangeProofGenError(format!(
+                        "message {} does not satisfy the predicate",
+                        predicate.index
+                    ))
+                })?;
+
+            let s = get_random_with_rng(rng);
+            let s_tilde = get_random_with_rng(rng);
+            let C = G * Scalar::from(value) + H * s;
+
+            let lower_bits = bits_commit(G, H, value - predicate.lower, n, s, rng);
+            let upper_bits = bits_commit(G, H, predicate.upper - value, n, -s, rng);
+
+            commitments.push((predicate.index, C, s_tilde));
+            provers.push((C, s, s_tilde, lower_bits, upper_bits));
+        }
+
+        let points = provers
+            .iter()
+            .flat_map(|(_, _, _, lower_bits, upper_bits)| lower_bits.iter().chain(upper_bits))
+            .flat_map(|b| [b.D, b.a0, b.a1])
+            .collect();
+        let binding = CommitmentBinding {
+            G,
+            H,
+            commitments,
+            points,
+        };
+
+        let proof = core_proof_gen::<CS, _>(
+            pk,
+            &signature,
+            &generators,
+            &message_scalars,
+            disclosed_indexes,
+            header,
+            ph,
+            Some(CS::API_ID),
+            None,
+            Some(&binding),
+            Randomness::Rng(&mut *rng),
+        )?;
+
+        let challenge = proof.challenge;
+        let proofs = provers
+            .into_iter()
+            .map(|(C, s, s_tilde, lower_bits, upper_bits)| PredicateProof {
+                C,
+                s_cap: s_tilde + s * challenge,
+                lower_bits: lower_bits.iter().map(|b| b.finalize(challenge)).collect(),
+                upper_bits: upper_bits.iter().map(|b| b.finalize(challenge)).collect(),
+            })
+            .collect();
+
+        Ok((Self::BBSplus(proof), RangeProof { proofs }))
+    }
+
+    /// # Description
+    /// This operation validates a BBS proof together with the [`RangeProof`] generated by [`Self::range_proof_gen`], checking that the undisclosed integer messages satisfy the supplied [`RangePredicate`]s.
+    ///
+    /// # Inputs:
+    /// * `self`, a proof.
+    /// * `range_proof` (REQUIRED), a [`RangeProof`].
+    /// * `pk` (REQUIRED), the Signer public key.
+    /// * `disclosed_messages` (OPTIONAL), a vector of octet strings representing the messages disclosed to the Verifier.
+    /// * `disclosed_indexes` (OPTIONAL), vector of unsigned integers in ascending order. Indexes of disclosed messages.
+    /// * `integer_indexes` (OPTIONAL), vector of unsigned integers. Indexes of the integer messages.
+    /// * `header` (OPTIONAL), an octet string containing context and application.
+    /// * `ph` (OPTIONAL), an octet string containing the presentation header.
+    /// * `predicates` (REQUIRED), a vector of [`RangePredicate`], in the same order used to generate the proof.
+    ///
+    /// # Output:
+    /// a result: [`Ok`] or [`Error`].
+    ///
+    pub fn range_proof_verify(
+        &self,
+        range_proof: &RangeProof,
+        pk: &BBSplusPublicKey,
+        disclosed_messages: Option<&[Vec<u8>]>,
+        disclosed_indexes: Option<&[usize]>,
+        integer_indexes: Option<&[usize]>,
+        header: Option<&[u8]>,
+        ph: Option<&[u8]>,
+        predicates: &[RangePredicate],
+    ) -> Result<(), Error>
+    where
+        CS::Expander: for<'a> ExpandMsg<'a>,
+    {
+        let proof = self.to_bbsplus_proof();
+        let disclosed_messages = disclosed_messages.unwrap_or(&[]);
+        let integer_indexes = integer_indexes.unwrap_or(&[]);
+        let mut disclosed_indexes = disclosed_indexes.unwrap_or(&[]).to_vec();
+        disclosed_indexes.sort();
+        disclosed_indexes.dedup();
+
+        if range_proof.proofs.len() != predicates.len() {
+            return Err(Error::RangeProofVerificationError(
+                "number of proofs different from number of predicates".to_owned(),
+            ));
+        }
+
+        let U = proof.m_cap.len();
+        let R = disclosed_indexes.len();
+
+        let disclosed_integer_positions: Vec<usize> = disclosed_indexes
+            .iter()
+            .enumerate()
+            .filter(|(_, i)| integer_indexes.contains(i))
+            .map(|(p, _)| p)
+            .collect();
+        let disclosed_message_scalars = BBSplusMessage::messages_to_scalar_with_integers::<CS>(
+            disclosed_messages,
+            &disclosed_integer_positions,
+            CS::API_ID,
+        )?;
+
+        let generators = Generators::create::<CS>(U + R + 1, Some(CS::API_ID));
+        let (G, H) = range_generators::<CS>();
+        let challenge = proof.challenge;
+
+        let mut commitments = Vec::new();
+        let mut points = Vec::new();
+        for (predicate, p) in core::iter::zip(predicates, &range_proof.proofs) {
+            let n = predicate
+                .bits()
+                .map_err(|e| Error::RangeProofVerificationError(e.to_string()))?;
+            if !integer_indexes.contains(&predicate.index) {
+                return Err(Error::RangeProofVerificationError(format!(
+                    "message {} is not an integer",
+                    predicate.index
+                )));
+            }
+            if p.lower_bits.len() != n || p.upper_bits.len() != n {
+                return Err(Error::RangeProofVerificationError(
+                    "invalid number of bits".to_owned(),
+                ));
+            }
+
+            let lower_target = p.C - G * Scalar::from(predicate.lower);
+            let upper_target = G * Scalar::from(predicate.upper) - p.C;
+            if bits_sum(&p.lower_bits) != lower_target || bits_sum(&p.upper_bits) != upper_target {
+                return Err(Error::RangeProofVerificationError(
+                    "bit commitments do not match the predicate".to_owned(),
+                ));
+            }
+
+            for b in p.lower_bits.iter().chain(&p.upper_bits) {
+                points.extend(b.verify_init(G, H, challenge));
+            }
+            commitments.push((predicate.index, p.C, p.s_cap));
+        }
+
+        let binding = CommitmentBinding {
+            G,
+            H,
+            commitments,
+            points,
+        };
+
+        core_proof_verify::<CS>(
+            pk,
+            proof,
+            &generators,
+            header,
+            ph,
+            &disclosed_message_scalars,
+            &disclosed_indexes,
+            Some(CS::API_ID),
+            None,
+            Some(&binding),
+        )
+    }
+}
+
+/// Prover state of a [`BitProof`], before the challenge is known
+struct BitProver {
+    bit: bool,
+    t: Scalar,
+    k: Scalar,
+    c_sim: Scalar,
+    z_sim: Scalar,
+    D: G1Projective,
+    a0: G1Projective,
+    a1: G1Projective,
+}
+
+impl BitProver {
+    /// Commits to `bit` and computes the first messages of the OR proof, simulating the false branch
+    fn new<R: RngCore + CryptoRng>(
+        G: G1Projective,
+        H: G1Projective,
+        bit: bool,
+        t: Scalar,
+        rng: &mut R,
+    ) -> Self {
+        let k = get_random_with_rng(rng);
+        let c_sim = get_random_with_rng(rng);
+        let z_sim = get_random_with_rng(rng);
+
+        let (D, a0, a1) = if bit {
+            let D = G + H * t;
+            (D, H * z_sim - D * c_sim, H * k)
+        } else {
+            let D = H * t;
+            (D, H * k, H * z_sim - (D - G) * c_sim)
+        };
+
+        Self {
+            bit,
+            t,
+            k,
+            c_sim,
+            z_sim,
+            D,
+            a0,
+            a1,
+        }
+    }
+
+    fn finalize(&self, challenge: Scalar) -> BitProof {
+        let c_real = challenge - self.c_sim;
+        let z_real = self.k + c_real * self.t;
+        let (c0, z0, z1) = if self.bit {
+            (self.c_sim, self.z_sim, z_real)
+        } else {
+            (c_real, z_real, self.z_sim)
+        };
+
+        BitProof {
+            D: self.D,
+            c0,
+            z0,
+            z1,
+        }
+    }
+}
+
+impl BitProof {
+    /// Recomputes `D`, `a0 = H * z0 - D * c0` and `a1 = H * z1 - (D - G) * c1`, with `c1 = challenge - c0`
+    fn verify_init(
+        &self,
+        G: G1Projective,
+        H: G1Projective,
+        challenge: Scalar,
+    ) -> [G1Projective; 3] {
+        let c1 = challenge - self.c0;
+        [
+            self.D,
+            H * self.z0 - self.D * self.c0,
+            H * self.z1 - (self.D - G) * c1,
+        ]
+    }
+}
+
+/// Commits to the `n` bits of `value`, such that `sum(2^i * D_i) = G * value + H * blinding`
+fn bits_commit<R: RngCore + CryptoRng>(
+    G: G1Projective,
+    H: G1Projective,
+    value: u64,
+    n: usize,
+    blinding: Scalar,
+    rng: &mut R,
+) -> Vec<BitProver> {
+    let mut t: Vec<Scalar> = (0..n).map(|_| get_random_with_rng(rng)).collect();
+    let mut t_sum = Scalar::ZERO;
+    let mut power = Scalar::ONE;
+    for t_i in &t[1..] {
+        power = power.double();
+        t_sum += power * t_i;
+    }
+    t[0] = blinding - t_sum;
+
+    t.into_iter()
+        .enumerate()
+        .map(|(i, t_i)| BitProver::new(G, H, (value >> i) & 1 == 1, t_i, rng))
+        .collect()
+}
+
+/// `sum(2^i * D_i)`
+fn bits_sum(bits: &[BitProof]) -> G1Projective {
+    bits.iter()
+        .rev()
+        .fold(G1Projective::IDENTITY, |acc, b| acc.double() + b.D)
+}
+
+/// The generators `G` and `H` of the Pedersen commitments used by the range proofs
+fn range_generators<CS>() -> (G1Projective, G1Projective)
+where
+    CS: BbsCiphersuite,
+    CS::Expander: for<'a> ExpandMsg<'a>,
+{
+    let generators = Generators::create::<CS>(2, Some(&[CS::API_ID, CS::RANGE_PROOF_DST].concat()));
+    (generators.values[0], generators.values[1])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{RangePredicate, RangeProof};
+    use crate::{
+        bbsplus::{
+            ciphersuites::BbsCiphersuite,
+            keys::{BBSplusPublicKey, BBSplusSecretKey},
+        },
+        schemes::{
+            algorithms::{BBSplus, BbsBls12381Sha256, BbsBls12381Shake256, Scheme},
+            generics::{PoKSignature, Signature},
+        },
+        utils::util::bbsplus_utils::get_messages_vec,
+    };
+    use elliptic_curve::hash2curve::ExpandMsg;
+    use std::fs;
+
+    macro_rules! range_proof_tests {
+        ( $( ($t:ident, $p:literal): { $( ($n:ident, $f:literal), )+ },)+ ) => { $($(
+            #[test] fn $n() { range_proof_check::<$t>($p, $f); }
+        )+)+ }
+    }
+
+    range_proof_tests! {
+        (BbsBls12381Sha256, "./fixture_data/bls12-381-sha-256/"): {
+            (range_proof_check_sha256_1, "./fixture_data_range/rangeProof001.json"),
+            (range_proof_check_sha256_2, "./fixture_data_range/rangeProof002.json"),
+            (range_proof_check_sha256_3, "./fixture_data_range/rangeProof003.json"),
+            (range_proof_check_sha256_4, "./fixture_data_range/rangeProof004.json"),
+            (range_proof_check_sha256_5, "./fixture_data_range/rangeProof005.json"),
+            (range_proof_check_sha256_6, "./fixture_data_range/rangeProof006.json"),
+        },
+        (BbsBls12381Shake256, "./fixture_data/bls12-381-shake-256/"): {
+            (range_proof_check_shake256_1, "./fixture_data_range/rangeProof001.json"),
+            (range_proof_check_shake256_2, "./fixture_data_range/rangeProof002.json"),
+            (range_proof_check_shake256_3, "./fixture_data_range/rangeProof003.json"),
+            (range_proof_check_shake256_4, "./fixture_data_range/rangeProof004.json"),
+            (range_proof_check_shake256_5, "./fixture_data_range/rangeProof005.json"),
+            (range_proof_check_shake256_6, "./fixture_data_range/rangeProof006.json"),
+        },
+    }
+
+    fn get_predicates(json: &serde_json::Value) -> Vec<RangePredicate> {
+        json.as_array()
+            .unwrap()
+            .iter()
+            .map(|p| serde_json::from_value(p.clone()).unwrap())
+            .collect()
+    }
+
+    fn get_indexes(json: &serde_json::Value) -> Vec<usize> {
+        json.as_array()
+            .unwrap()
+            .iter()
+            .map(|i| serde_json::from_value(i.clone()).unwrap())
+            .collect()
+    }
+
+    fn range_proof_check<S: Scheme>(pathname: &str, filename: &str)
+    where
+        S::Ciphersuite: BbsCiphersuite,
+        <S::Ciphersuite as BbsCiphersuite>::Expander: for<'a> ExpandMsg<'a>,
+    {
+        let keypair_data =
+            fs::read_to_string([pathname, "keypair.json"].concat()).expect("Unable to read file");
+        let keypair_json: serde_json::Value =
+            serde_json::from_str(&keypair_data).expect("Unable to parse");
+        let data = fs::read_to_string(filename).expect("Unable to read file");
+        let json: serde_json::Value = serde_json::from_str(&data).expect("Unable to parse");
+        eprintln!("{}", json["caseName"]);
+
+        let SK = BBSplusSecretKey::from_bytes(
+            &hex::decode(keypair_json["keyPair"]["secretKey"].as_str().unwrap()).unwrap(),
+        )
+        .unwrap();
+        let PK = BBSplusPublicKey::from_bytes(
+            &hex::decode(keypair_json["keyPair"]["publicKey"].as_str().unwrap()).unwrap(),
+        )
+        .unwrap();
+
+        let header = hex::decode(json["header"].as_str().unwrap()).unwrap();
+        let ph = hex::decode(json["presentationHeader"].as_str().unwrap()).unwrap();
+        let messages: Vec<Vec<u8>> = json["messages"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|m| hex::decode(m.as_str().unwrap()).unwrap())
+            .collect();
+        let integer_indexes = get_indexes(&json["integerIndexes"]);
+        let disclosed_indexes = get_indexes(&json["disclosedIndexes"]);
+        let prover_predicates = get_predicates(&json["proverPredicates"]);
+        let verifier_predicates = get_predicates(&json["verifierPredicates"]);
+        let result_expected = json["result"]["valid"].as_bool().unwrap();
+
+        let signature = Signature::<BBSplus<S::Ciphersuite>>::sign_with_integers(
+            Some(&messages),
+            Some(&integer_indexes),
+            &SK,
+            &PK,
+            Some(&header),
+        )
+        .unwrap();
+        assert!(signature
+            .verify_with_integers(&PK, Some(&messages), Some(&integer_indexes), Some(&header))
+            .is_ok());
+
+        let result = PoKSignature::<BBSplus<S::Ciphersuite>>::range_proof_gen(
+            &PK,
+            &signature.to_bytes(),
+            Some(&header),
+            Some(&ph),
+            Some(&messages),
+            Some(&integer_indexes),
+            Some(&disclosed_indexes),
+            &prover_predicates,
+        );
+
+        let (proof, range_proof) = match result {
+            Ok(proofs) => proofs,
+            Err(e) => {
+                eprintln!("  rangeProofGen: {:?}", e);
+                assert!(!result_expected, "failed");
+                return;
+            }
+        };
+
+        // serialization round trip
+        let range_proof = RangeProof::from_bytes(&range_proof.to_bytes()).unwrap();
+        let proof = PoKSignature::<BBSplus<S::Ciphersuite>>::from_bytes(&proof.to_bytes()).unwrap();
+
+        let disclosed_messages = get_messages_vec(&messages, &disclosed_indexes);
+        let result = proof.range_proof_verify(
+            &range_proof,
+            &PK,
+            Some(&disclosed_messages),
+            Some(&disclosed_indexes),
+            Some(&integer_indexes),
+            Some(&header),
+            Some(&ph),
+            &verifier_predicates,
+        );
+        eprintln!("  rangeProofVerify: {:?}", result);
+        assert_eq!(result.is_ok(), result_expected, "failed");
+
+        // the range proof is bound to the proof of knowledge of the signature
+        assert!(proof
+            .proof_verify(
+                &PK,
+                Some(&disclosed_messages),
+                Some(&disclosed_indexes),
+                Some(&header),
+                Some(&ph),
+            )
+            .is_err());
+    }
+}
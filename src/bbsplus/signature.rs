@@ -18,14 +18,22 @@ use crate::{
     errors::Error,
     schemes::{algorithms::BBSplus, generics::Signature},
     utils::{
+        cbor::{decode_with_ciphersuite, encode_with_ciphersuite},
         message::bbsplus_message::BBSplusMessage,
         util::bbsplus_utils::{
-            calculate_domain, hash_to_scalar, parse_g1_projective, serialize, ScalarExt,
+            calculate_domain, get_random_with_rng, hash_to_scalar, parse_g1_projective, serialize,
+            ScalarExt,
         },
     },
 };
-use bls12_381_plus::{multi_miller_loop, G1Projective, G2Prepared, G2Projective, Gt, Scalar};
-use elliptic_curve::{group::Curve, hash2curve::ExpandMsg};
+use alloc::{borrow::ToOwned, vec::Vec};
+use bls12_381_plus::{
+    multi_miller_loop, G1Affine, G1Projective, G2Prepared, G2Projective, Gt, Scalar,
+};
+use elliptic_curve::{group::Curve, hash2curve::ExpandMsg, Group};
+#[cfg(feature = "std")]
+use rand::thread_rng;
+use rand::{CryptoRng, RngCore};
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
@@ -56,6 +64,15 @@ impl BBSplusSignature {
     }
 }
 
+/// A signature to be verified with [`Signature::batch_verify`], together with the inputs of [`Signature::verify`]
+#[derive(Clone, Debug)]
+pub struct SignatureBatchItem<'a, CS: BbsCiphersuite> {
+    pub signature: &'a Signature<BBSplus<CS>>,
+    pub pk: &'a BBSplusPublicKey,
+    pub messages: Option<&'a [Vec<u8>]>,
+    pub header: Option<&'a [u8]>,
+}
+
 impl<CS: BbsCiphersuite> Signature<BBSplus<CS>> {
     pub fn a(&self) -> G1Projective {
         match self {
@@ -142,6 +159,190 @@ impl<CS: BbsCiphersuite> Signature<BBSplus<CS>> {
         )
     }
 
+    /// # Description
+    /// Same as [`Self::sign`], but using prebuilt generators (e.g., obtained from a [`crate::bbsplus::generators::GeneratorsCache`]) instead of creating them at each call.
+    ///
+    /// # Inputs:
+    /// * `messages` (OPTIONAL), a vector of octet strings representing the messages, it could be an empty vector.
+    /// * `sk` (REQUIRED), a secret key
+    /// * `pk` (REQUIRED), a public key
+    /// * `header` (OPTIONAL), an octet string containing context and application specific information.
+    /// * `generators` (REQUIRED), at least L+1 [`Generators`] created with `api_id` equal to [`BbsCiphersuite::API_ID`], where L is the number of messages.
+    ///
+    /// # Output:
+    /// * new [`Signature::BBSplus`] or [`Error`]
+    pub fn sign_with_generators(
+        messages: Option<&[Vec<u8>]>,
+        sk: &BBSplusSecretKey,
+        pk: &BBSplusPublicKey,
+        header: Option<&[u8]>,
+        generators: &Generators,
+    ) -> Result<Self, Error>
+    where
+        CS::Expander: for<'a> ExpandMsg<'a>,
+    {
+        let messages = messages.unwrap_or(&[]);
+        let message_scalars = BBSplusMessage::messages_to_scalar::<CS>(messages, CS::API_ID)?;
+        let generators = generators.first::<CS>(messages.len() + 1, CS::API_ID)?;
+        let signature = core_sign::<CS>(
+            sk,
+            pk,
+            generators,
+            header,
+            &message_scalars,
+            Some(CS::API_ID),
+        )?;
+
+        Ok(Self::BBSplus(signature))
+    }
+
+    /// # Description
+    /// Same as [`Self::verify`], but using prebuilt generators (e.g., obtained from a [`crate::bbsplus::generators::GeneratorsCache`]) instead of creating them at each call.
+    ///
+    /// # Inputs:
+    /// * `self`, the signature
+    /// * `pk` (REQUIRED), a public key
+    /// * `messages` (OPTIONAL), a vector of octet strings representing the messages, it could be an empty vector.
+    /// * `header` (OPTIONAL), an octet string containing context and application specific information.
+    /// * `generators` (REQUIRED), at least L+1 [`Generators`] created with `api_id` equal to [`BbsCiphersuite::API_ID`], where L is the number of messages.
+    ///
+    /// # Output:
+    /// * a result either [`Ok()`] or [`Error`]
+    pub fn verify_with_generators(
+        &self,
+        pk: &BBSplusPublicKey,
+        messages: Option<&[Vec<u8>]>,
+        header: Option<&[u8]>,
+        generators: &Generators,
+    ) -> Result<(), Error>
+    where
+        CS::Expander: for<'a> ExpandMsg<'a>,
+    {
+        let messages = messages.unwrap_or(&[]);
+        let message_scalars = BBSplusMessage::messages_to_scalar::<CS>(messages, CS::API_ID)?;
+        let generators = generators.first::<CS>(messages.len() + 1, CS::API_ID)?;
+        let signature = self.bbsPlusSignature();
+
+        core_verify::<CS>(
+            pk,
+            signature,
+            &message_scalars,
+            generators,
+            header,
+            Some(CS::API_ID),
+        )
+    }
+
+    /// # Description
+    /// The `batch_verify` API validates many BBS signatures at once. The pairing equations of all the signatures are combined with random weights, so that a single multi-pairing and a single final exponentiation are computed. If the combined check fails, the signatures are verified one by one to find the invalid one.
+    ///
+    /// # Inputs:
+    /// * `items` (REQUIRED), a vector of [`SignatureBatchItem`], each one containing the signature and the inputs of [`Self::verify`].
+    ///
+    /// # Output:
+    /// * a result either [`Ok()`] or [`Error::BatchVerificationError`] with the index of the first invalid signature
+    #[cfg(feature = "std")]
+    pub fn batch_verify(items: &[SignatureBatchItem<CS>]) -> Result<(), Error>
+    where
+        CS::Expander: for<'a> ExpandMsg<'a>,
+    {
+        Self::batch_verify_with_rng(items, &mut thread_rng())
+    }
+
+    /// # Description
+    /// Same as [`Self::batch_verify`], drawing the random weights from `rng`.
+    ///
+    /// # Inputs:
+    /// * `items` (REQUIRED), a vector of [`SignatureBatchItem`], each one containing the signature and the inputs of [`Self::verify`].
+    /// * `rng` (REQUIRED), a cryptographically secure random number generator.
+    ///
+    /// # Output:
+    /// * a result either [`Ok()`] or [`Error::BatchVerificationError`] with the index of the first invalid signature
+    pub fn batch_verify_with_rng<R: RngCore + CryptoRng>(
+        items: &[SignatureBatchItem<CS>],
+        rng: &mut R,
+    ) -> Result<(), Error>
+    where
+        CS::Expander: for<'a> ExpandMsg<'a>,
+    {
+        // e(A, PK + BP2 * e) = e(B, BP2)  <=>  e(A, PK) * e(A * e - B, BP2) = 1
+        // The first terms are grouped by public key, the second ones are all merged together
+        let mut pk_terms: Vec<(G2Projective, G1Projective)> = Vec::new();
+        let mut bp2_term = G1Projective::IDENTITY;
+        let mut first_invalid_input = None;
+
+        for (i, item) in items.iter().enumerate() {
+            let messages = item.messages.unwrap_or(&[]);
+            let signature = item.signature.bbsPlusSignature();
+            let B = match BBSplusMessage::messages_to_scalar::<CS>(messages, CS::API_ID).and_then(
+                |message_scalars| {
+                    let generators = Generators::create::<CS>(messages.len() + 1, Some(CS::API_ID));
+                    calculate_B::<CS>(
+                        item.pk,
+                        &message_scalars,
+                        &generators,
+                        item.header,
+                        Some(CS::API_ID),
+                    )
+                },
+            ) {
+                Ok(B) => B,
+                Err(_) => {
+                    first_invalid_input = Some(i);
+                    break;
+                }
+            };
+
+            let r = get_random_with_rng(rng);
+            let rA = signature.A * r;
+            match pk_terms.iter_mut().find(|(pk, _)| *pk == item.pk.0) {
+                Some((_, A_sum)) => *A_sum += rA,
+                None => pk_terms.push((item.pk.0, rA)),
+            }
+            bp2_term += rA * signature.e - B * r;
+        }
+
+        let g1_points: Vec<G1Affine> = pk_terms
+            .iter()
+            .map(|(_, A_sum)| A_sum.to_affine())
+            .chain(core::iter::once(bp2_term.to_affine()))
+            .collect();
+        let g2_points: Vec<G2Prepared> = pk_terms
+            .iter()
+            .map(|(pk, _)| G2Prepared::from(pk.to_affine()))
+            .chain(core::iter::once(G2Prepared::from(
+                G2Projective::GENERATOR.to_affine(),
+            )))
+            .collect();
+        let terms: Vec<(&G1Affine, &G2Prepared)> =
+            core::iter::zip(&g1_points, &g2_points).collect();
+
+        if multi_miller_loop(&terms)
+            .final_exponentiation()
+            .is_identity()
+            .into()
+        {
+            return match first_invalid_input {
+                Some(i) => Err(Error::BatchVerificationError(i)),
+                None => Ok(()),
+            };
+        }
+
+        // The signatures before the first invalid input are the ones in the combined check
+        let checked = first_invalid_input.unwrap_or(items.len());
+        let invalid_index = items[..checked]
+            .iter()
+            .position(|item| {
+                item.signature
+                    .verify(item.pk, item.messages, item.header)
+                    .is_err()
+            })
+            .or(first_invalid_input)
+            .ok_or(Error::SignatureVerificationError)?;
+
+        Err(Error::BatchVerificationError(invalid_index))
+    }
+
     pub fn bbsPlusSignature(&self) -> &BBSplusSignature {
         match self {
             Self::BBSplus(inner) => inner,
@@ -157,6 +358,17 @@ impl<CS: BbsCiphersuite> Signature<BBSplus<CS>> {
         Ok(Self::BBSplus(BBSplusSignature::from_bytes(data)?))
     }
 
+    /// Encodes the signature as the CBOR array `[ciphersuite_id, signature]`, with the `ciphersuite_id` of `CS` as a text string and the signature octets as a byte string
+    pub fn to_cbor(&self) -> Vec<u8> {
+        encode_with_ciphersuite(CS::ID, &self.to_bytes())
+    }
+
+    /// Decodes a signature encoded with [`Self::to_cbor`], failing with [`Error::CiphersuiteMismatch`] if it was encoded for another ciphersuite
+    pub fn from_cbor(bytes: &[u8]) -> Result<Self, Error> {
+        let payload = decode_with_ciphersuite(CS::ID, bytes)?;
+        Self::from_bytes(&payload.try_into().map_err(|_| Error::InvalidSignature)?)
+    }
+
     /// # Description
     /// Update signature with a new value of a signed message
     ///
@@ -194,8 +406,8 @@ impl<CS: BbsCiphersuite> Signature<BBSplus<CS>> {
         let H_i = H_points.get(update_index).ok_or(Error::Unspecified)?;
         let sk_e = sk.0 + self.e();
         let mut B = self.a() * sk_e;
-        B = B + (-H_i * old_message_scalar.value);
-        B = B + (H_i * new_message_scalar.value);
+        B += -H_i * old_message_scalar.value;
+        B += H_i * new_message_scalar.value;
 
         let sk_e_inv = Option::<Scalar>::from(sk_e.invert())
             .ok_or_else(|| Error::UpdateSignatureError("Invert scalar failed".to_owned()))?;
@@ -205,7 +417,7 @@ impl<CS: BbsCiphersuite> Signature<BBSplus<CS>> {
             return Err(Error::UpdateSignatureError("A == IDENTITY G1".to_owned()));
         }
 
-        return Ok(Self::BBSplus(BBSplusSignature { A, e: self.e() }));
+        Ok(Self::BBSplus(BBSplusSignature { A, e: self.e() }))
     }
 }
 
@@ -223,7 +435,7 @@ impl<CS: BbsCiphersuite> Signature<BBSplus<CS>> {
 ///
 /// # Output:
 /// * new [`BBSplusSignature`] or [`Error`]
-fn core_sign<CS>(
+pub(super) fn core_sign<CS>(
     sk: &BBSplusSecretKey,
     pk: &BBSplusPublicKey,
     generators: Generators,
@@ -263,7 +475,7 @@ where
     let mut B = generators.g1_base_point + Q1 * domain;
 
     for i in 0..L {
-        B = B + H_points[i] * messages[i].value;
+        B += H_points[i] * messages[i].value;
     }
 
     // A = B * (1 / (SK + e))
@@ -275,7 +487,7 @@ where
         ));
     }
 
-    Ok(BBSplusSignature { A, e: e })
+    Ok(BBSplusSignature { A, e })
 }
 
 /// https://datatracker.ietf.org/doc/html/draft-irtf-cfrg-bbs-signatures-07#name-coreverify
@@ -304,22 +516,7 @@ where
     CS: BbsCiphersuite,
     CS::Expander: for<'a> ExpandMsg<'a>,
 {
-    let L = messages.len();
-
-    if generators.values.len() != L + 1 {
-        return Err(Error::NotEnoughGenerators);
-    }
-
-    let Q1 = generators.values[0];
-    let H_points: &[G1Projective] = &generators.values[1..];
-
-    let domain = calculate_domain::<CS>(pk, Q1, H_points, header, api_id)?;
-
-    let mut B = generators.g1_base_point + Q1 * domain;
-
-    for i in 0..L {
-        B = B + H_points[i] * messages[i].value;
-    }
+    let B = calculate_B::<CS>(pk, messages, &generators, header, api_id)?;
 
     let BP2 = G2Projective::GENERATOR;
     let A2 = pk.0 + BP2 * signature.e;
@@ -338,10 +535,55 @@ where
     }
 }
 
+/// # Description
+/// Computes the point B = P1 + Q_1 * domain + H_1 * msg_1 + ... + H_L * msg_L, used by the [`core_verify`] operation
+///
+/// # Inputs:
+/// * `pk` (REQUIRED), a public key
+/// * `messages` (REQUIRED), a vector of scalars (`BBSplusMessage`) representing the messages, it could be an empty vector.
+/// * `generators` (REQUIRED), vector of pseudo-random points in G1.
+/// * `header` (OPTIONAL), an octet string containing context and application specific information.
+/// * `api_id` (OPTIONAL), an octet string. If not supplied it defaults to the empty octet string ("").
+///
+/// # Output:
+/// * a [`G1Projective`] point or [`Error`]
+pub(super) fn calculate_B<CS>(
+    pk: &BBSplusPublicKey,
+    messages: &[BBSplusMessage],
+    generators: &Generators,
+    header: Option<&[u8]>,
+    api_id: Option<&[u8]>,
+) -> Result<G1Projective, Error>
+where
+    CS: BbsCiphersuite,
+{
+    let L = messages.len();
+
+    if generators.values.len() != L + 1 {
+        return Err(Error::NotEnoughGenerators);
+    }
+
+    let Q1 = generators.values[0];
+    let H_points: &[G1Projective] = &generators.values[1..];
+
+    let domain = calculate_domain::<CS>(pk, Q1, H_points, header, api_id)?;
+
+    let mut B = generators.g1_base_point + Q1 * domain;
+
+    for i in 0..L {
+        B += H_points[i] * messages[i].value;
+    }
+
+    Ok(B)
+}
+
 #[cfg(test)]
 mod tests {
 
+    use super::SignatureBatchItem;
     use crate::bbsplus::ciphersuites::BbsCiphersuite;
+    use crate::bbsplus::generators::GeneratorsCache;
+    use crate::errors::Error;
     use crate::keys::pair::KeyPair;
     use crate::schemes::algorithms::Scheme;
     use crate::schemes::algorithms::{BbsBls12381Sha256, BbsBls12381Shake256};
@@ -387,6 +629,45 @@ mod tests {
         },
     }
 
+    //BATCH VERIFY
+
+    macro_rules! batch_verify_tests {
+        ( $( ($t:ident, $p:literal): { $( ($n:ident, [$($f:literal),+], $e:expr), )+ },)+ ) => { $($(
+            #[test] fn $n() { batch_verify::<$t>($p, &[$($f),+], $e); }
+        )+)+ }
+    }
+
+    batch_verify_tests! {
+        (BbsBls12381Sha256, "./fixture_data/bls12-381-sha-256/"): {
+            (batch_verify_sha256_1, ["signature/signature001.json", "signature/signature004.json", "signature/signature010.json"], None),
+            (batch_verify_sha256_2, ["signature/signature001.json", "signature/signature004.json", "signature/signature002.json", "signature/signature010.json"], Some(2)),
+            (batch_verify_sha256_3, ["signature/signature007.json", "signature/signature001.json"], Some(0)),
+        },
+        (BbsBls12381Shake256, "./fixture_data/bls12-381-shake-256/"): {
+            (batch_verify_shake256_1, ["signature/signature001.json", "signature/signature004.json", "signature/signature010.json"], None),
+            (batch_verify_shake256_2, ["signature/signature001.json", "signature/signature004.json", "signature/signature002.json", "signature/signature010.json"], Some(2)),
+            (batch_verify_shake256_3, ["signature/signature007.json", "signature/signature001.json"], Some(0)),
+        },
+    }
+
+    //SIGNATURE WITH PREBUILT GENERATORS
+
+    #[test]
+    fn signature_with_generators_sha256() {
+        signature_with_generators::<BbsBls12381Sha256>(
+            "./fixture_data/bls12-381-sha-256/",
+            &["signature/signature001.json", "signature/signature004.json"],
+        );
+    }
+
+    #[test]
+    fn signature_with_generators_shake256() {
+        signature_with_generators::<BbsBls12381Shake256>(
+            "./fixture_data/bls12-381-shake-256/",
+            &["signature/signature001.json", "signature/signature004.json"],
+        );
+    }
+
     //Update Signature - SHA256
     #[test]
     fn update_signature_sha256() {
@@ -464,7 +745,7 @@ mod tests {
             eprintln!("  VERIFY: {}", result3);
             eprintln!("  Expected: {}", RESULT_expected);
             eprintln!("  Computed: {}", result2);
-            if RESULT_expected == false {
+            if !RESULT_expected {
                 eprintln!(
                     "{} ({})",
                     result3,
@@ -474,6 +755,125 @@ mod tests {
         }
     }
 
+    fn batch_verify<S: Scheme>(pathname: &str, filenames: &[&str], invalid_index: Option<usize>)
+    where
+        S::Ciphersuite: BbsCiphersuite,
+        <S::Ciphersuite as BbsCiphersuite>::Expander: for<'a> ExpandMsg<'a>,
+    {
+        let mut inputs = Vec::new();
+        for filename in filenames {
+            let data =
+                fs::read_to_string([pathname, filename].concat()).expect("Unable to read file");
+            let res: serde_json::Value = serde_json::from_str(&data).expect("Unable to parse");
+
+            let header = hex::decode(res["header"].as_str().unwrap()).unwrap();
+            let messages: Vec<Vec<u8>> = res["messages"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|m| hex::decode(m.as_str().unwrap()).unwrap())
+                .collect();
+            let PK = BBSplusPublicKey::from_bytes(
+                &hex::decode(res["signerKeyPair"]["publicKey"].as_str().unwrap()).unwrap(),
+            )
+            .unwrap();
+            let signature = Signature::<BBSplus<S::Ciphersuite>>::from_bytes(
+                &hex::decode(res["signature"].as_str().unwrap())
+                    .unwrap()
+                    .try_into()
+                    .unwrap(),
+            )
+            .unwrap();
+
+            inputs.push((signature, PK, messages, header));
+        }
+
+        let items: Vec<SignatureBatchItem<S::Ciphersuite>> = inputs
+            .iter()
+            .map(|(signature, pk, messages, header)| SignatureBatchItem {
+                signature,
+                pk,
+                messages: Some(messages),
+                header: Some(header),
+            })
+            .collect();
+
+        let result = Signature::<BBSplus<S::Ciphersuite>>::batch_verify(&items);
+
+        match invalid_index {
+            None => assert!(result.is_ok(), "failed: {:?}", result),
+            Some(index) => assert!(
+                matches!(result, Err(Error::BatchVerificationError(i)) if i == index),
+                "failed: {:?}",
+                result
+            ),
+        }
+    }
+
+    fn signature_with_generators<S: Scheme>(pathname: &str, filenames: &[&str])
+    where
+        S::Ciphersuite: BbsCiphersuite,
+        <S::Ciphersuite as BbsCiphersuite>::Expander: for<'a> ExpandMsg<'a>,
+    {
+        let api_id = <S::Ciphersuite as BbsCiphersuite>::API_ID;
+        let cache = GeneratorsCache::<S::Ciphersuite>::new();
+
+        for filename in filenames {
+            let data =
+                fs::read_to_string([pathname, filename].concat()).expect("Unable to read file");
+            let res: serde_json::Value = serde_json::from_str(&data).expect("Unable to parse");
+
+            let header = hex::decode(res["header"].as_str().unwrap()).unwrap();
+            let messages: Vec<Vec<u8>> = res["messages"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|m| hex::decode(m.as_str().unwrap()).unwrap())
+                .collect();
+            let SK = BBSplusSecretKey::from_bytes(
+                &hex::decode(res["signerKeyPair"]["secretKey"].as_str().unwrap()).unwrap(),
+            )
+            .unwrap();
+            let PK = BBSplusPublicKey::from_bytes(
+                &hex::decode(res["signerKeyPair"]["publicKey"].as_str().unwrap()).unwrap(),
+            )
+            .unwrap();
+            let SIGNATURE_expected = res["signature"].as_str().unwrap();
+
+            let generators = cache.get(messages.len() + 1, Some(api_id));
+            let signature = Signature::<BBSplus<S::Ciphersuite>>::sign_with_generators(
+                Some(&messages),
+                &SK,
+                &PK,
+                Some(&header),
+                &generators,
+            )
+            .unwrap();
+            assert_eq!(hex::encode(signature.to_bytes()), SIGNATURE_expected);
+
+            // more generators than needed can be supplied
+            let generators = cache.get(messages.len() + 5, Some(api_id));
+            assert!(signature
+                .verify_with_generators(&PK, Some(&messages), Some(&header), &generators)
+                .is_ok());
+
+            // not enough generators
+            let generators = cache.get(messages.len(), Some(api_id));
+            assert!(matches!(
+                signature.verify_with_generators(&PK, Some(&messages), Some(&header), &generators),
+                Err(Error::NotEnoughGenerators)
+            ));
+
+            // generators created for another api_id
+            let blind_api_id = <S::Ciphersuite as BbsCiphersuite>::API_ID_BLIND;
+            let generators = cache.get(messages.len() + 1, Some(blind_api_id));
+            assert!(matches!(
+                signature.verify_with_generators(&PK, Some(&messages), Some(&header), &generators),
+                Err(Error::CiphersuiteMismatch)
+            ));
+        }
+    }
+
     fn update_signature<S: Scheme>()
     where
         S::Ciphersuite: BbsCiphersuite,
@@ -490,8 +890,8 @@ mod tests {
         let header = hex::decode(header_hex).unwrap();
 
         let keypair = KeyPair::<BBSplus<S::Ciphersuite>>::generate(
-            &hex::decode(&IKM).unwrap(),
-            Some(&hex::decode(&KEY_INFO).unwrap()),
+            &hex::decode(IKM).unwrap(),
+            Some(&hex::decode(KEY_INFO).unwrap()),
             None,
         )
         .unwrap();
@@ -518,7 +918,7 @@ mod tests {
         let updated_signature = signature
             .update_signature(
                 sk,
-                &old_message_bytes,
+                old_message_bytes,
                 &new_message_bytes,
                 update_index,
                 messages.len(),
@@ -0,0 +1,1218 @@
+// Copyright 2023 Fondazione LINKS
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Threshold (t-of-n) issuance of BBS signatures.
+//!
+//! The secret key is split into `n` Shamir shares ([`SecretKeyShare`]) and any `t` of the parties can jointly produce a standard
+//! [`BBSplusSignature`], verifiable with [`Signature::verify`], without ever reconstructing the secret key:
+//! 1. all the `n` parties jointly generate one-time [`PresignatureShare`]s, i.e. shares of a random `r` and of a Beaver triple `(a, b, c = a * b)`,
+//!    together with the public random `e` of the signature ([`PresignatureParticipant`]). Each party deals Shamir shares of its random contributions
+//!    to `r`, `a` and `b` ([`PresignatureDealing`]), then reshares the product of its shares of `a` and `b` ([`PresignatureProduct`]), from which every
+//!    party interpolates its share of `c`. This needs `n >= 2t - 1`. Presignatures do not depend on the secret key, so they can be prepared in advance;
+//! 2. each party sends a [`Round1Message`] ([`ThresholdSigner::round1`]), opening `r - a` and `SK + e - b`;
+//! 3. each party sends a [`PartialSignature`] ([`ThresholdSigner::round2`]), i.e. its (Lagrange weighted) shares of `B * r` and of `u = r * (SK + e)`;
+//! 4. anyone combines the partial signatures ([`combine_partial_signatures`]) into `A = (B * r) * (1 / u) = B * (1 / (SK + e))`.
+//!
+//! No party, and no coalition of less than `t` parties, learns the secret key or the values of a presignature. The parties are assumed to follow
+//! the protocol: the dealings and the products are not verifiable, so a misbehaving party can make the signing fail.
+//!
+//! [`simulate_presignatures`] and [`simulate_threshold_sign`] run all the parties in-process.
+
+use super::{
+    ciphersuites::BbsCiphersuite,
+    generators::Generators,
+    keys::{BBSplusPublicKey, BBSplusSecretKey},
+    signature::{calculate_B, BBSplusSignature},
+};
+use crate::{
+    errors::Error,
+    schemes::{algorithms::BBSplus, generics::Signature},
+    utils::{message::bbsplus_message::BBSplusMessage, util::bbsplus_utils::get_random_with_rng},
+};
+use alloc::{borrow::ToOwned, collections::BTreeMap, format, string::String, vec::Vec};
+use bls12_381_plus::{G1Projective, Scalar};
+use core::{fmt, marker::PhantomData};
+use elliptic_curve::hash2curve::ExpandMsg;
+#[cfg(feature = "std")]
+use rand::thread_rng;
+use rand::{CryptoRng, RngCore};
+use serde::{Deserialize, Serialize};
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+/// Shamir share of a [`BBSplusSecretKey`] held by one of the parties.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SecretKeyShare {
+    index: usize,
+    threshold: usize,
+    participants: usize,
+    value: Scalar,
+}
+
+impl SecretKeyShare {
+    /// Index of the party holding the share, starting from 1
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Number of parties needed to sign
+    pub fn threshold(&self) -> usize {
+        self.threshold
+    }
+
+    /// Number of parties holding a share
+    pub fn participants(&self) -> usize {
+        self.participants
+    }
+
+    /// Returns the public key share `BP2 * SK_i`.
+    pub fn public_key_share(&self) -> BBSplusPublicKey {
+        BBSplusSecretKey(self.value).public_key()
+    }
+
+    pub(crate) fn new(index: usize, threshold: usize, participants: usize, value: Scalar) -> Self {
+        Self {
+            index,
+            threshold,
+            participants,
+            value,
+        }
+    }
+}
+
+impl Zeroize for SecretKeyShare {
+    fn zeroize(&mut self) {
+        self.value.zeroize();
+    }
+}
+
+impl Drop for SecretKeyShare {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for SecretKeyShare {}
+
+impl fmt::Debug for SecretKeyShare {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SecretKeyShare")
+            .field("index", &self.index)
+            .field("threshold", &self.threshold)
+            .field("participants", &self.participants)
+            .field("value", &"<redacted>")
+            .finish()
+    }
+}
+
+/// # Description
+/// Splits a secret key into `participants` Shamir shares, any `threshold` of which can sign.
+///
+/// # Inputs:
+/// * `sk` (REQUIRED), the secret key to split.
+/// * `threshold` (REQUIRED), the number of parties needed to sign, `1 <= threshold <= participants`.
+/// * `participants` (REQUIRED), the number of shares.
+///
+/// # Output:
+/// a vector of [`SecretKeyShare`], with indexes from 1 to `participants`, or [`Error`].
+///
+#[cfg(feature = "std")]
+pub fn split_secret_key(
+    sk: &BBSplusSecretKey,
+    threshold: usize,
+    participants: usize,
+) -> Result<Vec<SecretKeyShare>, Error> {
+    split_secret_key_with_rng(sk, threshold, participants, &mut thread_rng())
+}
+
+/// # Description
+/// Same as [`split_secret_key`], drawing the coefficients of the sharing polynomial from `rng`.
+///
+/// # Inputs:
+/// * `rng` (REQUIRED), a cryptographically secure random number generator.
+/// * the other inputs are the same as [`split_secret_key`].
+///
+/// # Output:
+/// a vector of [`SecretKeyShare`], with indexes from 1 to `participants`, or [`Error`].
+///
+pub fn split_secret_key_with_rng<R: RngCore + CryptoRng>(
+    sk: &BBSplusSecretKey,
+    threshold: usize,
+    participants: usize,
+    rng: &mut R,
+) -> Result<Vec<SecretKeyShare>, Error> {
+    check_threshold(threshold, participants).map_err(Error::KeyGenError)?;
+    let shares = share_secret(sk.0, threshold, participants, rng);
+    Ok(shares
+        .into_iter()
+        .enumerate()
+        .map(|(i, value)| SecretKeyShare::new(i + 1, threshold, participants, value))
+        .collect())
+}
+
+/// One-time signing material of a party: shares of a random `r` and of a Beaver triple `(a, b, c = a * b)`, and the random `e` of the signature.
+/// It MUST be used for a single signature, hence it can be neither cloned nor serialized and [`ThresholdSigner::new`] takes ownership of it.
+#[derive(PartialEq, Eq)]
+pub struct PresignatureShare {
+    index: usize,
+    threshold: usize,
+    participants: usize,
+    e: Scalar,
+    r: Scalar,
+    a: Scalar,
+    b: Scalar,
+    c: Scalar,
+}
+
+impl PresignatureShare {
+    /// Index of the party holding the share, starting from 1
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Number of parties needed to sign
+    pub fn threshold(&self) -> usize {
+        self.threshold
+    }
+
+    /// Number of parties that generated the presignature
+    pub fn participants(&self) -> usize {
+        self.participants
+    }
+}
+
+impl Zeroize for PresignatureShare {
+    fn zeroize(&mut self) {
+        self.r.zeroize();
+        self.a.zeroize();
+        self.b.zeroize();
+        self.c.zeroize();
+    }
+}
+
+impl Drop for PresignatureShare {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for PresignatureShare {}
+
+impl fmt::Debug for PresignatureShare {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PresignatureShare")
+            .field("index", &self.index)
+            .field("threshold", &self.threshold)
+            .field("participants", &self.participants)
+            .field("e", &self.e)
+            .field("r", &"<redacted>")
+            .field("a", &"<redacted>")
+            .field("b", &"<redacted>")
+            .field("c", &"<redacted>")
+            .finish()
+    }
+}
+
+/// Share of the random contributions of `sender` to `r`, `a` and `b` for `receiver`, together with its contribution to `e`. It is sent privately.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PresignatureDealing {
+    sender: usize,
+    receiver: usize,
+    e: Scalar,
+    r: Scalar,
+    a: Scalar,
+    b: Scalar,
+}
+
+impl PresignatureDealing {
+    pub fn sender(&self) -> usize {
+        self.sender
+    }
+
+    pub fn receiver(&self) -> usize {
+        self.receiver
+    }
+}
+
+impl Zeroize for PresignatureDealing {
+    fn zeroize(&mut self) {
+        self.r.zeroize();
+        self.a.zeroize();
+        self.b.zeroize();
+    }
+}
+
+impl Drop for PresignatureDealing {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for PresignatureDealing {}
+
+impl fmt::Debug for PresignatureDealing {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PresignatureDealing")
+            .field("sender", &self.sender)
+            .field("receiver", &self.receiver)
+            .field("e", &self.e)
+            .field("r", &"<redacted>")
+            .field("a", &"<redacted>")
+            .field("b", &"<redacted>")
+            .finish()
+    }
+}
+
+/// Share for `receiver` of the product of the shares of `a` and `b` held by `sender`. It is sent privately.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PresignatureProduct {
+    sender: usize,
+    receiver: usize,
+    value: Scalar,
+}
+
+impl PresignatureProduct {
+    pub fn sender(&self) -> usize {
+        self.sender
+    }
+
+    pub fn receiver(&self) -> usize {
+        self.receiver
+    }
+}
+
+impl Zeroize for PresignatureProduct {
+    fn zeroize(&mut self) {
+        self.value.zeroize();
+    }
+}
+
+impl Drop for PresignatureProduct {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for PresignatureProduct {}
+
+impl fmt::Debug for PresignatureProduct {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PresignatureProduct")
+            .field("sender", &self.sender)
+            .field("receiver", &self.receiver)
+            .field("value", &"<redacted>")
+            .finish()
+    }
+}
+
+/// State of a party during the generation of one presignature
+pub struct PresignatureParticipant {
+    index: usize,
+    threshold: usize,
+    participants: usize,
+    dealings: BTreeMap<usize, PresignatureDealing>,
+    products: BTreeMap<usize, PresignatureProduct>,
+}
+
+impl PresignatureParticipant {
+    /// # Description
+    /// Starts the generation of a presignature for the party `index`: picks its random contributions to `r`, `a`, `b` and `e`, and shares them
+    /// with polynomials of degree `threshold - 1`.
+    ///
+    /// # Inputs:
+    /// * `index` (REQUIRED), the index of the party, from 1 to `participants`.
+    /// * `threshold` (REQUIRED), the number of parties needed to sign, `1 <= threshold` and `2 * threshold - 1 <= participants`.
+    /// * `participants` (REQUIRED), the number of parties.
+    ///
+    /// # Output:
+    /// the state of the party and the [`PresignatureDealing`]s to send privately to each of the other parties, or [`Error`].
+    ///
+    #[cfg(feature = "std")]
+    pub fn new(
+        index: usize,
+        threshold: usize,
+        participants: usize,
+    ) -> Result<(Self, Vec<PresignatureDealing>), Error> {
+        Self::new_with_rng(index, threshold, participants, &mut thread_rng())
+    }
+
+    /// # Description
+    /// Same as [`Self::new`], drawing the random values from `rng`.
+    ///
+    /// # Inputs:
+    /// * `rng` (REQUIRED), a cryptographically secure random number generator.
+    /// * the other inputs are the same as [`Self::new`].
+    ///
+    /// # Output:
+    /// the state of the party and the [`PresignatureDealing`]s to send privately to each of the other parties, or [`Error`].
+    ///
+    pub fn new_with_rng<R: RngCore + CryptoRng>(
+        index: usize,
+        threshold: usize,
+        participants: usize,
+        rng: &mut R,
+    ) -> Result<(Self, Vec<PresignatureDealing>), Error> {
+        check_presignature_threshold(threshold, participants)?;
+        if index == 0 || index > participants {
+            return Err(Error::SignatureGenerationError(format!(
+                "Invalid party index {}",
+                index
+            )));
+        }
+
+        let e = get_random_with_rng(rng);
+        let mut r = get_random_with_rng(rng);
+        let mut a = get_random_with_rng(rng);
+        let mut b = get_random_with_rng(rng);
+        let r_shares = share_secret(r, threshold, participants, rng);
+        let a_shares = share_secret(a, threshold, participants, rng);
+        let b_shares = share_secret(b, threshold, participants, rng);
+        r.zeroize();
+        a.zeroize();
+        b.zeroize();
+
+        let mut dealings: Vec<PresignatureDealing> = (0..participants)
+            .map(|i| PresignatureDealing {
+                sender: index,
+                receiver: i + 1,
+                e,
+                r: r_shares[i],
+                a: a_shares[i],
+                b: b_shares[i],
+            })
+            .collect();
+        let own_dealing = dealings.remove(index - 1);
+
+        let mut participant = Self {
+            index,
+            threshold,
+            participants,
+            dealings: BTreeMap::new(),
+            products: BTreeMap::new(),
+        };
+        participant.dealings.insert(index, own_dealing);
+
+        Ok((participant, dealings))
+    }
+
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// # Description
+    /// Stores the [`PresignatureDealing`] sent by another party.
+    ///
+    /// # Inputs:
+    /// * `dealing` (REQUIRED), a [`PresignatureDealing`] addressed to this party.
+    ///
+    /// # Output:
+    /// a result: [`Ok`] or [`Error`].
+    ///
+    pub fn receive_dealing(&mut self, dealing: PresignatureDealing) -> Result<(), Error> {
+        self.check_message(dealing.sender, dealing.receiver)?;
+        if self.dealings.contains_key(&dealing.sender) {
+            return Err(Error::SignatureGenerationError(format!(
+                "Duplicate dealing of party {}",
+                dealing.sender
+            )));
+        }
+        self.dealings.insert(dealing.sender, dealing);
+        Ok(())
+    }
+
+    /// # Description
+    /// Once the dealings of all the parties have been received, shares the product of the shares of `a` and `b` of this party, a point of
+    /// a polynomial of degree `2 * threshold - 2` whose value at 0 is `c = a * b`, with a polynomial of degree `threshold - 1`.
+    ///
+    /// # Output:
+    /// the [`PresignatureProduct`]s to send privately to each of the other parties, or [`Error`].
+    ///
+    #[cfg(feature = "std")]
+    pub fn share_product(&mut self) -> Result<Vec<PresignatureProduct>, Error> {
+        self.share_product_with_rng(&mut thread_rng())
+    }
+
+    /// # Description
+    /// Same as [`Self::share_product`], drawing the coefficients of the sharing polynomial from `rng`.
+    ///
+    /// # Inputs:
+    /// * `rng` (REQUIRED), a cryptographically secure random number generator.
+    ///
+    /// # Output:
+    /// the [`PresignatureProduct`]s to send privately to each of the other parties, or [`Error`].
+    ///
+    pub fn share_product_with_rng<R: RngCore + CryptoRng>(
+        &mut self,
+        rng: &mut R,
+    ) -> Result<Vec<PresignatureProduct>, Error> {
+        if self.products.contains_key(&self.index) {
+            return Err(Error::SignatureGenerationError(
+                "Product already shared".to_owned(),
+            ));
+        }
+        let (_, _, a, b) = self.sum_dealings()?;
+        let mut product = a * b;
+        let product_shares = share_secret(product, self.threshold, self.participants, rng);
+        product.zeroize();
+
+        let mut products: Vec<PresignatureProduct> = product_shares
+            .into_iter()
+            .enumerate()
+            .map(|(i, value)| PresignatureProduct {
+                sender: self.index,
+                receiver: i + 1,
+                value,
+            })
+            .collect();
+        self.products
+            .insert(self.index, products.remove(self.index - 1));
+        Ok(products)
+    }
+
+    /// # Description
+    /// Stores the [`PresignatureProduct`] sent by another party.
+    ///
+    /// # Inputs:
+    /// * `product` (REQUIRED), a [`PresignatureProduct`] addressed to this party.
+    ///
+    /// # Output:
+    /// a result: [`Ok`] or [`Error`].
+    ///
+    pub fn receive_product(&mut self, product: PresignatureProduct) -> Result<(), Error> {
+        self.check_message(product.sender, product.receiver)?;
+        if self.products.contains_key(&product.sender) {
+            return Err(Error::SignatureGenerationError(format!(
+                "Duplicate product of party {}",
+                product.sender
+            )));
+        }
+        self.products.insert(product.sender, product);
+        Ok(())
+    }
+
+    /// # Description
+    /// Completes the generation, once the products of all the parties have been received: the share of `c` is the Lagrange interpolation
+    /// at 0 of the received products.
+    ///
+    /// # Output:
+    /// the [`PresignatureShare`] of the party or [`Error`].
+    ///
+    pub fn finalize(self) -> Result<PresignatureShare, Error> {
+        let (e, r, a, b) = self.sum_dealings()?;
+        let senders: Vec<usize> = (1..=self.participants).collect();
+        let mut c = Scalar::ZERO;
+        for &sender in &senders {
+            let product = self.products.get(&sender).ok_or_else(|| {
+                Error::SignatureGenerationError(format!("Missing product of party {}", sender))
+            })?;
+            c += lagrange_coefficient(sender, &senders)? * product.value;
+        }
+
+        Ok(PresignatureShare {
+            index: self.index,
+            threshold: self.threshold,
+            participants: self.participants,
+            e,
+            r,
+            a,
+            b,
+            c,
+        })
+    }
+
+    fn check_message(&self, sender: usize, receiver: usize) -> Result<(), Error> {
+        if receiver != self.index {
+            return Err(Error::SignatureGenerationError(format!(
+                "Message addressed to party {}",
+                receiver
+            )));
+        }
+        if sender == 0 || sender > self.participants {
+            return Err(Error::SignatureGenerationError(format!(
+                "Invalid party index {}",
+                sender
+            )));
+        }
+        Ok(())
+    }
+
+    /// Returns `e` and the shares of `r`, `a` and `b`, i.e. the sums of the dealings of all the parties
+    fn sum_dealings(&self) -> Result<(Scalar, Scalar, Scalar, Scalar), Error> {
+        if self.dealings.len() != self.participants {
+            return Err(Error::SignatureGenerationError(format!(
+                "{} dealings received, {} needed",
+                self.dealings.len(),
+                self.participants
+            )));
+        }
+        Ok(self.dealings.values().fold(
+            (Scalar::ZERO, Scalar::ZERO, Scalar::ZERO, Scalar::ZERO),
+            |(e, r, a, b), d| (e + d.e, r + d.r, a + d.a, b + d.b),
+        ))
+    }
+}
+
+impl fmt::Debug for PresignatureParticipant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PresignatureParticipant")
+            .field("index", &self.index)
+            .field("threshold", &self.threshold)
+            .field("participants", &self.participants)
+            .field("dealings", &self.dealings)
+            .field("products", &self.products)
+            .finish()
+    }
+}
+
+/// # Description
+/// Runs the generation of one presignature in-process for all the parties, delivering the messages through in-memory queues.
+///
+/// # Inputs:
+/// * `threshold` (REQUIRED), the number of parties needed to sign, `1 <= threshold` and `2 * threshold - 1 <= participants`.
+/// * `participants` (REQUIRED), the number of parties.
+///
+/// # Output:
+/// the [`PresignatureShare`]s of the parties, ordered by index, or [`Error`].
+///
+#[cfg(feature = "std")]
+pub fn simulate_presignatures(
+    threshold: usize,
+    participants: usize,
+) -> Result<Vec<PresignatureShare>, Error> {
+    simulate_presignatures_with_rng(threshold, participants, &mut thread_rng())
+}
+
+/// # Description
+/// Same as [`simulate_presignatures`], drawing the random values from `rng`.
+///
+/// # Inputs:
+/// * `rng` (REQUIRED), a cryptographically secure random number generator.
+/// * the other inputs are the same as [`simulate_presignatures`].
+///
+/// # Output:
+/// the [`PresignatureShare`]s of the parties, ordered by index, or [`Error`].
+///
+pub fn simulate_presignatures_with_rng<R: RngCore + CryptoRng>(
+    threshold: usize,
+    participants: usize,
+    rng: &mut R,
+) -> Result<Vec<PresignatureShare>, Error> {
+    let mut parties = Vec::with_capacity(participants);
+    let mut dealings = Vec::new();
+    for index in 1..=participants {
+        let (party, party_dealings) =
+            PresignatureParticipant::new_with_rng(index, threshold, participants, rng)?;
+        parties.push(party);
+        dealings.extend(party_dealings);
+    }
+    for dealing in dealings {
+        parties[dealing.receiver - 1].receive_dealing(dealing)?;
+    }
+
+    let mut products = Vec::new();
+    for party in parties.iter_mut() {
+        products.extend(party.share_product_with_rng(rng)?);
+    }
+    for product in products {
+        parties[product.receiver - 1].receive_product(product)?;
+    }
+
+    parties
+        .into_iter()
+        .map(PresignatureParticipant::finalize)
+        .collect()
+}
+
+/// First message of a party: the shares of `r - a` and `SK + e - b`
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct Round1Message {
+    index: usize,
+    d: Scalar,
+    epsilon: Scalar,
+}
+
+impl Round1Message {
+    pub fn index(&self) -> usize {
+        self.index
+    }
+}
+
+/// Second message of a party: its shares of `B * r` and `u = r * (SK + e)`, already multiplied by its Lagrange coefficient
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct PartialSignature {
+    index: usize,
+    R: G1Projective,
+    u: Scalar,
+    e: Scalar,
+}
+
+impl PartialSignature {
+    pub fn index(&self) -> usize {
+        self.index
+    }
+}
+
+/// A party taking part in a threshold signature
+pub struct ThresholdSigner<CS: BbsCiphersuite> {
+    key_share: SecretKeyShare,
+    presignature: PresignatureShare,
+    _ciphersuite: PhantomData<CS>,
+}
+
+impl<CS: BbsCiphersuite> ThresholdSigner<CS> {
+    /// # Description
+    /// Creates the state of a party for one signature.
+    ///
+    /// # Inputs:
+    /// * `key_share` (REQUIRED), the [`SecretKeyShare`] of the party.
+    /// * `presignature` (REQUIRED), an unused [`PresignatureShare`] of the party, generated with the same threshold and participants as the key share.
+    ///
+    /// # Output:
+    /// a [`ThresholdSigner`] or [`Error`].
+    ///
+    pub fn new(key_share: SecretKeyShare, presignature: PresignatureShare) -> Result<Self, Error> {
+        if key_share.index != presignature.index {
+            return Err(Error::SignatureGenerationError(
+                "Key share and presignature belong to different parties".to_owned(),
+            ));
+        }
+        if key_share.threshold != presignature.threshold
+            || key_share.participants != presignature.participants
+        {
+            return Err(Error::SignatureGenerationError(format!(
+                "Presignature generated by {}-of-{} parties, key shared among {}-of-{}",
+                presignature.threshold,
+                presignature.participants,
+                key_share.threshold,
+                key_share.participants
+            )));
+        }
+        Ok(Self {
+            key_share,
+            presignature,
+            _ciphersuite: PhantomData,
+        })
+    }
+
+    pub fn index(&self) -> usize {
+        self.key_share.index
+    }
+
+    /// # Description
+    /// First round: masks the shares of `r` and `SK + e` with the Beaver triple.
+    ///
+    /// # Output:
+    /// the [`Round1Message`] to send to the other parties of the signing set.
+    ///
+    pub fn round1(&self) -> Round1Message {
+        let presignature = &self.presignature;
+        Round1Message {
+            index: self.index(),
+            d: presignature.r - presignature.a,
+            epsilon: self.key_share.value + presignature.e - presignature.b,
+        }
+    }
+
+    /// # Description
+    /// Second round: computes the partial signature on the messages, once the [`Round1Message`]s of all the parties of the signing set (including this one) have been received.
+    /// The state is consumed, so that the presignature is never used twice.
+    ///
+    /// # Inputs:
+    /// * `pk` (REQUIRED), the public key.
+    /// * `header` (OPTIONAL), an octet string containing context and application specific information.
+    /// * `messages` (OPTIONAL), a vector of octet strings.
+    /// * `round1` (REQUIRED), the [`Round1Message`]s of the signing set.
+    ///
+    /// # Output:
+    /// the [`PartialSignature`] of the party or [`Error`].
+    ///
+    pub fn round2(
+        self,
+        pk: &BBSplusPublicKey,
+        header: Option<&[u8]>,
+        messages: Option<&[Vec<u8>]>,
+        round1: &[Round1Message],
+    ) -> Result<PartialSignature, Error>
+    where
+        CS::Expander: for<'a> ExpandMsg<'a>,
+    {
+        let indexes: Vec<usize> = round1.iter().map(|m| m.index).collect();
+        if !indexes.contains(&self.index()) {
+            return Err(Error::SignatureGenerationError(
+                "Party is not in the signing set".to_owned(),
+            ));
+        }
+        if indexes.len() < self.key_share.threshold {
+            return Err(Error::SignatureGenerationError(format!(
+                "{} parties needed to sign, got {}",
+                self.key_share.threshold,
+                indexes.len()
+            )));
+        }
+
+        let mut d = Scalar::ZERO;
+        let mut epsilon = Scalar::ZERO;
+        for m in round1 {
+            let lambda = lagrange_coefficient(m.index, &indexes)?;
+            d += lambda * m.d;
+            epsilon += lambda * m.epsilon;
+        }
+
+        let messages = messages.unwrap_or(&[]);
+        let message_scalars = BBSplusMessage::messages_to_scalar::<CS>(messages, CS::API_ID)?;
+        let generators = Generators::create::<CS>(messages.len() + 1, Some(CS::API_ID));
+        let B = calculate_B::<CS>(pk, &message_scalars, &generators, header, Some(CS::API_ID))?;
+
+        // u_i = c_i + d * b_i + epsilon * a_i + d * epsilon, a share of r * (SK + e)
+        let presignature = &self.presignature;
+        let u = presignature.c + d * presignature.b + epsilon * presignature.a + d * epsilon;
+        let lambda = lagrange_coefficient(self.index(), &indexes)?;
+
+        Ok(PartialSignature {
+            index: self.index(),
+            R: B * (lambda * presignature.r),
+            u: lambda * u,
+            e: presignature.e,
+        })
+    }
+}
+
+impl<CS: BbsCiphersuite> fmt::Debug for ThresholdSigner<CS> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ThresholdSigner")
+            .field("key_share", &self.key_share)
+            .field("presignature", &self.presignature)
+            .finish()
+    }
+}
+
+/// # Description
+/// Combines the partial signatures of a signing set into a [`BBSplusSignature`], which is verified before being returned.
+///
+/// # Inputs:
+/// * `pk` (REQUIRED), the public key.
+/// * `header` (OPTIONAL), an octet string containing context and application specific information.
+/// * `messages` (OPTIONAL), a vector of octet strings.
+/// * `partial_signatures` (REQUIRED), the [`PartialSignature`]s of all the parties of the signing set.
+///
+/// # Output:
+/// a [`Signature::BBSplus`] or [`Error`].
+///
+pub fn combine_partial_signatures<CS: BbsCiphersuite>(
+    pk: &BBSplusPublicKey,
+    header: Option<&[u8]>,
+    messages: Option<&[Vec<u8>]>,
+    partial_signatures: &[PartialSignature],
+) -> Result<Signature<BBSplus<CS>>, Error>
+where
+    CS::Expander: for<'a> ExpandMsg<'a>,
+{
+    let e = partial_signatures
+        .first()
+        .ok_or_else(|| Error::SignatureGenerationError("No partial signatures".to_owned()))?
+        .e;
+    if partial_signatures.iter().any(|p| p.e != e) {
+        return Err(Error::SignatureGenerationError(
+            "Partial signatures use different presignatures".to_owned(),
+        ));
+    }
+
+    let R: G1Projective = partial_signatures.iter().map(|p| p.R).sum();
+    let u: Scalar = partial_signatures.iter().map(|p| p.u).sum();
+    let u_inv = Option::<Scalar>::from(u.invert())
+        .ok_or_else(|| Error::SignatureGenerationError("Invert scalar failed".to_owned()))?;
+
+    // A = B * r * (1 / (r * (SK + e)))
+    let A = R * u_inv;
+    if A == G1Projective::IDENTITY {
+        return Err(Error::SignatureGenerationError(
+            "A == Identity_G1".to_owned(),
+        ));
+    }
+
+    let signature = Signature::<BBSplus<CS>>::BBSplus(BBSplusSignature { A, e });
+    signature
+        .verify(pk, messages, header)
+        .map_err(|_| Error::SignatureGenerationError("Invalid partial signatures".to_owned()))?;
+
+    Ok(signature)
+}
+
+/// # Description
+/// Runs the threshold signing protocol in-process for the parties holding `key_shares`, e.g. to test a deployment.
+///
+/// # Inputs:
+/// * `pk` (REQUIRED), the public key.
+/// * `key_shares` (REQUIRED), the [`SecretKeyShare`]s of the signing set.
+/// * `presignatures` (REQUIRED), the [`PresignatureShare`]s of one signature, for (at least) the same parties. They are consumed.
+/// * `header` (OPTIONAL), an octet string containing context and application specific information.
+/// * `messages` (OPTIONAL), a vector of octet strings.
+///
+/// # Output:
+/// a [`Signature::BBSplus`] or [`Error`].
+///
+pub fn simulate_threshold_sign<CS: BbsCiphersuite>(
+    pk: &BBSplusPublicKey,
+    key_shares: &[SecretKeyShare],
+    presignatures: Vec<PresignatureShare>,
+    header: Option<&[u8]>,
+    messages: Option<&[Vec<u8>]>,
+) -> Result<Signature<BBSplus<CS>>, Error>
+where
+    CS::Expander: for<'a> ExpandMsg<'a>,
+{
+    let mut presignatures: BTreeMap<usize, PresignatureShare> =
+        presignatures.into_iter().map(|p| (p.index, p)).collect();
+    let signers = key_shares
+        .iter()
+        .map(|key_share| {
+            let presignature = presignatures.remove(&key_share.index).ok_or_else(|| {
+                Error::SignatureGenerationError(format!(
+                    "Missing presignature of party {}",
+                    key_share.index
+                ))
+            })?;
+            ThresholdSigner::<CS>::new(key_share.clone(), presignature)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let round1: Vec<Round1Message> = signers.iter().map(|s| s.round1()).collect();
+
+    let partial_signatures = signers
+        .into_iter()
+        .map(|s| s.round2(pk, header, messages, &round1))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    combine_partial_signatures::<CS>(pk, header, messages, &partial_signatures)
+}
+
+pub(super) fn check_threshold(threshold: usize, participants: usize) -> Result<(), String> {
+    if threshold == 0 || threshold > participants {
+        return Err(format!(
+            "Invalid threshold {} for {} participants",
+            threshold, participants
+        ));
+    }
+    Ok(())
+}
+
+/// The products of the shares of `a` and `b` lie on a polynomial of degree `2 * threshold - 2`, interpolated from `2 * threshold - 1` points
+fn check_presignature_threshold(threshold: usize, participants: usize) -> Result<(), Error> {
+    check_threshold(threshold, participants).map_err(Error::SignatureGenerationError)?;
+    if 2 * threshold - 1 > participants {
+        return Err(Error::SignatureGenerationError(format!(
+            "Presignatures with threshold {} need at least {} participants, got {}",
+            threshold,
+            2 * threshold - 1,
+            participants
+        )));
+    }
+    Ok(())
+}
+
+/// Shares `secret` with a random polynomial `f` of degree `threshold - 1` such that `f(0) = secret`, i.e. returns `f(1), ..., f(participants)`
+fn share_secret<R: RngCore + CryptoRng>(
+    secret: Scalar,
+    threshold: usize,
+    participants: usize,
+    rng: &mut R,
+) -> Vec<Scalar> {
+    let mut coefficients: Vec<Scalar> = core::iter::once(secret)
+        .chain((1..threshold).map(|_| get_random_with_rng(rng)))
+        .collect();
+    let shares = (1..=participants)
+        .map(|i| evaluate_polynomial(&coefficients, Scalar::from(i as u64)))
+        .collect();
+    coefficients.zeroize();
+    shares
+}
+
+/// Evaluates `coefficients[0] + coefficients[1] * x + ... ` with the Horner method
+pub(crate) fn evaluate_polynomial(coefficients: &[Scalar], x: Scalar) -> Scalar {
+    coefficients
+        .iter()
+        .rev()
+        .fold(Scalar::ZERO, |acc, c| acc * x + c)
+}
+
+/// Lagrange coefficient of `index` for the interpolation at 0 over `indexes`
+pub(crate) fn lagrange_coefficient(index: usize, indexes: &[usize]) -> Result<Scalar, Error> {
+    let mut numerator = Scalar::ONE;
+    let mut denominator = Scalar::ONE;
+    for (position, &j) in indexes.iter().enumerate() {
+        if j == 0 || indexes[..position].contains(&j) {
+            return Err(Error::SignatureGenerationError(format!(
+                "Invalid party index {}",
+                j
+            )));
+        }
+        if j == index {
+            continue;
+        }
+        let j = Scalar::from(j as u64);
+        numerator *= j;
+        denominator *= j - Scalar::from(index as u64);
+    }
+    Option::<Scalar>::from(denominator.invert())
+        .map(|inv| numerator * inv)
+        .ok_or_else(|| Error::SignatureGenerationError("Invert scalar failed".to_owned()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        combine_partial_signatures, lagrange_coefficient, simulate_presignatures_with_rng,
+        simulate_threshold_sign, split_secret_key_with_rng, PresignatureParticipant,
+        PresignatureShare, ThresholdSigner,
+    };
+    use crate::{
+        bbsplus::ciphersuites::{BbsCiphersuite, Bls12381Sha256, Bls12381Shake256},
+        errors::Error,
+        keys::pair::KeyPair,
+        schemes::algorithms::BBSplus,
+    };
+    use bls12_381_plus::Scalar;
+    use elliptic_curve::hash2curve::ExpandMsg;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    fn threshold_sign<CS: BbsCiphersuite>(seed: u64)
+    where
+        CS::Expander: for<'a> ExpandMsg<'a>,
+    {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let keypair = KeyPair::<BBSplus<CS>>::random_with_rng(&mut rng).unwrap();
+        let pk = keypair.public_key();
+        let header = b"threshold header";
+        let messages = vec![b"first".to_vec(), b"second".to_vec(), b"third".to_vec()];
+
+        let key_shares = split_secret_key_with_rng(keypair.private_key(), 3, 5, &mut rng).unwrap();
+
+        // any 3 of the 5 parties can sign
+        for signing_set in [[0, 1, 2], [4, 2, 0], [1, 3, 4]] {
+            let presignatures = simulate_presignatures_with_rng(3, 5, &mut rng).unwrap();
+            let shares: Vec<_> = signing_set.iter().map(|&i| key_shares[i].clone()).collect();
+            let signature = simulate_threshold_sign::<CS>(
+                pk,
+                &shares,
+                presignatures,
+                Some(header),
+                Some(&messages),
+            )
+            .unwrap();
+            assert!(signature.verify(pk, Some(&messages), Some(header)).is_ok());
+        }
+
+        // all of them as well
+        let presignatures = simulate_presignatures_with_rng(3, 5, &mut rng).unwrap();
+        let signature =
+            simulate_threshold_sign::<CS>(pk, &key_shares, presignatures, None, Some(&messages))
+                .unwrap();
+        assert!(signature.verify(pk, Some(&messages), None).is_ok());
+
+        // but not 2 of them
+        let presignatures = simulate_presignatures_with_rng(3, 5, &mut rng).unwrap();
+        assert!(simulate_threshold_sign::<CS>(
+            pk,
+            &key_shares[..2],
+            presignatures,
+            None,
+            Some(&messages)
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn threshold_sign_sha256() {
+        threshold_sign::<Bls12381Sha256>(1);
+    }
+
+    #[test]
+    fn threshold_sign_shake256() {
+        threshold_sign::<Bls12381Shake256>(2);
+    }
+
+    #[test]
+    fn threshold_sign_rounds() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let keypair = KeyPair::<BBSplus<Bls12381Sha256>>::random_with_rng(&mut rng).unwrap();
+        let pk = keypair.public_key();
+        let messages = vec![b"message".to_vec()];
+
+        let key_shares = split_secret_key_with_rng(keypair.private_key(), 2, 3, &mut rng).unwrap();
+        let mut presignatures = simulate_presignatures_with_rng(2, 3, &mut rng).unwrap();
+
+        // the presignature must be of the same party
+        assert!(matches!(
+            ThresholdSigner::<Bls12381Sha256>::new(
+                key_shares[0].clone(),
+                presignatures.pop().unwrap()
+            ),
+            Err(Error::SignatureGenerationError(_))
+        ));
+        // and generated with the same threshold and participants
+        for (threshold, participants) in [(3, 5), (2, 4)] {
+            let presignature = simulate_presignatures_with_rng(threshold, participants, &mut rng)
+                .unwrap()
+                .swap_remove(0);
+            assert!(matches!(
+                ThresholdSigner::<Bls12381Sha256>::new(key_shares[0].clone(), presignature),
+                Err(Error::SignatureGenerationError(_))
+            ));
+        }
+
+        let presignatures = simulate_presignatures_with_rng(2, 3, &mut rng).unwrap();
+        let signers: Vec<_> = presignatures
+            .into_iter()
+            .filter(|p| p.index() != 2)
+            .map(|p| {
+                ThresholdSigner::<Bls12381Sha256>::new(key_shares[p.index() - 1].clone(), p)
+                    .unwrap()
+            })
+            .collect();
+        let round1: Vec<_> = signers.iter().map(|s| s.round1()).collect();
+        let partial_signatures: Vec<_> = signers
+            .into_iter()
+            .map(|s| s.round2(pk, None, Some(&messages), &round1).unwrap())
+            .collect();
+
+        let signature = combine_partial_signatures::<Bls12381Sha256>(
+            pk,
+            None,
+            Some(&messages),
+            &partial_signatures,
+        )
+        .unwrap();
+        assert!(signature.verify(pk, Some(&messages), None).is_ok());
+
+        // the partial signatures are bound to the messages
+        let other_messages = vec![b"other message".to_vec()];
+        assert!(combine_partial_signatures::<Bls12381Sha256>(
+            pk,
+            None,
+            Some(&other_messages),
+            &partial_signatures
+        )
+        .is_err());
+        assert!(combine_partial_signatures::<Bls12381Sha256>(
+            pk,
+            None,
+            Some(&messages),
+            &partial_signatures[..1]
+        )
+        .is_err());
+
+        assert!(split_secret_key_with_rng(keypair.private_key(), 4, 3, &mut rng).is_err());
+        assert!(split_secret_key_with_rng(keypair.private_key(), 0, 3, &mut rng).is_err());
+    }
+
+    /// Interpolates at 0 the values of the presignatures of `indexes`
+    fn reconstruct(
+        presignatures: &[PresignatureShare],
+        indexes: &[usize],
+        value: fn(&PresignatureShare) -> Scalar,
+    ) -> Scalar {
+        indexes
+            .iter()
+            .map(|&i| lagrange_coefficient(i, indexes).unwrap() * value(&presignatures[i - 1]))
+            .sum()
+    }
+
+    #[test]
+    fn distributed_presignatures() {
+        let mut rng = StdRng::seed_from_u64(4);
+        let presignatures = simulate_presignatures_with_rng(3, 5, &mut rng).unwrap();
+
+        let e = presignatures[0].e;
+        assert!(presignatures.iter().all(|p| p.e == e));
+        assert_eq!(
+            presignatures.iter().map(|p| p.index()).collect::<Vec<_>>(),
+            [1, 2, 3, 4, 5]
+        );
+
+        // any 3 parties agree on r, a, b and c = a * b
+        let r = reconstruct(&presignatures, &[1, 2, 3], |p| p.r);
+        let a = reconstruct(&presignatures, &[1, 2, 3], |p| p.a);
+        let b = reconstruct(&presignatures, &[1, 2, 3], |p| p.b);
+        for indexes in [[1, 2, 3], [2, 4, 5], [5, 1, 3]] {
+            assert_eq!(reconstruct(&presignatures, &indexes, |p| p.r), r);
+            assert_eq!(reconstruct(&presignatures, &indexes, |p| p.a), a);
+            assert_eq!(reconstruct(&presignatures, &indexes, |p| p.b), b);
+            assert_eq!(reconstruct(&presignatures, &indexes, |p| p.c), a * b);
+        }
+    }
+
+    #[test]
+    fn distributed_presignatures_errors() {
+        let mut rng = StdRng::seed_from_u64(5);
+
+        // the products need 2 * threshold - 1 parties
+        assert!(matches!(
+            simulate_presignatures_with_rng(3, 4, &mut rng),
+            Err(Error::SignatureGenerationError(_))
+        ));
+        assert!(PresignatureParticipant::new_with_rng(1, 2, 2, &mut rng).is_err());
+        assert!(PresignatureParticipant::new_with_rng(0, 2, 3, &mut rng).is_err());
+        assert!(PresignatureParticipant::new_with_rng(4, 2, 3, &mut rng).is_err());
+
+        let mut parties = vec![];
+        let mut dealings = vec![];
+        for i in 1..=3 {
+            let (party, party_dealings) =
+                PresignatureParticipant::new_with_rng(i, 2, 3, &mut rng).unwrap();
+            parties.push(party);
+            dealings.extend(party_dealings);
+        }
+
+        // the product is shared once all the dealings have been received
+        assert!(parties[0].share_product_with_rng(&mut rng).is_err());
+        let misdelivered = dealings.iter().find(|d| d.receiver() == 2).unwrap();
+        assert!(parties[0].receive_dealing(misdelivered.clone()).is_err());
+        for dealing in dealings {
+            let receiver = dealing.receiver();
+            parties[receiver - 1].receive_dealing(dealing).unwrap();
+        }
+        let duplicate = parties[1].dealings[&1].clone();
+        assert!(parties[1].receive_dealing(duplicate).is_err());
+
+        let mut products = vec![];
+        for party in parties.iter_mut() {
+            products.extend(party.share_product_with_rng(&mut rng).unwrap());
+        }
+        assert!(parties[0].share_product_with_rng(&mut rng).is_err());
+
+        // party 1 misses the product of party 3
+        for product in products {
+            let (sender, receiver) = (product.sender(), product.receiver());
+            if (sender, receiver) != (3, 1) {
+                parties[receiver - 1].receive_product(product).unwrap();
+            }
+        }
+        let mut parties = parties.into_iter();
+        assert!(matches!(
+            parties.next().unwrap().finalize(),
+            Err(Error::SignatureGenerationError(_))
+        ));
+        assert!(parties.all(|party| party.finalize().is_ok()));
+    }
+
+    #[test]
+    fn lagrange_interpolation() {
+        // f(x) = 5 + 3x: f(1) = 8, f(3) = 14
+        let indexes = [1, 3];
+        let secret = lagrange_coefficient(1, &indexes).unwrap() * Scalar::from(8u64)
+            + lagrange_coefficient(3, &indexes).unwrap() * Scalar::from(14u64);
+        assert_eq!(secret, Scalar::from(5u64));
+        assert!(lagrange_coefficient(1, &[1, 1]).is_err());
+        assert!(lagrange_coefficient(1, &[0, 1]).is_err());
+    }
+}
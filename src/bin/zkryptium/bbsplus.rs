@@ -0,0 +1,307 @@
+// Copyright 2023 Fondazione LINKS
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    io::{
+        decode_hex_arg, read_array, read_artifact, read_optional_messages, write_artifact,
+        write_secret_artifact,
+    },
+    CliResult, Command, Format,
+};
+use elliptic_curve::hash2curve::ExpandMsg;
+use zkryptium::{
+    bbsplus::{
+        ciphersuites::BbsCiphersuite,
+        commitment::BlindFactor,
+        keys::{BBSplusPublicKey, BBSplusSecretKey},
+    },
+    keys::pair::KeyPair,
+    schemes::{
+        algorithms::BBSplus,
+        generics::{BlindSignature, Commitment, PoKSignature, Signature},
+    },
+};
+
+fn read_pk(path: &std::path::Path) -> CliResult<BBSplusPublicKey> {
+    Ok(BBSplusPublicKey::from_bytes(&read_artifact(path, "pk")?)?)
+}
+
+fn read_sk(path: &std::path::Path) -> CliResult<BBSplusSecretKey> {
+    Ok(BBSplusSecretKey::from_bytes(&read_artifact(path, "sk")?)?)
+}
+
+fn read_prover_blind(path: Option<&std::path::Path>) -> CliResult<Option<BlindFactor>> {
+    path.map(|path| Ok(BlindFactor::from_bytes(&read_array(path, "prover_blind")?)?))
+        .transpose()
+}
+
+/// Options that only make sense for CL03 ciphersuites
+fn reject_cl03_options(options: &[(&str, bool)]) -> CliResult<()> {
+    match options.iter().find(|(_, supplied)| *supplied) {
+        Some((name, _)) => Err(format!("--{} is not supported by BBS+ ciphersuites", name).into()),
+        None => Ok(()),
+    }
+}
+
+pub(crate) fn run<CS: BbsCiphersuite>(command: Command, format: Format) -> CliResult<()>
+where
+    CS::Expander: for<'a> ExpandMsg<'a>,
+{
+    match command {
+        Command::Keygen {
+            sk,
+            pk,
+            key_material,
+            key_info,
+            bases,
+            messages_count,
+        } => {
+            reject_cl03_options(&[
+                ("bases", bases.is_some()),
+                ("messages-count", messages_count.is_some()),
+            ])?;
+            let key_info = decode_hex_arg(key_info.as_deref())?;
+            let keypair = match decode_hex_arg(key_material.as_deref())? {
+                Some(key_material) => {
+                    KeyPair::<BBSplus<CS>>::generate(&key_material, key_info.as_deref(), None)?
+                }
+                None if key_info.is_none() => KeyPair::<BBSplus<CS>>::random()?,
+                None => return Err("--key-info requires --key-material".into()),
+            };
+            write_secret_artifact(&sk, &keypair.private_key().to_bytes(), "sk", format)?;
+            write_artifact(Some(&pk), &keypair.public_key().to_bytes(), "pk", format)
+        }
+        Command::Sign {
+            sk,
+            pk,
+            messages,
+            header,
+            bases,
+            out,
+        } => {
+            reject_cl03_options(&[("bases", bases.is_some())])?;
+            let messages = read_optional_messages(Some(&messages))?;
+            let header = decode_hex_arg(header.as_deref())?;
+            let signature = Signature::<BBSplus<CS>>::sign(
+                messages.as_deref(),
+                &read_sk(&sk)?,
+                &read_pk(&pk)?,
+                header.as_deref(),
+            )?;
+            write_artifact(out.as_deref(), &signature.to_bytes(), "signature", format)
+        }
+        Command::Verify {
+            pk,
+            signature,
+            messages,
+            committed_messages,
+            prover_blind,
+            header,
+            bases,
+        } => {
+            reject_cl03_options(&[("bases", bases.is_some())])?;
+            let pk = read_pk(&pk)?;
+            let signature = read_array(&signature, "signature")?;
+            let messages = read_optional_messages(messages.as_deref())?;
+            let committed_messages = read_optional_messages(committed_messages.as_deref())?;
+            let prover_blind = read_prover_blind(prover_blind.as_deref())?;
+            let header = decode_hex_arg(header.as_deref())?;
+            if committed_messages.is_some() || prover_blind.is_some() {
+                BlindSignature::<BBSplus<CS>>::from_bytes(&signature)?.verify(
+                    &pk,
+                    header.as_deref(),
+                    messages.as_deref(),
+                    committed_messages.as_deref(),
+                    prover_blind.as_ref(),
+                    None,
+                    None,
+                )?;
+            } else {
+                Signature::<BBSplus<CS>>::from_bytes(&signature)?.verify(
+                    &pk,
+                    messages.as_deref(),
+                    header.as_deref(),
+                )?;
+            }
+            println!("Signature is valid");
+            Ok(())
+        }
+        Command::Prove {
+            pk,
+            signature,
+            messages,
+            disclosed,
+            committed_messages,
+            disclosed_committed,
+            prover_blind,
+            header,
+            ph,
+            bases,
+            commitment_pk,
+            out,
+        } => {
+            reject_cl03_options(&[
+                ("bases", bases.is_some()),
+                ("commitment-pk", commitment_pk.is_some()),
+            ])?;
+            let pk = read_pk(&pk)?;
+            let signature = read_artifact(&signature, "signature")?;
+            let messages = read_optional_messages(messages.as_deref())?;
+            let committed_messages = read_optional_messages(committed_messages.as_deref())?;
+            let prover_blind = read_prover_blind(prover_blind.as_deref())?;
+            let header = decode_hex_arg(header.as_deref())?;
+            let ph = decode_hex_arg(ph.as_deref())?;
+            let proof = if committed_messages.is_some() || prover_blind.is_some() {
+                PoKSignature::<BBSplus<CS>>::blind_proof_gen(
+                    &pk,
+                    &signature,
+                    header.as_deref(),
+                    ph.as_deref(),
+                    messages.as_deref(),
+                    committed_messages.as_deref(),
+                    Some(&disclosed),
+                    Some(&disclosed_committed),
+                    prover_blind.as_ref(),
+                    None,
+                )?
+            } else {
+                if !disclosed_committed.is_empty() {
+                    return Err("--disclosed-committed requires --committed-messages".into());
+                }
+                PoKSignature::<BBSplus<CS>>::proof_gen(
+                    &pk,
+                    &signature,
+                    header.as_deref(),
+                    ph.as_deref(),
+                    messages.as_deref(),
+                    Some(&disclosed),
+                )?
+            };
+            write_artifact(out.as_deref(), &proof.to_bytes(), "proof", format)
+        }
+        Command::VerifyProof {
+            pk,
+            proof,
+            messages,
+            disclosed,
+            committed_messages,
+            disclosed_committed,
+            messages_count,
+            header,
+            ph,
+            bases,
+            commitment_pk,
+        } => {
+            reject_cl03_options(&[
+                ("bases", bases.is_some()),
+                ("commitment-pk", commitment_pk.is_some()),
+            ])?;
+            let pk = read_pk(&pk)?;
+            let proof = PoKSignature::<BBSplus<CS>>::from_bytes(&read_artifact(&proof, "proof")?)?;
+            let messages = read_optional_messages(messages.as_deref())?;
+            let committed_messages = read_optional_messages(committed_messages.as_deref())?;
+            let header = decode_hex_arg(header.as_deref())?;
+            let ph = decode_hex_arg(ph.as_deref())?;
+            if messages_count.is_some() || committed_messages.is_some() {
+                proof.blind_proof_verify(
+                    &pk,
+                    header.as_deref(),
+                    ph.as_deref(),
+                    messages_count,
+                    messages.as_deref(),
+                    committed_messages.as_deref(),
+                    Some(&disclosed),
+                    Some(&disclosed_committed),
+                )?;
+            } else {
+                proof.proof_verify(
+                    &pk,
+                    messages.as_deref(),
+                    Some(&disclosed),
+                    header.as_deref(),
+                    ph.as_deref(),
+                )?;
+            }
+            println!("Proof is valid");
+            Ok(())
+        }
+        Command::Commit {
+            messages,
+            out,
+            prover_blind,
+            committed,
+            pk,
+            bases,
+            proof,
+        } => {
+            reject_cl03_options(&[
+                ("committed", !committed.is_empty()),
+                ("pk", pk.is_some()),
+                ("bases", bases.is_some()),
+                ("proof", proof.is_some()),
+            ])?;
+            let messages = read_optional_messages(Some(&messages))?;
+            let (commitment_with_proof, secret_prover_blind) =
+                Commitment::<BBSplus<CS>>::commit(messages.as_deref(), None)?;
+            write_secret_artifact(
+                &prover_blind,
+                &secret_prover_blind.to_bytes(),
+                "prover_blind",
+                format,
+            )?;
+            write_artifact(
+                out.as_deref(),
+                &commitment_with_proof.to_bytes(),
+                "commitment",
+                format,
+            )
+        }
+        Command::BlindSign {
+            sk,
+            pk,
+            commitment,
+            messages,
+            header,
+            proof,
+            committed,
+            bases,
+            out,
+        } => {
+            reject_cl03_options(&[
+                ("proof", proof.is_some()),
+                ("committed", !committed.is_empty()),
+                ("bases", bases.is_some()),
+            ])?;
+            let commitment = read_artifact(&commitment, "commitment")?;
+            let messages = read_optional_messages(messages.as_deref())?;
+            let header = decode_hex_arg(header.as_deref())?;
+            let signature = BlindSignature::<BBSplus<CS>>::blind_sign(
+                &read_sk(&sk)?,
+                &read_pk(&pk)?,
+                Some(&commitment),
+                header.as_deref(),
+                messages.as_deref(),
+                None,
+                None,
+            )?;
+            write_artifact(out.as_deref(), &signature.to_bytes(), "signature", format)
+        }
+        Command::CommitmentKey { .. } => {
+            Err("commitment-key is only supported by CL03 ciphersuites".into())
+        }
+        Command::Unblind { .. } => {
+            Err("BBS+ blind signatures are used as they are, unblind is only needed by CL03".into())
+        }
+    }
+}
@@ -0,0 +1,327 @@
+// Copyright 2023 Fondazione LINKS
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    io::{read_artifact, read_messages, required, write_artifact, write_secret_artifact},
+    CliResult, Command, Format,
+};
+use digest::Digest;
+use rug::Integer;
+use std::path::Path;
+use zkryptium::{
+    cl03::{
+        bases::Bases,
+        ciphersuites::CLCiphersuite,
+        commitment::CL03Commitment,
+        keys::{CL03CommitmentPublicKey, CL03PublicKey, CL03SecretKey},
+    },
+    keys::pair::KeyPair,
+    schemes::{
+        algorithms::CL03,
+        generics::{BlindSignature, Commitment, PoKSignature, Signature, ZKPoK},
+    },
+    utils::message::cl03_message::CL03Message,
+};
+
+fn read_pk(path: &Path) -> CliResult<CL03PublicKey> {
+    Ok(CL03PublicKey::from_bytes(&read_artifact(path, "pk")?)?)
+}
+
+fn read_sk(path: &Path) -> CliResult<CL03SecretKey> {
+    Ok(CL03SecretKey::from_bytes(&read_artifact(path, "sk")?)?)
+}
+
+fn read_bases(path: Option<&Path>) -> CliResult<Bases> {
+    Ok(Bases::from_bytes(&read_artifact(
+        required(path, "bases")?,
+        "bases",
+    )?)?)
+}
+
+fn read_commitment_pk(path: Option<&Path>) -> CliResult<CL03CommitmentPublicKey> {
+    Ok(CL03CommitmentPublicKey::from_bytes(&read_artifact(
+        required(path, "commitment-pk")?,
+        "commitment_pk",
+    )?)?)
+}
+
+fn read_cl03_messages<CS: CLCiphersuite>(path: Option<&Path>) -> CliResult<Vec<CL03Message>>
+where
+    CS::HashAlg: Digest,
+{
+    Ok(read_messages(required(path, "messages")?)?
+        .iter()
+        .map(|m| CL03Message::map_message_to_integer_as_hash::<CS>(m))
+        .collect())
+}
+
+/// Indexes in `0..n` which are not in `indexes`
+fn complement(indexes: &[usize], n: usize) -> Vec<usize> {
+    (0..n).filter(|i| !indexes.contains(i)).collect()
+}
+
+/// Options that only make sense for BBS+ ciphersuites
+fn reject_bbsplus_options(options: &[(&str, bool)]) -> CliResult<()> {
+    match options.iter().find(|(_, supplied)| *supplied) {
+        Some((name, _)) => Err(format!("--{} is not supported by CL03 ciphersuites", name).into()),
+        None => Ok(()),
+    }
+}
+
+pub(crate) fn run<CS: CLCiphersuite>(command: Command, format: Format) -> CliResult<()>
+where
+    CS::HashAlg: Digest,
+{
+    match command {
+        Command::Keygen {
+            sk,
+            pk,
+            key_material,
+            key_info,
+            bases,
+            messages_count,
+        } => {
+            reject_bbsplus_options(&[
+                ("key-material", key_material.is_some()),
+                ("key-info", key_info.is_some()),
+            ])?;
+            let bases = required(bases.as_deref(), "bases")?;
+            let messages_count = *required(messages_count.as_ref(), "messages-count")?;
+            let keypair = KeyPair::<CL03<CS>>::generate();
+            let a_bases = Bases::generate(keypair.public_key(), messages_count);
+            write_secret_artifact(&sk, &keypair.private_key().to_bytes(), "sk", format)?;
+            write_artifact(Some(&pk), &keypair.public_key().to_bytes(), "pk", format)?;
+            write_artifact(Some(bases), &a_bases.to_bytes(), "bases", format)
+        }
+        Command::CommitmentKey {
+            pk,
+            messages_count,
+            out,
+        } => {
+            let pk = read_pk(&pk)?;
+            let commitment_pk =
+                CL03CommitmentPublicKey::generate::<CS>(Some(pk.N.clone()), Some(messages_count));
+            write_artifact(
+                out.as_deref(),
+                &commitment_pk.to_bytes(),
+                "commitment_pk",
+                format,
+            )
+        }
+        Command::Sign {
+            sk,
+            pk,
+            messages,
+            header,
+            bases,
+            out,
+        } => {
+            reject_bbsplus_options(&[("header", header.is_some())])?;
+            let messages = read_cl03_messages::<CS>(Some(&messages))?;
+            let signature = Signature::<CL03<CS>>::sign_multiattr(
+                &read_pk(&pk)?,
+                &read_sk(&sk)?,
+                &read_bases(bases.as_deref())?,
+                &messages,
+            )?;
+            write_artifact(out.as_deref(), &signature.to_bytes(), "signature", format)
+        }
+        Command::Verify {
+            pk,
+            signature,
+            messages,
+            committed_messages,
+            prover_blind,
+            header,
+            bases,
+        } => {
+            reject_bbsplus_options(&[
+                ("committed-messages", committed_messages.is_some()),
+                ("prover-blind", prover_blind.is_some()),
+                ("header", header.is_some()),
+            ])?;
+            let signature =
+                Signature::<CL03<CS>>::from_bytes(&read_artifact(&signature, "signature")?)?;
+            signature.verify_multiattr(
+                &read_pk(&pk)?,
+                &read_bases(bases.as_deref())?,
+                &read_cl03_messages::<CS>(messages.as_deref())?,
+            )?;
+            println!("Signature is valid");
+            Ok(())
+        }
+        Command::Prove {
+            pk,
+            signature,
+            messages,
+            disclosed,
+            committed_messages,
+            disclosed_committed,
+            prover_blind,
+            header,
+            ph,
+            bases,
+            commitment_pk,
+            out,
+        } => {
+            reject_bbsplus_options(&[
+                ("committed-messages", committed_messages.is_some()),
+                ("disclosed-committed", !disclosed_committed.is_empty()),
+                ("prover-blind", prover_blind.is_some()),
+                ("header", header.is_some()),
+                ("ph", ph.is_some()),
+            ])?;
+            let signature =
+                Signature::<CL03<CS>>::from_bytes(&read_artifact(&signature, "signature")?)?;
+            let messages = read_cl03_messages::<CS>(messages.as_deref())?;
+            let proof = PoKSignature::<CL03<CS>>::proof_gen(
+                signature.cl03Signature(),
+                &read_commitment_pk(commitment_pk.as_deref())?,
+                &read_pk(&pk)?,
+                &read_bases(bases.as_deref())?,
+                &messages,
+                &complement(&disclosed, messages.len()),
+            )?;
+            write_artifact(out.as_deref(), &proof.to_bytes(), "proof", format)
+        }
+        Command::VerifyProof {
+            pk,
+            proof,
+            messages,
+            disclosed,
+            committed_messages,
+            disclosed_committed,
+            messages_count,
+            header,
+            ph,
+            bases,
+            commitment_pk,
+        } => {
+            reject_bbsplus_options(&[
+                ("committed-messages", committed_messages.is_some()),
+                ("disclosed-committed", !disclosed_committed.is_empty()),
+                ("header", header.is_some()),
+                ("ph", ph.is_some()),
+            ])?;
+            let messages_count = *required(messages_count.as_ref(), "messages-count")?;
+            let proof = PoKSignature::<CL03<CS>>::from_bytes(&read_artifact(&proof, "proof")?)?;
+            proof.proof_verify(
+                &read_commitment_pk(commitment_pk.as_deref())?,
+                &read_pk(&pk)?,
+                &read_bases(bases.as_deref())?,
+                &read_cl03_messages::<CS>(messages.as_deref())?,
+                &complement(&disclosed, messages_count),
+                messages_count,
+            )?;
+            println!("Proof is valid");
+            Ok(())
+        }
+        Command::Commit {
+            messages,
+            out,
+            prover_blind,
+            committed,
+            pk,
+            bases,
+            proof,
+        } => {
+            if committed.is_empty() {
+                return Err("--committed is required".into());
+            }
+            let pk = read_pk(required(pk.as_deref(), "pk")?)?;
+            let bases = read_bases(bases.as_deref())?;
+            let proof_path = required(proof.as_deref(), "proof")?;
+            let messages = read_cl03_messages::<CS>(Some(&messages))?;
+            let commitment =
+                Commitment::<CL03<CS>>::commit_with_pk(&messages, &pk, &bases, Some(&committed))?;
+            let zkpok = ZKPoK::<CL03<CS>>::generate_proof(
+                &messages,
+                commitment.cl03Commitment(),
+                None,
+                &pk,
+                &bases,
+                None,
+                &committed,
+            )?;
+            // The randomness of the commitment stays with the holder, who needs it to unblind the signature
+            let public_commitment = Commitment::<CL03<CS>>::CL03(CL03Commitment {
+                value: commitment.value().clone(),
+                randomness: Integer::new(),
+            });
+            write_secret_artifact(
+                &prover_blind,
+                &commitment.to_bytes(),
+                "prover_blind",
+                format,
+            )?;
+            write_artifact(Some(proof_path), &zkpok.to_bytes(), "proof", format)?;
+            write_artifact(
+                out.as_deref(),
+                &public_commitment.to_bytes(),
+                "commitment",
+                format,
+            )
+        }
+        Command::BlindSign {
+            sk,
+            pk,
+            commitment,
+            messages,
+            header,
+            proof,
+            committed,
+            bases,
+            out,
+        } => {
+            reject_bbsplus_options(&[("header", header.is_some())])?;
+            if committed.is_empty() {
+                return Err("--committed is required".into());
+            }
+            let commitment =
+                Commitment::<CL03<CS>>::from_bytes(&read_artifact(&commitment, "commitment")?)?;
+            let zkpok = ZKPoK::<CL03<CS>>::from_bytes(&read_artifact(
+                required(proof.as_deref(), "proof")?,
+                "proof",
+            )?)?;
+            let revealed_messages = read_cl03_messages::<CS>(messages.as_deref())?;
+            let revealed_indexes =
+                complement(&committed, revealed_messages.len() + committed.len());
+            let signature = BlindSignature::<CL03<CS>>::blind_sign(
+                &read_pk(&pk)?,
+                &read_sk(&sk)?,
+                &read_bases(bases.as_deref())?,
+                &zkpok,
+                Some(&revealed_messages),
+                commitment.cl03Commitment(),
+                None,
+                None,
+                &committed,
+                Some(&revealed_indexes),
+            )?;
+            write_artifact(out.as_deref(), &signature.to_bytes(), "signature", format)
+        }
+        Command::Unblind {
+            signature,
+            prover_blind,
+            out,
+        } => {
+            let blind_signature =
+                BlindSignature::<CL03<CS>>::from_bytes(&read_artifact(&signature, "signature")?)?;
+            let commitment =
+                Commitment::<CL03<CS>>::from_bytes(&read_artifact(&prover_blind, "prover_blind")?)?;
+            let signature = blind_signature.unblind_sign(&commitment);
+            write_artifact(out.as_deref(), &signature.to_bytes(), "signature", format)
+        }
+    }
+}
@@ -0,0 +1,179 @@
+// Copyright 2023 Fondazione LINKS
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{CliResult, Format};
+use serde_json::Value;
+use std::{fs, io::Write, path::Path};
+
+/// Decodes the content of an artifact file, either a hex string or a JSON object `{"<kind>": "<hex>"}`
+pub(crate) fn decode_artifact(content: &str, kind: &str) -> CliResult<Vec<u8>> {
+    let content = content.trim();
+    let hex_str = if content.starts_with('{') {
+        let value: Value = serde_json::from_str(content)?;
+        let object = value.as_object().ok_or("expected a JSON object")?;
+        object
+            .get(kind)
+            .ok_or_else(|| format!("missing \"{}\" field", kind))?
+            .as_str()
+            .ok_or_else(|| format!("\"{}\" must be a hex string", kind))?
+            .to_owned()
+    } else {
+        content.to_owned()
+    };
+    Ok(hex::decode(hex_str)?)
+}
+
+/// Encodes an artifact as a hex string or as a JSON object `{"<kind>": "<hex>"}`
+pub(crate) fn encode_artifact(bytes: &[u8], kind: &str, format: Format) -> String {
+    match format {
+        Format::Hex => hex::encode(bytes),
+        Format::Json => serde_json::json!({ kind: hex::encode(bytes) }).to_string(),
+    }
+}
+
+/// Decodes a list of messages, either a JSON array of hex strings or one hex message per line. Every line is a message, so an empty line is an empty message.
+pub(crate) fn decode_messages(content: &str) -> CliResult<Vec<Vec<u8>>> {
+    if content.trim_start().starts_with('[') {
+        let messages: Vec<String> = serde_json::from_str(content)?;
+        messages.iter().map(|m| Ok(hex::decode(m)?)).collect()
+    } else {
+        content
+            .lines()
+            .map(|l| Ok(hex::decode(l.trim())?))
+            .collect()
+    }
+}
+
+pub(crate) fn read_artifact(path: &Path, kind: &str) -> CliResult<Vec<u8>> {
+    let content =
+        fs::read_to_string(path).map_err(|e| format!("cannot read {}: {}", path.display(), e))?;
+    decode_artifact(&content, kind).map_err(|e| format!("{}: {}", path.display(), e).into())
+}
+
+/// Same as [`read_artifact`], checking the length of the artifact
+pub(crate) fn read_array<const N: usize>(path: &Path, kind: &str) -> CliResult<[u8; N]> {
+    read_artifact(path, kind)?
+        .try_into()
+        .map_err(|_| format!("{}: expected a {} bytes {}", path.display(), N, kind).into())
+}
+
+pub(crate) fn read_messages(path: &Path) -> CliResult<Vec<Vec<u8>>> {
+    let content =
+        fs::read_to_string(path).map_err(|e| format!("cannot read {}: {}", path.display(), e))?;
+    decode_messages(&content).map_err(|e| format!("{}: {}", path.display(), e).into())
+}
+
+pub(crate) fn read_optional_messages(path: Option<&Path>) -> CliResult<Option<Vec<Vec<u8>>>> {
+    path.map(read_messages).transpose()
+}
+
+/// Writes an artifact to `path`, or to stdout if no path is supplied
+pub(crate) fn write_artifact(
+    path: Option<&Path>,
+    bytes: &[u8],
+    kind: &str,
+    format: Format,
+) -> CliResult<()> {
+    let encoded = encode_artifact(bytes, kind, format);
+    match path {
+        Some(path) => fs::write(path, encoded + "\n")
+            .map_err(|e| format!("cannot write {}: {}", path.display(), e).into()),
+        None => {
+            println!("{}", encoded);
+            Ok(())
+        }
+    }
+}
+
+/// Writes a secret artifact to `path`, creating the file readable and writable only by its owner
+pub(crate) fn write_secret_artifact(
+    path: &Path,
+    bytes: &[u8],
+    kind: &str,
+    format: Format,
+) -> CliResult<()> {
+    let encoded = encode_artifact(bytes, kind, format);
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    options
+        .open(path)
+        .and_then(|mut file| {
+            // the mode only applies to new files
+            #[cfg(unix)]
+            file.set_permissions(std::os::unix::fs::PermissionsExt::from_mode(0o600))?;
+            file.write_all((encoded + "\n").as_bytes())
+        })
+        .map_err(|e| format!("cannot write {}: {}", path.display(), e).into())
+}
+
+pub(crate) fn decode_hex_arg(arg: Option<&str>) -> CliResult<Option<Vec<u8>>> {
+    Ok(arg.map(hex::decode).transpose()?)
+}
+
+#[cfg_attr(not(feature = "cl03"), allow(dead_code))]
+pub(crate) fn required<'a, T: ?Sized>(value: Option<&'a T>, option: &str) -> CliResult<&'a T> {
+    value.ok_or_else(|| format!("--{} is required", option).into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn artifact_round_trip() {
+        let bytes = [0xde, 0xad, 0xbe, 0xef];
+        for format in [Format::Hex, Format::Json] {
+            let encoded = encode_artifact(&bytes, "signature", format);
+            assert_eq!(decode_artifact(&encoded, "signature").unwrap(), bytes);
+        }
+        assert_eq!(
+            decode_artifact("{\"signature\": \"0102\", \"other\": 3}", "signature").unwrap(),
+            [1, 2]
+        );
+        assert!(decode_artifact("{\"proof\": \"0102\"}", "signature").is_err());
+        assert!(decode_artifact("{\"a\": \"01\", \"b\": \"02\"}", "signature").is_err());
+    }
+
+    #[test]
+    fn messages_formats() {
+        let expected = vec![vec![0x01, 0x02], vec![], vec![0xff]];
+        assert_eq!(
+            decode_messages("[\"0102\", \"\", \"ff\"]").unwrap(),
+            expected
+        );
+        assert_eq!(decode_messages("0102\n\nff\n").unwrap(), expected);
+        assert_eq!(decode_messages("0102\r\n\r\nff").unwrap(), expected);
+        assert!(decode_messages("zz").is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn secret_artifact_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let path = std::env::temp_dir().join(format!("zkryptium-sk-{}", std::process::id()));
+        fs::write(&path, "").unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o644)).unwrap();
+
+        write_secret_artifact(&path, &[1, 2], "sk", Format::Hex).unwrap();
+        let mode = fs::metadata(&path).unwrap().permissions().mode();
+        let content = fs::read_to_string(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(mode & 0o777, 0o600);
+        assert_eq!(content, "0102\n");
+    }
+}
@@ -0,0 +1,314 @@
+// Copyright 2023 Fondazione LINKS
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod bbsplus;
+#[cfg(feature = "cl03")]
+mod cl03;
+mod io;
+
+use clap::{Parser, Subcommand, ValueEnum};
+use std::{path::PathBuf, process};
+use zkryptium::bbsplus::ciphersuites::{Bls12381Sha256, Bls12381Shake256};
+#[cfg(feature = "cl03")]
+use zkryptium::cl03::ciphersuites::{CL1024Sha256, CL2048Sha256, CL3072Sha384, CL3072Sha512};
+
+pub(crate) type CliResult<T> = Result<T, Box<dyn std::error::Error>>;
+
+/// Key generation, signing, selective disclosure proofs and blind signatures from the command line.
+///
+/// Binary artifacts (keys, signatures, proofs, commitments) are read and written as hex strings
+/// or as JSON objects (`{"<kind>": "<hex>"}`), detected automatically on input.
+/// Messages are read from a JSON array of hex strings or from a file with one hex message per line (an empty line is an empty message).
+#[derive(Parser)]
+#[clap(name = "zkryptium", version)]
+struct Cli {
+    /// Ciphersuite to use
+    #[clap(
+        short,
+        long,
+        value_enum,
+        default_value = "BLS12-381-SHA-256",
+        global = true
+    )]
+    ciphersuite: CipherSuite,
+
+    /// Encoding of the output files
+    #[clap(short, long, value_enum, default_value = "hex", global = true)]
+    format: Format,
+
+    #[clap(subcommand)]
+    command: Command,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum CipherSuite {
+    #[clap(name = "BLS12-381-SHA-256")]
+    Bls12381Sha256,
+    #[clap(name = "BLS12-381-SHAKE-256")]
+    Bls12381Shake256,
+    #[cfg(feature = "cl03")]
+    #[clap(name = "CL1024-SHA-256")]
+    CL1024Sha256,
+    #[cfg(feature = "cl03")]
+    #[clap(name = "CL2048-SHA-256")]
+    CL2048Sha256,
+    #[cfg(feature = "cl03")]
+    #[clap(name = "CL3072-SHA-384")]
+    CL3072Sha384,
+    #[cfg(feature = "cl03")]
+    #[clap(name = "CL3072-SHA-512")]
+    CL3072Sha512,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, ValueEnum)]
+pub(crate) enum Format {
+    Hex,
+    Json,
+}
+
+#[derive(Subcommand)]
+pub(crate) enum Command {
+    /// Generate an issuer keypair (and, for CL03, the bases for the messages)
+    Keygen {
+        /// Output file of the secret key
+        #[clap(long)]
+        sk: PathBuf,
+        /// Output file of the public key
+        #[clap(long)]
+        pk: PathBuf,
+        /// BBS+: hex encoded key material, random if not supplied
+        #[clap(long)]
+        key_material: Option<String>,
+        /// BBS+: hex encoded key info
+        #[clap(long)]
+        key_info: Option<String>,
+        /// CL03: output file of the bases
+        #[clap(long)]
+        bases: Option<PathBuf>,
+        /// CL03: number of messages the bases are generated for
+        #[clap(long)]
+        messages_count: Option<usize>,
+    },
+    /// CL03: generate the commitment public key a verifier hands to the holder for proofs
+    CommitmentKey {
+        /// Public key of the issuer
+        #[clap(long)]
+        pk: PathBuf,
+        /// Number of messages signed by the issuer
+        #[clap(long)]
+        messages_count: usize,
+        /// Output file, stdout if not supplied
+        #[clap(long)]
+        out: Option<PathBuf>,
+    },
+    /// Sign a list of messages
+    Sign {
+        #[clap(long)]
+        sk: PathBuf,
+        #[clap(long)]
+        pk: PathBuf,
+        /// Messages to sign
+        #[clap(long)]
+        messages: PathBuf,
+        /// BBS+: hex encoded header
+        #[clap(long)]
+        header: Option<String>,
+        /// CL03: bases of the issuer
+        #[clap(long)]
+        bases: Option<PathBuf>,
+        /// Output file, stdout if not supplied
+        #[clap(long)]
+        out: Option<PathBuf>,
+    },
+    /// Verify a signature (a blind one if the committed messages are supplied)
+    Verify {
+        #[clap(long)]
+        pk: PathBuf,
+        #[clap(long)]
+        signature: PathBuf,
+        /// Messages signed by the issuer
+        #[clap(long)]
+        messages: Option<PathBuf>,
+        /// BBS+: messages committed by the holder
+        #[clap(long)]
+        committed_messages: Option<PathBuf>,
+        /// BBS+: secret_prover_blind returned by `commit`
+        #[clap(long)]
+        prover_blind: Option<PathBuf>,
+        /// BBS+: hex encoded header
+        #[clap(long)]
+        header: Option<String>,
+        /// CL03: bases of the issuer
+        #[clap(long)]
+        bases: Option<PathBuf>,
+    },
+    /// Generate a proof of knowledge of a signature disclosing some of the messages
+    Prove {
+        #[clap(long)]
+        pk: PathBuf,
+        #[clap(long)]
+        signature: PathBuf,
+        /// All the signed messages
+        #[clap(long)]
+        messages: Option<PathBuf>,
+        /// Comma separated indexes of the messages to disclose
+        #[clap(long, use_value_delimiter = true)]
+        disclosed: Vec<usize>,
+        /// BBS+: messages committed by the holder
+        #[clap(long)]
+        committed_messages: Option<PathBuf>,
+        /// BBS+: comma separated indexes of the committed messages to disclose
+        #[clap(long, use_value_delimiter = true)]
+        disclosed_committed: Vec<usize>,
+        /// BBS+: secret_prover_blind returned by `commit`
+        #[clap(long)]
+        prover_blind: Option<PathBuf>,
+        /// BBS+: hex encoded header
+        #[clap(long)]
+        header: Option<String>,
+        /// BBS+: hex encoded presentation header
+        #[clap(long)]
+        ph: Option<String>,
+        /// CL03: bases of the issuer
+        #[clap(long)]
+        bases: Option<PathBuf>,
+        /// CL03: commitment public key of the verifier
+        #[clap(long)]
+        commitment_pk: Option<PathBuf>,
+        /// Output file, stdout if not supplied
+        #[clap(long)]
+        out: Option<PathBuf>,
+    },
+    /// Verify a proof of knowledge of a signature
+    VerifyProof {
+        #[clap(long)]
+        pk: PathBuf,
+        #[clap(long)]
+        proof: PathBuf,
+        /// Disclosed messages
+        #[clap(long)]
+        messages: Option<PathBuf>,
+        /// Comma separated indexes of the disclosed messages
+        #[clap(long, use_value_delimiter = true)]
+        disclosed: Vec<usize>,
+        /// BBS+: disclosed committed messages
+        #[clap(long)]
+        committed_messages: Option<PathBuf>,
+        /// BBS+: comma separated indexes of the disclosed committed messages
+        #[clap(long, use_value_delimiter = true)]
+        disclosed_committed: Vec<usize>,
+        /// Number of messages signed by the issuer (BBS+: only for proofs of blind signatures)
+        #[clap(long)]
+        messages_count: Option<usize>,
+        /// BBS+: hex encoded header
+        #[clap(long)]
+        header: Option<String>,
+        /// BBS+: hex encoded presentation header
+        #[clap(long)]
+        ph: Option<String>,
+        /// CL03: bases of the issuer
+        #[clap(long)]
+        bases: Option<PathBuf>,
+        /// CL03: commitment public key of the verifier
+        #[clap(long)]
+        commitment_pk: Option<PathBuf>,
+    },
+    /// Commit to messages to be blindly signed
+    Commit {
+        /// Messages to commit to (CL03: all the messages, see `--committed`)
+        #[clap(long)]
+        messages: PathBuf,
+        /// Output file of the commitment to send to the issuer, stdout if not supplied
+        #[clap(long)]
+        out: Option<PathBuf>,
+        /// Output file of the secret the holder keeps to use the signature
+        #[clap(long)]
+        prover_blind: PathBuf,
+        /// CL03: comma separated indexes of the messages hidden from the issuer
+        #[clap(long, use_value_delimiter = true)]
+        committed: Vec<usize>,
+        /// CL03: public key of the issuer
+        #[clap(long)]
+        pk: Option<PathBuf>,
+        /// CL03: bases of the issuer
+        #[clap(long)]
+        bases: Option<PathBuf>,
+        /// CL03: output file of the proof of knowledge of the committed messages
+        #[clap(long)]
+        proof: Option<PathBuf>,
+    },
+    /// Sign the messages committed by the holder together with the issuer messages
+    BlindSign {
+        #[clap(long)]
+        sk: PathBuf,
+        #[clap(long)]
+        pk: PathBuf,
+        /// Commitment received from the holder
+        #[clap(long)]
+        commitment: PathBuf,
+        /// Messages known to the issuer
+        #[clap(long)]
+        messages: Option<PathBuf>,
+        /// BBS+: hex encoded header
+        #[clap(long)]
+        header: Option<String>,
+        /// CL03: proof of knowledge of the committed messages
+        #[clap(long)]
+        proof: Option<PathBuf>,
+        /// CL03: comma separated indexes of the messages committed by the holder
+        #[clap(long, use_value_delimiter = true)]
+        committed: Vec<usize>,
+        /// CL03: bases of the issuer
+        #[clap(long)]
+        bases: Option<PathBuf>,
+        /// Output file, stdout if not supplied
+        #[clap(long)]
+        out: Option<PathBuf>,
+    },
+    /// CL03: turn a blind signature into a signature over all the messages
+    Unblind {
+        #[clap(long)]
+        signature: PathBuf,
+        /// Secret returned by `commit`
+        #[clap(long)]
+        prover_blind: PathBuf,
+        /// Output file, stdout if not supplied
+        #[clap(long)]
+        out: Option<PathBuf>,
+    },
+}
+
+fn run(cli: Cli) -> CliResult<()> {
+    match cli.ciphersuite {
+        CipherSuite::Bls12381Sha256 => bbsplus::run::<Bls12381Sha256>(cli.command, cli.format),
+        CipherSuite::Bls12381Shake256 => bbsplus::run::<Bls12381Shake256>(cli.command, cli.format),
+        #[cfg(feature = "cl03")]
+        CipherSuite::CL1024Sha256 => cl03::run::<CL1024Sha256>(cli.command, cli.format),
+        #[cfg(feature = "cl03")]
+        CipherSuite::CL2048Sha256 => cl03::run::<CL2048Sha256>(cli.command, cli.format),
+        #[cfg(feature = "cl03")]
+        CipherSuite::CL3072Sha384 => cl03::run::<CL3072Sha384>(cli.command, cli.format),
+        #[cfg(feature = "cl03")]
+        CipherSuite::CL3072Sha512 => cl03::run::<CL3072Sha512>(cli.command, cli.format),
+    }
+}
+
+fn main() {
+    env_logger::init();
+
+    if let Err(e) = run(Cli::parse()) {
+        eprintln!("Error: {}", e);
+        process::exit(1);
+    }
+}
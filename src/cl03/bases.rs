@@ -12,8 +12,12 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use super::keys::CL03PublicKey;
-use crate::utils::random::random_qr;
+use super::{
+    encoding::{self, ByteReader, CanonicalEncoding},
+    keys::CL03PublicKey,
+};
+use crate::{errors::Error, utils::random::random_qr_with_rng};
+use rand::{thread_rng, CryptoRng, RngCore};
 use rug::Integer;
 use serde::{Deserialize, Serialize};
 
@@ -22,12 +26,39 @@ pub struct Bases(pub Vec<Integer>);
 
 impl Bases {
     pub fn generate(pk: &CL03PublicKey, n_attributes: usize) -> Self {
+        Self::generate_with_rng(pk, n_attributes, &mut thread_rng())
+    }
+
+    /// Generates `n_attributes` random quadratic residues modulo `pk.N`, drawn from `rng`
+    pub fn generate_with_rng<R: RngCore + CryptoRng>(
+        pk: &CL03PublicKey,
+        n_attributes: usize,
+        rng: &mut R,
+    ) -> Self {
         let mut a_bases: Vec<Integer> = Vec::new();
         for _i in 0..n_attributes {
-            let a = random_qr(&pk.N);
+            let a = random_qr_with_rng(&pk.N, rng);
             a_bases.push(a);
         }
 
         Self(a_bases)
     }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        encoding::to_bytes(self)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        encoding::from_bytes(bytes)
+    }
+}
+
+impl CanonicalEncoding for Bases {
+    fn write(&self, out: &mut Vec<u8>) {
+        self.0.write(out);
+    }
+
+    fn read(reader: &mut ByteReader) -> Result<Self, Error> {
+        Ok(Self(Vec::read(reader)?))
+    }
 }
@@ -14,17 +14,21 @@
 
 use super::{
     commitment::CL03Commitment,
+    encoding::{self, impl_canonical_encoding},
     keys::{CL03CommitmentPublicKey, CL03PublicKey, CL03SecretKey},
     signature::CL03Signature,
 };
 use crate::{
     cl03::{bases::Bases, ciphersuites::CLCiphersuite},
+    errors::Error,
     schemes::algorithms::CL03,
     schemes::generics::{BlindSignature, Commitment, Signature, ZKPoK},
     utils::message::cl03_message::CL03Message,
-    utils::random::{random_bits, random_prime},
+    utils::random::{random_bits_with_rng, random_prime_with_rng},
+    utils::util::cl03_utils::IntegerExt,
 };
 use digest::Digest;
+use rand::{thread_rng, CryptoRng, RngCore};
 use rug::{ops::Pow, Integer};
 use serde::{Deserialize, Serialize};
 use std::panic;
@@ -36,6 +40,8 @@ pub struct CL03BlindSignature {
     pub(crate) v: Integer,
 }
 
+impl_canonical_encoding!(CL03BlindSignature { e, rprime, v });
+
 impl<CS: CLCiphersuite> BlindSignature<CL03<CS>> {
     pub fn e(&self) -> &Integer {
         match self {
@@ -71,55 +77,82 @@ impl<CS: CLCiphersuite> BlindSignature<CL03<CS>> {
         commitment_pk: Option<&CL03CommitmentPublicKey>,
         unrevealed_message_indexes: &[usize],
         revealed_message_indexes: Option<&[usize]>,
-    ) -> Self
+    ) -> Result<Self, Error>
+    where
+        CS::HashAlg: Digest,
+    {
+        Self::blind_sign_with_rng(
+            pk,
+            sk,
+            a_bases,
+            zkpok,
+            revealed_messages,
+            C,
+            C_trusted,
+            commitment_pk,
+            unrevealed_message_indexes,
+            revealed_message_indexes,
+            &mut thread_rng(),
+        )
+    }
+
+    /// Same as [`Self::blind_sign`], drawing `e` and `rprime` from `rng`
+    pub fn blind_sign_with_rng<R: RngCore + CryptoRng>(
+        pk: &CL03PublicKey,
+        sk: &CL03SecretKey,
+        a_bases: &Bases,
+        zkpok: &ZKPoK<CL03<CS>>,
+        revealed_messages: Option<&[CL03Message]>,
+        C: &CL03Commitment,
+        C_trusted: Option<&CL03Commitment>,
+        commitment_pk: Option<&CL03CommitmentPublicKey>,
+        unrevealed_message_indexes: &[usize],
+        revealed_message_indexes: Option<&[usize]>,
+        rng: &mut R,
+    ) -> Result<Self, Error>
     where
         CS::HashAlg: Digest,
     {
-        if !zkpok.verify_proof(
+        zkpok.verify_proof(
             C,
             C_trusted,
             pk,
             a_bases,
             commitment_pk,
             unrevealed_message_indexes,
-        ) {
-            panic!("Knowledge of committed secrets not verified");
-        }
+        )?;
 
         let mut extended_commitment: Commitment<CL03<CS>> = Commitment::CL03(C.clone());
-        if revealed_messages.is_some() && revealed_message_indexes.is_some() {
+        if let (Some(revealed_messages), Some(_)) = (revealed_messages, revealed_message_indexes) {
             extended_commitment.extend_commitment_with_pk(
-                revealed_messages.unwrap(),
+                revealed_messages,
                 pk,
                 a_bases,
                 revealed_message_indexes,
-            );
+            )?;
         }
-        let mut e = random_prime(CS::le);
+        let mut e = random_prime_with_rng(CS::le, rng);
         let phi_n = (&sk.p - Integer::from(1)) * (&sk.q - Integer::from(1));
         while ((&e > &Integer::from(2).pow(CS::le - 1))
             && (&e < &Integer::from(2).pow(CS::le))
             && (Integer::from(e.gcd_ref(&phi_n)) == 1))
             == false
         {
-            e = random_prime(CS::le);
+            e = random_prime_with_rng(CS::le, rng);
         }
 
-        let rprime = random_bits(CS::ls);
-        let e2n = Integer::from(e.invert_ref(&phi_n).unwrap());
+        let rprime = random_bits_with_rng(CS::ls, rng);
+        let e2n = Integer::from(e.invert_ref(&phi_n).ok_or(Error::BlindSignError(
+            "e is not invertible modulo phi(N)".to_owned(),
+        ))?);
 
         // v = powmod(((Cx) * powmod(pk['b'], rprime, pk['N']) * pk['c']), e2n, pk['N'])
-        let v = Integer::from(
-            (extended_commitment.value()
-                * Integer::from(pk.b.pow_mod_ref(&rprime, &pk.N).unwrap())
-                * &pk.c)
-                .pow_mod_ref(&e2n, &pk.N)
-                .unwrap(),
-        );
+        let v = (extended_commitment.value() * pk.b.pow_mod_checked(&rprime, &pk.N)? * &pk.c)
+            .pow_mod_checked(&e2n, &pk.N)?;
         let sig = CL03BlindSignature { e, rprime, v };
         // sig = { 'e':e, 'rprime':rprime, 'v':v }
 
-        Self::CL03(sig)
+        Ok(Self::CL03(sig))
     }
 
     pub fn unblind_sign(&self, commitment: &Commitment<CL03<CS>>) -> Signature<CL03<CS>> {
@@ -139,34 +172,46 @@ impl<CS: CLCiphersuite> BlindSignature<CL03<CS>> {
         pk: &CL03PublicKey,
         a_bases: &Bases,
         revealed_message_indexes: Option<&[usize]>,
-    ) -> Self {
+    ) -> Result<Self, Error> {
         let mut extended_commitment: Commitment<CL03<CS>> = Commitment::CL03(C.clone());
-        if revealed_messages.is_some() && revealed_message_indexes.is_some() {
+        if let (Some(revealed_messages), Some(_)) = (revealed_messages, revealed_message_indexes) {
             extended_commitment.extend_commitment_with_pk(
-                revealed_messages.unwrap(),
+                revealed_messages,
                 pk,
                 a_bases,
                 revealed_message_indexes,
-            );
+            )?;
         }
 
         let phi_N = (&sk.p - Integer::from(1)) * (&sk.q - Integer::from(1));
-        let e2n = Integer::from(self.e().invert_ref(&phi_N).unwrap());
-
-        let v = Integer::from(
-            (extended_commitment.value()
-                * Integer::from(pk.b.pow_mod_ref(self.rprime(), &pk.N).unwrap())
-                * &pk.c)
-                .pow_mod_ref(&e2n, &pk.N)
-                .unwrap(),
+        let e2n = Integer::from(
+            self.e()
+                .invert_ref(&phi_N)
+                .ok_or(Error::UpdateSignatureError(
+                    "e is not invertible modulo phi(N)".to_owned(),
+                ))?,
         );
 
+        let v = (extended_commitment.value() * pk.b.pow_mod_checked(self.rprime(), &pk.N)? * &pk.c)
+            .pow_mod_checked(&e2n, &pk.N)?;
+
         let sig = CL03BlindSignature {
             e: self.e().clone(),
             rprime: self.rprime().clone(),
             v,
         };
-        Self::CL03(sig)
+        Ok(Self::CL03(sig))
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        match self {
+            Self::CL03(inner) => encoding::to_bytes(inner),
+            _ => panic!("Cannot happen!"),
+        }
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        Ok(Self::CL03(encoding::from_bytes(bytes)?))
     }
 }
 
@@ -196,6 +241,52 @@ mod tests {
         update_signature::<CL03_CL1024_SHA256>();
     }
 
+    //Commitment proof on messages other than the first one - CL1024-SHA256
+    #[test]
+    fn commitment_proof_unrevealed_not_first_cl1024_sha256() {
+        type S = CL03_CL1024_SHA256;
+        let cl03_keypair = KeyPair::<S>::generate();
+        let a_bases = Bases::generate(cl03_keypair.public_key(), 3);
+        let messages: Vec<CL03Message> = ["9872ad08", "87a8bd65", "96012096"]
+            .iter()
+            .map(|&m| {
+                CL03Message::map_message_to_integer_as_hash::<<S as Scheme>::Ciphersuite>(
+                    &hex::decode(m).unwrap(),
+                )
+            })
+            .collect();
+
+        for unrevealed_message_indexes in [&[1usize][..], &[0, 2]] {
+            let commitment = Commitment::<S>::commit_with_pk(
+                &messages,
+                cl03_keypair.public_key(),
+                &a_bases,
+                Some(unrevealed_message_indexes),
+            )
+            .unwrap();
+            let zkpok = ZKPoK::<S>::generate_proof(
+                &messages,
+                commitment.cl03Commitment(),
+                None,
+                cl03_keypair.public_key(),
+                &a_bases,
+                None,
+                unrevealed_message_indexes,
+            )
+            .unwrap();
+            assert!(zkpok
+                .verify_proof(
+                    commitment.cl03Commitment(),
+                    None,
+                    cl03_keypair.public_key(),
+                    &a_bases,
+                    None,
+                    unrevealed_message_indexes,
+                )
+                .is_ok());
+        }
+    }
+
     fn blind_sign<S: Scheme>()
     where
         S::Ciphersuite: CLCiphersuite,
@@ -255,7 +346,8 @@ mod tests {
             cl03_keypair.public_key(),
             &a_bases,
             Some(&unrevealed_message_indexes),
-        );
+        )
+        .unwrap();
 
         let zkpok = ZKPoK::<CL03<S::Ciphersuite>>::generate_proof(
             &messages,
@@ -265,7 +357,8 @@ mod tests {
             &a_bases,
             None,
             &unrevealed_message_indexes,
-        );
+        )
+        .unwrap();
 
         let blind_signature = BlindSignature::<CL03<S::Ciphersuite>>::blind_sign(
             cl03_keypair.public_key(),
@@ -278,13 +371,14 @@ mod tests {
             None,
             &unrevealed_message_indexes,
             Some(&revealed_message_indexes),
-        );
+        )
+        .unwrap();
         let unblided_signature = blind_signature.unblind_sign(&commitment);
         let verify =
             unblided_signature.verify_multiattr(cl03_keypair.public_key(), &a_bases, &messages);
 
         assert!(
-            verify,
+            verify.is_ok(),
             "Error! The unblided signature verification should PASS!"
         );
 
@@ -299,7 +393,8 @@ mod tests {
             None,
             &unrevealed_message_indexes,
             Some(&revealed_message_indexes),
-        );
+        )
+        .unwrap();
         let unblided_signature_wrong = blind_signature_wrong.unblind_sign(&commitment);
         let verify = unblided_signature_wrong.verify_multiattr(
             cl03_keypair.public_key(),
@@ -308,7 +403,7 @@ mod tests {
         );
 
         assert!(
-            !verify,
+            verify.is_err(),
             "Error! The unblinded signature verification SHOULD FAIL!"
         );
     }
@@ -369,7 +464,8 @@ mod tests {
             cl03_keypair.public_key(),
             &a_bases,
             Some(&unrevealed_message_indexes),
-        );
+        )
+        .unwrap();
 
         let zkpok = ZKPoK::<CL03<S::Ciphersuite>>::generate_proof(
             &messages,
@@ -379,7 +475,8 @@ mod tests {
             &a_bases,
             None,
             &unrevealed_message_indexes,
-        );
+        )
+        .unwrap();
 
         let blind_signature = BlindSignature::<CL03<S::Ciphersuite>>::blind_sign(
             cl03_keypair.public_key(),
@@ -392,24 +489,27 @@ mod tests {
             None,
             &unrevealed_message_indexes,
             Some(&revealed_message_indexes),
-        );
+        )
+        .unwrap();
         let unblided_signature = blind_signature.unblind_sign(&commitment);
         let verify =
             unblided_signature.verify_multiattr(cl03_keypair.public_key(), &a_bases, &messages);
 
         assert!(
-            verify,
+            verify.is_ok(),
             "Error! The unblided signature verification should PASS!"
         );
 
-        let updated_signature = blind_signature.update_signature(
-            Some(&revealed_updated_messages),
-            &commitment.cl03Commitment(),
-            cl03_keypair.private_key(),
-            cl03_keypair.public_key(),
-            &a_bases,
-            Some(&revealed_message_indexes),
-        );
+        let updated_signature = blind_signature
+            .update_signature(
+                Some(&revealed_updated_messages),
+                &commitment.cl03Commitment(),
+                cl03_keypair.private_key(),
+                cl03_keypair.public_key(),
+                &a_bases,
+                Some(&revealed_message_indexes),
+            )
+            .unwrap();
         let unblinded_updated_signature = updated_signature.unblind_sign(&commitment);
 
         let verify = unblinded_updated_signature.verify_multiattr(
@@ -418,7 +518,7 @@ mod tests {
             &updated_messages,
         );
         assert!(
-            verify,
+            verify.is_ok(),
             "Error! The unblided signature verification should PASS!"
         );
     }
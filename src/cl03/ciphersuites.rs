@@ -15,9 +15,10 @@
 use super::range_proof::RangeProof;
 use crate::schemes::algorithms::Ciphersuite;
 use serde::{Deserialize, Serialize};
-use sha2::Sha256;
+use sha2::{Sha256, Sha384, Sha512};
 
 pub trait CLCiphersuite: Eq + 'static + Ciphersuite {
+    const ID: &'static [u8];
     const SECPARAM: u32;
     const ln: u32; // NOTE: length of n (i.e. special RSA modulus), ln will be used to generate all randomness and bases for public keys
     const lm: u32; // NOTE: length of each secret attribute in the credential, i.e. x = (m0, m1, ... m(M-1))
@@ -38,6 +39,7 @@ pub trait CLCiphersuite: Eq + 'static + Ciphersuite {
 pub struct CL1024Sha256 {}
 
 impl CLCiphersuite for CL1024Sha256 {
+    const ID: &'static [u8] = b"CL1024-SHA-256";
     const SECPARAM: u32 = 512;
     const ln: u32 = 2 * Self::SECPARAM; // NOTE: length of n (i.e. special RSA modulus), ln will be used to generate all randomness and bases for public keys
     const lm: u32 = 256; // NOTE: length of each secret attribute in the credential, i.e. x = (m0, m1, ... m(M-1))
@@ -66,3 +68,122 @@ impl CLCiphersuite for CL1024Sha256 {
 impl Ciphersuite for CL1024Sha256 {
     type HashAlg = Sha256;
 }
+
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct CL2048Sha256 {}
+
+impl CLCiphersuite for CL2048Sha256 {
+    const ID: &'static [u8] = b"CL2048-SHA-256";
+    const SECPARAM: u32 = 1024;
+    const ln: u32 = 2 * Self::SECPARAM;
+    const lm: u32 = 256;
+    const lin: u32 = 256;
+    const le: u32 = Self::lm + 2;
+    const ls: u32 = Self::ln + Self::lm + Self::lin;
+
+    //RANGEPROOF
+    const RANGEPROOF_ALG: RangeProof = RangeProof::Boudot2000;
+    const t: u32 = 128;
+    const l: u32 = 40;
+    const s: u32 = 40;
+    const s1: u32 = 40;
+    const s2: u32 = Self::SECPARAM + Self::s;
+}
+
+impl Ciphersuite for CL2048Sha256 {
+    type HashAlg = Sha256;
+}
+
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct CL3072Sha384 {}
+
+impl CLCiphersuite for CL3072Sha384 {
+    const ID: &'static [u8] = b"CL3072-SHA-384";
+    const SECPARAM: u32 = 1536;
+    const ln: u32 = 2 * Self::SECPARAM;
+    // NOTE: messages are mapped to integers with the suite hash, so lm matches its output length
+    const lm: u32 = 384;
+    const lin: u32 = 384;
+    const le: u32 = Self::lm + 2;
+    const ls: u32 = Self::ln + Self::lm + Self::lin;
+
+    //RANGEPROOF
+    const RANGEPROOF_ALG: RangeProof = RangeProof::Boudot2000;
+    // NOTE: 2*t bits is the length of the SHA-384 output
+    const t: u32 = 192;
+    const l: u32 = 40;
+    const s: u32 = 40;
+    const s1: u32 = 40;
+    const s2: u32 = Self::SECPARAM + Self::s;
+}
+
+impl Ciphersuite for CL3072Sha384 {
+    type HashAlg = Sha384;
+}
+
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct CL3072Sha512 {}
+
+impl CLCiphersuite for CL3072Sha512 {
+    const ID: &'static [u8] = b"CL3072-SHA-512";
+    const SECPARAM: u32 = 1536;
+    const ln: u32 = 2 * Self::SECPARAM;
+    // NOTE: messages are mapped to integers with the suite hash, so lm matches its output length
+    const lm: u32 = 512;
+    const lin: u32 = 512;
+    const le: u32 = Self::lm + 2;
+    const ls: u32 = Self::ln + Self::lm + Self::lin;
+
+    //RANGEPROOF
+    const RANGEPROOF_ALG: RangeProof = RangeProof::Boudot2000;
+    // NOTE: 2*t bits is the length of the SHA-512 output
+    const t: u32 = 256;
+    const l: u32 = 40;
+    const s: u32 = 40;
+    const s1: u32 = 40;
+    const s2: u32 = Self::SECPARAM + Self::s;
+}
+
+impl Ciphersuite for CL3072Sha512 {
+    type HashAlg = Sha512;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CL1024Sha256, CL2048Sha256, CL3072Sha384, CL3072Sha512, CLCiphersuite};
+    use digest::Digest;
+
+    fn check_parameters<CS: CLCiphersuite>()
+    where
+        CS::HashAlg: Digest,
+    {
+        let hash_bits = (<CS::HashAlg as Digest>::output_size() * 8) as u32;
+
+        assert_eq!(CS::ln, 2 * CS::SECPARAM);
+        assert_eq!(CS::lm, hash_bits, "messages are mapped with the suite hash");
+        assert_eq!(CS::le, CS::lm + 2);
+        assert_eq!(CS::ls, CS::ln + CS::lm + CS::lin);
+        assert_eq!(2 * CS::t, hash_bits, "2*t must be the hash output length");
+        assert_eq!(CS::s2, CS::SECPARAM + CS::s);
+    }
+
+    #[test]
+    fn parameters_cl1024_sha256() {
+        check_parameters::<CL1024Sha256>();
+    }
+
+    #[test]
+    fn parameters_cl2048_sha256() {
+        check_parameters::<CL2048Sha256>();
+    }
+
+    #[test]
+    fn parameters_cl3072_sha384() {
+        check_parameters::<CL3072Sha384>();
+    }
+
+    #[test]
+    fn parameters_cl3072_sha512() {
+        check_parameters::<CL3072Sha512>();
+    }
+}
@@ -12,14 +12,20 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use super::keys::{CL03CommitmentPublicKey, CL03PublicKey};
+use super::{
+    encoding::{self, impl_canonical_encoding},
+    keys::{CL03CommitmentPublicKey, CL03PublicKey},
+};
 use crate::{
     cl03::{bases::Bases, ciphersuites::CLCiphersuite},
+    errors::Error,
     schemes::algorithms::CL03,
     schemes::generics::Commitment,
     utils::message::cl03_message::CL03Message,
-    utils::random::random_bits,
+    utils::random::random_bits_with_rng,
+    utils::util::cl03_utils::IntegerExt,
 };
+use rand::{thread_rng, CryptoRng, RngCore};
 use rug::Integer;
 use serde::{Deserialize, Serialize};
 
@@ -29,20 +35,23 @@ pub struct CL03Commitment {
     pub randomness: Integer,
 }
 
+impl_canonical_encoding!(CL03Commitment { value, randomness });
+
 impl<CS: CLCiphersuite> Commitment<CL03<CS>> {
-    pub(crate) fn commit_v(v: &Integer, commitment_pk: &CL03CommitmentPublicKey) -> Self {
-        let w = random_bits(CS::ln);
+    pub(crate) fn commit_v<R: RngCore + CryptoRng>(
+        v: &Integer,
+        commitment_pk: &CL03CommitmentPublicKey,
+        rng: &mut R,
+    ) -> Result<Self, Error> {
+        let w = random_bits_with_rng(CS::ln, rng);
 
-        let Cv = (v * Integer::from(
-            commitment_pk.g_bases[0]
-                .pow_mod_ref(&w, &commitment_pk.N)
-                .unwrap(),
-        )) % &commitment_pk.N;
+        let g0 = commitment_pk.g_bases.first().ok_or(Error::NotEnoughBases)?;
+        let Cv = (v * g0.pow_mod_checked(&w, &commitment_pk.N)?) % &commitment_pk.N;
 
-        Self::CL03(CL03Commitment {
+        Ok(Self::CL03(CL03Commitment {
             value: Cv,
             randomness: w,
-        })
+        }))
     }
 
     pub fn commit_with_pk(
@@ -50,67 +59,89 @@ impl<CS: CLCiphersuite> Commitment<CL03<CS>> {
         pk: &CL03PublicKey,
         a_bases: &Bases,
         unrevealed_message_indexes: Option<&[usize]>,
-    ) -> Self {
+    ) -> Result<Self, Error> {
+        Self::commit_with_pk_with_rng(
+            messages,
+            pk,
+            a_bases,
+            unrevealed_message_indexes,
+            &mut thread_rng(),
+        )
+    }
+
+    /// Same as [`Self::commit_with_pk`], drawing the randomness of the commitment from `rng`
+    pub fn commit_with_pk_with_rng<R: RngCore + CryptoRng>(
+        messages: &[CL03Message],
+        pk: &CL03PublicKey,
+        a_bases: &Bases,
+        unrevealed_message_indexes: Option<&[usize]>,
+        rng: &mut R,
+    ) -> Result<Self, Error> {
         let unrevealed_message_indexes: Vec<usize> = match unrevealed_message_indexes {
             Some(indexes) => indexes.to_vec(),
             None => (0..messages.len()).collect(),
         };
 
-        let r = random_bits(CS::ln);
+        let r = random_bits_with_rng(CS::ln, rng);
         let mut Cx = Integer::from(1);
 
         for i in unrevealed_message_indexes {
-            let ai = a_bases
-                .0
-                .get(i)
-                .and_then(|a| {
-                    return Some(a);
-                })
-                .expect("Invalid unrevealed message index!");
-            let mi = &messages[i];
-            Cx = Cx * Integer::from(ai.pow_mod_ref(&mi.get_value(), &pk.N).unwrap());
+            let ai = a_bases.0.get(i).ok_or(Error::InvalidMessageIndex)?;
+            let mi = messages.get(i).ok_or(Error::InvalidMessageIndex)?;
+            Cx = Cx * ai.pow_mod_checked(&mi.get_value(), &pk.N)?;
         }
 
-        Cx = (Cx * Integer::from(pk.b.pow_mod_ref(&r, &pk.N).unwrap())) % &pk.N;
+        Cx = (Cx * pk.b.pow_mod_checked(&r, &pk.N)?) % &pk.N;
 
-        Self::CL03(CL03Commitment {
+        Ok(Self::CL03(CL03Commitment {
             value: Cx,
             randomness: r,
-        })
+        }))
     }
 
     pub fn commit_with_commitment_pk(
         messages: &[CL03Message],
         commitment_pk: &CL03CommitmentPublicKey,
         unrevealed_message_indexes: Option<&[usize]>,
-    ) -> Self {
+    ) -> Result<Self, Error> {
+        Self::commit_with_commitment_pk_with_rng(
+            messages,
+            commitment_pk,
+            unrevealed_message_indexes,
+            &mut thread_rng(),
+        )
+    }
+
+    /// Same as [`Self::commit_with_commitment_pk`], drawing the randomness of the commitment from `rng`
+    pub fn commit_with_commitment_pk_with_rng<R: RngCore + CryptoRng>(
+        messages: &[CL03Message],
+        commitment_pk: &CL03CommitmentPublicKey,
+        unrevealed_message_indexes: Option<&[usize]>,
+        rng: &mut R,
+    ) -> Result<Self, Error> {
         let unrevealed_message_indexes: Vec<usize> = match unrevealed_message_indexes {
             Some(indexes) => indexes.to_vec(),
             None => (0..messages.len()).collect(),
         };
 
-        let r = random_bits(CS::ln);
+        let r = random_bits_with_rng(CS::ln, rng);
         let mut Cx = Integer::from(1);
 
         for i in unrevealed_message_indexes {
             let ai = commitment_pk
                 .g_bases
                 .get(i)
-                .and_then(|a| {
-                    return Some(a);
-                })
-                .expect("Invalid unrevealed message index!");
-            let mi = &messages[i];
-            Cx = Cx * Integer::from(ai.pow_mod_ref(&mi.get_value(), &commitment_pk.N).unwrap());
+                .ok_or(Error::InvalidMessageIndex)?;
+            let mi = messages.get(i).ok_or(Error::InvalidMessageIndex)?;
+            Cx = Cx * ai.pow_mod_checked(&mi.get_value(), &commitment_pk.N)?;
         }
 
-        Cx = (Cx * Integer::from(commitment_pk.h.pow_mod_ref(&r, &commitment_pk.N).unwrap()))
-            % &commitment_pk.N;
+        Cx = (Cx * commitment_pk.h.pow_mod_checked(&r, &commitment_pk.N)?) % &commitment_pk.N;
 
-        Self::CL03(CL03Commitment {
+        Ok(Self::CL03(CL03Commitment {
             value: Cx,
             randomness: r,
-        })
+        }))
     }
 
     pub fn extend_commitment_with_pk(
@@ -119,7 +150,7 @@ impl<CS: CLCiphersuite> Commitment<CL03<CS>> {
         pk: &CL03PublicKey,
         a_bases: &Bases,
         revealed_message_indexes: Option<&[usize]>,
-    ) {
+    ) -> Result<(), Error> {
         // let mut extended_Cx = self.value().clone();
         let revealed_message_indexes: Vec<usize> = match revealed_message_indexes {
             Some(indexes) => indexes.to_vec(),
@@ -127,29 +158,23 @@ impl<CS: CLCiphersuite> Commitment<CL03<CS>> {
         };
 
         if revealed_message_indexes.len() != revealed_messages.len() {
-            panic!("Number of revealed messages not corresponds to the number of revelead message indexes!");
+            return Err(Error::InvalidMessageIndex);
         }
 
         let extended_Cx = self.cl03Commitment_mut();
         let mut extended_Cx_value = extended_Cx.value.clone();
         let mut index = 0usize;
         for i in revealed_message_indexes {
-            let ai = a_bases
-                .0
-                .get(i)
-                .and_then(|a| {
-                    return Some(a);
-                })
-                .expect("Invalid revealed message index!");
-            let mi = &revealed_messages.get(index).expect("Index overflow");
-            extended_Cx_value = (extended_Cx_value
-                * Integer::from(ai.pow_mod_ref(&mi.get_value(), &pk.N).unwrap()))
-                % &pk.N;
+            let ai = a_bases.0.get(i).ok_or(Error::InvalidMessageIndex)?;
+            let mi = &revealed_messages[index];
+            extended_Cx_value =
+                (extended_Cx_value * ai.pow_mod_checked(&mi.get_value(), &pk.N)?) % &pk.N;
             index += 1;
         }
 
         extended_Cx.value = extended_Cx_value;
         // self.set_value(extended_Cx);
+        Ok(())
     }
 
     //TODO: Forse da cambiare (messages sono revealed_messages e non tutti i messages)
@@ -158,7 +183,7 @@ impl<CS: CLCiphersuite> Commitment<CL03<CS>> {
         messages: &[CL03Message],
         commitment_pk: &CL03CommitmentPublicKey,
         revealed_message_indexes: Option<&[usize]>,
-    ) {
+    ) -> Result<(), Error> {
         // let mut extended_Cx = self.value().clone();
         let revealed_message_indexes: Vec<usize> = match revealed_message_indexes {
             Some(indexes) => indexes.to_vec(),
@@ -170,18 +195,16 @@ impl<CS: CLCiphersuite> Commitment<CL03<CS>> {
             let ai = commitment_pk
                 .g_bases
                 .get(i)
-                .and_then(|a| {
-                    return Some(a);
-                })
-                .expect("Invalid revealed message index!");
-            let mi = &messages[i];
+                .ok_or(Error::InvalidMessageIndex)?;
+            let mi = messages.get(i).ok_or(Error::InvalidMessageIndex)?;
             extended_Cx_value = (extended_Cx_value
-                * Integer::from(ai.pow_mod_ref(&mi.get_value(), &commitment_pk.N).unwrap()))
+                * ai.pow_mod_checked(&mi.get_value(), &commitment_pk.N)?)
                 % &commitment_pk.N;
         }
 
         extended_Cx.value = extended_Cx_value;
         // self.set_value(extended_Cx);
+        Ok(())
     }
 
     pub fn value(&self) -> &Integer {
@@ -218,4 +241,12 @@ impl<CS: CLCiphersuite> Commitment<CL03<CS>> {
             _ => panic!("Cannot happen!"),
         }
     }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        encoding::to_bytes(self.cl03Commitment())
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        Ok(Self::CL03(encoding::from_bytes(bytes)?))
+    }
 }
@@ -0,0 +1,272 @@
+// Copyright 2023 Fondazione LINKS
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Canonical binary encoding of the CL03 artifacts.
+//!
+//! Every CL03 key, signature, commitment and proof is serialized as the
+//! concatenation of its fields, in declaration order, using the following rules:
+//!
+//! - `Integer`: one sign byte (`0x00` non-negative, `0x01` negative), a 4-byte
+//!   big-endian length `L` and the `L` bytes of the big-endian magnitude.
+//!   The magnitude has no leading zero bytes, and zero is encoded with `L = 0`
+//!   and a non-negative sign.
+//! - `Vec<T>`: a 4-byte big-endian element count followed by the elements.
+//! - `Option<T>`: one byte (`0x00` absent, `0x01` present) followed by the
+//!   value when present.
+//!
+//! Decoding rejects any input that does not follow these rules, including
+//! truncated input and trailing bytes, so that every artifact has exactly one
+//! valid encoding.
+
+use crate::errors::Error;
+use rug::{integer::Order, Integer};
+
+const SIGN_NON_NEGATIVE: u8 = 0x00;
+const SIGN_NEGATIVE: u8 = 0x01;
+const OPTION_NONE: u8 = 0x00;
+const OPTION_SOME: u8 = 0x01;
+
+/// Types with a canonical binary encoding.
+pub(crate) trait CanonicalEncoding: Sized {
+    /// Appends the encoding of `self` to `out`.
+    fn write(&self, out: &mut Vec<u8>);
+
+    /// Reads a value from the front of `reader`.
+    fn read(reader: &mut ByteReader) -> Result<Self, Error>;
+}
+
+/// Cursor over an encoded byte string.
+pub(crate) struct ByteReader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> ByteReader<'a> {
+    pub(crate) fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes }
+    }
+
+    fn take(&mut self, n: usize) -> Result<&'a [u8], Error> {
+        if self.bytes.len() < n {
+            return Err(Error::DeserializationError(
+                "Unexpected end of CL03 encoding".to_owned(),
+            ));
+        }
+        let (head, tail) = self.bytes.split_at(n);
+        self.bytes = tail;
+        Ok(head)
+    }
+
+    fn read_u8(&mut self) -> Result<u8, Error> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn read_len(&mut self) -> Result<usize, Error> {
+        let bytes = self.take(4)?;
+        Ok(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as usize)
+    }
+}
+
+fn write_len(out: &mut Vec<u8>, len: usize) {
+    let len = u32::try_from(len).expect("CL03 encoding length exceeds u32::MAX");
+    out.extend_from_slice(&len.to_be_bytes());
+}
+
+impl CanonicalEncoding for Integer {
+    fn write(&self, out: &mut Vec<u8>) {
+        let sign = if self.is_negative() {
+            SIGN_NEGATIVE
+        } else {
+            SIGN_NON_NEGATIVE
+        };
+        let magnitude = self.to_digits::<u8>(Order::MsfBe);
+        out.push(sign);
+        write_len(out, magnitude.len());
+        out.extend_from_slice(&magnitude);
+    }
+
+    fn read(reader: &mut ByteReader) -> Result<Self, Error> {
+        let sign = reader.read_u8()?;
+        let len = reader.read_len()?;
+        let magnitude = reader.take(len)?;
+
+        if magnitude.first() == Some(&0) {
+            return Err(Error::DeserializationError(
+                "Non-minimal CL03 integer encoding".to_owned(),
+            ));
+        }
+
+        let value = Integer::from_digits(magnitude, Order::MsfBe);
+        match sign {
+            SIGN_NON_NEGATIVE => Ok(value),
+            SIGN_NEGATIVE if len > 0 => Ok(-value),
+            _ => Err(Error::DeserializationError(
+                "Invalid CL03 integer sign".to_owned(),
+            )),
+        }
+    }
+}
+
+impl<T: CanonicalEncoding> CanonicalEncoding for Vec<T> {
+    fn write(&self, out: &mut Vec<u8>) {
+        write_len(out, self.len());
+        for item in self {
+            item.write(out);
+        }
+    }
+
+    fn read(reader: &mut ByteReader) -> Result<Self, Error> {
+        let count = reader.read_len()?;
+        // Do not trust `count` for the allocation: every element takes at least one byte
+        let mut items = Vec::with_capacity(count.min(reader.bytes.len()));
+        for _ in 0..count {
+            items.push(T::read(reader)?);
+        }
+        Ok(items)
+    }
+}
+
+impl<T: CanonicalEncoding> CanonicalEncoding for Option<T> {
+    fn write(&self, out: &mut Vec<u8>) {
+        match self {
+            None => out.push(OPTION_NONE),
+            Some(value) => {
+                out.push(OPTION_SOME);
+                value.write(out);
+            }
+        }
+    }
+
+    fn read(reader: &mut ByteReader) -> Result<Self, Error> {
+        match reader.read_u8()? {
+            OPTION_NONE => Ok(None),
+            OPTION_SOME => Ok(Some(T::read(reader)?)),
+            _ => Err(Error::DeserializationError(
+                "Invalid CL03 option tag".to_owned(),
+            )),
+        }
+    }
+}
+
+/// Encodes `value` in its canonical form.
+pub(crate) fn to_bytes<T: CanonicalEncoding>(value: &T) -> Vec<u8> {
+    let mut out = Vec::new();
+    value.write(&mut out);
+    out
+}
+
+/// Decodes a value from `bytes`, rejecting any trailing data.
+pub(crate) fn from_bytes<T: CanonicalEncoding>(bytes: &[u8]) -> Result<T, Error> {
+    let mut reader = ByteReader::new(bytes);
+    let value = T::read(&mut reader)?;
+    if !reader.bytes.is_empty() {
+        return Err(Error::DeserializationError(
+            "Trailing bytes after CL03 encoding".to_owned(),
+        ));
+    }
+    Ok(value)
+}
+
+/// Implements [`CanonicalEncoding`] for a struct by encoding its fields in order.
+macro_rules! impl_canonical_encoding {
+    ($ty:ty { $($field:ident),+ $(,)? }) => {
+        impl $crate::cl03::encoding::CanonicalEncoding for $ty {
+            fn write(&self, out: &mut Vec<u8>) {
+                $($crate::cl03::encoding::CanonicalEncoding::write(&self.$field, out);)+
+            }
+
+            fn read(
+                reader: &mut $crate::cl03::encoding::ByteReader,
+            ) -> Result<Self, $crate::errors::Error> {
+                Ok(Self {
+                    $($field: $crate::cl03::encoding::CanonicalEncoding::read(reader)?,)+
+                })
+            }
+        }
+    };
+}
+
+pub(crate) use impl_canonical_encoding;
+
+#[cfg(test)]
+mod tests {
+    use super::{from_bytes, to_bytes};
+    use crate::errors::Error;
+    use rug::Integer;
+
+    #[test]
+    fn integer_round_trip() {
+        for value in [
+            Integer::ZERO,
+            Integer::from(1),
+            Integer::from(-1),
+            Integer::from(256),
+            Integer::from(-65537),
+            Integer::from(u128::MAX) * 3,
+        ] {
+            let bytes = to_bytes(&value);
+            assert_eq!(from_bytes::<Integer>(&bytes).unwrap(), value);
+        }
+        assert_eq!(to_bytes(&Integer::ZERO), vec![0, 0, 0, 0, 0]);
+        assert_eq!(to_bytes(&Integer::from(-2)), vec![1, 0, 0, 0, 1, 2]);
+    }
+
+    #[test]
+    fn vec_and_option_round_trip() {
+        let values = vec![Integer::from(7), Integer::from(-300)];
+        assert_eq!(
+            from_bytes::<Vec<Integer>>(&to_bytes(&values)).unwrap(),
+            values
+        );
+
+        let some = Some(Integer::from(42));
+        assert_eq!(
+            from_bytes::<Option<Integer>>(&to_bytes(&some)).unwrap(),
+            some
+        );
+        let none: Option<Integer> = None;
+        assert_eq!(
+            from_bytes::<Option<Integer>>(&to_bytes(&none)).unwrap(),
+            none
+        );
+    }
+
+    #[test]
+    fn malformed_input_is_rejected() {
+        let invalid: &[&[u8]] = &[
+            // empty input
+            &[],
+            // truncated length
+            &[0, 0, 0],
+            // truncated magnitude
+            &[0, 0, 0, 0, 2, 1],
+            // leading zero byte
+            &[0, 0, 0, 0, 2, 0, 1],
+            // negative zero
+            &[1, 0, 0, 0, 0],
+            // unknown sign
+            &[2, 0, 0, 0, 1, 1],
+            // trailing bytes
+            &[0, 0, 0, 0, 1, 1, 0],
+        ];
+        for bytes in invalid {
+            assert!(matches!(
+                from_bytes::<Integer>(bytes),
+                Err(Error::DeserializationError(_))
+            ));
+        }
+
+        assert!(from_bytes::<Option<Integer>>(&[2]).is_err());
+        assert!(from_bytes::<Vec<Integer>>(&[0xff, 0xff, 0xff, 0xff]).is_err());
+    }
+}
@@ -13,19 +13,26 @@
 // limitations under the License.
 
 use crate::{
-    cl03::ciphersuites::CLCiphersuite,
+    cl03::{
+        ciphersuites::CLCiphersuite,
+        encoding::{self, impl_canonical_encoding},
+    },
+    errors::Error,
     keys::{
         pair::KeyPair,
         traits::{PrivateKey, PublicKey},
     },
-    schemes::algorithms::{Scheme, CL03},
-    utils::random::{random_number, random_prime, random_qr},
-};
-use rug::{
-    integer::{IsPrime, Order},
-    Integer,
+    schemes::algorithms::CL03,
+    utils::{
+        random::{random_number_with_rng, random_prime_with_rng, random_qr_with_rng},
+        util::cl03_utils::zeroize_integer,
+    },
 };
+use rand::{thread_rng, CryptoRng, RngCore};
+use rug::{integer::IsPrime, Integer};
 use serde::{Deserialize, Serialize};
+use std::fmt;
+use zeroize::{Zeroize, ZeroizeOnDrop};
 
 #[derive(Clone, PartialEq, PartialOrd, Eq, Hash, Debug, Ord, Serialize, Deserialize)]
 pub struct CL03PublicKey {
@@ -39,47 +46,19 @@ impl CL03PublicKey {
         Self { N, b, c }
     }
 
-    pub fn to_bytes<S: Scheme>(&self) -> Vec<u8>
-    where
-        S: Scheme,
-        S::Ciphersuite: CLCiphersuite,
-    {
-        let mut bytes: Vec<u8> = Vec::new();
-        let mut N_digits = vec![0u8; <S as Scheme>::Ciphersuite::ln as usize];
-        let mut b_digits = vec![0u8; <S as Scheme>::Ciphersuite::ln as usize];
-        let mut c_digits = vec![0u8; <S as Scheme>::Ciphersuite::ln as usize];
-        self.N.write_digits(&mut N_digits, Order::MsfBe);
-        self.b.write_digits(&mut b_digits, Order::MsfBe);
-        self.c.write_digits(&mut c_digits, Order::MsfBe);
-        bytes.extend_from_slice(&N_digits);
-        bytes.extend_from_slice(&b_digits);
-        bytes.extend_from_slice(&c_digits);
-
-        bytes
+    pub fn to_bytes(&self) -> Vec<u8> {
+        encoding::to_bytes(self)
     }
 
-    pub fn from_bytes<S: Scheme>(bytes: &[u8]) -> Self
-    where
-        S: Scheme,
-        S::Ciphersuite: CLCiphersuite,
-    {
-        // let delta: usize = (<S as Scheme>::Ciphersuite::SECPARAM as usize) / 8usize + 1usize;
-        let N_len = <S as Scheme>::Ciphersuite::ln as usize;
-
-        let len = bytes.len();
-        if len < 3 * N_len || (len - (3 * N_len)) % (N_len) != 0 {
-            panic!("Invalid number of bytes submitted!");
-        }
-
-        let N = Integer::from_digits(&bytes[0usize..N_len], Order::MsfBe);
-        let b = Integer::from_digits(&bytes[N_len..2 * N_len], Order::MsfBe);
-        let c = Integer::from_digits(&bytes[2 * N_len..3 * N_len], Order::MsfBe);
-
-        Self { N, b, c }
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        encoding::from_bytes(bytes).map_err(|_| Error::KeyDeserializationError)
     }
 }
 
-#[derive(Clone, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
+impl_canonical_encoding!(CL03PublicKey { N, b, c });
+
+/// CL03 secret key, i.e. the safe primes `p` and `q` factoring `N`.
+#[derive(Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct CL03SecretKey {
     pub p: Integer,
     pub q: Integer,
@@ -90,45 +69,47 @@ impl CL03SecretKey {
         Self { p, q }
     }
 
-    pub fn to_bytes<S>(&self) -> Vec<u8>
-    where
-        S: Scheme,
-        S::Ciphersuite: CLCiphersuite,
-    {
-        let mut bytes: Vec<u8> = Vec::new();
-        let mut p_digits =
-            vec![0u8; (<S as Scheme>::Ciphersuite::SECPARAM as usize) / 8usize + 1usize];
-        let mut q_digits =
-            vec![0u8; (<S as Scheme>::Ciphersuite::SECPARAM as usize) / 8usize + 1usize];
-        self.p.write_digits(&mut p_digits, Order::MsfBe);
-        self.q.write_digits(&mut q_digits, Order::MsfBe);
-        bytes.extend_from_slice(&p_digits);
-        bytes.extend_from_slice(&q_digits);
-        bytes
+    pub fn to_bytes(&self) -> Vec<u8> {
+        encoding::to_bytes(self)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        encoding::from_bytes(bytes).map_err(|_| Error::KeyDeserializationError)
     }
+}
 
-    pub fn from_bytes<S: Scheme>(bytes: &[u8]) -> Self
-    where
-        S: Scheme,
-        S::Ciphersuite: CLCiphersuite,
-    {
-        let delta = (<S as Scheme>::Ciphersuite::SECPARAM as usize) / 8usize + 1usize;
-        let p = Integer::from_digits(&bytes[0usize..delta], Order::MsfBe);
-        let q = Integer::from_digits(&bytes[delta..(2 * delta)], Order::MsfBe);
+impl Zeroize for CL03SecretKey {
+    fn zeroize(&mut self) {
+        zeroize_integer(&mut self.p);
+        zeroize_integer(&mut self.q);
+    }
+}
 
-        Self { p, q }
+impl Drop for CL03SecretKey {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for CL03SecretKey {}
+
+impl fmt::Debug for CL03SecretKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("CL03SecretKey { p: <redacted>, q: <redacted> }")
     }
 }
 
+impl_canonical_encoding!(CL03SecretKey { p, q });
+
 impl PublicKey for CL03PublicKey {
-    type Output = [u8; 512];
+    type Output = Vec<u8>;
     // type Params = (Integer, Integer, Integer, Vec<(Integer, bool)>);
     fn encode(&self) -> String {
-        todo!()
+        hex::encode(PublicKey::to_bytes(self))
     }
 
     fn to_bytes(&self) -> Self::Output {
-        todo!()
+        CL03PublicKey::to_bytes(self)
     }
 
     // fn get_params(&self) -> (Integer, Integer, Integer, Vec<(Integer, bool)>) {
@@ -137,36 +118,41 @@ impl PublicKey for CL03PublicKey {
 }
 
 impl PrivateKey for CL03SecretKey {
-    type Output = [u8; 512];
+    type Output = Vec<u8>;
     fn encode(&self) -> String {
-        todo!()
+        hex::encode(PrivateKey::to_bytes(self))
     }
 
     fn to_bytes(&self) -> Self::Output {
-        todo!()
+        CL03SecretKey::to_bytes(self)
     }
 }
 
 impl<CS: CLCiphersuite> KeyPair<CL03<CS>> {
     pub fn generate() -> Self {
+        Self::generate_with_rng(&mut thread_rng())
+    }
+
+    /// Generates a keypair, drawing the safe primes and the bases from `rng`
+    pub fn generate_with_rng<R: RngCore + CryptoRng>(rng: &mut R) -> Self {
         let n = CS::SECPARAM;
-        let mut pprime = random_prime(n);
+        let mut pprime = random_prime_with_rng(n, rng);
         let mut p = Integer::from(2) * pprime.clone() + Integer::from(1);
         loop {
             if p.is_probably_prime(50) != IsPrime::No {
                 break;
             }
-            pprime = random_prime(n);
+            pprime = random_prime_with_rng(n, rng);
             p = Integer::from(2) * pprime + Integer::from(1);
         }
 
-        let mut qprime = random_prime(n);
+        let mut qprime = random_prime_with_rng(n, rng);
         let mut q = Integer::from(2) * qprime.clone() + Integer::from(1);
         loop {
             if p != q && q.is_probably_prime(100) != IsPrime::No {
                 break;
             }
-            qprime = random_prime(n);
+            qprime = random_prime_with_rng(n, rng);
             q = Integer::from(2) * qprime + Integer::from(1);
         }
 
@@ -180,8 +166,8 @@ impl<CS: CLCiphersuite> KeyPair<CL03<CS>> {
         //     a_bases.push(a);
         // }
 
-        let b = random_qr(&N);
-        let c = random_qr(&N);
+        let b = random_qr_with_rng(&N, rng);
+        let c = random_qr_with_rng(&N, rng);
 
         let pk = CL03PublicKey::new(N, b, c);
         let sk = CL03SecretKey::new(p, q);
@@ -193,6 +179,21 @@ impl<CS: CLCiphersuite> KeyPair<CL03<CS>> {
     }
 }
 
+#[cfg(test)]
+impl<CS: CLCiphersuite> KeyPair<CL03<CS>> {
+    /// Loads a precomputed key pair, stored in its canonical encoding, to avoid generating large safe primes in the tests
+    pub(crate) fn from_fixture(filename: &str) -> Self {
+        let data = std::fs::read_to_string(filename).expect("Unable to read file");
+        let keypair: serde_json::Value = serde_json::from_str(&data).expect("Unable to parse");
+        let decode = |key: &str| hex::decode(keypair[key].as_str().unwrap()).unwrap();
+
+        Self {
+            public: CL03PublicKey::from_bytes(&decode("publicKey")).unwrap(),
+            private: CL03SecretKey::from_bytes(&decode("secretKey")).unwrap(),
+        }
+    }
+}
+
 pub struct CL03CommitmentPublicKey {
     pub N: Integer,
     pub h: Integer,
@@ -203,10 +204,19 @@ impl CL03CommitmentPublicKey {
     //verifier_pk -> N = issuer_pk.N
     //trusted_party_pk -> N = None
     pub fn generate<CS: CLCiphersuite>(N: Option<Integer>, n_attributes: Option<usize>) -> Self {
+        Self::generate_with_rng::<CS, _>(N, n_attributes, &mut thread_rng())
+    }
+
+    /// Generates a commitment public key, drawing its randomness from `rng`
+    pub fn generate_with_rng<CS: CLCiphersuite, R: RngCore + CryptoRng>(
+        N: Option<Integer>,
+        n_attributes: Option<usize>,
+        rng: &mut R,
+    ) -> Self {
         let n = CS::SECPARAM; //SECPARAM
         let n_attributes = n_attributes.unwrap_or(1);
         let N = N.unwrap_or_else(|| {
-            let mut pprime = random_prime(n);
+            let mut pprime = random_prime_with_rng(n, rng);
             let mut p = Integer::from(2) * pprime.clone() + Integer::from(1);
             loop {
                 // println!("{} INT", p);
@@ -216,11 +226,11 @@ impl CL03CommitmentPublicKey {
                 if p.is_probably_prime(50) != IsPrime::No {
                     break;
                 }
-                pprime = random_prime(n);
+                pprime = random_prime_with_rng(n, rng);
                 p = Integer::from(2) * pprime + Integer::from(1);
             }
 
-            let mut qprime = random_prime(n);
+            let mut qprime = random_prime_with_rng(n, rng);
             let mut q = Integer::from(2) * qprime.clone() + Integer::from(1);
             loop {
                 // println!("{} INT", p);
@@ -230,7 +240,7 @@ impl CL03CommitmentPublicKey {
                 if p != q && q.is_probably_prime(100) != IsPrime::No {
                     break;
                 }
-                qprime = random_prime(n);
+                qprime = random_prime_with_rng(n, rng);
                 q = Integer::from(2) * qprime + Integer::from(1);
             }
 
@@ -238,12 +248,12 @@ impl CL03CommitmentPublicKey {
             N
         });
 
-        let h = random_qr(&N);
+        let h = random_qr_with_rng(&N, rng);
 
         let mut g_bases: Vec<Integer> = Vec::new();
 
         for _i in 0..n_attributes {
-            let mut f = random_number(N.clone());
+            let mut f = random_number_with_rng(N.clone(), rng);
             let mut g_i = Integer::from(h.pow_mod_ref(&f, &N).unwrap());
 
             loop {
@@ -251,7 +261,7 @@ impl CL03CommitmentPublicKey {
                     && (Integer::from(g_i.gcd_ref(&N)) == Integer::from(1)))
                     == false
                 {
-                    f = random_number(N.clone());
+                    f = random_number_with_rng(N.clone(), rng);
                     g_i = Integer::from(h.pow_mod_ref(&f, &N).unwrap());
                 } else {
                     break;
@@ -266,4 +276,14 @@ impl CL03CommitmentPublicKey {
             g_bases: g_bases,
         }
     }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        encoding::to_bytes(self)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        encoding::from_bytes(bytes).map_err(|_| Error::KeyDeserializationError)
+    }
 }
+
+impl_canonical_encoding!(CL03CommitmentPublicKey { N, h, g_bases });
@@ -16,6 +16,7 @@ pub mod bases;
 pub mod blind;
 pub mod ciphersuites;
 pub mod commitment;
+pub mod encoding;
 pub mod keys;
 pub mod proof;
 pub mod range_proof;
@@ -14,6 +14,7 @@
 
 use super::{
     commitment::CL03Commitment,
+    encoding::{self, impl_canonical_encoding},
     keys::{CL03CommitmentPublicKey, CL03PublicKey},
     signature::CL03Signature,
 };
@@ -24,11 +25,13 @@ use crate::{
         range_proof::{Boudot2000RangeProof, RangeProof},
         sigma_protocols::{NISP2Commitments, NISPMultiSecrets, NISPSecrets, NISPSignaturePoK},
     },
+    errors::Error,
     schemes::algorithms::CL03,
     schemes::generics::{Commitment, PoKSignature, ZKPoK},
     utils::message::cl03_message::CL03Message,
 };
-use digest::{Digest};
+use digest::Digest;
+use rand::{thread_rng, CryptoRng, RngCore};
 use rug::{ops::Pow, Integer};
 use serde::{Deserialize, Serialize};
 
@@ -38,6 +41,8 @@ struct ProofOfValue {
     commitment: CL03Commitment,
 }
 
+impl_canonical_encoding!(ProofOfValue { value, commitment });
+
 #[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
 pub struct CL03PoKSignature {
     spok: NISPSignaturePoK,
@@ -46,6 +51,13 @@ pub struct CL03PoKSignature {
     range_proofs_commited_mi: Vec<Boudot2000RangeProof>,
 }
 
+impl_canonical_encoding!(CL03PoKSignature {
+    spok,
+    range_proof_e,
+    proofs_commited_mi,
+    range_proofs_commited_mi,
+});
+
 impl<CS: CLCiphersuite> PoKSignature<CL03<CS>> {
     pub fn proof_gen(
         signature: &CL03Signature,
@@ -54,7 +66,31 @@ impl<CS: CLCiphersuite> PoKSignature<CL03<CS>> {
         a_bases: &Bases,
         messages: &[CL03Message],
         unrevealed_message_indexes: &[usize],
-    ) -> Self
+    ) -> Result<Self, Error>
+    where
+        CS::HashAlg: Digest,
+    {
+        Self::proof_gen_with_rng(
+            signature,
+            commitment_pk,
+            signer_pk,
+            a_bases,
+            messages,
+            unrevealed_message_indexes,
+            &mut thread_rng(),
+        )
+    }
+
+    /// Same as [`Self::proof_gen`], drawing the blindings of the proofs from `rng`
+    pub fn proof_gen_with_rng<R: RngCore + CryptoRng>(
+        signature: &CL03Signature,
+        commitment_pk: &CL03CommitmentPublicKey,
+        signer_pk: &CL03PublicKey,
+        a_bases: &Bases,
+        messages: &[CL03Message],
+        unrevealed_message_indexes: &[usize],
+        rng: &mut R,
+    ) -> Result<Self, Error>
     where
         CS::HashAlg: Digest,
     {
@@ -63,18 +99,19 @@ impl<CS: CLCiphersuite> PoKSignature<CL03<CS>> {
         let min_x = Integer::from(0);
         let max_x = Integer::from(2).pow(CS::lm) - 1;
 
-        let spok = NISPSignaturePoK::nisp5_MultiAttr_generate_proof::<CS>(
+        let spok = NISPSignaturePoK::nisp5_MultiAttr_generate_proof::<CS, _>(
             signature,
             commitment_pk,
             signer_pk,
             a_bases,
             messages,
             unrevealed_message_indexes,
-        );
+            rng,
+        )?;
 
         //range proof e
         let r_proof_e = match CS::RANGEPROOF_ALG {
-            RangeProof::Boudot2000 => Boudot2000RangeProof::prove::<CS::HashAlg>(
+            RangeProof::Boudot2000 => Boudot2000RangeProof::prove_with_rng::<CS, _>(
                 &signature.e,
                 &spok.Ce,
                 &commitment_pk.g_bases[0],
@@ -82,57 +119,60 @@ impl<CS: CLCiphersuite> PoKSignature<CL03<CS>> {
                 &commitment_pk.N,
                 &min_e,
                 &max_e,
-            ),
+                rng,
+            )?,
         };
 
         let mut proofs_mi: Vec<ProofOfValue> = Vec::new();
         let mut r_proofs_mi: Vec<Boudot2000RangeProof> = Vec::new();
         for i in unrevealed_message_indexes {
-            let mi = messages
+            let mi = messages.get(*i).ok_or(Error::InvalidMessageIndex)?;
+            let gi = commitment_pk
+                .g_bases
                 .get(*i)
-                .expect("unrevealed_message_indexes not valid with respect to the messages!");
-            let gi = &commitment_pk.g_bases.get(*i).expect(
-                "unrevealed_message_indexes not valid with respect to the commitment_pk.g_bases!",
-            );
-            let cmi = Commitment::<CL03<CS>>::commit_with_commitment_pk(
+                .ok_or(Error::InvalidMessageIndex)?;
+            let cmi = Commitment::<CL03<CS>>::commit_with_commitment_pk_with_rng(
                 messages,
                 commitment_pk,
                 Some(&[*i]),
-            )
+                rng,
+            )?
             .cl03Commitment()
             .to_owned();
-            let proof_mi_ri = NISPSecrets::nisp2sec_generate_proof::<CS>(
+            let proof_mi_ri = NISPSecrets::nisp2sec_generate_proof::<CS, _>(
                 mi,
                 &cmi,
-                &gi,
+                gi,
                 &commitment_pk.h,
                 &commitment_pk.N,
-            );
+                rng,
+            )?;
             proofs_mi.push(ProofOfValue {
                 value: proof_mi_ri,
                 commitment: cmi.clone(),
             });
             let r_proof_mi = match CS::RANGEPROOF_ALG {
-                RangeProof::Boudot2000 => Boudot2000RangeProof::prove::<CS::HashAlg>(
+                RangeProof::Boudot2000 => Boudot2000RangeProof::prove_with_rng::<CS, _>(
                     &mi.value,
                     &cmi,
-                    &gi,
+                    gi,
                     &commitment_pk.h,
                     &commitment_pk.N,
                     &min_x,
                     &max_x,
-                ),
+                    rng,
+                )?,
             };
 
             r_proofs_mi.push(r_proof_mi);
         }
 
-        Self::CL03(CL03PoKSignature {
+        Ok(Self::CL03(CL03PoKSignature {
             spok,
             range_proof_e: r_proof_e,
             proofs_commited_mi: proofs_mi,
             range_proofs_commited_mi: r_proofs_mi,
-        })
+        }))
     }
 
     pub fn proof_verify(
@@ -143,7 +183,7 @@ impl<CS: CLCiphersuite> PoKSignature<CL03<CS>> {
         messages: &[CL03Message],
         unrevealed_message_indexes: &[usize],
         n_signed_messages: usize,
-    ) -> bool
+    ) -> Result<(), Error>
     where
         CS::HashAlg: Digest,
     {
@@ -152,77 +192,84 @@ impl<CS: CLCiphersuite> PoKSignature<CL03<CS>> {
         let min_x = Integer::from(0);
         let max_x = Integer::from(2).pow(CS::lm) - 1;
         let CLSPoK = self.to_cl03_proof();
-        let boolean_spok = NISPSignaturePoK::nisp5_MultiAttr_verify_proof::<CS>(
-            &CLSPoK.spok,
+
+        if CLSPoK.proofs_commited_mi.len() != unrevealed_message_indexes.len()
+            || CLSPoK.range_proofs_commited_mi.len() != unrevealed_message_indexes.len()
+        {
+            return Err(Error::PoKSVerificationError(
+                "Number of proofs on the undisclosed messages does not match the indexes"
+                    .to_owned(),
+            ));
+        }
+
+        let valid_spok = CLSPoK.spok.nisp5_MultiAttr_verify_proof::<CS>(
             commitment_pk,
             signer_pk,
             a_bases,
             messages,
             unrevealed_message_indexes,
             n_signed_messages,
-        );
-        if !boolean_spok {
-            println!("Signature PoK Failed!");
-            return false;
+        )?;
+        if !valid_spok {
+            return Err(Error::PoKSVerificationError(
+                "Signature PoK Failed!".to_owned(),
+            ));
+        }
+
+        if CLSPoK.spok.Ce.value != CLSPoK.range_proof_e.E {
+            return Err(Error::PoKSVerificationError(
+                "Commitment on 'e' used in the SPoK different from the one used in the Range Proof!"
+                    .to_owned(),
+            ));
         }
-        if CLSPoK.spok.Ce.value == CLSPoK.range_proof_e.E {
-            //Verify RANGE PROOFS e
-            let boolean_rproof_e = CLSPoK.range_proof_e.verify::<CS::HashAlg>(
+
+        //Verify RANGE PROOFS e
+        CLSPoK
+            .range_proof_e
+            .verify::<CS>(
                 &commitment_pk.g_bases[0],
                 &commitment_pk.h,
                 &commitment_pk.N,
                 &min_e,
                 &max_e,
-            );
-
-            if boolean_rproof_e {
-                //Verify RANGE PROOFS mi
-                let mut idx: usize = 0;
-                for i in unrevealed_message_indexes {
-                    let gi = &commitment_pk.g_bases.get(*i).expect("unrevealed_message_indexes not valid with respect to the commitment_pk.g_bases!");
-                    let ProofOfValue {
-                        value: proof_mi,
-                        commitment: cmi,
-                    } = CLSPoK.proofs_commited_mi.get(idx).expect("index overflow");
-
-                    let boolean_proof_mi = proof_mi.nisp2sec_verify_proof::<CS>(
-                        &cmi,
-                        gi,
-                        &commitment_pk.h,
-                        &commitment_pk.N,
-                    );
-                    if !boolean_proof_mi {
-                        println!("Knowledge verification of mi Failed!");
-                        return false;
-                    }
-
-                    let boolean_rproofs_mi = CLSPoK
-                        .range_proofs_commited_mi
-                        .get(idx)
-                        .expect("index overflow")
-                        .verify::<CS::HashAlg>(
-                            &gi,
-                            &commitment_pk.h,
-                            &commitment_pk.N,
-                            &min_x,
-                            &max_x,
-                        );
-                    if !boolean_rproofs_mi {
-                        println!("Range proof verification on mi Failed!");
-                        return false;
-                    }
-                    idx += 1;
-                }
-            } else {
-                println!("Range proof verification on e Failed!");
-                return false;
+            )
+            .map_err(|_| {
+                Error::PoKSVerificationError("Range proof verification on e Failed!".to_owned())
+            })?;
+
+        //Verify RANGE PROOFS mi
+        for (idx, i) in unrevealed_message_indexes.iter().enumerate() {
+            let gi = commitment_pk
+                .g_bases
+                .get(*i)
+                .ok_or(Error::InvalidMessageIndex)?;
+            let ProofOfValue {
+                value: proof_mi,
+                commitment: cmi,
+            } = &CLSPoK.proofs_commited_mi[idx];
+
+            let valid_proof_mi = proof_mi.nisp2sec_verify_proof::<CS>(
+                cmi,
+                gi,
+                &commitment_pk.h,
+                &commitment_pk.N,
+            )?;
+            if !valid_proof_mi {
+                return Err(Error::PoKSVerificationError(
+                    "Knowledge verification of mi Failed!".to_owned(),
+                ));
             }
-        } else {
-            println!("Commitment on 'e' used in the SPoK different from the one used in the Range Proof!");
-            return false;
+
+            CLSPoK.range_proofs_commited_mi[idx]
+                .verify::<CS>(gi, &commitment_pk.h, &commitment_pk.N, &min_x, &max_x)
+                .map_err(|_| {
+                    Error::PoKSVerificationError(
+                        "Range proof verification on mi Failed!".to_owned(),
+                    )
+                })?;
         }
 
-        true
+        Ok(())
     }
 
     pub fn to_cl03_proof(&self) -> &CL03PoKSignature {
@@ -231,6 +278,14 @@ impl<CS: CLCiphersuite> PoKSignature<CL03<CS>> {
             _ => panic!("Cannot happen!"),
         }
     }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        encoding::to_bytes(self.to_cl03_proof())
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        Ok(Self::CL03(encoding::from_bytes(bytes)?))
+    }
 }
 
 #[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
@@ -243,6 +298,15 @@ pub struct CL03ZKPoK {
     range_proof_r: Boudot2000RangeProof,
 }
 
+impl_canonical_encoding!(CL03ZKPoK {
+    proof_C_Ctrusted,
+    proof_commited_msgs,
+    proofs_commited_mi,
+    range_proofs_mi,
+    proof_r,
+    range_proof_r,
+});
+
 impl CL03ZKPoK {}
 
 impl<CS: CLCiphersuite> ZKPoK<CL03<CS>> {
@@ -254,14 +318,45 @@ impl<CS: CLCiphersuite> ZKPoK<CL03<CS>> {
         a_bases: &Bases,
         commitment_pk: Option<&CL03CommitmentPublicKey>,
         unrevealed_message_indexes: &[usize],
-    ) -> Self
+    ) -> Result<Self, Error>
     where
         CS::HashAlg: Digest,
     {
+        Self::generate_proof_with_rng(
+            messages,
+            C,
+            C_trusted,
+            signer_pk,
+            a_bases,
+            commitment_pk,
+            unrevealed_message_indexes,
+            &mut thread_rng(),
+        )
+    }
+
+    /// Same as [`Self::generate_proof`], drawing the blindings of the proofs from `rng`
+    pub fn generate_proof_with_rng<R: RngCore + CryptoRng>(
+        messages: &[CL03Message],
+        C: &CL03Commitment,
+        C_trusted: Option<&CL03Commitment>,
+        signer_pk: &CL03PublicKey,
+        a_bases: &Bases,
+        commitment_pk: Option<&CL03CommitmentPublicKey>,
+        unrevealed_message_indexes: &[usize],
+        rng: &mut R,
+    ) -> Result<Self, Error>
+    where
+        CS::HashAlg: Digest,
+    {
+        let a0 = a_bases.0.first().ok_or(Error::NotEnoughBases)?;
+
         let mut proof_C_Ctrusted: Option<NISP2Commitments> = None;
         if let Some(C_trusted) = C_trusted {
             if let Some(commitment_pk) = commitment_pk {
-                proof_C_Ctrusted = Some(NISP2Commitments::nisp2_generate_proof_MultiSecrets::<CS>(
+                proof_C_Ctrusted = Some(NISP2Commitments::nisp2_generate_proof_MultiSecrets::<
+                    CS,
+                    _,
+                >(
                     messages,
                     C,
                     &C_trusted,
@@ -269,17 +364,19 @@ impl<CS: CLCiphersuite> ZKPoK<CL03<CS>> {
                     a_bases,
                     commitment_pk,
                     unrevealed_message_indexes,
-                ));
+                    rng,
+                )?);
             }
         }
 
-        let proof_msgs = NISPMultiSecrets::nispMultiSecrets_generate_proof::<CS>(
+        let proof_msgs = NISPMultiSecrets::nispMultiSecrets_generate_proof::<CS, _>(
             messages,
             C,
             signer_pk,
             a_bases,
             Some(unrevealed_message_indexes),
-        );
+            rng,
+        )?;
 
         let min_x = Integer::from(0);
         let max_x = Integer::from(2).pow(CS::lm) - 1;
@@ -287,38 +384,42 @@ impl<CS: CLCiphersuite> ZKPoK<CL03<CS>> {
         let mut proofs_mi: Vec<ProofOfValue> = Vec::new();
         let mut r_proofs_msgs: Vec<Boudot2000RangeProof> = Vec::new();
         for i in unrevealed_message_indexes {
-            let mi = messages
-                .get(*i)
-                .expect("unreaveled_message_indexes not valid with respect to the messages!");
-            let ai = &a_bases.0.get(*i).expect(
-                "unreaveled_message_indexes not valid with respect to the commitment_pk.g_bases!",
-            );
-            let cmi =
-                Commitment::<CL03<CS>>::commit_with_pk(&[mi.clone()], signer_pk, a_bases, None)
-                    .cl03Commitment()
-                    .to_owned();
-            let proof_mi = NISPSecrets::nisp2sec_generate_proof::<CS>(
+            let mi = messages.get(*i).ok_or(Error::InvalidMessageIndex)?;
+            let ai = a_bases.0.get(*i).ok_or(Error::InvalidMessageIndex)?;
+            // mi is committed with its own base ai, the one the proofs below are checked against
+            let cmi = Commitment::<CL03<CS>>::commit_with_pk_with_rng(
+                messages,
+                signer_pk,
+                a_bases,
+                Some(&[*i]),
+                rng,
+            )?
+            .cl03Commitment()
+            .to_owned();
+            let proof_mi = NISPSecrets::nisp2sec_generate_proof::<CS, _>(
                 mi,
                 &cmi,
-                &ai,
+                ai,
                 &signer_pk.b,
                 &signer_pk.N,
-            );
+                rng,
+            )?;
             proofs_mi.push(ProofOfValue {
                 value: proof_mi,
                 commitment: cmi.clone(),
             });
             match CS::RANGEPROOF_ALG {
                 RangeProof::Boudot2000 => {
-                    let r_proof_mi = Boudot2000RangeProof::prove::<CS::HashAlg>(
+                    let r_proof_mi = Boudot2000RangeProof::prove_with_rng::<CS, _>(
                         &mi.value,
                         &cmi,
-                        &ai,
+                        ai,
                         &signer_pk.b,
                         &signer_pk.N,
                         &min_x,
                         &max_x,
-                    );
+                        rng,
+                    )?;
                     r_proofs_msgs.push(r_proof_mi);
                 }
             };
@@ -328,38 +429,46 @@ impl<CS: CLCiphersuite> ZKPoK<CL03<CS>> {
         let min_r = Integer::from(0);
         let max_r = Integer::from(2).pow(CS::ln) - 1;
         let r = CL03Message::new(C.randomness.clone());
-        let cr = Commitment::<CL03<CS>>::commit_with_pk(&[r.clone()], &signer_pk, a_bases, None);
+        let cr = Commitment::<CL03<CS>>::commit_with_pk_with_rng(
+            &[r.clone()],
+            &signer_pk,
+            a_bases,
+            None,
+            rng,
+        )?;
         let proof_r = ProofOfValue {
-            value: NISPSecrets::nisp2sec_generate_proof::<CS>(
+            value: NISPSecrets::nisp2sec_generate_proof::<CS, _>(
                 &r,
                 cr.cl03Commitment(),
-                &a_bases.0[0],
+                a0,
                 &signer_pk.b,
                 &signer_pk.N,
-            ),
+                rng,
+            )?,
             commitment: cr.cl03Commitment().to_owned(),
         };
 
         let rproof_r = match CS::RANGEPROOF_ALG {
-            RangeProof::Boudot2000 => Boudot2000RangeProof::prove::<CS::HashAlg>(
+            RangeProof::Boudot2000 => Boudot2000RangeProof::prove_with_rng::<CS, _>(
                 &r.value,
                 cr.cl03Commitment(),
-                &a_bases.0[0],
+                a0,
                 &signer_pk.b,
                 &signer_pk.N,
                 &min_r,
                 &max_r,
-            ),
+                rng,
+            )?,
         };
 
-        Self::CL03(CL03ZKPoK {
+        Ok(Self::CL03(CL03ZKPoK {
             proof_C_Ctrusted,
             proof_commited_msgs: proof_msgs,
             proofs_commited_mi: proofs_mi,
             range_proofs_mi: r_proofs_msgs,
             proof_r: proof_r,
             range_proof_r: rproof_r,
-        })
+        }))
     }
 
     pub fn verify_proof(
@@ -370,19 +479,26 @@ impl<CS: CLCiphersuite> ZKPoK<CL03<CS>> {
         a_bases: &Bases,
         commitment_pk: Option<&CL03CommitmentPublicKey>,
         unrevealed_message_indexes: &[usize],
-    ) -> bool
+    ) -> Result<(), Error>
     where
         CS::HashAlg: Digest,
     {
         let zkpok = self.to_cl03_zkpok();
+        let a0 = a_bases.0.first().ok_or(Error::NotEnoughBases)?;
+
+        if zkpok.proofs_commited_mi.len() != unrevealed_message_indexes.len()
+            || zkpok.range_proofs_mi.len() != unrevealed_message_indexes.len()
+        {
+            return Err(Error::InvalidCommitmentProof);
+        }
 
-        let mut boolean_C_Ctrusted: bool = true;
         if let Some(C_trusted) = C_trusted {
             if let Some(commitment_pk) = commitment_pk {
-                boolean_C_Ctrusted = zkpok
+                //The trusted commitment must commit to the same secrets as the commitment received
+                let valid_C_Ctrusted = zkpok
                     .proof_C_Ctrusted
-                    .clone()
-                    .unwrap()
+                    .as_ref()
+                    .ok_or(Error::InvalidCommitmentProof)?
                     .nisp2_verify_proof_MultiSecrets::<CS>(
                         C,
                         C_trusted,
@@ -390,87 +506,69 @@ impl<CS: CLCiphersuite> ZKPoK<CL03<CS>> {
                         a_bases,
                         commitment_pk,
                         unrevealed_message_indexes,
-                    );
+                    )?;
+                if !valid_C_Ctrusted {
+                    return Err(Error::InvalidCommitmentProof);
+                }
             }
         }
 
-        if !boolean_C_Ctrusted {
-            println!("The trusted commitment is different from commitment received!");
-            return false;
-        }
-
-        let boolean_proof_msgs = zkpok
+        //Verification of the PoK of secrets
+        let valid_proof_msgs = zkpok
             .proof_commited_msgs
             .nispMultiSecrets_verify_proof::<CS>(
                 C,
                 signer_pk,
                 a_bases,
                 Some(unrevealed_message_indexes),
-            );
-
-        if !boolean_proof_msgs {
-            println!("Verification of the PoK of secrets Failed!");
-            return false;
+            )?;
+        if !valid_proof_msgs {
+            return Err(Error::InvalidCommitmentProof);
         }
 
         let min_x = Integer::from(0);
         let max_x = Integer::from(2).pow(CS::lm) - 1;
-        let mut idx = 0usize;
 
-        for i in unrevealed_message_indexes {
-            let ai = &a_bases
-                .0
-                .get(*i)
-                .expect("unreaveled_message_indexes not valid with respect to the messages!");
-            let proof_mi = zkpok.proofs_commited_mi.get(idx).expect("index overflow");
-            let boolean_proof_mi = proof_mi.value.nisp2sec_verify_proof::<CS>(
+        for (idx, i) in unrevealed_message_indexes.iter().enumerate() {
+            let ai = a_bases.0.get(*i).ok_or(Error::InvalidMessageIndex)?;
+            //Verification of the Proof of Knowledge of mi
+            let proof_mi = &zkpok.proofs_commited_mi[idx];
+            let valid_proof_mi = proof_mi.value.nisp2sec_verify_proof::<CS>(
                 &proof_mi.commitment,
                 ai,
                 &signer_pk.b,
                 &signer_pk.N,
-            );
-
-            if !boolean_proof_mi {
-                println!("Verification of the Proof of Knowledge of m{}. Failed!", i);
-                return false;
-            }
-            let rproof_mi = zkpok.range_proofs_mi.get(idx).expect("index overflow");
-            let boolean_rproof_mi =
-                rproof_mi.verify::<CS::HashAlg>(&ai, &signer_pk.b, &signer_pk.N, &min_x, &max_x);
-            if !boolean_rproof_mi {
-                println!("Verification of the Range Proof of m{}. Failed", i);
-                return false;
+            )?;
+            if !valid_proof_mi {
+                return Err(Error::InvalidCommitmentProof);
             }
 
-            idx += 1;
+            //Verification of the Range Proof of mi
+            zkpok.range_proofs_mi[idx]
+                .verify::<CS>(ai, &signer_pk.b, &signer_pk.N, &min_x, &max_x)
+                .map_err(|_| Error::InvalidCommitmentProof)?;
         }
 
-        let boolean_proof_r = zkpok.proof_r.value.nisp2sec_verify_proof::<CS>(
+        //Verification of the Proof of Knowledge of r
+        let valid_proof_r = zkpok.proof_r.value.nisp2sec_verify_proof::<CS>(
             &zkpok.proof_r.commitment,
-            &a_bases.0[0],
+            a0,
             &signer_pk.b,
             &signer_pk.N,
-        );
-        if !boolean_proof_r {
-            println!("Verification of the Proof of Knowledge of r. Failed!");
-            return false;
+        )?;
+        if !valid_proof_r {
+            return Err(Error::InvalidCommitmentProof);
         }
 
+        //Verification of the Range Proof of r
         let min_r = Integer::from(0);
         let max_r = Integer::from(2).pow(CS::ln) - 1;
-        let boolean_rproof_r = zkpok.range_proof_r.verify::<CS::HashAlg>(
-            &a_bases.0[0],
-            &signer_pk.b,
-            &signer_pk.N,
-            &min_r,
-            &max_r,
-        );
-        if !boolean_rproof_r {
-            println!("Verification of the Range Proof of r. Failed");
-            return false;
-        }
+        zkpok
+            .range_proof_r
+            .verify::<CS>(a0, &signer_pk.b, &signer_pk.N, &min_r, &max_r)
+            .map_err(|_| Error::InvalidCommitmentProof)?;
 
-        true
+        Ok(())
     }
 
     pub fn to_cl03_zkpok(&self) -> &CL03ZKPoK {
@@ -479,13 +577,23 @@ impl<CS: CLCiphersuite> ZKPoK<CL03<CS>> {
             _ => panic!("Cannot happen!"),
         }
     }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        encoding::to_bytes(self.to_cl03_zkpok())
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        Ok(Self::CL03(encoding::from_bytes(bytes)?))
+    }
 }
 
 #[cfg(test)]
 mod tests {
 
-    use crate::cl03::ciphersuites::CLCiphersuite;
-    use crate::schemes::algorithms::CL03_CL1024_SHA256;
+    use crate::cl03::ciphersuites::{CL1024Sha256, CLCiphersuite};
+    use crate::schemes::algorithms::{
+        CL03_CL1024_SHA256, CL03_CL2048_SHA256, CL03_CL3072_SHA384, CL03_CL3072_SHA512,
+    };
     use crate::{
         cl03::{bases::Bases, keys::CL03CommitmentPublicKey},
         keys::pair::KeyPair,
@@ -506,7 +614,31 @@ mod tests {
     //Signature Proof of Knowledge - CL1024-SHA256
     #[test]
     fn spok_cl1024_sha256() {
-        spok::<CL03_CL1024_SHA256>();
+        spok::<CL03_CL1024_SHA256>(KeyPair::<CL03<CL1024Sha256>>::generate());
+    }
+
+    //Signature Proof of Knowledge - CL2048-SHA256
+    #[test]
+    fn spok_cl2048_sha256() {
+        spok::<CL03_CL2048_SHA256>(KeyPair::from_fixture(
+            "./fixture_data_cl03/cl2048/keypair.json",
+        ));
+    }
+
+    //Signature Proof of Knowledge - CL3072-SHA384
+    #[test]
+    fn spok_cl3072_sha384() {
+        spok::<CL03_CL3072_SHA384>(KeyPair::from_fixture(
+            "./fixture_data_cl03/cl3072/keypair.json",
+        ));
+    }
+
+    //Signature Proof of Knowledge - CL3072-SHA512
+    #[test]
+    fn spok_cl3072_sha512() {
+        spok::<CL03_CL3072_SHA512>(KeyPair::from_fixture(
+            "./fixture_data_cl03/cl3072/keypair.json",
+        ));
     }
 
     fn zkpok<S: Scheme>()
@@ -552,13 +684,15 @@ mod tests {
             cl03_keypair.public_key(),
             &a_bases,
             Some(&unrevealed_message_indexes),
-        );
+        )
+        .unwrap();
         let wrong_commitment = Commitment::<CL03<S::Ciphersuite>>::commit_with_pk(
             &wrong_messages,
             cl03_keypair.public_key(),
             &a_bases,
             Some(&unrevealed_message_indexes),
-        );
+        )
+        .unwrap();
 
         let zkpok = ZKPoK::<CL03<S::Ciphersuite>>::generate_proof(
             &messages,
@@ -568,7 +702,8 @@ mod tests {
             &a_bases,
             None,
             &unrevealed_message_indexes,
-        );
+        )
+        .unwrap();
 
         let valid = zkpok.verify_proof(
             commitment.cl03Commitment(),
@@ -579,7 +714,26 @@ mod tests {
             &unrevealed_message_indexes,
         );
 
-        assert!(valid, "Error! ZKPoK verification should PASS");
+        assert!(valid.is_ok(), "Error! ZKPoK verification should PASS");
+
+        let decoded_zkpok = ZKPoK::<CL03<S::Ciphersuite>>::from_bytes(&zkpok.to_bytes()).unwrap();
+        let decoded_commitment =
+            Commitment::<CL03<S::Ciphersuite>>::from_bytes(&commitment.to_bytes()).unwrap();
+        assert!(decoded_zkpok == zkpok);
+        assert!(decoded_commitment == commitment);
+        let valid = decoded_zkpok.verify_proof(
+            decoded_commitment.cl03Commitment(),
+            None,
+            cl03_keypair.public_key(),
+            &a_bases,
+            None,
+            &unrevealed_message_indexes,
+        );
+
+        assert!(
+            valid.is_ok(),
+            "Error! Decoded ZKPoK verification should PASS"
+        );
 
         let valid = zkpok.verify_proof(
             wrong_commitment.cl03Commitment(),
@@ -590,7 +744,7 @@ mod tests {
             &unrevealed_message_indexes,
         );
 
-        assert!(!valid, "Error! ZKPok verification should FAIL");
+        assert!(valid.is_err(), "Error! ZKPok verification should FAIL");
 
         //Trusted Party Commitment
 
@@ -603,13 +757,15 @@ mod tests {
                 &messages,
                 &trusted_party_commitment_pk,
                 Some(&unrevealed_message_indexes),
-            );
+            )
+            .unwrap();
         let trusted_party_commitment_wrong =
             Commitment::<CL03<S::Ciphersuite>>::commit_with_commitment_pk(
                 &wrong_messages,
                 &trusted_party_commitment_pk,
                 Some(&unrevealed_message_indexes),
-            );
+            )
+            .unwrap();
 
         let zkpok2 = ZKPoK::<CL03<S::Ciphersuite>>::generate_proof(
             &messages,
@@ -619,7 +775,8 @@ mod tests {
             &a_bases,
             Some(&trusted_party_commitment_pk),
             &unrevealed_message_indexes,
-        );
+        )
+        .unwrap();
 
         let valid = zkpok2.verify_proof(
             commitment.cl03Commitment(),
@@ -630,7 +787,7 @@ mod tests {
             &unrevealed_message_indexes,
         );
 
-        assert!(valid, "Error! ZKPoK verification should PASS");
+        assert!(valid.is_ok(), "Error! ZKPoK verification should PASS");
 
         let zkpok2_wrong_tp_commitment = ZKPoK::<CL03<S::Ciphersuite>>::generate_proof(
             &messages,
@@ -640,7 +797,8 @@ mod tests {
             &a_bases,
             Some(&trusted_party_commitment_pk),
             &unrevealed_message_indexes,
-        );
+        )
+        .unwrap();
 
         let valid = zkpok2_wrong_tp_commitment.verify_proof(
             commitment.cl03Commitment(),
@@ -651,10 +809,10 @@ mod tests {
             &unrevealed_message_indexes,
         );
 
-        assert!(!valid, "Error! ZKPoK verification should FAIL");
+        assert!(valid.is_err(), "Error! ZKPoK verification should FAIL");
     }
 
-    pub(crate) fn spok<S: Scheme>()
+    pub(crate) fn spok<S: Scheme>(cl03_keypair: KeyPair<CL03<S::Ciphersuite>>)
     where
         S::Ciphersuite: CLCiphersuite,
         <S::Ciphersuite as Ciphersuite>::HashAlg: Digest,
@@ -672,7 +830,6 @@ mod tests {
         ];
 
         let n_attr = msgs.len();
-        let cl03_keypair = KeyPair::<CL03<S::Ciphersuite>>::generate();
         let a_bases = Bases::generate(cl03_keypair.public_key(), n_attr);
 
         let messages: Vec<CL03Message> = msgs
@@ -714,7 +871,8 @@ mod tests {
             cl03_keypair.public_key(),
             &a_bases,
             Some(&unrevealed_message_indexes),
-        );
+        )
+        .unwrap();
 
         let zkpok = ZKPoK::<CL03<S::Ciphersuite>>::generate_proof(
             &messages,
@@ -724,7 +882,8 @@ mod tests {
             &a_bases,
             None,
             &unrevealed_message_indexes,
-        );
+        )
+        .unwrap();
 
         let blind_signature = BlindSignature::<CL03<S::Ciphersuite>>::blind_sign(
             cl03_keypair.public_key(),
@@ -737,13 +896,14 @@ mod tests {
             None,
             &unrevealed_message_indexes,
             Some(&revealed_message_indexes),
-        );
+        )
+        .unwrap();
         let unblided_signature = blind_signature.unblind_sign(&commitment);
         let verify =
             unblided_signature.verify_multiattr(cl03_keypair.public_key(), &a_bases, &messages);
 
         assert!(
-            verify,
+            verify.is_ok(),
             "Error! The unblided signature verification should PASS!"
         );
 
@@ -758,7 +918,8 @@ mod tests {
             None,
             &unrevealed_message_indexes,
             Some(&revealed_message_indexes),
-        );
+        )
+        .unwrap();
         let unblided_signature_wrong = blind_signature_wrong.unblind_sign(&commitment);
         let verify = unblided_signature_wrong.verify_multiattr(
             cl03_keypair.public_key(),
@@ -767,7 +928,7 @@ mod tests {
         );
 
         assert!(
-            !verify,
+            verify.is_err(),
             "Error! The unblinded signature verification SHOULD FAIL!"
         );
 
@@ -783,7 +944,8 @@ mod tests {
             &a_bases,
             &messages,
             &unrevealed_message_indexes,
-        );
+        )
+        .unwrap();
         let valid_proof = signature_pok.proof_verify(
             &commitment_pk,
             cl03_keypair.public_key(),
@@ -794,10 +956,36 @@ mod tests {
         );
 
         assert!(
-            valid_proof,
+            valid_proof.is_ok(),
             "Error! The signature proof of knowledge should PASS!"
         );
 
+        let bytes = signature_pok.to_bytes();
+        let decoded_pok = PoKSignature::<CL03<S::Ciphersuite>>::from_bytes(&bytes).unwrap();
+        assert!(decoded_pok == signature_pok);
+        let valid_proof = decoded_pok.proof_verify(
+            &commitment_pk,
+            cl03_keypair.public_key(),
+            &a_bases,
+            &revealed_messages,
+            &unrevealed_message_indexes,
+            n_attr,
+        );
+
+        assert!(
+            valid_proof.is_ok(),
+            "Error! The decoded signature proof of knowledge should PASS!"
+        );
+        assert!(
+            PoKSignature::<CL03<S::Ciphersuite>>::from_bytes(&bytes[..bytes.len() - 1]).is_err(),
+            "Error! Deserialization of a truncated proof should FAIL"
+        );
+        assert!(
+            PoKSignature::<CL03<S::Ciphersuite>>::from_bytes(&[bytes.as_slice(), &[0]].concat())
+                .is_err(),
+            "Error! Deserialization of a proof with trailing bytes should FAIL"
+        );
+
         let signature_pok = PoKSignature::<CL03<S::Ciphersuite>>::proof_gen(
             unblided_signature.cl03Signature(),
             &commitment_pk,
@@ -805,7 +993,8 @@ mod tests {
             &a_bases,
             &messages,
             &unrevealed_message_indexes,
-        );
+        )
+        .unwrap();
         let valid_proof = signature_pok.proof_verify(
             &commitment_pk,
             cl03_keypair.public_key(),
@@ -816,7 +1005,7 @@ mod tests {
         );
 
         assert!(
-            !valid_proof,
+            valid_proof.is_err(),
             "Error! The signature proof of knowledge should FAIL!"
         );
     }
@@ -12,9 +12,20 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use super::commitment::CL03Commitment;
-use crate::utils::{random::rand_int, util::cl03_utils::divm};
+use super::{
+    ciphersuites::CLCiphersuite,
+    commitment::CL03Commitment,
+    encoding::{self, impl_canonical_encoding},
+};
+use crate::{
+    errors::Error,
+    utils::{
+        random::rand_int_with_rng,
+        util::cl03_utils::{divm, IntegerExt},
+    },
+};
 use digest::Digest;
+use rand::{thread_rng, CryptoRng, RngCore};
 use rug::{integer::Order, ops::Pow, Complete, Integer};
 use serde::{Deserialize, Serialize};
 
@@ -31,6 +42,13 @@ struct ProofSs {
     d_2: Integer,
 }
 
+impl_canonical_encoding!(ProofSs {
+    challenge,
+    d,
+    d_1,
+    d_2
+});
+
 #[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
 struct ProofOfS {
     E: Integer,
@@ -38,6 +56,8 @@ struct ProofOfS {
     proof_ss: ProofSs,
 }
 
+impl_canonical_encoding!(ProofOfS { E, F, proof_ss });
+
 #[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
 struct ProofLi {
     C: Integer,
@@ -45,6 +65,8 @@ struct ProofLi {
     D_2: Integer,
 }
 
+impl_canonical_encoding!(ProofLi { C, D_1, D_2 });
+
 #[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
 pub struct ProofWt {
     E_a_1: Integer,
@@ -57,6 +79,17 @@ pub struct ProofWt {
     proof_large_i_b: ProofLi,
 }
 
+impl_canonical_encoding!(ProofWt {
+    E_a_1,
+    E_a_2,
+    E_b_1,
+    E_b_2,
+    proof_of_square_a,
+    proof_of_square_b,
+    proof_large_i_a,
+    proof_large_i_b,
+});
+
 #[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
 pub struct Boudot2000RangeProof {
     pub proof_of_tolerance: ProofWt,
@@ -64,24 +97,15 @@ pub struct Boudot2000RangeProof {
     pub E: Integer,
 }
 
-impl Boudot2000RangeProof {
-    /* Security parameter - Half of the length of the Hash function output
-    NOTE: i.e., 2*t bits is the length of the Hash function output.
-    The soundness characteristic of the range proof is given by 2**(t−1).
-    t = 80: Original value in [Boudot2000], appropriate for SHA-1 - sha160 (i.e. 2*t = 160 bits),
-    replaced by t = 128, appropriate for SHA256 (i.e. 2*t = 256). */
-    const t: u32 = 128;
-    // Security parameter - Zero knowledge property is guaranteed given that 1∕l is negligible
-    const l: u32 = 40;
-    // Security parameter for the commitment - 2**s  must be negligible
-    const s: u32 = 40;
-    // Security parameter for the commitment - 2**s1 must be negligible
-    const s1: u32 = 40;
-    // Security parameter for the commitment - 2**s2 must be negligible
-    const s2: u32 = 552;
+impl_canonical_encoding!(Boudot2000RangeProof {
+    proof_of_tolerance,
+    E_prime,
+    E
+});
 
+impl Boudot2000RangeProof {
     /* Algorithm 1 Proof of Same Secret */
-    fn proof_same_secret<H>(
+    fn proof_same_secret<H, R: RngCore + CryptoRng>(
         x: &Integer,
         r_1: &Integer,
         r_2: &Integer,
@@ -95,28 +119,28 @@ impl Boudot2000RangeProof {
         s1: u32,
         s2: u32,
         n: &Integer,
-    ) -> ProofSs
+        rng: &mut R,
+    ) -> Result<ProofSs, Error>
     where
         H: Digest,
     {
-        let omega = rand_int(
+        let omega = rand_int_with_rng(
             Integer::from(1),
             Integer::from(2).pow(l + t) * b - Integer::from(1),
+            rng,
         );
-        let mu_1 = rand_int(
+        let mu_1 = rand_int_with_rng(
             Integer::from(1),
             Integer::from(2).pow(l + t + s1) * n - Integer::from(1),
+            rng,
         );
-        let mu_2 = rand_int(
+        let mu_2 = rand_int_with_rng(
             Integer::from(1),
             Integer::from(2).pow(l + t + s2) * n - Integer::from(1),
+            rng,
         );
-        let w_1 = (Integer::from(g_1.pow_mod_ref(&omega, n).unwrap())
-            * Integer::from(h_1.pow_mod_ref(&mu_1, n).unwrap()))
-            % n;
-        let w_2 = (Integer::from(g_2.pow_mod_ref(&omega, n).unwrap())
-            * Integer::from(h_2.pow_mod_ref(&mu_2, n).unwrap()))
-            % n;
+        let w_1 = (g_1.pow_mod_checked(&omega, n)? * h_1.pow_mod_checked(&mu_1, n)?) % n;
+        let w_2 = (g_2.pow_mod_checked(&omega, n)? * h_2.pow_mod_checked(&mu_2, n)?) % n;
 
         let str = w_1.to_string() + &w_2.to_string();
         let hash = <H as Digest>::digest(str);
@@ -126,12 +150,12 @@ impl Boudot2000RangeProof {
         let d_1 = mu_1 + &challenge * r_1;
         let d_2 = mu_2 + &challenge * r_2;
 
-        ProofSs {
+        Ok(ProofSs {
             challenge,
             d,
             d_1,
             d_2,
-        }
+        })
         // proof_ss = {'challenge': int(challenge), 'd': int(d), 'd_1': int(d_1), 'd_2': int(d_2)}
     }
 
@@ -145,7 +169,7 @@ impl Boudot2000RangeProof {
         h_2: &Integer,
         n: &Integer,
         proof_ss: &ProofSs,
-    ) -> bool
+    ) -> Result<bool, Error>
     where
         H: Digest,
     {
@@ -156,27 +180,21 @@ impl Boudot2000RangeProof {
             d_2,
         } = proof_ss;
 
-        let inv_E = Integer::from(E.pow_mod_ref(&(-Integer::from(1) * challenge), n).unwrap());
-        let inv_F = Integer::from(F.pow_mod_ref(&(-Integer::from(1) * challenge), n).unwrap());
+        let inv_E = E.pow_mod_checked(&(-Integer::from(1) * challenge), n)?;
+        let inv_F = F.pow_mod_checked(&(-Integer::from(1) * challenge), n)?;
 
-        let lhs = (Integer::from(g_1.pow_mod_ref(d, n).unwrap())
-            * Integer::from(h_1.pow_mod_ref(d_1, n).unwrap())
-            * &inv_E)
-            % n;
-        let rhs = (Integer::from(g_2.pow_mod_ref(d, n).unwrap())
-            * Integer::from(h_2.pow_mod_ref(d_2, n).unwrap())
-            * &inv_F)
-            % n;
+        let lhs = (g_1.pow_mod_checked(d, n)? * h_1.pow_mod_checked(d_1, n)? * &inv_E) % n;
+        let rhs = (g_2.pow_mod_checked(d, n)? * h_2.pow_mod_checked(d_2, n)? * &inv_F) % n;
 
         let str = lhs.to_string() + &rhs.to_string();
         let hash = <H as Digest>::digest(str);
         let output = Integer::from_digits(hash.as_slice(), Order::MsfBe);
 
-        challenge == &output
+        Ok(challenge == &output)
     }
 
     /* Algorithm 3 Proof of Square */
-    fn proof_of_square<H>(
+    fn proof_of_square<H, R: RngCore + CryptoRng>(
         x: &Integer,
         r_1: &Integer,
         g: &Integer,
@@ -189,30 +207,36 @@ impl Boudot2000RangeProof {
         s1: u32,
         s2: u32,
         n: &Integer,
-    ) -> ProofOfS
+        rng: &mut R,
+    ) -> Result<ProofOfS, Error>
     where
         H: Digest,
     {
-        let r_2 = rand_int(
+        let r_2 = rand_int_with_rng(
             -Integer::from(2).pow(s) * n + Integer::from(1),
             Integer::from(2).pow(s) * n - Integer::from(1),
+            rng,
         );
-        let F = (Integer::from(g.pow_mod_ref(x, n).unwrap())
-            * Integer::from(h.pow_mod_ref(&r_2, n).unwrap()))
-            % n;
+        let F = (g.pow_mod_checked(x, n)? * h.pow_mod_checked(&r_2, n)?) % n;
         let r_3 = r_1 - (&r_2 * x).complete();
 
-        let proof_ss = Self::proof_same_secret::<H>(x, &r_2, &r_3, g, h, &F, h, l, t, b, s1, s2, n);
+        let proof_ss =
+            Self::proof_same_secret::<H, _>(x, &r_2, &r_3, g, h, &F, h, l, t, b, s1, s2, n, rng)?;
         // proof_of_s = {'E': int(E), 'F': int(F), 'proof_ss': proof_ss}
-        ProofOfS {
+        Ok(ProofOfS {
             E: E.clone(),
             F,
             proof_ss,
-        }
+        })
     }
 
     /* Algorithm 4 Verify Proof of Square */
-    fn verify_of_square<H>(proof_of_s: &ProofOfS, g: &Integer, h: &Integer, n: &Integer) -> bool
+    fn verify_of_square<H>(
+        proof_of_s: &ProofOfS,
+        g: &Integer,
+        h: &Integer,
+        n: &Integer,
+    ) -> Result<bool, Error>
     where
         H: Digest,
     {
@@ -229,7 +253,7 @@ impl Boudot2000RangeProof {
     }
 
     /* Algorithm 5 Proof of Larger Interval Specific factor 2 ** T */
-    fn proof_large_interval_specific<H>(
+    fn proof_large_interval_specific<H, R: RngCore + CryptoRng>(
         x: &Integer,
         r: &Integer,
         g: &Integer,
@@ -240,7 +264,8 @@ impl Boudot2000RangeProof {
         s: u32,
         n: &Integer,
         T: u32,
-    ) -> ProofLi
+        rng: &mut R,
+    ) -> Result<ProofLi, Error>
     where
         H: Digest,
     {
@@ -250,17 +275,17 @@ impl Boudot2000RangeProof {
         let mut D_2 = Integer::from(0);
 
         while boolean {
-            let w = rand_int(
+            let w = rand_int_with_rng(
                 Integer::from(0),
                 (Integer::from(2).pow(T) * Integer::from(2).pow(t + l)) * b - Integer::from(1),
+                rng,
             );
-            let nu = rand_int(
+            let nu = rand_int_with_rng(
                 -(Integer::from(2).pow(T) * Integer::from(2).pow(t + l + s)) * n + Integer::from(1),
                 (Integer::from(2).pow(T) * Integer::from(2).pow(t + l + s)) * n - Integer::from(1),
+                rng,
             );
-            let omega = (Integer::from(g.pow_mod_ref(&w, n).unwrap())
-                * Integer::from(h.pow_mod_ref(&nu, n).unwrap()))
-                % n;
+            let omega = (g.pow_mod_checked(&w, n)? * h.pow_mod_checked(&nu, n)?) % n;
 
             let str = omega.to_string();
             let hash = <H as Digest>::digest(str);
@@ -281,7 +306,7 @@ impl Boudot2000RangeProof {
         }
 
         // proof_li = {'C': int(C), 'D_1': int(D_1), 'D_2': int(D_2)}
-        ProofLi { C, D_1, D_2 }
+        Ok(ProofLi { C, D_1, D_2 })
     }
 
     /* Algorithm 6 Verify Proof of Larger Interval Specific factor 2 ** T */
@@ -295,35 +320,28 @@ impl Boudot2000RangeProof {
         l: u32,
         b: &Integer,
         T: u32,
-    ) -> bool
+    ) -> Result<bool, Error>
     where
         H: Digest,
     {
         let ProofLi { C, D_1, D_2 } = proof_li;
         let c = C % (Integer::from(2).pow(t));
-        let inv_E = Integer::from(E.pow_mod_ref(&(-Integer::from(1) * &c), n).unwrap());
-        let commit = (Integer::from(g.pow_mod_ref(D_1, n).unwrap())
-            * Integer::from(h.pow_mod_ref(D_2, n).unwrap())
-            * &inv_E)
-            % n;
+        let inv_E = E.pow_mod_checked(&(-Integer::from(1) * &c), n)?;
+        let commit = (g.pow_mod_checked(D_1, n)? * h.pow_mod_checked(D_2, n)? * &inv_E) % n;
 
         let str = commit.to_string();
         let hash = <H as Digest>::digest(str);
         let output = Integer::from_digits(hash.as_slice(), Order::MsfBe);
 
-        if &(c * Integer::from(b)) <= D_1
+        Ok(&(c * Integer::from(b)) <= D_1
             && D_1
-                <= &(Integer::from(2).pow(T) * (Integer::from(2).pow(t + l) * b - Integer::from(1)))
-            && C == &output
-        {
-            return true;
-        }
-
-        false
+                <= &(Integer::from(2).pow(T)
+                    * (Integer::from(2).pow(t + l) * b - Integer::from(1)))
+            && C == &output)
     }
 
     /* Algorithm 7 Proof with Tolerance Specific factor 2 ** T */
-    fn proof_of_tolerance_specific<H>(
+    fn proof_of_tolerance_specific<H, R: RngCore + CryptoRng>(
         x: Integer,
         r: Integer,
         g: &Integer,
@@ -337,7 +355,8 @@ impl Boudot2000RangeProof {
         s1: u32,
         s2: u32,
         T: u32,
-    ) -> ProofWt
+        rng: &mut R,
+    ) -> Result<ProofWt, Error>
     where
         H: Digest,
     {
@@ -368,9 +387,10 @@ impl Boudot2000RangeProof {
         let mut r_a_1 = Integer::from(1);
         let mut r_a_2 = Integer::from(1);
         while boolean {
-            r_a_1 = rand_int(
+            r_a_1 = rand_int_with_rng(
                 -Integer::from(2).pow(s) * Integer::from(2).pow(T) * n + Integer::from(1),
                 Integer::from(2).pow(s) * Integer::from(2).pow(T) * n - Integer::from(1),
+                rng,
             );
             r_a_2 = (&r - &r_a_1).complete();
             if -Integer::from(2).pow(s) * Integer::from(2).pow(T) * n + Integer::from(1) <= r_a_2
@@ -386,9 +406,10 @@ impl Boudot2000RangeProof {
 
         boolean = true;
         while boolean {
-            r_b_1 = rand_int(
+            r_b_1 = rand_int_with_rng(
                 -Integer::from(2).pow(s) * Integer::from(2).pow(T) * n + Integer::from(1),
                 Integer::from(2).pow(s) * Integer::from(2).pow(T) * n - Integer::from(1),
+                rng,
             );
             r_b_2 = (-Integer::from(1)) * &r - &r_b_1;
             if -Integer::from(2).pow(s) * Integer::from(2).pow(T) * n + Integer::from(1) <= r_b_2
@@ -399,28 +420,26 @@ impl Boudot2000RangeProof {
             }
         }
 
-        let E_a_1 = (Integer::from(g.pow_mod_ref(&x_a_1.clone().pow(2), n).unwrap())
-            * Integer::from(h.pow_mod_ref(&r_a_1, n).unwrap()))
-            % n;
-        let E_a_2 = (Integer::from(g.pow_mod_ref(&x_a_2, n).unwrap())
-            * Integer::from(h.pow_mod_ref(&r_a_2, n).unwrap()))
-            % n;
-
-        let E_b_1 = (Integer::from(g.pow_mod_ref(&x_b_1.clone().pow(2), n).unwrap())
-            * Integer::from(h.pow_mod_ref(&r_b_1, n).unwrap()))
-            % n;
-        let E_b_2 = (Integer::from(g.pow_mod_ref(&x_b_2, n).unwrap())
-            * Integer::from(h.pow_mod_ref(&r_b_2, n).unwrap()))
-            % n;
-
-        let proof_of_square_a =
-            Self::proof_of_square::<H>(&x_a_1, &r_a_1, g, h, &E_a_1, l, t, b, s, s1, s2, n);
-        let proof_of_square_b =
-            Self::proof_of_square::<H>(&x_b_1, &r_b_1, g, h, &E_b_1, l, t, b, s, s1, s2, n);
-        let proof_large_i_a =
-            Self::proof_large_interval_specific::<H>(&x_a_2, &r_a_2, g, h, t, l, b, s, n, T);
-        let proof_large_i_b =
-            Self::proof_large_interval_specific::<H>(&x_b_2, &r_b_2, g, h, t, l, b, s, n, T);
+        let E_a_1 =
+            (g.pow_mod_checked(&x_a_1.clone().pow(2), n)? * h.pow_mod_checked(&r_a_1, n)?) % n;
+        let E_a_2 = (g.pow_mod_checked(&x_a_2, n)? * h.pow_mod_checked(&r_a_2, n)?) % n;
+
+        let E_b_1 =
+            (g.pow_mod_checked(&x_b_1.clone().pow(2), n)? * h.pow_mod_checked(&r_b_1, n)?) % n;
+        let E_b_2 = (g.pow_mod_checked(&x_b_2, n)? * h.pow_mod_checked(&r_b_2, n)?) % n;
+
+        let proof_of_square_a = Self::proof_of_square::<H, _>(
+            &x_a_1, &r_a_1, g, h, &E_a_1, l, t, b, s, s1, s2, n, rng,
+        )?;
+        let proof_of_square_b = Self::proof_of_square::<H, _>(
+            &x_b_1, &r_b_1, g, h, &E_b_1, l, t, b, s, s1, s2, n, rng,
+        )?;
+        let proof_large_i_a = Self::proof_large_interval_specific::<H, _>(
+            &x_a_2, &r_a_2, g, h, t, l, b, s, n, T, rng,
+        )?;
+        let proof_large_i_b = Self::proof_large_interval_specific::<H, _>(
+            &x_b_2, &r_b_2, g, h, t, l, b, s, n, T, rng,
+        )?;
 
         // proof_wt = {
         //     'E_a_1': int(E_a_1), 'E_a_2': int(E_a_2), 'E_b_1': int(E_b_1), 'E_b_2': int(E_b_2),
@@ -428,7 +447,7 @@ impl Boudot2000RangeProof {
         //     'proof_large_i_a': proof_large_i_a, 'proof_large_i_b': proof_large_i_b
         // }
 
-        ProofWt {
+        Ok(ProofWt {
             E_a_1,
             E_a_2,
             E_b_1,
@@ -437,7 +456,7 @@ impl Boudot2000RangeProof {
             proof_of_square_b,
             proof_large_i_a,
             proof_large_i_b,
-        }
+        })
     }
 
     /* Algorithm 8 Verify Proof with Tolerance Specific factor 2 ** T */
@@ -452,7 +471,7 @@ impl Boudot2000RangeProof {
         t: u32,
         l: u32,
         T: u32,
-    ) -> bool
+    ) -> Result<bool, Error>
     where
         H: Digest,
     {
@@ -462,8 +481,8 @@ impl Boudot2000RangeProof {
         let bb = Integer::from(2).pow(T) * Integer::from(b)
             + Integer::from(2).pow(l + t + rug::ops::DivRounding::div_floor(T, 2) + 1)
                 * Integer::from(Integer::from(b - a).sqrt_ref());
-        let E_a = divm(E, &Integer::from(g.pow_mod_ref(&aa, n).unwrap()), n);
-        let E_b = divm(&Integer::from(g.pow_mod_ref(&bb, n).unwrap()), E, n);
+        let E_a = divm(E, &g.pow_mod_checked(&aa, n)?, n)?;
+        let E_b = divm(&g.pow_mod_checked(&bb, n)?, E, n)?;
         // NOTE: E_a and E_b must be recomputed during the verification,
         //        see Section 3.1.1 in [Boudot2000] ("Both Alice and Bob compute...")
 
@@ -478,12 +497,12 @@ impl Boudot2000RangeProof {
             proof_large_i_b,
         } = proof_wt;
 
-        let div_a = divm(&E_a, E_a_1, n);
-        let div_b = divm(&E_b, E_b_1, n);
+        let div_a = divm(&E_a, E_a_1, n)?;
+        let div_b = divm(&E_b, E_b_1, n)?;
 
         if E_a_2 == &div_a && E_b_2 == &div_b {
-            let b_s = Self::verify_of_square::<H>(proof_of_square_a, g, h, n)
-                && Self::verify_of_square::<H>(proof_of_square_b, g, h, n);
+            let b_s = Self::verify_of_square::<H>(proof_of_square_a, g, h, n)?
+                && Self::verify_of_square::<H>(proof_of_square_b, g, h, n)?;
             let b_li = Self::verify_large_interval_specific::<H>(
                 proof_large_i_a,
                 E_a_2,
@@ -494,7 +513,7 @@ impl Boudot2000RangeProof {
                 l,
                 b,
                 T,
-            ) && Self::verify_large_interval_specific::<H>(
+            )? && Self::verify_large_interval_specific::<H>(
                 proof_large_i_b,
                 E_b_2,
                 g,
@@ -504,15 +523,15 @@ impl Boudot2000RangeProof {
                 l,
                 b,
                 T,
-            );
-            return b_s && b_li;
+            )?;
+            return Ok(b_s && b_li);
         }
 
-        false
+        Ok(false)
     }
 
     /* Algorithm 9 Square Decomposition Range Proof (i.e. Proof without tolerance) from [Boudot2000] on section 3.1.2 */
-    fn proof_of_square_decomposition_range<H>(
+    fn proof_of_square_decomposition_range<H, R: RngCore + CryptoRng>(
         x: &Integer,
         r: &Integer,
         g: &Integer,
@@ -527,24 +546,25 @@ impl Boudot2000RangeProof {
         s1: u32,
         s2: u32,
         T: u32,
-    ) -> Self
+        rng: &mut R,
+    ) -> Result<Self, Error>
     where
         H: Digest,
     {
         let x_prime = Integer::from(2).pow(T) * x;
         let r_prime = Integer::from(2).pow(T) * r;
 
-        let E_prime = Integer::from(E.pow_mod_ref(&(Integer::from(2).pow(T)), n).unwrap());
+        let E_prime = E.pow_mod_checked(&(Integer::from(2).pow(T)), n)?;
 
-        let proof_of_tolerance = Self::proof_of_tolerance_specific::<H>(
-            x_prime, r_prime, g, h, n, a, b, t, l, s, s1, s2, T,
-        );
+        let proof_of_tolerance = Self::proof_of_tolerance_specific::<H, _>(
+            x_prime, r_prime, g, h, n, a, b, t, l, s, s1, s2, T, rng,
+        )?;
 
-        Self {
+        Ok(Self {
             proof_of_tolerance,
             E_prime,
             E: E.clone(),
-        }
+        })
     }
 
     /* Algorithm 10 Verify Square Decomposition Range Proof (i.e. Proof without tolerance) from [Boudot2000] on section 3.1.2 */
@@ -558,11 +578,11 @@ impl Boudot2000RangeProof {
         t: u32,
         l: u32,
         T: u32,
-    ) -> bool
+    ) -> Result<bool, Error>
     where
         H: Digest,
     {
-        if self.E_prime == Integer::from(self.E.pow_mod_ref(&Integer::from(2).pow(T), n).unwrap()) {
+        if self.E_prime == self.E.pow_mod_checked(&Integer::from(2).pow(T), n)? {
             let res_verify_ts = Self::verify_of_tolerance_specific::<H>(
                 &self.proof_of_tolerance,
                 g,
@@ -577,10 +597,10 @@ impl Boudot2000RangeProof {
             );
             return res_verify_ts;
         }
-        return false;
+        Ok(false)
     }
 
-    pub fn prove<H>(
+    pub fn prove<CS: CLCiphersuite>(
         value: &Integer,
         commitment: &CL03Commitment,
         base1: &Integer,
@@ -588,16 +608,48 @@ impl Boudot2000RangeProof {
         module: &Integer,
         rmin: &Integer,
         rmax: &Integer,
-    ) -> Self
+    ) -> Result<Self, Error>
     where
-        H: Digest,
+        CS::HashAlg: Digest,
+    {
+        Self::prove_with_rng::<CS, _>(
+            value,
+            commitment,
+            base1,
+            base2,
+            module,
+            rmin,
+            rmax,
+            &mut thread_rng(),
+        )
+    }
+
+    /// Same as [`Self::prove`], drawing the blindings of the proof from `rng`
+    pub fn prove_with_rng<CS: CLCiphersuite, R: RngCore + CryptoRng>(
+        value: &Integer,
+        commitment: &CL03Commitment,
+        base1: &Integer,
+        base2: &Integer,
+        module: &Integer,
+        rmin: &Integer,
+        rmax: &Integer,
+        rng: &mut R,
+    ) -> Result<Self, Error>
+    where
+        CS::HashAlg: Digest,
     {
         if rmax <= rmin {
-            panic!("rmin > rmax");
+            return Err(Error::InvalidRangeBounds);
         }
 
-        let T = 2 * (Self::t + Self::l + 1) + ((rmax - rmin).complete().significant_bits());
-        let proof_of_sdr = Self::proof_of_square_decomposition_range::<H>(
+        if value < rmin || value > rmax {
+            return Err(Error::RangeProofGenError(
+                "value outside of the range".to_owned(),
+            ));
+        }
+
+        let T = 2 * (CS::t + CS::l + 1) + ((rmax - rmin).complete().significant_bits());
+        Self::proof_of_square_decomposition_range::<CS::HashAlg, _>(
             value,
             &commitment.randomness,
             base1,
@@ -606,44 +658,58 @@ impl Boudot2000RangeProof {
             module,
             rmin,
             rmax,
-            Self::t,
-            Self::l,
-            Self::s,
-            Self::s1,
-            Self::s2,
+            CS::t,
+            CS::l,
+            CS::s,
+            CS::s1,
+            CS::s2,
             T,
-        );
-        proof_of_sdr
+            rng,
+        )
     }
 
-    pub fn verify<H>(
+    pub fn verify<CS: CLCiphersuite>(
         &self,
         base1: &Integer,
         base2: &Integer,
         module: &Integer,
         rmin: &Integer,
         rmax: &Integer,
-    ) -> bool
+    ) -> Result<(), Error>
     where
-        H: Digest,
+        CS::HashAlg: Digest,
     {
         if rmax <= rmin {
-            panic!("rmin > rmax");
+            return Err(Error::InvalidRangeBounds);
         }
 
-        let T = 2 * (Self::t + Self::l + 1) + ((rmax - rmin).complete().significant_bits());
+        let T = 2 * (CS::t + CS::l + 1) + ((rmax - rmin).complete().significant_bits());
 
-        let valid = Self::verify_of_square_decomposition_range::<H>(
+        let valid = Self::verify_of_square_decomposition_range::<CS::HashAlg>(
             self,
             base1,
             base2,
             module,
             rmin,
             rmax,
-            Self::t,
-            Self::l,
+            CS::t,
+            CS::l,
             T,
-        );
-        valid
+        )?;
+
+        match valid {
+            true => Ok(()),
+            false => Err(Error::RangeProofVerificationError(
+                "Boudot2000 range proof not valid".to_owned(),
+            )),
+        }
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        encoding::to_bytes(self)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        encoding::from_bytes(bytes)
     }
 }
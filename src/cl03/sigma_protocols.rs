@@ -16,15 +16,22 @@ use super::{
     bases::Bases,
     ciphersuites::CLCiphersuite,
     commitment::CL03Commitment,
+    encoding::impl_canonical_encoding,
     keys::{CL03CommitmentPublicKey, CL03PublicKey},
     signature::CL03Signature,
 };
 use crate::{
+    errors::Error,
     schemes::algorithms::CL03,
     schemes::generics::Commitment,
-    utils::{message::cl03_message::CL03Message, random::random_bits, util::cl03_utils::divm},
+    utils::{
+        message::cl03_message::CL03Message,
+        random::random_bits_with_rng,
+        util::cl03_utils::{divm, IntegerExt},
+    },
 };
 use digest::Digest;
+use rand::{CryptoRng, RngCore};
 use rug::{integer::Order, Complete, Integer};
 use serde::{Deserialize, Serialize};
 
@@ -36,9 +43,16 @@ pub(crate) struct NISP2Commitments {
     d_2: Integer,
 }
 
+impl_canonical_encoding!(NISP2Commitments {
+    challenge,
+    d,
+    d_1,
+    d_2
+});
+
 impl NISP2Commitments {
     /* Generation of the proof related to two commitments (C1 and C2) (generate proof that C1 is a commitment to the same secrets as C2) */
-    pub(crate) fn nisp2_generate_proof_MultiSecrets<CS>(
+    pub(crate) fn nisp2_generate_proof_MultiSecrets<CS, R: RngCore + CryptoRng>(
         messages: &[CL03Message],
         c1: &CL03Commitment,
         c2: &CL03Commitment,
@@ -46,15 +60,16 @@ impl NISP2Commitments {
         a_bases: &Bases,
         commitment_pk: &CL03CommitmentPublicKey,
         unrevealed_message_indexes: &[usize],
-    ) -> Self
+        rng: &mut R,
+    ) -> Result<Self, Error>
     where
         CS: CLCiphersuite,
         CS::HashAlg: Digest,
     {
         let n_attr = messages.len();
 
-        if a_bases.0.len() < n_attr && n_attr < commitment_pk.g_bases.len() {
-            panic!("Not enough a_bases OR g_bases for the number of attributes");
+        if a_bases.0.len() < n_attr || commitment_pk.g_bases.len() < n_attr {
+            return Err(Error::NotEnoughBases);
         }
 
         let h1 = &signer_pk.b;
@@ -65,11 +80,11 @@ impl NISP2Commitments {
         // Initialize multiple random values, equivalent to secrets m_i and stored in a list
         let mut omega: Vec<Integer> = Vec::new();
         for _i in unrevealed_message_indexes {
-            omega.push(random_bits(CS::lm));
+            omega.push(random_bits_with_rng(CS::lm, rng));
         }
 
-        let mu_1 = random_bits(CS::ln);
-        let mu_2 = random_bits(CS::ln);
+        let mu_1 = random_bits_with_rng(CS::ln, rng);
+        let mu_2 = random_bits_with_rng(CS::ln, rng);
 
         let mut w_1 = Integer::from(1);
         let mut w_2 = Integer::from(1);
@@ -77,27 +92,21 @@ impl NISP2Commitments {
 
         for i in unrevealed_message_indexes {
             w_1 = w_1
-                * (Integer::from(
-                    a_bases
-                        .0
-                        .get(*i)
-                        .expect("unrevealed_message_indexes not valid (overflow)")
-                        .pow_mod_ref(&omega[idx], n1)
-                        .unwrap(),
-                ));
+                * (a_bases
+                    .0
+                    .get(*i)
+                    .ok_or(Error::InvalidMessageIndex)?
+                    .pow_mod_checked(&omega[idx], n1)?);
             w_2 = w_2
-                * (Integer::from(
-                    commitment_pk
-                        .g_bases
-                        .get(*i)
-                        .expect("unrevealed_message_indexes not valid (overflow)")
-                        .pow_mod_ref(&omega[idx], n2)
-                        .unwrap(),
-                ));
+                * (commitment_pk
+                    .g_bases
+                    .get(*i)
+                    .ok_or(Error::InvalidMessageIndex)?
+                    .pow_mod_checked(&omega[idx], n2)?);
             idx = idx + 1;
         }
-        w_1 = (w_1 * Integer::from(h1.pow_mod_ref(&mu_1, n1).unwrap())) % n1;
-        w_2 = (w_2 * Integer::from(h2.pow_mod_ref(&mu_2, n2).unwrap())) % n2;
+        w_1 = (w_1 * h1.pow_mod_checked(&mu_1, n1)?) % n1;
+        w_2 = (w_2 * h2.pow_mod_checked(&mu_2, n2)?) % n2;
 
         let str = w_1.to_string() + &w_2.to_string();
         let hash = <CS::HashAlg as Digest>::digest(str);
@@ -109,11 +118,7 @@ impl NISP2Commitments {
         for i in unrevealed_message_indexes {
             d.push(
                 (&omega[idx]
-                    + &challenge
-                        * &messages
-                            .get(*i)
-                            .expect("unrevealed_message_indexes not valid (overflow)")
-                            .value)
+                    + &challenge * &messages.get(*i).ok_or(Error::InvalidMessageIndex)?.value)
                     .complete(),
             );
             idx = idx + 1;
@@ -122,12 +127,12 @@ impl NISP2Commitments {
         let d_1 = mu_1 + &challenge * &c1.randomness;
         let d_2 = mu_2 + &challenge * &c2.randomness;
 
-        Self {
+        Ok(Self {
             challenge,
             d,
             d_1,
             d_2,
-        }
+        })
     }
 
     /* Verification of the proof for two commitments (C1 and C2) */
@@ -139,7 +144,7 @@ impl NISP2Commitments {
         a_bases: &Bases,
         commitment_pk: &CL03CommitmentPublicKey,
         unrevealed_message_indexes: &[usize],
-    ) -> bool
+    ) -> Result<bool, Error>
     where
         CS: CLCiphersuite,
         CS::HashAlg: Digest,
@@ -156,16 +161,16 @@ impl NISP2Commitments {
             d_2,
         } = self;
 
-        let inv_C1 = Integer::from(
-            c1.value
-                .pow_mod_ref(&(-Integer::from(1) * challenge), n1)
-                .unwrap(),
-        );
-        let inv_C2 = Integer::from(
-            c2.value
-                .pow_mod_ref(&(-Integer::from(1) * challenge), n2)
-                .unwrap(),
-        );
+        if d.len() != unrevealed_message_indexes.len() {
+            return Ok(false);
+        }
+
+        let inv_C1 = c1
+            .value
+            .pow_mod_checked(&(-Integer::from(1) * challenge), n1)?;
+        let inv_C2 = c2
+            .value
+            .pow_mod_checked(&(-Integer::from(1) * challenge), n2)?;
 
         let mut lhs = Integer::from(1);
         let mut rhs = Integer::from(1);
@@ -173,35 +178,29 @@ impl NISP2Commitments {
 
         for i in unrevealed_message_indexes {
             lhs = lhs
-                * Integer::from(
-                    a_bases
-                        .0
-                        .get(*i)
-                        .expect("unrevealed_message_indexes not valid (overflow)")
-                        .pow_mod_ref(&d[idx], n1)
-                        .unwrap(),
-                );
+                * a_bases
+                    .0
+                    .get(*i)
+                    .ok_or(Error::InvalidMessageIndex)?
+                    .pow_mod_checked(&d[idx], n1)?;
             rhs = rhs
-                * Integer::from(
-                    commitment_pk
-                        .g_bases
-                        .get(*i)
-                        .expect("unrevealed_message_indexes not valid (overflow)")
-                        .pow_mod_ref(&d[idx], n2)
-                        .unwrap(),
-                );
+                * commitment_pk
+                    .g_bases
+                    .get(*i)
+                    .ok_or(Error::InvalidMessageIndex)?
+                    .pow_mod_checked(&d[idx], n2)?;
             idx += 1;
         }
         // lhs = ((lhs * powmod(h1, d_1, n1)) * inv_C1) % n1
         // rhs = ((rhs * powmod(h2, d_2, n2)) * inv_C2) % n2
-        lhs = ((lhs * Integer::from(h1.pow_mod_ref(d_1, n1).unwrap())) * inv_C1) % n1;
-        rhs = ((rhs * Integer::from(h2.pow_mod_ref(d_2, n2).unwrap())) * inv_C2) % n2;
+        lhs = ((lhs * h1.pow_mod_checked(d_1, n1)?) * inv_C1) % n1;
+        rhs = ((rhs * h2.pow_mod_checked(d_2, n2)?) * inv_C2) % n2;
 
         let str = lhs.to_string() + &rhs.to_string();
         let hash = <CS::HashAlg as Digest>::digest(str);
         let output = Integer::from_digits(hash.as_slice(), Order::MsfBe);
 
-        challenge == &output
+        Ok(challenge == &output)
     }
 }
 
@@ -212,24 +211,25 @@ pub(crate) struct NISPSecrets {
     s2: Integer,
 }
 
+impl_canonical_encoding!(NISPSecrets { t, s1, s2 });
+
 impl NISPSecrets {
-    pub(crate) fn nisp2sec_generate_proof<CS>(
+    pub(crate) fn nisp2sec_generate_proof<CS, R: RngCore + CryptoRng>(
         message: &CL03Message,
         commitment: &CL03Commitment,
         g1: &Integer,
         h1: &Integer,
         n1: &Integer,
-    ) -> Self
+        rng: &mut R,
+    ) -> Result<Self, Error>
     where
         CS: CLCiphersuite,
         CS::HashAlg: Digest,
     {
-        let r1 = random_bits(CS::lm);
-        let r2 = random_bits(CS::ln);
+        let r1 = random_bits_with_rng(CS::lm, rng);
+        let r2 = random_bits_with_rng(CS::ln, rng);
 
-        let t = (Integer::from(g1.pow_mod_ref(&r1, &n1).unwrap())
-            * Integer::from(h1.pow_mod_ref(&r2, &n1).unwrap()))
-            % n1;
+        let t = (g1.pow_mod_checked(&r1, &n1)? * h1.pow_mod_checked(&r2, &n1)?) % n1;
         let str_input =
             g1.to_string() + &h1.to_string() + &commitment.value.to_string() + &t.to_string();
         let hash = <CS::HashAlg as Digest>::digest(str_input);
@@ -238,7 +238,7 @@ impl NISPSecrets {
         let s1 = r1 + (&challenge * &message.value);
         let s2 = r2 + (&challenge * &commitment.randomness);
 
-        Self { t, s1, s2 }
+        Ok(Self { t, s1, s2 })
     }
 
     pub(crate) fn nisp2sec_verify_proof<CS>(
@@ -247,23 +247,21 @@ impl NISPSecrets {
         g1: &Integer,
         h1: &Integer,
         n1: &Integer,
-    ) -> bool
+    ) -> Result<bool, Error>
     where
         CS: CLCiphersuite,
         CS::HashAlg: Digest,
     {
         let Self { t, s1, s2 } = self;
-        let lhs = (Integer::from(g1.pow_mod_ref(s1, &n1).unwrap())
-            * Integer::from(h1.pow_mod_ref(s2, &n1).unwrap()))
-            % n1;
+        let lhs = (g1.pow_mod_checked(s1, &n1)? * h1.pow_mod_checked(s2, &n1)?) % n1;
         let str_input =
             g1.to_string() + &h1.to_string() + &commitment.value.to_string() + &t.to_string();
         let hash = <CS::HashAlg as Digest>::digest(str_input);
         let challenge = Integer::from_digits(hash.as_slice(), Order::MsfBe);
 
-        let rhs = (t * Integer::from(commitment.value.pow_mod_ref(&challenge, &n1).unwrap())) % n1;
+        let rhs = (t * commitment.value.pow_mod_checked(&challenge, &n1)?) % n1;
 
-        lhs == rhs
+        Ok(lhs == rhs)
     }
 }
 
@@ -274,15 +272,18 @@ pub(crate) struct NISPMultiSecrets {
     s2: Integer,
 }
 
+impl_canonical_encoding!(NISPMultiSecrets { t, s1, s2 });
+
 impl NISPMultiSecrets {
     /* Generation of the proof related to multiple secrets (x and r) */
-    pub(crate) fn nispMultiSecrets_generate_proof<CS>(
+    pub(crate) fn nispMultiSecrets_generate_proof<CS, R: RngCore + CryptoRng>(
         messages: &[CL03Message],
         commitment: &CL03Commitment,
         signer_pk: &CL03PublicKey,
         a_bases: &Bases,
         unrevealed_message_indexes: Option<&[usize]>,
-    ) -> Self
+        rng: &mut R,
+    ) -> Result<Self, Error>
     where
         CS: CLCiphersuite,
         CS::HashAlg: Digest,
@@ -296,10 +297,10 @@ impl NISPMultiSecrets {
 
         let mut r1: Vec<Integer> = Vec::new();
         for _ in unrevealed_message_indexes {
-            r1.push(random_bits(CS::lm));
+            r1.push(random_bits_with_rng(CS::lm, rng));
         }
 
-        let r2 = random_bits(CS::ln);
+        let r2 = random_bits_with_rng(CS::ln, rng);
 
         let h1 = &signer_pk.b;
         let n1 = &signer_pk.N;
@@ -308,18 +309,15 @@ impl NISPMultiSecrets {
         let mut str_input = String::from("");
         let mut idx = 0usize;
         for i in unrevealed_message_indexes {
-            t = t * Integer::from(
-                a_bases
-                    .0
-                    .get(*i)
-                    .expect("unrevealed_message_indexes not valid (overflow)")
-                    .pow_mod_ref(&r1[idx], n1)
-                    .unwrap(),
-            );
+            t = t * a_bases
+                .0
+                .get(*i)
+                .ok_or(Error::InvalidMessageIndex)?
+                .pow_mod_checked(&r1[idx], n1)?;
             str_input = str_input + &a_bases.0[*i].to_string();
             idx += 1;
         }
-        t = (t * Integer::from(h1.pow_mod_ref(&r2, n1).unwrap())) % n1;
+        t = (t * h1.pow_mod_checked(&r2, n1)?) % n1;
 
         str_input = str_input + &h1.to_string() + &commitment.value.to_string() + &t.to_string();
         let hash = <CS::HashAlg as Digest>::digest(str_input);
@@ -331,18 +329,14 @@ impl NISPMultiSecrets {
         for i in unrevealed_message_indexes {
             s1.push(
                 (&r1[idx]
-                    + &challenge
-                        * &messages
-                            .get(*i)
-                            .expect("unrevealed_message_indexes not valid (overflow)")
-                            .value)
+                    + &challenge * &messages.get(*i).ok_or(Error::InvalidMessageIndex)?.value)
                     .complete(),
             );
             idx += 1;
         }
         let s2 = r2 + (challenge * &commitment.randomness);
 
-        Self { t, s1, s2 }
+        Ok(Self { t, s1, s2 })
 
         //NOTE: s1 is a list with number_of_secrets values
     }
@@ -353,7 +347,7 @@ impl NISPMultiSecrets {
         signer_pk: &CL03PublicKey,
         a_bases: &Bases,
         unrevealed_message_indexes: Option<&[usize]>,
-    ) -> bool
+    ) -> Result<bool, Error>
     where
         CS: CLCiphersuite,
         CS::HashAlg: Digest,
@@ -365,7 +359,7 @@ impl NISPMultiSecrets {
         let Self { t, s1, s2 } = self;
 
         if unrevealed_message_indexes.len() != s1.len() {
-            panic!("unrevealed_message_indexes not valid");
+            return Err(Error::InvalidMessageIndex);
         }
 
         let mut lhs = Integer::from(1);
@@ -374,25 +368,22 @@ impl NISPMultiSecrets {
 
         for i in unrevealed_message_indexes {
             lhs = lhs
-                * Integer::from(
-                    a_bases
-                        .0
-                        .get(*i)
-                        .expect("unrevealed_message_indexes not valid (overflow)")
-                        .pow_mod_ref(&s1[idx], n1)
-                        .unwrap(),
-                );
+                * a_bases
+                    .0
+                    .get(*i)
+                    .ok_or(Error::InvalidMessageIndex)?
+                    .pow_mod_checked(&s1[idx], n1)?;
             str_input = str_input + &a_bases.0[*i].to_string();
             idx += 1;
         }
-        lhs = (lhs * Integer::from(h1.pow_mod_ref(&s2, n1).unwrap())) % n1;
+        lhs = (lhs * h1.pow_mod_checked(&s2, n1)?) % n1;
         str_input = str_input + &h1.to_string() + &commitment.value.to_string() + &t.to_string();
         let hash = <CS::HashAlg as Digest>::digest(str_input);
         let challenge = Integer::from_digits(hash.as_slice(), Order::MsfBe);
 
-        let rhs = (t * Integer::from(commitment.value.pow_mod_ref(&challenge, n1).unwrap())) % n1;
+        let rhs = (t * commitment.value.pow_mod_checked(&challenge, n1)?) % n1;
 
-        lhs == rhs
+        Ok(lhs == rhs)
     }
 }
 
@@ -414,62 +405,87 @@ pub(crate) struct NISPSignaturePoK {
     pub(crate) Ce: CL03Commitment,
 }
 
+impl_canonical_encoding!(NISPSignaturePoK {
+    challenge,
+    s_1,
+    s_2,
+    s_3,
+    s_4,
+    s_5,
+    s_6,
+    s_7,
+    s_8,
+    s_9,
+    Cx,
+    Cv,
+    Cw,
+    Ce
+});
+
 impl NISPSignaturePoK {
-    pub(crate) fn nisp5_MultiAttr_generate_proof<CS: CLCiphersuite>(
+    pub(crate) fn nisp5_MultiAttr_generate_proof<CS: CLCiphersuite, R: RngCore + CryptoRng>(
         signature: &CL03Signature,
         commitment_pk: &CL03CommitmentPublicKey,
         signer_pk: &CL03PublicKey,
         a_bases: &Bases,
         messages: &[CL03Message],
         unrevealed_message_indexes: &[usize],
-    ) -> NISPSignaturePoK
+        rng: &mut R,
+    ) -> Result<NISPSignaturePoK, Error>
     where
         CS::HashAlg: Digest,
     {
         let n_attr = messages.len();
 
-        if a_bases.0.len() < n_attr && commitment_pk.g_bases.len() < n_attr {
-            panic!("Not enough a_bases OR g_bases for the number of attributes");
+        if a_bases.0.len() < n_attr || commitment_pk.g_bases.len() < n_attr || n_attr == 0 {
+            return Err(Error::NotEnoughBases);
         }
 
-        let C_Cx = Commitment::<CL03<CS>>::commit_with_commitment_pk(messages, commitment_pk, None);
+        let C_Cx = Commitment::<CL03<CS>>::commit_with_commitment_pk_with_rng(
+            messages,
+            commitment_pk,
+            None,
+            rng,
+        )?;
         let (_Cx, rx) = (C_Cx.value(), C_Cx.randomness());
 
-        let C_Cv = Commitment::<CL03<CS>>::commit_v(&signature.v, commitment_pk);
+        let C_Cv = Commitment::<CL03<CS>>::commit_v(&signature.v, commitment_pk, rng)?;
         let (Cv, w) = (C_Cv.value(), C_Cv.randomness());
 
-        let C_Cw = Commitment::<CL03<CS>>::commit_with_commitment_pk(
+        let C_Cw = Commitment::<CL03<CS>>::commit_with_commitment_pk_with_rng(
             &[CL03Message::new(w.clone())],
             commitment_pk,
             None,
-        );
+            rng,
+        )?;
         let (Cw, rw) = (C_Cw.value(), C_Cw.randomness());
 
-        let C_Ce = Commitment::<CL03<CS>>::commit_with_commitment_pk(
+        let C_Ce = Commitment::<CL03<CS>>::commit_with_commitment_pk_with_rng(
             &[CL03Message::new(signature.e.clone())],
             commitment_pk,
             None,
-        );
+            rng,
+        )?;
         let (_Ce, re) = (C_Ce.value(), C_Ce.randomness());
 
         let (r_1, r_2, r_3, r_4, r_6, r_7, r_8, r_9) = (
-            random_bits(CS::ln),
-            random_bits(CS::ln),
-            random_bits(CS::ln),
-            random_bits(CS::ln),
-            random_bits(CS::ln),
-            random_bits(CS::ln),
-            random_bits(CS::ln),
-            random_bits(CS::ln),
+            random_bits_with_rng(CS::ln, rng),
+            random_bits_with_rng(CS::ln, rng),
+            random_bits_with_rng(CS::ln, rng),
+            random_bits_with_rng(CS::ln, rng),
+            random_bits_with_rng(CS::ln, rng),
+            random_bits_with_rng(CS::ln, rng),
+            random_bits_with_rng(CS::ln, rng),
+            random_bits_with_rng(CS::ln, rng),
         );
 
         let mut r_5: Vec<Integer> = Vec::new();
 
         for i in 0..n_attr {
             if unrevealed_message_indexes.contains(&i) {
-                r_5.push(random_bits(CS::ln));
+                r_5.push(random_bits_with_rng(CS::ln, rng));
             } else {
-                r_5.push(messages.get(i).expect("index overflow").value.clone());
+                r_5.push(messages[i].value.clone());
             }
         }
 
@@ -477,48 +493,32 @@ impl NISPSignaturePoK {
 
         let mut t_Cx = Integer::from(1);
         for i in 0..n_attr {
-            t_Cx = t_Cx * Integer::from(a_bases.0[i].pow_mod_ref(&r_5[i], N).unwrap())
+            t_Cx = t_Cx * a_bases.0[i].pow_mod_checked(&r_5[i], N)?
         }
 
         t_Cx = t_Cx % N;
 
-        let t_1 = (Integer::from(Cv.pow_mod_ref(&r_4, N).unwrap())
-            * divm(&Integer::from(1), &t_Cx, N)
-            * Integer::from(
-                divm(&Integer::from(1), &signer_pk.b, N)
-                    .pow_mod_ref(&r_6, N)
-                    .unwrap(),
-            )
-            * Integer::from(
-                divm(&Integer::from(1), &commitment_pk.g_bases[0], N)
-                    .pow_mod_ref(&r_8, N)
-                    .unwrap(),
-            ))
+        let t_1 = (Cv.pow_mod_checked(&r_4, N)?
+            * divm(&Integer::from(1), &t_Cx, N)?
+            * divm(&Integer::from(1), &signer_pk.b, N)?.pow_mod_checked(&r_6, N)?
+            * divm(&Integer::from(1), &commitment_pk.g_bases[0], N)?.pow_mod_checked(&r_8, N)?)
             % N;
-        let t_2 = (Integer::from(commitment_pk.g_bases[0].pow_mod_ref(&r_7, N).unwrap())
-            * Integer::from(commitment_pk.h.pow_mod_ref(&r_1, N).unwrap()))
+        let t_2 = (commitment_pk.g_bases[0].pow_mod_checked(&r_7, N)?
+            * commitment_pk.h.pow_mod_checked(&r_1, N)?)
             % N;
-        let t_3 = (Integer::from(Cw.pow_mod_ref(&r_4, N).unwrap())
-            * Integer::from(
-                divm(&Integer::from(1), &commitment_pk.g_bases[0], N)
-                    .pow_mod_ref(&r_8, N)
-                    .unwrap(),
-            )
-            * Integer::from(
-                divm(&Integer::from(1), &commitment_pk.h, N)
-                    .pow_mod_ref(&r_2, N)
-                    .unwrap(),
-            ))
+        let t_3 = (Cw.pow_mod_checked(&r_4, N)?
+            * divm(&Integer::from(1), &commitment_pk.g_bases[0], N)?.pow_mod_checked(&r_8, N)?
+            * divm(&Integer::from(1), &commitment_pk.h, N)?.pow_mod_checked(&r_2, N)?)
             % N;
 
         let mut t_4 = Integer::from(1);
         for i in 0..n_attr {
-            t_4 = t_4 * Integer::from(commitment_pk.g_bases[i].pow_mod_ref(&r_5[i], N).unwrap());
+            t_4 = t_4 * commitment_pk.g_bases[i].pow_mod_checked(&r_5[i], N)?;
         }
-        t_4 = (t_4 * Integer::from(commitment_pk.h.pow_mod_ref(&r_3, N).unwrap())) % N;
+        t_4 = (t_4 * commitment_pk.h.pow_mod_checked(&r_3, N)?) % N;
 
-        let t_5 = (Integer::from(commitment_pk.g_bases[0].pow_mod_ref(&r_4, N).unwrap())
-            * Integer::from(commitment_pk.h.pow_mod_ref(&r_9, N).unwrap()))
+        let t_5 = (commitment_pk.g_bases[0].pow_mod_checked(&r_4, N)?
+            * commitment_pk.h.pow_mod_checked(&r_9, N)?)
             % N;
         let str = t_1.to_string()
             + &t_2.to_string()
@@ -534,12 +534,10 @@ impl NISPSignaturePoK {
         let s_4 = r_4 + signature.e.clone() * &challenge;
         let mut s_5: Vec<Integer> = Vec::new();
         for i in unrevealed_message_indexes {
-            let si = r_5
-                .get(*i)
-                .expect("unrevealed_message_indexes not valid (overflow)")
+            let si = r_5.get(*i).ok_or(Error::InvalidMessageIndex)?
                 + messages
                     .get(*i)
-                    .expect("unrevealed_message_indexes not valid (overflow)")
+                    .ok_or(Error::InvalidMessageIndex)?
                     .value
                     .clone()
                     * &challenge;
@@ -551,7 +549,7 @@ impl NISPSignaturePoK {
         let s_8 = r_8 + w * signature.e.clone() * &challenge;
         let s_9 = r_9 + re * &challenge;
 
-        NISPSignaturePoK {
+        Ok(NISPSignaturePoK {
             challenge,
             s_1,
             s_2,
@@ -566,7 +564,7 @@ impl NISPSignaturePoK {
             Cv: C_Cv.cl03Commitment().clone(),
             Cw: C_Cw.cl03Commitment().clone(),
             Ce: C_Ce.cl03Commitment().clone(),
-        }
+        })
     }
 
     pub(crate) fn nisp5_MultiAttr_verify_proof<CS: CLCiphersuite>(
@@ -577,12 +575,29 @@ impl NISPSignaturePoK {
         messages: &[CL03Message],
         unrevealed_message_indexes: &[usize],
         n_signed_messages: usize,
-    ) -> bool
+    ) -> Result<bool, Error>
     where
         CS::HashAlg: Digest,
     {
-        if a_bases.0.len() < n_signed_messages && commitment_pk.g_bases.len() < n_signed_messages {
-            panic!("Not enough a_bases OR g_bases for the number of attributes");
+        if a_bases.0.len() < n_signed_messages
+            || commitment_pk.g_bases.len() < n_signed_messages
+            || n_signed_messages == 0
+        {
+            return Err(Error::NotEnoughBases);
+        }
+
+        if unrevealed_message_indexes
+            .iter()
+            .any(|&i| i >= n_signed_messages)
+        {
+            return Err(Error::InvalidMessageIndex);
+        }
+
+        let n_unrevealed = (0..n_signed_messages)
+            .filter(|i| unrevealed_message_indexes.contains(i))
+            .count();
+        if self.s_5.len() != n_unrevealed || messages.len() != n_signed_messages - n_unrevealed {
+            return Ok(false);
         }
 
         let mut t_Cx = Integer::from(1);
@@ -592,59 +607,40 @@ impl NISPSignaturePoK {
 
         for i in 0..n_signed_messages {
             if unrevealed_message_indexes.contains(&i) {
-                t_Cx = t_Cx * Integer::from(a_bases.0[i].pow_mod_ref(&self.s_5[idx], N).unwrap());
+                t_Cx = t_Cx * a_bases.0[i].pow_mod_checked(&self.s_5[idx], N)?;
                 idx += 1;
             } else {
                 let mi = &messages
                     .get(idx_revealed_msgs)
-                    .expect("index overflow!")
+                    .ok_or(Error::InvalidMessageIndex)?
                     .value;
                 let val = mi + (mi * &self.challenge).complete();
-                t_Cx = t_Cx * Integer::from(a_bases.0[i].pow_mod_ref(&val, N).unwrap());
+                t_Cx = t_Cx * a_bases.0[i].pow_mod_checked(&val, N)?;
                 idx_revealed_msgs += 1;
             }
         }
         t_Cx = t_Cx % N;
 
-        let input1 = (Integer::from(self.Cv.value.pow_mod_ref(&self.s_4, N).unwrap())
-            * divm(&Integer::from(1), &t_Cx, N)
-            * Integer::from(
-                divm(&Integer::from(1), &signer_pk.b, N)
-                    .pow_mod_ref(&self.s_6, N)
-                    .unwrap(),
-            )
-            * Integer::from(
-                divm(&Integer::from(1), &commitment_pk.g_bases[0], N)
-                    .pow_mod_ref(&self.s_8, N)
-                    .unwrap(),
-            )
-            * Integer::from(
-                signer_pk
-                    .c
-                    .pow_mod_ref(&(Integer::from(-1) * &self.challenge), N)
-                    .unwrap(),
-            ))
+        let input1 = (self.Cv.value.pow_mod_checked(&self.s_4, N)?
+            * divm(&Integer::from(1), &t_Cx, N)?
+            * divm(&Integer::from(1), &signer_pk.b, N)?.pow_mod_checked(&self.s_6, N)?
+            * divm(&Integer::from(1), &commitment_pk.g_bases[0], N)?
+                .pow_mod_checked(&self.s_8, N)?
+            * signer_pk
+                .c
+                .pow_mod_checked(&(Integer::from(-1) * &self.challenge), N)?)
             % N;
-        let input2 = (Integer::from(commitment_pk.g_bases[0].pow_mod_ref(&self.s_7, N).unwrap())
-            * Integer::from(commitment_pk.h.pow_mod_ref(&self.s_1, N).unwrap())
-            * Integer::from(
-                self.Cw
-                    .value
-                    .pow_mod_ref(&(Integer::from(-1) * &self.challenge), N)
-                    .unwrap(),
-            ))
+        let input2 = (commitment_pk.g_bases[0].pow_mod_checked(&self.s_7, N)?
+            * commitment_pk.h.pow_mod_checked(&self.s_1, N)?
+            * self
+                .Cw
+                .value
+                .pow_mod_checked(&(Integer::from(-1) * &self.challenge), N)?)
             % N;
-        let input3 = (Integer::from(self.Cw.value.pow_mod_ref(&self.s_4, N).unwrap())
-            * Integer::from(
-                divm(&Integer::from(1), &commitment_pk.g_bases[0], N)
-                    .pow_mod_ref(&self.s_8, N)
-                    .unwrap(),
-            )
-            * Integer::from(
-                divm(&Integer::from(1), &commitment_pk.h, N)
-                    .pow_mod_ref(&self.s_2, N)
-                    .unwrap(),
-            ))
+        let input3 = (self.Cw.value.pow_mod_checked(&self.s_4, N)?
+            * divm(&Integer::from(1), &commitment_pk.g_bases[0], N)?
+                .pow_mod_checked(&self.s_8, N)?
+            * divm(&Integer::from(1), &commitment_pk.h, N)?.pow_mod_checked(&self.s_2, N)?)
             % N;
 
         let mut input4 = Integer::from(1);
@@ -653,43 +649,33 @@ impl NISPSignaturePoK {
 
         for i in 0..n_signed_messages {
             if unrevealed_message_indexes.contains(&i) {
-                input4 = input4
-                    * Integer::from(
-                        commitment_pk.g_bases[i]
-                            .pow_mod_ref(&self.s_5[idx], N)
-                            .unwrap(),
-                    );
+                input4 = input4 * commitment_pk.g_bases[i].pow_mod_checked(&self.s_5[idx], N)?;
                 idx += 1;
             } else {
                 let mi = &messages
                     .get(idx_revealed_msgs)
-                    .expect("index overflow")
+                    .ok_or(Error::InvalidMessageIndex)?
                     .value;
                 let val = mi + (mi * &self.challenge).complete();
-                input4 =
-                    input4 * Integer::from(commitment_pk.g_bases[i].pow_mod_ref(&val, N).unwrap());
+                input4 = input4 * commitment_pk.g_bases[i].pow_mod_checked(&val, N)?;
                 idx_revealed_msgs += 1;
             }
         }
 
         input4 = (input4
-            * Integer::from(commitment_pk.h.pow_mod_ref(&self.s_3, N).unwrap())
-            * Integer::from(
-                self.Cx
-                    .value
-                    .pow_mod_ref(&(Integer::from(-1) * &self.challenge), N)
-                    .unwrap(),
-            ))
+            * commitment_pk.h.pow_mod_checked(&self.s_3, N)?
+            * self
+                .Cx
+                .value
+                .pow_mod_checked(&(Integer::from(-1) * &self.challenge), N)?)
             % N;
 
-        let input5 = (Integer::from(commitment_pk.g_bases[0].pow_mod_ref(&self.s_4, N).unwrap())
-            * Integer::from(commitment_pk.h.pow_mod_ref(&self.s_9, N).unwrap())
-            * Integer::from(
-                self.Ce
-                    .value
-                    .pow_mod_ref(&(Integer::from(-1) * &self.challenge), N)
-                    .unwrap(),
-            ))
+        let input5 = (commitment_pk.g_bases[0].pow_mod_checked(&self.s_4, N)?
+            * commitment_pk.h.pow_mod_checked(&self.s_9, N)?
+            * self
+                .Ce
+                .value
+                .pow_mod_checked(&(Integer::from(-1) * &self.challenge), N)?)
             % N;
 
         let str = input1.to_string()
@@ -700,6 +686,6 @@ impl NISPSignaturePoK {
         let hash = <CS::HashAlg as Digest>::digest(str);
         let challenge = Integer::from_digits(hash.as_slice(), Order::MsfBe);
 
-        challenge == self.challenge
+        Ok(challenge == self.challenge)
     }
 }
@@ -14,13 +14,20 @@
 
 use super::keys::{CL03PublicKey, CL03SecretKey};
 use crate::{
-    cl03::{bases::Bases, ciphersuites::CLCiphersuite},
+    cl03::{
+        bases::Bases,
+        ciphersuites::CLCiphersuite,
+        encoding::{self, impl_canonical_encoding},
+    },
+    errors::Error,
     schemes::algorithms::CL03,
     schemes::generics::Signature,
     utils::message::cl03_message::CL03Message,
-    utils::random::{random_bits, random_prime},
+    utils::random::{random_bits_with_rng, random_prime_with_rng},
+    utils::util::cl03_utils::IntegerExt,
 };
-use rug::{integer::Order, ops::Pow, Integer};
+use rand::{thread_rng, CryptoRng, RngCore};
+use rug::{ops::Pow, Integer};
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
@@ -30,35 +37,27 @@ pub struct CL03Signature {
     pub(crate) v: Integer,
 }
 
+impl_canonical_encoding!(CL03Signature { e, s, v });
+
 impl<CS: CLCiphersuite> Signature<CL03<CS>> {
     pub fn sign(
         pk: &CL03PublicKey,
         sk: &CL03SecretKey,
         a_bases: &Bases,
         message: &CL03Message,
-    ) -> Self {
-        let mut e = random_prime(CS::le);
-        let phi_n = (&sk.p - Integer::from(1)) * (&sk.q - Integer::from(1));
-
-        while ((&e > &Integer::from(2).pow(CS::le - 1))
-            && (&e < &Integer::from(2).pow(CS::le))
-            && (Integer::from(e.gcd_ref(&phi_n)) == 1))
-            == false
-        {
-            e = random_prime(CS::le);
-        }
-
-        let s = random_bits(CS::ls);
-        let e2n = Integer::from(e.invert_ref(&phi_n).unwrap());
-        // v = powmod((powmod(pk['a0'], m, pk['N']) * powmod(pk['b'], s, pk['N']) * pk['c']), (e2n), pk['N'])
-        let v = ((Integer::from(a_bases.0[0].pow_mod_ref(&message.value, &pk.N).unwrap()))
-            * Integer::from(pk.b.pow_mod_ref(&s, &pk.N).unwrap())
-            * &pk.c)
-            .pow_mod(&e2n, &pk.N)
-            .unwrap();
+    ) -> Result<Self, Error> {
+        Self::sign_with_rng(pk, sk, a_bases, message, &mut thread_rng())
+    }
 
-        let sig = CL03Signature { e, s, v };
-        Self::CL03(sig)
+    /// Same as [`Self::sign`], drawing `e` and `s` from `rng`
+    pub fn sign_with_rng<R: RngCore + CryptoRng>(
+        pk: &CL03PublicKey,
+        sk: &CL03SecretKey,
+        a_bases: &Bases,
+        message: &CL03Message,
+        rng: &mut R,
+    ) -> Result<Self, Error> {
+        Self::sign_multiattr_with_rng(pk, sk, a_bases, std::slice::from_ref(message), rng)
     }
 
     pub fn sign_multiattr(
@@ -66,8 +65,23 @@ impl<CS: CLCiphersuite> Signature<CL03<CS>> {
         sk: &CL03SecretKey,
         a_bases: &Bases,
         messages: &[CL03Message],
-    ) -> Self {
-        let mut e = random_prime(CS::le);
+    ) -> Result<Self, Error> {
+        Self::sign_multiattr_with_rng(pk, sk, a_bases, messages, &mut thread_rng())
+    }
+
+    /// Same as [`Self::sign_multiattr`], drawing `e` and `s` from `rng`
+    pub fn sign_multiattr_with_rng<R: RngCore + CryptoRng>(
+        pk: &CL03PublicKey,
+        sk: &CL03SecretKey,
+        a_bases: &Bases,
+        messages: &[CL03Message],
+        rng: &mut R,
+    ) -> Result<Self, Error> {
+        if messages.len() > a_bases.0.len() {
+            return Err(Error::NotEnoughBases);
+        }
+
+        let mut e = random_prime_with_rng(CS::le, rng);
         let phi_n = (&sk.p - Integer::from(1)) * (&sk.q - Integer::from(1));
 
         while ((&e > &Integer::from(2).pow(CS::le - 1))
@@ -75,44 +89,32 @@ impl<CS: CLCiphersuite> Signature<CL03<CS>> {
             && (Integer::from(e.gcd_ref(&phi_n)) == 1))
             == false
         {
-            e = random_prime(CS::le);
+            e = random_prime_with_rng(CS::le, rng);
         }
 
-        let s = random_bits(CS::ls);
-        let e2n = Integer::from(e.invert_ref(&phi_n).unwrap());
+        let s = random_bits_with_rng(CS::ls, rng);
+        let e2n = Integer::from(e.invert_ref(&phi_n).ok_or(Error::SignatureGenerationError(
+            "e is not invertible modulo phi(N)".to_owned(),
+        ))?);
         // v = powmod((powmod(pk['a0'], m, pk['N']) * powmod(pk['b'], s, pk['N']) * pk['c']), (e2n), pk['N'])
         let mut v: Integer = Integer::from(1);
         for (index, message) in messages.iter().enumerate() {
-            v = v * Integer::from(a_bases.0[index].pow_mod_ref(&message.value, &pk.N).unwrap())
+            v = v * a_bases.0[index].pow_mod_checked(&message.value, &pk.N)?
         }
-        v = (v * Integer::from(pk.b.pow_mod_ref(&s, &pk.N).unwrap()) * &pk.c)
-            .pow_mod(&e2n, &pk.N)
-            .unwrap();
+        v = (v * pk.b.pow_mod_checked(&s, &pk.N)? * &pk.c).pow_mod_checked(&e2n, &pk.N)?;
 
         let sig = CL03Signature { e, s, v };
-        Self::CL03(sig)
+        Ok(Self::CL03(sig))
     }
 
     //TODO: tenere solo verify_multiattr visto che funzione anche con un solo messaggio?
-    pub fn verify(&self, pk: &CL03PublicKey, a_bases: &Bases, message: &CL03Message) -> bool {
-        let sign = self.cl03Signature();
-
-        let lhs = Integer::from(sign.v.pow_mod_ref(&sign.e, &pk.N).unwrap());
-
-        let rhs = (Integer::from(a_bases.0[0].pow_mod_ref(&message.value, &pk.N).unwrap())
-            * Integer::from(pk.b.pow_mod_ref(&sign.s, &pk.N).unwrap())
-            * &pk.c)
-            % &pk.N;
-
-        if sign.e <= Integer::from(2).pow(CS::le - 1) || sign.e >= Integer::from(2).pow(CS::le) {
-            return false;
-        }
-
-        if lhs == rhs {
-            return true;
-        }
-
-        false
+    pub fn verify(
+        &self,
+        pk: &CL03PublicKey,
+        a_bases: &Bases,
+        message: &CL03Message,
+    ) -> Result<(), Error> {
+        self.verify_multiattr(pk, a_bases, std::slice::from_ref(message))
     }
 
     pub fn verify_multiattr(
@@ -120,54 +122,64 @@ impl<CS: CLCiphersuite> Signature<CL03<CS>> {
         pk: &CL03PublicKey,
         a_bases: &Bases,
         messages: &[CL03Message],
-    ) -> bool {
+    ) -> Result<(), Error> {
         if messages.len() > a_bases.0.len() {
-            panic!("Not enought a_bases!");
+            return Err(Error::NotEnoughBases);
         }
 
         let sign = self.cl03Signature();
 
-        let lhs = Integer::from(sign.v.pow_mod_ref(&sign.e, &pk.N).unwrap());
-
-        let mut rhs = Integer::from(1);
+        if sign.e <= Integer::from(2).pow(CS::le - 1) || sign.e >= Integer::from(2).pow(CS::le) {
+            return Err(Error::SignatureVerificationError);
+        }
 
-        messages.iter().enumerate().for_each(|(i, m)| {
-            rhs = &rhs * Integer::from(a_bases.0[i].pow_mod_ref(&m.value, &pk.N).unwrap())
-        });
+        let lhs = sign.v.pow_mod_checked(&sign.e, &pk.N)?;
 
-        rhs = (&rhs * Integer::from(pk.b.pow_mod_ref(&sign.s, &pk.N).unwrap()) * &pk.c) % &pk.N;
+        let mut rhs = Integer::from(1);
 
-        if sign.e <= Integer::from(2).pow(CS::le - 1) {
-            return false;
+        for (i, m) in messages.iter().enumerate() {
+            rhs = &rhs * a_bases.0[i].pow_mod_checked(&m.value, &pk.N)?;
         }
 
-        if lhs == rhs {
-            return true;
+        rhs = (&rhs * pk.b.pow_mod_checked(&sign.s, &pk.N)? * &pk.c) % &pk.N;
+
+        if lhs != rhs {
+            return Err(Error::SignatureVerificationError);
         }
 
-        false
+        Ok(())
     }
 
-    pub fn disclose_selectively(&self, messages: &[CL03Message], a_bases: Bases, pk: &CL03PublicKey, unrevealed_indexes: &[usize]) -> (Vec<CL03Message>, Bases) {
-
+    pub fn disclose_selectively(
+        &self,
+        messages: &[CL03Message],
+        a_bases: Bases,
+        pk: &CL03PublicKey,
+        unrevealed_indexes: &[usize],
+    ) -> Result<(Vec<CL03Message>, Bases), Error> {
         if messages.len() != a_bases.0.len() {
-            panic!("Mismatch between messages and bases length!");
+            return Err(Error::NotEnoughBases);
+        }
+
+        if unrevealed_indexes.iter().any(|&i| i >= messages.len()) {
+            return Err(Error::InvalidMessageIndex);
         }
 
         let mut sd_messages: Vec<CL03Message> = Vec::from(messages);
 
         if unrevealed_indexes.len() == 0 {
-            return (sd_messages, a_bases)
+            return Ok((sd_messages, a_bases));
         }
 
         let mut sd_bases: Bases = a_bases.clone();
 
         for index in unrevealed_indexes {
-            sd_bases.0[*index] = Integer::from(a_bases.0[*index].pow_mod_ref(&messages[*index].value, &pk.N).unwrap());
+            sd_bases.0[*index] =
+                a_bases.0[*index].pow_mod_checked(&messages[*index].value, &pk.N)?;
             sd_messages[*index].value = Integer::from(1);
         }
 
-        (sd_messages, sd_bases)
+        Ok((sd_messages, sd_bases))
     }
 
     pub fn cl03Signature(&self) -> &CL03Signature {
@@ -178,52 +190,112 @@ impl<CS: CLCiphersuite> Signature<CL03<CS>> {
     }
 
     pub fn to_bytes(&self) -> Vec<u8> {
-        let signature = self.cl03Signature();
-        let mut bytes: Vec<u8> = Vec::new();
-        let mut e_digits = vec![0u8; CS::le as usize];
-        let mut s_digits = vec![0u8; CS::ls as usize];
-        signature.e.write_digits(&mut e_digits, Order::MsfBe);
-        signature.s.write_digits(&mut s_digits, Order::MsfBe);
-        bytes.extend_from_slice(&e_digits);
-        bytes.extend_from_slice(&s_digits);
-        bytes.extend_from_slice(&signature.v.to_digits(Order::MsfBe));
-
-        bytes
+        encoding::to_bytes(self.cl03Signature())
     }
 
-    pub fn from_bytes(bytes: &[u8]) -> Self {
-        let e = Integer::from_digits(&bytes[0usize..CS::le as usize], Order::MsfBe);
-        let s = Integer::from_digits(
-            &bytes[CS::le as usize..(CS::le as usize + CS::ls as usize)],
-            Order::MsfBe,
-        );
-        let v = Integer::from_digits(&bytes[(CS::le as usize + CS::ls as usize)..], Order::MsfBe);
-
-        Self::CL03(CL03Signature { e, s, v })
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        Ok(Self::CL03(encoding::from_bytes(bytes)?))
     }
 }
 
 #[cfg(test)]
 mod tests {
 
-    use crate::cl03::ciphersuites::CLCiphersuite;
-    use crate::schemes::algorithms::CL03_CL1024_SHA256;
+    use crate::cl03::ciphersuites::{CL1024Sha256, CLCiphersuite};
+    use crate::schemes::algorithms::{
+        CL03_CL1024_SHA256, CL03_CL2048_SHA256, CL03_CL3072_SHA384, CL03_CL3072_SHA512,
+    };
     use crate::{
-        cl03::bases::Bases,
+        cl03::{
+            bases::Bases,
+            keys::{CL03PublicKey, CL03SecretKey},
+        },
+        errors::Error,
         keys::pair::KeyPair,
         schemes::algorithms::{Ciphersuite, Scheme, CL03},
         schemes::generics::Signature,
         utils::message::cl03_message::CL03Message,
     };
     use digest::Digest;
+    use rand::{rngs::StdRng, SeedableRng};
+    use rug::Integer;
+    use zeroize::Zeroize;
 
     //Signature (sign) - CL1024-SHA256
     #[test]
     fn signature_cl1024_sha256() {
-        signature::<CL03_CL1024_SHA256>();
+        signature::<CL03_CL1024_SHA256>(KeyPair::<CL03<CL1024Sha256>>::generate());
+    }
+
+    //Signature (sign) - CL2048-SHA256
+    #[test]
+    fn signature_cl2048_sha256() {
+        signature::<CL03_CL2048_SHA256>(KeyPair::from_fixture(
+            "./fixture_data_cl03/cl2048/keypair.json",
+        ));
     }
 
-    fn signature<S: Scheme>()
+    //Signature (sign) - CL3072-SHA384
+    #[test]
+    fn signature_cl3072_sha384() {
+        signature::<CL03_CL3072_SHA384>(KeyPair::from_fixture(
+            "./fixture_data_cl03/cl3072/keypair.json",
+        ));
+    }
+
+    //Signature (sign) - CL3072-SHA512
+    #[test]
+    fn signature_cl3072_sha512() {
+        signature::<CL03_CL3072_SHA512>(KeyPair::from_fixture(
+            "./fixture_data_cl03/cl3072/keypair.json",
+        ));
+    }
+
+    //Secret key - zeroize and redacted Debug
+    #[test]
+    fn secret_key_zeroize() {
+        let mut sk = CL03SecretKey::new(Integer::from(1019), Integer::from(2039));
+        assert_eq!(
+            format!("{:?}", sk),
+            "CL03SecretKey { p: <redacted>, q: <redacted> }"
+        );
+
+        sk.zeroize();
+        assert_eq!(sk.p, 0);
+        assert_eq!(sk.q, 0);
+    }
+
+    //Signature (sign) with a seeded rng - CL1024-SHA256
+    #[test]
+    fn signature_with_rng_cl1024_sha256() {
+        type CS = <CL03_CL1024_SHA256 as Scheme>::Ciphersuite;
+
+        let sign = |seed| {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let keypair = KeyPair::<CL03<CS>>::generate_with_rng(&mut rng);
+            let a_bases = Bases::generate_with_rng(keypair.public_key(), 1, &mut rng);
+            let message = CL03Message::map_message_to_integer_as_hash::<CS>(b"message");
+            let signature = Signature::<CL03<CS>>::sign_with_rng(
+                keypair.public_key(),
+                keypair.private_key(),
+                &a_bases,
+                &message,
+                &mut rng,
+            )
+            .unwrap();
+            assert!(signature
+                .verify(keypair.public_key(), &a_bases, &message)
+                .is_ok());
+            (keypair.public_key().clone(), signature)
+        };
+
+        let (pk, signature) = sign(1);
+        let (same_pk, same_signature) = sign(1);
+        assert_eq!(pk, same_pk);
+        assert!(signature == same_signature);
+    }
+
+    fn signature<S: Scheme>(cl03_keypair: KeyPair<CL03<S::Ciphersuite>>)
     where
         S::Ciphersuite: CLCiphersuite,
         <S::Ciphersuite as Ciphersuite>::HashAlg: Digest,
@@ -231,7 +303,6 @@ mod tests {
         const msg: &str = "9872ad089e452c7b6e283dfac2a80d58e8d0ff71cc4d5e310a1debdda4a45f02";
         const wrong_msg: &str = "9872ad089e452c7b6e283dfac2a80d58e8d0ff71cc4d5e310a1debdda4a45f03";
 
-        let cl03_keypair = KeyPair::<CL03<S::Ciphersuite>>::generate();
         let a_bases = Bases::generate(cl03_keypair.public_key(), 1);
 
         let message = CL03Message::map_message_to_integer_as_hash::<S::Ciphersuite>(
@@ -247,14 +318,51 @@ mod tests {
             cl03_keypair.private_key(),
             &a_bases,
             &message,
-        );
+        )
+        .unwrap();
 
         let valid = signature.verify(cl03_keypair.public_key(), &a_bases, &message);
 
-        assert!(valid, "Error! Signature should be VALID");
+        assert!(valid.is_ok(), "Error! Signature should be VALID");
 
         let valid = signature.verify(cl03_keypair.public_key(), &a_bases, &wrong_message);
 
-        assert!(!valid, "Error! Signature should be INVALID");
+        assert!(
+            matches!(valid, Err(Error::SignatureVerificationError)),
+            "Error! Signature should be INVALID"
+        );
+
+        let valid = signature.verify_multiattr(
+            cl03_keypair.public_key(),
+            &a_bases,
+            &[message.clone(), message],
+        );
+
+        assert!(
+            matches!(valid, Err(Error::NotEnoughBases)),
+            "Error! Verification with missing bases should FAIL without panicking"
+        );
+
+        let bytes = signature.to_bytes();
+        assert!(
+            Signature::<CL03<S::Ciphersuite>>::from_bytes(&bytes).unwrap() == signature,
+            "Error! Signature should round-trip through its byte encoding"
+        );
+
+        let truncated = &bytes[..bytes.len() - 1];
+        assert!(
+            Signature::<CL03<S::Ciphersuite>>::from_bytes(truncated).is_err(),
+            "Error! Deserialization of a truncated signature should FAIL"
+        );
+
+        let pk = cl03_keypair.public_key();
+        let sk = cl03_keypair.private_key();
+        assert_eq!(&CL03PublicKey::from_bytes(&pk.to_bytes()).unwrap(), pk);
+        assert_eq!(&CL03SecretKey::from_bytes(&sk.to_bytes()).unwrap(), sk);
+        assert_eq!(Bases::from_bytes(&a_bases.to_bytes()).unwrap().0, a_bases.0);
+        assert!(matches!(
+            CL03PublicKey::from_bytes(&sk.to_bytes()),
+            Err(Error::KeyDeserializationError)
+        ));
     }
 }
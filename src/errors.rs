@@ -12,56 +12,120 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use thiserror::Error;
+use alloc::string::String;
+use core::fmt;
 
-#[derive(Error, Clone, Debug)]
+#[derive(Clone, Debug)]
 pub enum Error {
-    #[error("Error during keypair generation")]
     KeyGenError(String),
-    #[error("Invalid key")]
     KeyDeserializationError,
-    #[error("Error during computation of a Blind Signature")]
+    /// The public key is the identity or not in the prime order subgroup (KeyValidate)
+    InvalidPublicKey,
+    InvalidProofOfPossession,
     BlindSignError(String),
-    #[error("Error during computation of a Signature")]
     SignatureGenerationError(String),
-    #[error("Not a valid Signature")]
     InvalidSignature,
-    #[error("Error during hash to scalar computation")]
     HashToScalarError,
-    #[error("Error mapping a message to scalar")]
     MapMessageToScalarError,
-    #[error("Not enough Generators")]
     NotEnoughGenerators,
     /// [More Info](https://datatracker.ietf.org/doc/html/draft-irtf-cfrg-bbs-signatures-07#name-coresign) in the `Note` at the end
-    #[error(" A == Identity_G1")]
     G1IdentityError,
-    #[error("Error during deserialization")]
     DeserializationError(String),
-    #[error("Signature is not valid")]
+    /// The encoded value or the prebuilt generators were produced for a different ciphersuite or api_id
+    CiphersuiteMismatch,
     SignatureVerificationError,
-    #[error("Error during computation of a Proof of Knowledge of a Signature")]
     ProofGenError(String),
-    #[error("Error during computation of a Blind Proof of Knowledge of a Signature")]
     BlindProofGenError(String),
-    #[error("Unknown error")]
     Unspecified,
 
-    #[error("Signature update failed")]
     UpdateSignatureError(String),
 
-    #[error("Invalid Proof of Knowledge of a Signature")]
     InvalidProofOfKnowledgeSignature,
-    #[error("Proof of Knowledge of a Signature verification failed")]
     PoKSVerificationError(String),
 
-    #[error("This should NOT happen!")]
     UnespectedError,
 
-    #[error("Invalid commitment")]
     InvalidCommitment,
-    #[error("Invalid commitment proof")]
     InvalidCommitmentProof,
 
-    #[error("Failed to compute the blind challenge")]
     ChallengeComputationFailed,
+
+    InvalidPseudonym,
+
+    BatchVerificationError(usize),
+
+    RangeProofGenError(String),
+    InvalidRangeProof,
+    RangeProofVerificationError(String),
+
+    NotEnoughBases,
+    InvalidMessageIndex,
+    NotInvertibleElement,
+    InvalidRangeBounds,
 }
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::KeyGenError(_) => f.write_str("Error during keypair generation"),
+            Error::KeyDeserializationError => f.write_str("Invalid key"),
+            Error::InvalidPublicKey => f.write_str("Public key is not valid"),
+            Error::InvalidProofOfPossession => f.write_str("Proof of possession is not valid"),
+            Error::BlindSignError(_) => {
+                f.write_str("Error during computation of a Blind Signature")
+            }
+            Error::SignatureGenerationError(_) => {
+                f.write_str("Error during computation of a Signature")
+            }
+            Error::InvalidSignature => f.write_str("Not a valid Signature"),
+            Error::HashToScalarError => f.write_str("Error during hash to scalar computation"),
+            Error::MapMessageToScalarError => f.write_str("Error mapping a message to scalar"),
+            Error::NotEnoughGenerators => f.write_str("Not enough Generators"),
+            Error::G1IdentityError => f.write_str(" A == Identity_G1"),
+            Error::DeserializationError(_) => f.write_str("Error during deserialization"),
+            Error::CiphersuiteMismatch => {
+                f.write_str("Encoded value belongs to a different ciphersuite")
+            }
+            Error::SignatureVerificationError => f.write_str("Signature is not valid"),
+            Error::ProofGenError(_) => {
+                f.write_str("Error during computation of a Proof of Knowledge of a Signature")
+            }
+            Error::BlindProofGenError(_) => {
+                f.write_str("Error during computation of a Blind Proof of Knowledge of a Signature")
+            }
+            Error::Unspecified => f.write_str("Unknown error"),
+            Error::UpdateSignatureError(_) => f.write_str("Signature update failed"),
+            Error::InvalidProofOfKnowledgeSignature => {
+                f.write_str("Invalid Proof of Knowledge of a Signature")
+            }
+            Error::PoKSVerificationError(_) => {
+                f.write_str("Proof of Knowledge of a Signature verification failed")
+            }
+            Error::UnespectedError => f.write_str("This should NOT happen!"),
+            Error::InvalidCommitment => f.write_str("Invalid commitment"),
+            Error::InvalidCommitmentProof => f.write_str("Invalid commitment proof"),
+            Error::ChallengeComputationFailed => {
+                f.write_str("Failed to compute the blind challenge")
+            }
+            Error::InvalidPseudonym => f.write_str("Invalid pseudonym"),
+            Error::BatchVerificationError(index) => write!(
+                f,
+                "Batch verification failed, first invalid item at index {index}"
+            ),
+            Error::RangeProofGenError(_) => {
+                f.write_str("Error during computation of a Range Proof")
+            }
+            Error::InvalidRangeProof => f.write_str("Invalid Range Proof"),
+            Error::RangeProofVerificationError(_) => f.write_str("Range Proof verification failed"),
+            Error::NotEnoughBases => f.write_str("Not enough bases for the number of messages"),
+            Error::InvalidMessageIndex => f.write_str("Invalid message index"),
+            Error::NotInvertibleElement => f.write_str("Element is not invertible modulo N"),
+            Error::InvalidRangeBounds => f.write_str(
+                "Invalid range bounds, the upper bound must be greater than the lower bound",
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
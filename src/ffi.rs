@@ -0,0 +1,1198 @@
+// Copyright 2023 Fondazione LINKS
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! C ABI of the BBS+ implementation (see `include/zkryptium.h`).
+//!
+//! Every function takes the ciphersuite identifier as first argument (e.g., [`ZKRYPTIUM_BBS_BLS12_381_SHA_256`]) and returns a [`ZkryptiumStatus`].
+//! Inputs are borrowed [`ZkryptiumBytes`]: an OPTIONAL input is omitted by passing a null `data` pointer with `len` 0.
+//! Outputs are [`ZkryptiumBuffer`]s allocated by the library, written only on success and released with [`zkryptium_buffer_free`].
+
+use crate::{
+    bbsplus::{
+        ciphersuites::{BbsCiphersuite, Bls12381Sha256, Bls12381Shake256},
+        commitment::BlindFactor,
+        keys::{BBSplusPublicKey, BBSplusSecretKey},
+        signature::BBSplusSignature,
+    },
+    errors::Error,
+    keys::pair::KeyPair,
+    schemes::{
+        algorithms::BBSplus,
+        generics::{BlindSignature, Commitment, PoKSignature, Signature},
+    },
+};
+use elliptic_curve::hash2curve::ExpandMsg;
+use std::{
+    os::raw::c_char,
+    panic::{self, AssertUnwindSafe},
+    ptr, slice,
+};
+use zeroize::Zeroize;
+
+/// BLS12-381-SHA-256 ciphersuite identifier.
+pub const ZKRYPTIUM_BBS_BLS12_381_SHA_256: u32 = 0;
+/// BLS12-381-SHAKE-256 ciphersuite identifier.
+pub const ZKRYPTIUM_BBS_BLS12_381_SHAKE_256: u32 = 1;
+
+/// Borrowed octet string.
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct ZkryptiumBytes {
+    pub data: *const u8,
+    pub len: usize,
+}
+
+/// Octet string owned by the library, to be released with [`zkryptium_buffer_free`].
+#[repr(C)]
+#[derive(Debug)]
+pub struct ZkryptiumBuffer {
+    pub data: *mut u8,
+    pub len: usize,
+}
+
+/// Result of a call. The codes from 100 on map the variants of [`Error`].
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ZkryptiumStatus {
+    Ok = 0,
+    NullPointer = 1,
+    InvalidCiphersuite = 2,
+    Panic = 3,
+    KeyGenError = 100,
+    KeyDeserializationError = 101,
+    BlindSignError = 102,
+    SignatureGenerationError = 103,
+    InvalidSignature = 104,
+    HashToScalarError = 105,
+    MapMessageToScalarError = 106,
+    NotEnoughGenerators = 107,
+    G1IdentityError = 108,
+    DeserializationError = 109,
+    SignatureVerificationError = 110,
+    ProofGenError = 111,
+    BlindProofGenError = 112,
+    Unspecified = 113,
+    UpdateSignatureError = 114,
+    InvalidProofOfKnowledgeSignature = 115,
+    PoKSVerificationError = 116,
+    UnespectedError = 117,
+    InvalidCommitment = 118,
+    InvalidCommitmentProof = 119,
+    ChallengeComputationFailed = 120,
+    InvalidPseudonym = 121,
+    BatchVerificationError = 122,
+    RangeProofGenError = 123,
+    InvalidRangeProof = 124,
+    RangeProofVerificationError = 125,
+    NotEnoughBases = 126,
+    InvalidMessageIndex = 127,
+    NotInvertibleElement = 128,
+    InvalidRangeBounds = 129,
+    CiphersuiteMismatch = 130,
+    InvalidPublicKey = 131,
+    InvalidProofOfPossession = 132,
+}
+
+impl From<Error> for ZkryptiumStatus {
+    fn from(error: Error) -> Self {
+        match error {
+            Error::KeyGenError(_) => Self::KeyGenError,
+            Error::KeyDeserializationError => Self::KeyDeserializationError,
+            Error::BlindSignError(_) => Self::BlindSignError,
+            Error::SignatureGenerationError(_) => Self::SignatureGenerationError,
+            Error::InvalidSignature => Self::InvalidSignature,
+            Error::HashToScalarError => Self::HashToScalarError,
+            Error::MapMessageToScalarError => Self::MapMessageToScalarError,
+            Error::NotEnoughGenerators => Self::NotEnoughGenerators,
+            Error::G1IdentityError => Self::G1IdentityError,
+            Error::DeserializationError(_) => Self::DeserializationError,
+            Error::SignatureVerificationError => Self::SignatureVerificationError,
+            Error::ProofGenError(_) => Self::ProofGenError,
+            Error::BlindProofGenError(_) => Self::BlindProofGenError,
+            Error::Unspecified => Self::Unspecified,
+            Error::UpdateSignatureError(_) => Self::UpdateSignatureError,
+            Error::InvalidProofOfKnowledgeSignature => Self::InvalidProofOfKnowledgeSignature,
+            Error::PoKSVerificationError(_) => Self::PoKSVerificationError,
+            Error::UnespectedError => Self::UnespectedError,
+            Error::InvalidCommitment => Self::InvalidCommitment,
+            Error::InvalidCommitmentProof => Self::InvalidCommitmentProof,
+            Error::ChallengeComputationFailed => Self::ChallengeComputationFailed,
+            Error::InvalidPseudonym => Self::InvalidPseudonym,
+            Error::BatchVerificationError(_) => Self::BatchVerificationError,
+            Error::RangeProofGenError(_) => Self::RangeProofGenError,
+            Error::InvalidRangeProof => Self::InvalidRangeProof,
+            Error::RangeProofVerificationError(_) => Self::RangeProofVerificationError,
+            Error::NotEnoughBases => Self::NotEnoughBases,
+            Error::InvalidMessageIndex => Self::InvalidMessageIndex,
+            Error::NotInvertibleElement => Self::NotInvertibleElement,
+            Error::InvalidRangeBounds => Self::InvalidRangeBounds,
+            Error::CiphersuiteMismatch => Self::CiphersuiteMismatch,
+            Error::InvalidPublicKey => Self::InvalidPublicKey,
+            Error::InvalidProofOfPossession => Self::InvalidProofOfPossession,
+        }
+    }
+}
+
+/// Calls the ciphersuite-generic function `$f` with the ciphersuite selected by `$ciphersuite`
+macro_rules! dispatch {
+    ($ciphersuite:expr, $f:ident($($arg:expr),* $(,)?)) => {
+        match $ciphersuite {
+            ZKRYPTIUM_BBS_BLS12_381_SHA_256 => $f::<Bls12381Sha256>($($arg),*),
+            ZKRYPTIUM_BBS_BLS12_381_SHAKE_256 => $f::<Bls12381Shake256>($($arg),*),
+            _ => Err(ZkryptiumStatus::InvalidCiphersuite),
+        }
+    };
+}
+
+/// Runs `f`, so that a panic never unwinds across the C ABI
+fn ffi_call(f: impl FnOnce() -> Result<(), ZkryptiumStatus>) -> ZkryptiumStatus {
+    match panic::catch_unwind(AssertUnwindSafe(f)) {
+        Ok(Ok(())) => ZkryptiumStatus::Ok,
+        Ok(Err(status)) => status,
+        Err(_) => ZkryptiumStatus::Panic,
+    }
+}
+
+unsafe fn optional_slice<'a, T>(
+    data: *const T,
+    len: usize,
+) -> Result<Option<&'a [T]>, ZkryptiumStatus> {
+    match (data.is_null(), len) {
+        (true, 0) => Ok(None),
+        (true, _) => Err(ZkryptiumStatus::NullPointer),
+        (false, _) => Ok(Some(slice::from_raw_parts(data, len))),
+    }
+}
+
+unsafe fn optional_bytes<'a>(bytes: ZkryptiumBytes) -> Result<Option<&'a [u8]>, ZkryptiumStatus> {
+    optional_slice(bytes.data, bytes.len)
+}
+
+unsafe fn required_bytes<'a>(bytes: ZkryptiumBytes) -> Result<&'a [u8], ZkryptiumStatus> {
+    optional_bytes(bytes)?.ok_or(ZkryptiumStatus::NullPointer)
+}
+
+unsafe fn optional_messages(
+    messages: *const ZkryptiumBytes,
+    len: usize,
+) -> Result<Option<Vec<Vec<u8>>>, ZkryptiumStatus> {
+    optional_slice(messages, len)?
+        .map(|messages| {
+            messages
+                .iter()
+                .map(|m| Ok(optional_bytes(*m)?.unwrap_or(&[]).to_vec()))
+                .collect()
+        })
+        .transpose()
+}
+
+unsafe fn optional_blind_factor(
+    bytes: ZkryptiumBytes,
+) -> Result<Option<BlindFactor>, ZkryptiumStatus> {
+    optional_bytes(bytes)?
+        .map(|b| {
+            let b = <&[u8; 32]>::try_from(b).map_err(|_| ZkryptiumStatus::DeserializationError)?;
+            Ok(BlindFactor::from_bytes(b)?)
+        })
+        .transpose()
+}
+
+fn signature_bytes(bytes: &[u8]) -> Result<&[u8; BBSplusSignature::BYTES], ZkryptiumStatus> {
+    <&[u8; BBSplusSignature::BYTES]>::try_from(bytes).map_err(|_| ZkryptiumStatus::InvalidSignature)
+}
+
+fn check_out(out: *mut ZkryptiumBuffer) -> Result<(), ZkryptiumStatus> {
+    if out.is_null() {
+        Err(ZkryptiumStatus::NullPointer)
+    } else {
+        Ok(())
+    }
+}
+
+/// Moves `bytes` into `out`. The caller must have checked that `out` is not null.
+unsafe fn write_out(out: *mut ZkryptiumBuffer, bytes: Vec<u8>) {
+    let len = bytes.len();
+    let data = Box::into_raw(bytes.into_boxed_slice()) as *mut u8;
+    out.write(ZkryptiumBuffer { data, len });
+}
+
+/// Releases a buffer returned by the library, zeroing its content. Passing a buffer with a null `data` pointer is a no-op.
+///
+/// # Safety
+/// `buffer` must have been returned by the library and not released yet.
+#[no_mangle]
+pub unsafe extern "C" fn zkryptium_buffer_free(buffer: ZkryptiumBuffer) {
+    if buffer.data.is_null() {
+        return;
+    }
+    let mut bytes = Box::from_raw(ptr::slice_from_raw_parts_mut(buffer.data, buffer.len));
+    bytes.zeroize();
+}
+
+/// Returns a static, NUL-terminated description of `status`.
+#[no_mangle]
+pub extern "C" fn zkryptium_status_message(status: ZkryptiumStatus) -> *const c_char {
+    let message: &'static [u8] = match status {
+        ZkryptiumStatus::Ok => b"Ok\0",
+        ZkryptiumStatus::NullPointer => b"Unexpected null pointer\0",
+        ZkryptiumStatus::InvalidCiphersuite => b"Unknown ciphersuite\0",
+        ZkryptiumStatus::Panic => b"Unexpected panic\0",
+        ZkryptiumStatus::KeyGenError => b"Error during keypair generation\0",
+        ZkryptiumStatus::KeyDeserializationError => b"Invalid key\0",
+        ZkryptiumStatus::BlindSignError => b"Error during computation of a Blind Signature\0",
+        ZkryptiumStatus::SignatureGenerationError => b"Error during computation of a Signature\0",
+        ZkryptiumStatus::InvalidSignature => b"Not a valid Signature\0",
+        ZkryptiumStatus::HashToScalarError => b"Error during hash to scalar computation\0",
+        ZkryptiumStatus::MapMessageToScalarError => b"Error mapping a message to scalar\0",
+        ZkryptiumStatus::NotEnoughGenerators => b"Not enough Generators\0",
+        ZkryptiumStatus::G1IdentityError => b"A == Identity_G1\0",
+        ZkryptiumStatus::DeserializationError => b"Error during deserialization\0",
+        ZkryptiumStatus::SignatureVerificationError => b"Signature is not valid\0",
+        ZkryptiumStatus::ProofGenError => {
+            b"Error during computation of a Proof of Knowledge of a Signature\0"
+        }
+        ZkryptiumStatus::BlindProofGenError => {
+            b"Error during computation of a Blind Proof of Knowledge of a Signature\0"
+        }
+        ZkryptiumStatus::Unspecified => b"Unknown error\0",
+        ZkryptiumStatus::UpdateSignatureError => b"Signature update failed\0",
+        ZkryptiumStatus::InvalidProofOfKnowledgeSignature => {
+            b"Invalid Proof of Knowledge of a Signature\0"
+        }
+        ZkryptiumStatus::PoKSVerificationError => {
+            b"Proof of Knowledge of a Signature verification failed\0"
+        }
+        ZkryptiumStatus::UnespectedError => b"This should NOT happen!\0",
+        ZkryptiumStatus::InvalidCommitment => b"Invalid commitment\0",
+        ZkryptiumStatus::InvalidCommitmentProof => b"Invalid commitment proof\0",
+        ZkryptiumStatus::ChallengeComputationFailed => b"Failed to compute the blind challenge\0",
+        ZkryptiumStatus::InvalidPseudonym => b"Invalid pseudonym\0",
+        ZkryptiumStatus::BatchVerificationError => b"Batch verification failed\0",
+        ZkryptiumStatus::RangeProofGenError => b"Error during computation of a Range Proof\0",
+        ZkryptiumStatus::InvalidRangeProof => b"Invalid Range Proof\0",
+        ZkryptiumStatus::RangeProofVerificationError => b"Range Proof verification failed\0",
+        ZkryptiumStatus::NotEnoughBases => b"Not enough bases for the number of messages\0",
+        ZkryptiumStatus::InvalidMessageIndex => b"Invalid message index\0",
+        ZkryptiumStatus::NotInvertibleElement => b"Element is not invertible modulo N\0",
+        ZkryptiumStatus::InvalidRangeBounds => b"Invalid range bounds\0",
+        ZkryptiumStatus::CiphersuiteMismatch => {
+            b"Encoded value belongs to a different ciphersuite\0"
+        }
+        ZkryptiumStatus::InvalidPublicKey => b"Public key is not valid\0",
+        ZkryptiumStatus::InvalidProofOfPossession => b"Proof of possession is not valid\0",
+    };
+    message.as_ptr() as *const c_char
+}
+
+unsafe fn keygen<CS: BbsCiphersuite>(
+    key_material: ZkryptiumBytes,
+    key_info: ZkryptiumBytes,
+    key_dst: ZkryptiumBytes,
+    out_sk: *mut ZkryptiumBuffer,
+    out_pk: *mut ZkryptiumBuffer,
+) -> Result<(), ZkryptiumStatus>
+where
+    CS::Expander: for<'a> ExpandMsg<'a>,
+{
+    check_out(out_sk)?;
+    check_out(out_pk)?;
+    let keypair = KeyPair::<BBSplus<CS>>::generate(
+        required_bytes(key_material)?,
+        optional_bytes(key_info)?,
+        optional_bytes(key_dst)?,
+    )?;
+    write_out(out_sk, keypair.private_key().to_bytes().to_vec());
+    write_out(out_pk, keypair.public_key().to_bytes().to_vec());
+    Ok(())
+}
+
+/// Generates a keypair from `key_material` (see [`KeyPair::generate`]). `out_sk` receives the 32 bytes secret key and `out_pk` the 96 bytes public key.
+///
+/// # Safety
+/// Every non-null pointer must be valid for the given length, and the output pointers must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn zkryptium_bbs_keygen(
+    ciphersuite: u32,
+    key_material: ZkryptiumBytes,
+    key_info: ZkryptiumBytes,
+    key_dst: ZkryptiumBytes,
+    out_sk: *mut ZkryptiumBuffer,
+    out_pk: *mut ZkryptiumBuffer,
+) -> ZkryptiumStatus {
+    ffi_call(|| {
+        dispatch!(
+            ciphersuite,
+            keygen(key_material, key_info, key_dst, out_sk, out_pk)
+        )
+    })
+}
+
+unsafe fn sign<CS: BbsCiphersuite>(
+    sk: ZkryptiumBytes,
+    pk: ZkryptiumBytes,
+    header: ZkryptiumBytes,
+    messages: *const ZkryptiumBytes,
+    messages_len: usize,
+    out_signature: *mut ZkryptiumBuffer,
+) -> Result<(), ZkryptiumStatus>
+where
+    CS::Expander: for<'a> ExpandMsg<'a>,
+{
+    check_out(out_signature)?;
+    let sk = BBSplusSecretKey::from_bytes(required_bytes(sk)?)?;
+    let pk = BBSplusPublicKey::from_bytes(required_bytes(pk)?)?;
+    let messages = optional_messages(messages, messages_len)?;
+    let signature =
+        Signature::<BBSplus<CS>>::sign(messages.as_deref(), &sk, &pk, optional_bytes(header)?)?;
+    write_out(out_signature, signature.to_bytes().to_vec());
+    Ok(())
+}
+
+/// Signs `messages` (see [`Signature::sign`]). `out_signature` receives the 80 bytes signature.
+///
+/// # Safety
+/// Every non-null pointer must be valid for the given length, and the output pointers must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn zkryptium_bbs_sign(
+    ciphersuite: u32,
+    sk: ZkryptiumBytes,
+    pk: ZkryptiumBytes,
+    header: ZkryptiumBytes,
+    messages: *const ZkryptiumBytes,
+    messages_len: usize,
+    out_signature: *mut ZkryptiumBuffer,
+) -> ZkryptiumStatus {
+    ffi_call(|| {
+        dispatch!(
+            ciphersuite,
+            sign(sk, pk, header, messages, messages_len, out_signature)
+        )
+    })
+}
+
+unsafe fn verify<CS: BbsCiphersuite>(
+    pk: ZkryptiumBytes,
+    signature: ZkryptiumBytes,
+    header: ZkryptiumBytes,
+    messages: *const ZkryptiumBytes,
+    messages_len: usize,
+) -> Result<(), ZkryptiumStatus>
+where
+    CS::Expander: for<'a> ExpandMsg<'a>,
+{
+    let pk = BBSplusPublicKey::from_bytes(required_bytes(pk)?)?;
+    let signature =
+        Signature::<BBSplus<CS>>::from_bytes(signature_bytes(required_bytes(signature)?)?)?;
+    let messages = optional_messages(messages, messages_len)?;
+    Ok(signature.verify(&pk, messages.as_deref(), optional_bytes(header)?)?)
+}
+
+/// Verifies a signature (see [`Signature::verify`]). Returns [`ZkryptiumStatus::Ok`] only if the signature is valid.
+///
+/// # Safety
+/// Every non-null pointer must be valid for the given length.
+#[no_mangle]
+pub unsafe extern "C" fn zkryptium_bbs_verify(
+    ciphersuite: u32,
+    pk: ZkryptiumBytes,
+    signature: ZkryptiumBytes,
+    header: ZkryptiumBytes,
+    messages: *const ZkryptiumBytes,
+    messages_len: usize,
+) -> ZkryptiumStatus {
+    ffi_call(|| {
+        dispatch!(
+            ciphersuite,
+            verify(pk, signature, header, messages, messages_len)
+        )
+    })
+}
+
+unsafe fn proof_gen<CS: BbsCiphersuite>(
+    pk: ZkryptiumBytes,
+    signature: ZkryptiumBytes,
+    header: ZkryptiumBytes,
+    ph: ZkryptiumBytes,
+    messages: *const ZkryptiumBytes,
+    messages_len: usize,
+    disclosed_indexes: *const usize,
+    disclosed_indexes_len: usize,
+    out_proof: *mut ZkryptiumBuffer,
+) -> Result<(), ZkryptiumStatus>
+where
+    CS::Expander: for<'a> ExpandMsg<'a>,
+{
+    check_out(out_proof)?;
+    let pk = BBSplusPublicKey::from_bytes(required_bytes(pk)?)?;
+    let messages = optional_messages(messages, messages_len)?;
+    let proof = PoKSignature::<BBSplus<CS>>::proof_gen(
+        &pk,
+        required_bytes(signature)?,
+        optional_bytes(header)?,
+        optional_bytes(ph)?,
+        messages.as_deref(),
+        optional_slice(disclosed_indexes, disclosed_indexes_len)?,
+    )?;
+    write_out(out_proof, proof.to_bytes());
+    Ok(())
+}
+
+/// Computes a proof of knowledge of a signature disclosing the messages at `disclosed_indexes` (see [`PoKSignature::proof_gen`]).
+///
+/// # Safety
+/// Every non-null pointer must be valid for the given length, and the output pointers must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn zkryptium_bbs_proof_gen(
+    ciphersuite: u32,
+    pk: ZkryptiumBytes,
+    signature: ZkryptiumBytes,
+    header: ZkryptiumBytes,
+    ph: ZkryptiumBytes,
+    messages: *const ZkryptiumBytes,
+    messages_len: usize,
+    disclosed_indexes: *const usize,
+    disclosed_indexes_len: usize,
+    out_proof: *mut ZkryptiumBuffer,
+) -> ZkryptiumStatus {
+    ffi_call(|| {
+        dispatch!(
+            ciphersuite,
+            proof_gen(
+                pk,
+                signature,
+                header,
+                ph,
+                messages,
+                messages_len,
+                disclosed_indexes,
+                disclosed_indexes_len,
+                out_proof,
+            )
+        )
+    })
+}
+
+unsafe fn proof_verify<CS: BbsCiphersuite>(
+    pk: ZkryptiumBytes,
+    proof: ZkryptiumBytes,
+    header: ZkryptiumBytes,
+    ph: ZkryptiumBytes,
+    disclosed_messages: *const ZkryptiumBytes,
+    disclosed_messages_len: usize,
+    disclosed_indexes: *const usize,
+    disclosed_indexes_len: usize,
+) -> Result<(), ZkryptiumStatus>
+where
+    CS::Expander: for<'a> ExpandMsg<'a>,
+{
+    let pk = BBSplusPublicKey::from_bytes(required_bytes(pk)?)?;
+    let proof = PoKSignature::<BBSplus<CS>>::from_bytes(required_bytes(proof)?)?;
+    let disclosed_messages = optional_messages(disclosed_messages, disclosed_messages_len)?;
+    Ok(proof.proof_verify(
+        &pk,
+        disclosed_messages.as_deref(),
+        optional_slice(disclosed_indexes, disclosed_indexes_len)?,
+        optional_bytes(header)?,
+        optional_bytes(ph)?,
+    )?)
+}
+
+/// Verifies a proof of knowledge of a signature (see [`PoKSignature::proof_verify`]). Returns [`ZkryptiumStatus::Ok`] only if the proof is valid.
+///
+/// # Safety
+/// Every non-null pointer must be valid for the given length.
+#[no_mangle]
+pub unsafe extern "C" fn zkryptium_bbs_proof_verify(
+    ciphersuite: u32,
+    pk: ZkryptiumBytes,
+    proof: ZkryptiumBytes,
+    header: ZkryptiumBytes,
+    ph: ZkryptiumBytes,
+    disclosed_messages: *const ZkryptiumBytes,
+    disclosed_messages_len: usize,
+    disclosed_indexes: *const usize,
+    disclosed_indexes_len: usize,
+) -> ZkryptiumStatus {
+    ffi_call(|| {
+        dispatch!(
+            ciphersuite,
+            proof_verify(
+                pk,
+                proof,
+                header,
+                ph,
+                disclosed_messages,
+                disclosed_messages_len,
+                disclosed_indexes,
+                disclosed_indexes_len,
+            )
+        )
+    })
+}
+
+unsafe fn commit<CS: BbsCiphersuite>(
+    committed_messages: *const ZkryptiumBytes,
+    committed_messages_len: usize,
+    out_commitment_with_proof: *mut ZkryptiumBuffer,
+    out_secret_prover_blind: *mut ZkryptiumBuffer,
+) -> Result<(), ZkryptiumStatus>
+where
+    CS::Expander: for<'a> ExpandMsg<'a>,
+{
+    check_out(out_commitment_with_proof)?;
+    check_out(out_secret_prover_blind)?;
+    let committed_messages = optional_messages(committed_messages, committed_messages_len)?;
+    let (commitment_with_proof, secret_prover_blind) =
+        Commitment::<BBSplus<CS>>::commit(committed_messages.as_deref(), None)?;
+    write_out(out_commitment_with_proof, commitment_with_proof.to_bytes());
+    write_out(
+        out_secret_prover_blind,
+        secret_prover_blind.to_bytes().to_vec(),
+    );
+    Ok(())
+}
+
+/// Commits to the messages to be blindly signed (see [`Commitment::commit`]). `out_secret_prover_blind` receives the 32 bytes `secret_prover_blind`.
+///
+/// # Safety
+/// Every non-null pointer must be valid for the given length, and the output pointers must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn zkryptium_bbs_commit(
+    ciphersuite: u32,
+    committed_messages: *const ZkryptiumBytes,
+    committed_messages_len: usize,
+    out_commitment_with_proof: *mut ZkryptiumBuffer,
+    out_secret_prover_blind: *mut ZkryptiumBuffer,
+) -> ZkryptiumStatus {
+    ffi_call(|| {
+        dispatch!(
+            ciphersuite,
+            commit(
+                committed_messages,
+                committed_messages_len,
+                out_commitment_with_proof,
+                out_secret_prover_blind,
+            )
+        )
+    })
+}
+
+unsafe fn blind_sign<CS: BbsCiphersuite>(
+    sk: ZkryptiumBytes,
+    pk: ZkryptiumBytes,
+    commitment_with_proof: ZkryptiumBytes,
+    header: ZkryptiumBytes,
+    messages: *const ZkryptiumBytes,
+    messages_len: usize,
+    signer_blind: ZkryptiumBytes,
+    out_signature: *mut ZkryptiumBuffer,
+) -> Result<(), ZkryptiumStatus>
+where
+    CS::Expander: for<'a> ExpandMsg<'a>,
+{
+    check_out(out_signature)?;
+    let sk = BBSplusSecretKey::from_bytes(required_bytes(sk)?)?;
+    let pk = BBSplusPublicKey::from_bytes(required_bytes(pk)?)?;
+    let messages = optional_messages(messages, messages_len)?;
+    let signer_blind = optional_blind_factor(signer_blind)?;
+    let signature = BlindSignature::<BBSplus<CS>>::blind_sign(
+        &sk,
+        &pk,
+        optional_bytes(commitment_with_proof)?,
+        optional_bytes(header)?,
+        messages.as_deref(),
+        signer_blind.as_ref(),
+        None,
+    )?;
+    write_out(out_signature, signature.to_bytes().to_vec());
+    Ok(())
+}
+
+/// Computes a blind signature over `messages` and the committed messages (see [`BlindSignature::blind_sign`]). `out_signature` receives the 80 bytes signature.
+///
+/// # Safety
+/// Every non-null pointer must be valid for the given length, and the output pointers must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn zkryptium_bbs_blind_sign(
+    ciphersuite: u32,
+    sk: ZkryptiumBytes,
+    pk: ZkryptiumBytes,
+    commitment_with_proof: ZkryptiumBytes,
+    header: ZkryptiumBytes,
+    messages: *const ZkryptiumBytes,
+    messages_len: usize,
+    signer_blind: ZkryptiumBytes,
+    out_signature: *mut ZkryptiumBuffer,
+) -> ZkryptiumStatus {
+    ffi_call(|| {
+        dispatch!(
+            ciphersuite,
+            blind_sign(
+                sk,
+                pk,
+                commitment_with_proof,
+                header,
+                messages,
+                messages_len,
+                signer_blind,
+                out_signature,
+            )
+        )
+    })
+}
+
+unsafe fn blind_verify<CS: BbsCiphersuite>(
+    pk: ZkryptiumBytes,
+    signature: ZkryptiumBytes,
+    header: ZkryptiumBytes,
+    messages: *const ZkryptiumBytes,
+    messages_len: usize,
+    committed_messages: *const ZkryptiumBytes,
+    committed_messages_len: usize,
+    secret_prover_blind: ZkryptiumBytes,
+    signer_blind: ZkryptiumBytes,
+) -> Result<(), ZkryptiumStatus>
+where
+    CS::Expander: for<'a> ExpandMsg<'a>,
+{
+    let pk = BBSplusPublicKey::from_bytes(required_bytes(pk)?)?;
+    let signature =
+        BlindSignature::<BBSplus<CS>>::from_bytes(signature_bytes(required_bytes(signature)?)?)?;
+    let messages = optional_messages(messages, messages_len)?;
+    let committed_messages = optional_messages(committed_messages, committed_messages_len)?;
+    let secret_prover_blind = optional_blind_factor(secret_prover_blind)?;
+    let signer_blind = optional_blind_factor(signer_blind)?;
+    Ok(signature.verify(
+        &pk,
+        optional_bytes(header)?,
+        messages.as_deref(),
+        committed_messages.as_deref(),
+        secret_prover_blind.as_ref(),
+        signer_blind.as_ref(),
+        None,
+    )?)
+}
+
+/// Verifies a blind signature (see [`BlindSignature::verify`]). Returns [`ZkryptiumStatus::Ok`] only if the signature is valid.
+///
+/// # Safety
+/// Every non-null pointer must be valid for the given length.
+#[no_mangle]
+pub unsafe extern "C" fn zkryptium_bbs_blind_verify(
+    ciphersuite: u32,
+    pk: ZkryptiumBytes,
+    signature: ZkryptiumBytes,
+    header: ZkryptiumBytes,
+    messages: *const ZkryptiumBytes,
+    messages_len: usize,
+    committed_messages: *const ZkryptiumBytes,
+    committed_messages_len: usize,
+    secret_prover_blind: ZkryptiumBytes,
+    signer_blind: ZkryptiumBytes,
+) -> ZkryptiumStatus {
+    ffi_call(|| {
+        dispatch!(
+            ciphersuite,
+            blind_verify(
+                pk,
+                signature,
+                header,
+                messages,
+                messages_len,
+                committed_messages,
+                committed_messages_len,
+                secret_prover_blind,
+                signer_blind,
+            )
+        )
+    })
+}
+
+unsafe fn blind_proof_gen<CS: BbsCiphersuite>(
+    pk: ZkryptiumBytes,
+    signature: ZkryptiumBytes,
+    header: ZkryptiumBytes,
+    ph: ZkryptiumBytes,
+    messages: *const ZkryptiumBytes,
+    messages_len: usize,
+    committed_messages: *const ZkryptiumBytes,
+    committed_messages_len: usize,
+    disclosed_indexes: *const usize,
+    disclosed_indexes_len: usize,
+    disclosed_commitment_indexes: *const usize,
+    disclosed_commitment_indexes_len: usize,
+    secret_prover_blind: ZkryptiumBytes,
+    signer_blind: ZkryptiumBytes,
+    out_proof: *mut ZkryptiumBuffer,
+) -> Result<(), ZkryptiumStatus>
+where
+    CS::Expander: for<'a> ExpandMsg<'a>,
+{
+    check_out(out_proof)?;
+    let pk = BBSplusPublicKey::from_bytes(required_bytes(pk)?)?;
+    let messages = optional_messages(messages, messages_len)?;
+    let committed_messages = optional_messages(committed_messages, committed_messages_len)?;
+    let secret_prover_blind = optional_blind_factor(secret_prover_blind)?;
+    let signer_blind = optional_blind_factor(signer_blind)?;
+    let proof = PoKSignature::<BBSplus<CS>>::blind_proof_gen(
+        &pk,
+        required_bytes(signature)?,
+        optional_bytes(header)?,
+        optional_bytes(ph)?,
+        messages.as_deref(),
+        committed_messages.as_deref(),
+        optional_slice(disclosed_indexes, disclosed_indexes_len)?,
+        optional_slice(
+            disclosed_commitment_indexes,
+            disclosed_commitment_indexes_len,
+        )?,
+        secret_prover_blind.as_ref(),
+        signer_blind.as_ref(),
+    )?;
+    write_out(out_proof, proof.to_bytes());
+    Ok(())
+}
+
+/// Computes a proof of knowledge of a blind signature (see [`PoKSignature::blind_proof_gen`]).
+///
+/// # Safety
+/// Every non-null pointer must be valid for the given length, and the output pointers must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn zkryptium_bbs_blind_proof_gen(
+    ciphersuite: u32,
+    pk: ZkryptiumBytes,
+    signature: ZkryptiumBytes,
+    header: ZkryptiumBytes,
+    ph: ZkryptiumBytes,
+    messages: *const ZkryptiumBytes,
+    messages_len: usize,
+    committed_messages: *const ZkryptiumBytes,
+    committed_messages_len: usize,
+    disclosed_indexes: *const usize,
+    disclosed_indexes_len: usize,
+    disclosed_commitment_indexes: *const usize,
+    disclosed_commitment_indexes_len: usize,
+    secret_prover_blind: ZkryptiumBytes,
+    signer_blind: ZkryptiumBytes,
+    out_proof: *mut ZkryptiumBuffer,
+) -> ZkryptiumStatus {
+    ffi_call(|| {
+        dispatch!(
+            ciphersuite,
+            blind_proof_gen(
+                pk,
+                signature,
+                header,
+                ph,
+                messages,
+                messages_len,
+                committed_messages,
+                committed_messages_len,
+                disclosed_indexes,
+                disclosed_indexes_len,
+                disclosed_commitment_indexes,
+                disclosed_commitment_indexes_len,
+                secret_prover_blind,
+                signer_blind,
+                out_proof,
+            )
+        )
+    })
+}
+
+unsafe fn blind_proof_verify<CS: BbsCiphersuite>(
+    pk: ZkryptiumBytes,
+    proof: ZkryptiumBytes,
+    header: ZkryptiumBytes,
+    ph: ZkryptiumBytes,
+    L: usize,
+    disclosed_messages: *const ZkryptiumBytes,
+    disclosed_messages_len: usize,
+    disclosed_committed_messages: *const ZkryptiumBytes,
+    disclosed_committed_messages_len: usize,
+    disclosed_indexes: *const usize,
+    disclosed_indexes_len: usize,
+    disclosed_commitment_indexes: *const usize,
+    disclosed_commitment_indexes_len: usize,
+) -> Result<(), ZkryptiumStatus>
+where
+    CS::Expander: for<'a> ExpandMsg<'a>,
+{
+    let pk = BBSplusPublicKey::from_bytes(required_bytes(pk)?)?;
+    let proof = PoKSignature::<BBSplus<CS>>::from_bytes(required_bytes(proof)?)?;
+    let disclosed_messages = optional_messages(disclosed_messages, disclosed_messages_len)?;
+    let disclosed_committed_messages = optional_messages(
+        disclosed_committed_messages,
+        disclosed_committed_messages_len,
+    )?;
+    Ok(proof.blind_proof_verify(
+        &pk,
+        optional_bytes(header)?,
+        optional_bytes(ph)?,
+        Some(L),
+        disclosed_messages.as_deref(),
+        disclosed_committed_messages.as_deref(),
+        optional_slice(disclosed_indexes, disclosed_indexes_len)?,
+        optional_slice(
+            disclosed_commitment_indexes,
+            disclosed_commitment_indexes_len,
+        )?,
+    )?)
+}
+
+/// Verifies a proof of knowledge of a blind signature, `L` being the number of messages known to the Signer (see [`PoKSignature::blind_proof_verify`]). Returns [`ZkryptiumStatus::Ok`] only if the proof is valid.
+///
+/// # Safety
+/// Every non-null pointer must be valid for the given length.
+#[no_mangle]
+pub unsafe extern "C" fn zkryptium_bbs_blind_proof_verify(
+    ciphersuite: u32,
+    pk: ZkryptiumBytes,
+    proof: ZkryptiumBytes,
+    header: ZkryptiumBytes,
+    ph: ZkryptiumBytes,
+    L: usize,
+    disclosed_messages: *const ZkryptiumBytes,
+    disclosed_messages_len: usize,
+    disclosed_committed_messages: *const ZkryptiumBytes,
+    disclosed_committed_messages_len: usize,
+    disclosed_indexes: *const usize,
+    disclosed_indexes_len: usize,
+    disclosed_commitment_indexes: *const usize,
+    disclosed_commitment_indexes_len: usize,
+) -> ZkryptiumStatus {
+    ffi_call(|| {
+        dispatch!(
+            ciphersuite,
+            blind_proof_verify(
+                pk,
+                proof,
+                header,
+                ph,
+                L,
+                disclosed_messages,
+                disclosed_messages_len,
+                disclosed_committed_messages,
+                disclosed_committed_messages_len,
+                disclosed_indexes,
+                disclosed_indexes_len,
+                disclosed_commitment_indexes,
+                disclosed_commitment_indexes_len,
+            )
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ffi::CStr;
+
+    const NONE: ZkryptiumBytes = ZkryptiumBytes {
+        data: ptr::null(),
+        len: 0,
+    };
+
+    fn bytes(b: &[u8]) -> ZkryptiumBytes {
+        ZkryptiumBytes {
+            data: b.as_ptr(),
+            len: b.len(),
+        }
+    }
+
+    fn empty_buffer() -> ZkryptiumBuffer {
+        ZkryptiumBuffer {
+            data: ptr::null_mut(),
+            len: 0,
+        }
+    }
+
+    fn take(buffer: ZkryptiumBuffer) -> Vec<u8> {
+        let out = unsafe { slice::from_raw_parts(buffer.data, buffer.len) }.to_vec();
+        unsafe { zkryptium_buffer_free(buffer) };
+        out
+    }
+
+    fn keypair(ciphersuite: u32) -> (Vec<u8>, Vec<u8>) {
+        let (mut sk, mut pk) = (empty_buffer(), empty_buffer());
+        let status = unsafe {
+            zkryptium_bbs_keygen(
+                ciphersuite,
+                bytes(b"this-IS-just-an-Test-IKM-to-check-the-ffi"),
+                NONE,
+                NONE,
+                &mut sk,
+                &mut pk,
+            )
+        };
+        assert_eq!(status, ZkryptiumStatus::Ok);
+        (take(sk), take(pk))
+    }
+
+    fn sign_verify_proof(ciphersuite: u32) {
+        let (sk, pk) = keypair(ciphersuite);
+        let header = b"header";
+        let ph = b"presentation header";
+        let messages_data: [&[u8]; 3] = [b"first", b"second", b"third"];
+        let messages: Vec<ZkryptiumBytes> = messages_data.iter().map(|m| bytes(m)).collect();
+
+        let mut signature = empty_buffer();
+        let status = unsafe {
+            zkryptium_bbs_sign(
+                ciphersuite,
+                bytes(&sk),
+                bytes(&pk),
+                bytes(header),
+                messages.as_ptr(),
+                messages.len(),
+                &mut signature,
+            )
+        };
+        assert_eq!(status, ZkryptiumStatus::Ok);
+        let signature = take(signature);
+        assert_eq!(signature.len(), BBSplusSignature::BYTES);
+
+        let status = unsafe {
+            zkryptium_bbs_verify(
+                ciphersuite,
+                bytes(&pk),
+                bytes(&signature),
+                bytes(header),
+                messages.as_ptr(),
+                messages.len(),
+            )
+        };
+        assert_eq!(status, ZkryptiumStatus::Ok);
+
+        let disclosed_indexes = [0usize, 2];
+        let mut proof = empty_buffer();
+        let status = unsafe {
+            zkryptium_bbs_proof_gen(
+                ciphersuite,
+                bytes(&pk),
+                bytes(&signature),
+                bytes(header),
+                bytes(ph),
+                messages.as_ptr(),
+                messages.len(),
+                disclosed_indexes.as_ptr(),
+                disclosed_indexes.len(),
+                &mut proof,
+            )
+        };
+        assert_eq!(status, ZkryptiumStatus::Ok);
+        let proof = take(proof);
+
+        let disclosed_messages = [messages[0], messages[2]];
+        let status = unsafe {
+            zkryptium_bbs_proof_verify(
+                ciphersuite,
+                bytes(&pk),
+                bytes(&proof),
+                bytes(header),
+                bytes(ph),
+                disclosed_messages.as_ptr(),
+                disclosed_messages.len(),
+                disclosed_indexes.as_ptr(),
+                disclosed_indexes.len(),
+            )
+        };
+        assert_eq!(status, ZkryptiumStatus::Ok);
+
+        let status = unsafe {
+            zkryptium_bbs_proof_verify(
+                ciphersuite,
+                bytes(&pk),
+                bytes(&proof),
+                bytes(header),
+                NONE,
+                disclosed_messages.as_ptr(),
+                disclosed_messages.len(),
+                disclosed_indexes.as_ptr(),
+                disclosed_indexes.len(),
+            )
+        };
+        assert_ne!(status, ZkryptiumStatus::Ok);
+    }
+
+    fn blind_flow(ciphersuite: u32) {
+        let (sk, pk) = keypair(ciphersuite);
+        let messages_data: [&[u8]; 2] = [b"known 1", b"known 2"];
+        let messages: Vec<ZkryptiumBytes> = messages_data.iter().map(|m| bytes(m)).collect();
+        let committed_data: [&[u8]; 2] = [b"secret 1", b"secret 2"];
+        let committed: Vec<ZkryptiumBytes> = committed_data.iter().map(|m| bytes(m)).collect();
+
+        let (mut commitment_with_proof, mut secret_prover_blind) = (empty_buffer(), empty_buffer());
+        let status = unsafe {
+            zkryptium_bbs_commit(
+                ciphersuite,
+                committed.as_ptr(),
+                committed.len(),
+                &mut commitment_with_proof,
+                &mut secret_prover_blind,
+            )
+        };
+        assert_eq!(status, ZkryptiumStatus::Ok);
+        let commitment_with_proof = take(commitment_with_proof);
+        let secret_prover_blind = take(secret_prover_blind);
+
+        let mut signature = empty_buffer();
+        let status = unsafe {
+            zkryptium_bbs_blind_sign(
+                ciphersuite,
+                bytes(&sk),
+                bytes(&pk),
+                bytes(&commitment_with_proof),
+                NONE,
+                messages.as_ptr(),
+                messages.len(),
+                NONE,
+                &mut signature,
+            )
+        };
+        assert_eq!(status, ZkryptiumStatus::Ok);
+        let signature = take(signature);
+
+        let status = unsafe {
+            zkryptium_bbs_blind_verify(
+                ciphersuite,
+                bytes(&pk),
+                bytes(&signature),
+                NONE,
+                messages.as_ptr(),
+                messages.len(),
+                committed.as_ptr(),
+                committed.len(),
+                bytes(&secret_prover_blind),
+                NONE,
+            )
+        };
+        assert_eq!(status, ZkryptiumStatus::Ok);
+
+        let disclosed_indexes = [1usize];
+        let disclosed_commitment_indexes = [0usize];
+        let mut proof = empty_buffer();
+        let status = unsafe {
+            zkryptium_bbs_blind_proof_gen(
+                ciphersuite,
+                bytes(&pk),
+                bytes(&signature),
+                NONE,
+                NONE,
+                messages.as_ptr(),
+                messages.len(),
+                committed.as_ptr(),
+                committed.len(),
+                disclosed_indexes.as_ptr(),
+                disclosed_indexes.len(),
+                disclosed_commitment_indexes.as_ptr(),
+                disclosed_commitment_indexes.len(),
+                bytes(&secret_prover_blind),
+                NONE,
+                &mut proof,
+            )
+        };
+        assert_eq!(status, ZkryptiumStatus::Ok);
+        let proof = take(proof);
+
+        let status = unsafe {
+            zkryptium_bbs_blind_proof_verify(
+                ciphersuite,
+                bytes(&pk),
+                bytes(&proof),
+                NONE,
+                NONE,
+                messages.len(),
+                &messages[1],
+                1,
+                &committed[0],
+                1,
+                disclosed_indexes.as_ptr(),
+                disclosed_indexes.len(),
+                disclosed_commitment_indexes.as_ptr(),
+                disclosed_commitment_indexes.len(),
+            )
+        };
+        assert_eq!(status, ZkryptiumStatus::Ok);
+    }
+
+    #[test]
+    fn sign_verify_proof_bls12_381_sha_256() {
+        sign_verify_proof(ZKRYPTIUM_BBS_BLS12_381_SHA_256);
+    }
+
+    #[test]
+    fn sign_verify_proof_bls12_381_shake_256() {
+        sign_verify_proof(ZKRYPTIUM_BBS_BLS12_381_SHAKE_256);
+    }
+
+    #[test]
+    fn blind_flow_bls12_381_sha_256() {
+        blind_flow(ZKRYPTIUM_BBS_BLS12_381_SHA_256);
+    }
+
+    #[test]
+    fn blind_flow_bls12_381_shake_256() {
+        blind_flow(ZKRYPTIUM_BBS_BLS12_381_SHAKE_256);
+    }
+
+    #[test]
+    fn errors() {
+        let (_, pk) = keypair(ZKRYPTIUM_BBS_BLS12_381_SHA_256);
+
+        let status = unsafe {
+            zkryptium_bbs_verify(42, bytes(&pk), bytes(&[0u8; 80]), NONE, ptr::null(), 0)
+        };
+        assert_eq!(status, ZkryptiumStatus::InvalidCiphersuite);
+
+        let status = unsafe {
+            zkryptium_bbs_verify(
+                ZKRYPTIUM_BBS_BLS12_381_SHA_256,
+                bytes(&pk),
+                NONE,
+                NONE,
+                ptr::null(),
+                0,
+            )
+        };
+        assert_eq!(status, ZkryptiumStatus::NullPointer);
+
+        let status = unsafe {
+            zkryptium_bbs_verify(
+                ZKRYPTIUM_BBS_BLS12_381_SHA_256,
+                bytes(&pk),
+                bytes(&[0u8; 79]),
+                NONE,
+                ptr::null(),
+                0,
+            )
+        };
+        assert_eq!(status, ZkryptiumStatus::InvalidSignature);
+
+        let status = unsafe {
+            zkryptium_bbs_keygen(
+                ZKRYPTIUM_BBS_BLS12_381_SHA_256,
+                bytes(&[0u8; 8]),
+                NONE,
+                NONE,
+                ptr::null_mut(),
+                ptr::null_mut(),
+            )
+        };
+        assert_eq!(status, ZkryptiumStatus::NullPointer);
+
+        let message = unsafe { CStr::from_ptr(zkryptium_status_message(ZkryptiumStatus::Ok)) };
+        assert_eq!(message.to_str().unwrap(), "Ok");
+    }
+}
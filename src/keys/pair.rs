@@ -15,6 +15,7 @@
 use crate::schemes::algorithms::Scheme;
 use serde::Deserialize;
 use serde::Serialize;
+#[cfg(feature = "std")]
 use std::env;
 
 #[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
@@ -40,6 +41,8 @@ where
         (self.private, self.public)
     }
 
+    /// Writes the key pair as JSON to `file` (relative to the current directory). Requires the `std` feature.
+    #[cfg(feature = "std")]
     pub fn write_keypair_to_file(&self, file: Option<String>) {
         println!("writhing to file...");
 
@@ -51,9 +54,11 @@ where
             &file_to_write,
             serde_json::to_string_pretty(&self).expect("failed to serializing key pair"),
         )
-        .expect(&format!(
-            "failed to write key pair to file: {}",
-            file_to_write.to_str().unwrap()
-        ));
+        .unwrap_or_else(|_| {
+            panic!(
+                "failed to write key pair to file: {}",
+                file_to_write.to_str().unwrap()
+            )
+        });
     }
 }
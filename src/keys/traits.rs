@@ -12,9 +12,13 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use alloc::string::String;
+use core::fmt::Debug;
 use serde::{de::DeserializeOwned, Serialize};
 
-pub trait PublicKey: Serialize + DeserializeOwned + Send + Sync + 'static {
+pub trait PublicKey:
+    Clone + PartialEq + Debug + Serialize + DeserializeOwned + Send + Sync + 'static
+{
     type Output: ?Sized;
 
     fn to_bytes(&self) -> Self::Output;
@@ -12,9 +12,20 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+//! # Secret values
+//!
+//! Secret keys, key shares, blinding factors, prover secrets and the state of an ongoing proof are zeroized when dropped,
+//! and their secret fields are printed as `<redacted>` by [`Debug`](core::fmt::Debug).
+
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 #![allow(non_snake_case)]
 #![allow(non_upper_case_globals)]
 #![allow(non_camel_case_types)]
+#![allow(clippy::too_many_arguments)]
+#![allow(clippy::large_enum_variant)]
+
+#[macro_use]
+extern crate alloc;
 
 pub mod errors;
 pub mod keys;
@@ -25,3 +36,5 @@ pub mod utils;
 pub mod bbsplus;
 #[cfg(feature = "cl03")]
 pub mod cl03;
+#[cfg(feature = "ffi")]
+pub mod ffi;
@@ -13,9 +13,9 @@
 // limitations under the License.
 
 use crate::keys::traits::{PrivateKey, PublicKey};
+use core::marker::PhantomData;
 use digest::HashMarker;
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
-use std::marker::PhantomData;
 
 #[cfg(feature = "bbsplus")]
 use crate::bbsplus::{
@@ -25,7 +25,7 @@ use crate::bbsplus::{
 
 #[cfg(feature = "cl03")]
 use crate::cl03::{
-    ciphersuites::{CL1024Sha256, CLCiphersuite},
+    ciphersuites::{CL1024Sha256, CL2048Sha256, CL3072Sha384, CL3072Sha512, CLCiphersuite},
     keys::{CL03PublicKey, CL03SecretKey},
 };
 
@@ -36,6 +36,12 @@ pub type BbsBls12381Sha256 = BBSplus<Bls12381Sha256>;
 
 #[cfg(feature = "cl03")]
 pub type CL03_CL1024_SHA256 = CL03<CL1024Sha256>;
+#[cfg(feature = "cl03")]
+pub type CL03_CL2048_SHA256 = CL03<CL2048Sha256>;
+#[cfg(feature = "cl03")]
+pub type CL03_CL3072_SHA384 = CL03<CL3072Sha384>;
+#[cfg(feature = "cl03")]
+pub type CL03_CL3072_SHA512 = CL03<CL3072Sha512>;
 
 #[cfg(feature = "bbsplus")]
 #[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
@@ -0,0 +1,544 @@
+// Copyright 2023 Fondazione LINKS
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Self-describing envelopes for keys, signatures, proofs and commitments.
+//!
+//! An [`Envelope`] tags the serialized artifact with the scheme, the ciphersuite and the kind of artifact it contains,
+//! so that it can be stored and later loaded without knowing its type in advance ([`Envelope::open_any`]), and so that an
+//! artifact is never decoded under a ciphersuite other than the one it was produced with ([`Envelope::open`]).
+
+use super::{
+    algorithms::Scheme,
+    generics::{BlindSignature, Commitment, PoKSignature, Signature, ZKPoK},
+};
+use crate::errors::Error;
+use alloc::{borrow::ToOwned, vec::Vec};
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "bbsplus")]
+use crate::{
+    bbsplus::{
+        ciphersuites::BbsCiphersuite, keys::BBSplusPublicKey, proof::BBSplusZKPoK,
+        signature::BBSplusSignature,
+    },
+    schemes::algorithms::{BBSplus, BbsBls12381Sha256, BbsBls12381Shake256},
+};
+#[cfg(feature = "bbsplus")]
+use bls12_381_plus::G2Affine;
+
+#[cfg(feature = "cl03")]
+use crate::{
+    cl03::{ciphersuites::CLCiphersuite, keys::CL03PublicKey},
+    schemes::algorithms::{
+        CL03, CL03_CL1024_SHA256, CL03_CL2048_SHA256, CL03_CL3072_SHA384, CL03_CL3072_SHA512,
+    },
+};
+
+/// Version of the envelope encoding produced by [`Envelope::to_bytes`]
+pub const ENVELOPE_VERSION: u8 = 1;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[repr(u8)]
+pub enum SchemeId {
+    BBSplus = 1,
+    CL03 = 2,
+}
+
+impl TryFrom<u8> for SchemeId {
+    type Error = Error;
+
+    fn try_from(value: u8) -> Result<Self, Error> {
+        match value {
+            1 => Ok(Self::BBSplus),
+            2 => Ok(Self::CL03),
+            _ => Err(Error::DeserializationError("Unknown scheme".to_owned())),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[repr(u8)]
+pub enum ArtifactKind {
+    PublicKey = 1,
+    Signature = 2,
+    BlindSignature = 3,
+    PoKSignature = 4,
+    Commitment = 5,
+    ZKPoK = 6,
+}
+
+impl TryFrom<u8> for ArtifactKind {
+    type Error = Error;
+
+    fn try_from(value: u8) -> Result<Self, Error> {
+        match value {
+            1 => Ok(Self::PublicKey),
+            2 => Ok(Self::Signature),
+            3 => Ok(Self::BlindSignature),
+            4 => Ok(Self::PoKSignature),
+            5 => Ok(Self::Commitment),
+            6 => Ok(Self::ZKPoK),
+            _ => Err(Error::DeserializationError(
+                "Unknown artifact kind".to_owned(),
+            )),
+        }
+    }
+}
+
+/// An artifact of the scheme `S` that can be sealed in an [`Envelope`]
+#[derive(Clone, PartialEq, Debug)]
+pub enum Artifact<S: Scheme> {
+    PublicKey(S::PubKey),
+    Signature(Signature<S>),
+    BlindSignature(BlindSignature<S>),
+    PoKSignature(PoKSignature<S>),
+    Commitment(Commitment<S>),
+    ZKPoK(ZKPoK<S>),
+}
+
+impl<S: Scheme> Artifact<S> {
+    pub fn kind(&self) -> ArtifactKind {
+        match self {
+            Self::PublicKey(_) => ArtifactKind::PublicKey,
+            Self::Signature(_) => ArtifactKind::Signature,
+            Self::BlindSignature(_) => ArtifactKind::BlindSignature,
+            Self::PoKSignature(_) => ArtifactKind::PoKSignature,
+            Self::Commitment(_) => ArtifactKind::Commitment,
+            Self::ZKPoK(_) => ArtifactKind::ZKPoK,
+        }
+    }
+}
+
+impl<S: Scheme> From<Signature<S>> for Artifact<S> {
+    fn from(signature: Signature<S>) -> Self {
+        Self::Signature(signature)
+    }
+}
+
+impl<S: Scheme> From<BlindSignature<S>> for Artifact<S> {
+    fn from(signature: BlindSignature<S>) -> Self {
+        Self::BlindSignature(signature)
+    }
+}
+
+impl<S: Scheme> From<PoKSignature<S>> for Artifact<S> {
+    fn from(proof: PoKSignature<S>) -> Self {
+        Self::PoKSignature(proof)
+    }
+}
+
+impl<S: Scheme> From<Commitment<S>> for Artifact<S> {
+    fn from(commitment: Commitment<S>) -> Self {
+        Self::Commitment(commitment)
+    }
+}
+
+impl<S: Scheme> From<ZKPoK<S>> for Artifact<S> {
+    fn from(proof: ZKPoK<S>) -> Self {
+        Self::ZKPoK(proof)
+    }
+}
+
+/// A [`Scheme`] whose artifacts can be sealed in an [`Envelope`]
+pub trait EnvelopeScheme: Scheme {
+    const SCHEME_ID: SchemeId;
+    const CIPHERSUITE_ID: &'static [u8];
+
+    /// Serializes the artifact with its own `to_bytes` encoding
+    fn artifact_to_bytes(artifact: &Artifact<Self>) -> Vec<u8>;
+
+    /// Deserializes an artifact of the given `kind` encoded with [`Self::artifact_to_bytes`]
+    fn artifact_from_bytes(kind: ArtifactKind, payload: &[u8]) -> Result<Artifact<Self>, Error>;
+}
+
+#[cfg(feature = "bbsplus")]
+impl<CS: BbsCiphersuite> EnvelopeScheme for BBSplus<CS> {
+    const SCHEME_ID: SchemeId = SchemeId::BBSplus;
+    const CIPHERSUITE_ID: &'static [u8] = CS::ID;
+
+    fn artifact_to_bytes(artifact: &Artifact<Self>) -> Vec<u8> {
+        match artifact {
+            Artifact::PublicKey(pk) => pk.to_bytes().to_vec(),
+            Artifact::Signature(signature) => signature.to_bytes().to_vec(),
+            Artifact::BlindSignature(signature) => signature.to_bytes().to_vec(),
+            Artifact::PoKSignature(proof) => proof.to_bytes(),
+            Artifact::Commitment(commitment) => commitment.to_bytes(),
+            Artifact::ZKPoK(ZKPoK::BBSplus(proof)) => proof.to_bytes(),
+            Artifact::ZKPoK(_) => panic!("Cannot happen!"),
+        }
+    }
+
+    fn artifact_from_bytes(kind: ArtifactKind, payload: &[u8]) -> Result<Artifact<Self>, Error> {
+        let signature_bytes = || -> Result<&[u8; BBSplusSignature::BYTES], Error> {
+            payload.try_into().map_err(|_| Error::InvalidSignature)
+        };
+        Ok(match kind {
+            ArtifactKind::PublicKey => {
+                if payload.len() != G2Affine::COMPRESSED_BYTES {
+                    return Err(Error::KeyDeserializationError);
+                }
+                Artifact::PublicKey(BBSplusPublicKey::from_bytes(payload)?)
+            }
+            ArtifactKind::Signature => {
+                Artifact::Signature(Signature::<Self>::from_bytes(signature_bytes()?)?)
+            }
+            ArtifactKind::BlindSignature => {
+                Artifact::BlindSignature(BlindSignature::<Self>::from_bytes(signature_bytes()?)?)
+            }
+            ArtifactKind::PoKSignature => {
+                Artifact::PoKSignature(PoKSignature::<Self>::from_bytes(payload)?)
+            }
+            ArtifactKind::Commitment => {
+                Artifact::Commitment(Commitment::<Self>::from_bytes(payload)?)
+            }
+            ArtifactKind::ZKPoK => {
+                Artifact::ZKPoK(ZKPoK::BBSplus(BBSplusZKPoK::from_bytes(payload)?))
+            }
+        })
+    }
+}
+
+#[cfg(feature = "cl03")]
+impl<CS: CLCiphersuite> EnvelopeScheme for CL03<CS> {
+    const SCHEME_ID: SchemeId = SchemeId::CL03;
+    const CIPHERSUITE_ID: &'static [u8] = CS::ID;
+
+    fn artifact_to_bytes(artifact: &Artifact<Self>) -> Vec<u8> {
+        match artifact {
+            Artifact::PublicKey(pk) => pk.to_bytes(),
+            Artifact::Signature(signature) => signature.to_bytes(),
+            Artifact::BlindSignature(signature) => signature.to_bytes(),
+            Artifact::PoKSignature(proof) => proof.to_bytes(),
+            Artifact::Commitment(commitment) => commitment.to_bytes(),
+            Artifact::ZKPoK(proof) => proof.to_bytes(),
+        }
+    }
+
+    fn artifact_from_bytes(kind: ArtifactKind, payload: &[u8]) -> Result<Artifact<Self>, Error> {
+        Ok(match kind {
+            ArtifactKind::PublicKey => Artifact::PublicKey(CL03PublicKey::from_bytes(payload)?),
+            ArtifactKind::Signature => Artifact::Signature(Signature::<Self>::from_bytes(payload)?),
+            ArtifactKind::BlindSignature => {
+                Artifact::BlindSignature(BlindSignature::<Self>::from_bytes(payload)?)
+            }
+            ArtifactKind::PoKSignature => {
+                Artifact::PoKSignature(PoKSignature::<Self>::from_bytes(payload)?)
+            }
+            ArtifactKind::Commitment => {
+                Artifact::Commitment(Commitment::<Self>::from_bytes(payload)?)
+            }
+            ArtifactKind::ZKPoK => Artifact::ZKPoK(ZKPoK::<Self>::from_bytes(payload)?),
+        })
+    }
+}
+
+/// Versioned envelope of a serialized artifact, tagged with its scheme, ciphersuite and kind.
+///
+/// Binary encoding ([`Self::to_bytes`]): `version || scheme_id || kind || len(ciphersuite_id) || ciphersuite_id || payload`,
+/// where `version`, `scheme_id`, `kind` and the length of the ciphersuite identifier are single octets.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct Envelope {
+    version: u8,
+    scheme: SchemeId,
+    ciphersuite: Vec<u8>,
+    kind: ArtifactKind,
+    payload: Vec<u8>,
+}
+
+impl Envelope {
+    /// # Description
+    /// Seals an artifact of the scheme `S` in an envelope tagged with the scheme and ciphersuite of `S`
+    ///
+    /// # Inputs:
+    /// * `artifact` (REQUIRED), the artifact to seal (e.g., `Artifact::from(signature)`).
+    ///
+    /// # Output:
+    /// * an [`Envelope`]
+    pub fn seal<S: EnvelopeScheme>(artifact: &Artifact<S>) -> Self {
+        Self {
+            version: ENVELOPE_VERSION,
+            scheme: S::SCHEME_ID,
+            ciphersuite: S::CIPHERSUITE_ID.to_vec(),
+            kind: artifact.kind(),
+            payload: S::artifact_to_bytes(artifact),
+        }
+    }
+
+    /// # Description
+    /// Opens an envelope holding an artifact of the scheme `S`
+    ///
+    /// # Output:
+    /// * the [`Artifact`], [`Error::CiphersuiteMismatch`] if the envelope was sealed for another scheme or ciphersuite, or another [`Error`] if the payload is not valid
+    pub fn open<S: EnvelopeScheme>(&self) -> Result<Artifact<S>, Error> {
+        self.check_version()?;
+        if !self.is::<S>() {
+            return Err(Error::CiphersuiteMismatch);
+        }
+        S::artifact_from_bytes(self.kind, &self.payload)
+    }
+
+    /// Whether the envelope was sealed for the scheme and ciphersuite of `S`
+    pub fn is<S: EnvelopeScheme>(&self) -> bool {
+        self.scheme == S::SCHEME_ID && self.ciphersuite == S::CIPHERSUITE_ID
+    }
+
+    pub fn scheme(&self) -> SchemeId {
+        self.scheme
+    }
+
+    pub fn ciphersuite(&self) -> &[u8] {
+        &self.ciphersuite
+    }
+
+    pub fn kind(&self) -> ArtifactKind {
+        self.kind
+    }
+
+    pub fn payload(&self) -> &[u8] {
+        &self.payload
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(4 + self.ciphersuite.len() + self.payload.len());
+        bytes.extend_from_slice(&[
+            self.version,
+            self.scheme as u8,
+            self.kind as u8,
+            self.ciphersuite.len() as u8,
+        ]);
+        bytes.extend_from_slice(&self.ciphersuite);
+        bytes.extend_from_slice(&self.payload);
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        let invalid = || Error::DeserializationError("Invalid envelope".to_owned());
+        let (version, scheme, kind, ciphersuite_len) = match bytes {
+            [version, scheme, kind, ciphersuite_len, ..] => {
+                (*version, *scheme, *kind, *ciphersuite_len as usize)
+            }
+            _ => return Err(invalid()),
+        };
+        if bytes.len() < 4 + ciphersuite_len {
+            return Err(invalid());
+        }
+        let (ciphersuite, payload) = bytes[4..].split_at(ciphersuite_len);
+        let envelope = Self {
+            version,
+            scheme: SchemeId::try_from(scheme)?,
+            ciphersuite: ciphersuite.to_vec(),
+            kind: ArtifactKind::try_from(kind)?,
+            payload: payload.to_vec(),
+        };
+        envelope.check_version()?;
+        Ok(envelope)
+    }
+
+    fn check_version(&self) -> Result<(), Error> {
+        if self.version != ENVELOPE_VERSION {
+            return Err(Error::DeserializationError(
+                "Unsupported envelope version".to_owned(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+macro_rules! any_artifact {
+    ( $( $(#[$cfg:meta])* $variant:ident, )+ ) => {
+        /// An artifact of any of the supported schemes and ciphersuites, returned by [`Envelope::open_any`]
+        #[derive(Clone, PartialEq, Debug)]
+        pub enum AnyArtifact {
+            $( $(#[$cfg])* $variant(Artifact<$variant>), )+
+        }
+
+        impl AnyArtifact {
+            pub fn kind(&self) -> ArtifactKind {
+                match self {
+                    $( $(#[$cfg])* Self::$variant(artifact) => artifact.kind(), )+
+                }
+            }
+        }
+
+        impl Envelope {
+            /// # Description
+            /// Opens an envelope dispatching on the scheme and ciphersuite it was sealed for
+            ///
+            /// # Output:
+            /// * the [`AnyArtifact`] or [`Error`] if the ciphersuite is unknown or the payload is not valid
+            pub fn open_any(&self) -> Result<AnyArtifact, Error> {
+                $(
+                    $(#[$cfg])*
+                    if self.is::<$variant>() {
+                        return self.open::<$variant>().map(AnyArtifact::$variant);
+                    }
+                )+
+                Err(Error::DeserializationError("Unknown ciphersuite".to_owned()))
+            }
+        }
+    };
+}
+
+any_artifact! {
+    #[cfg(feature = "bbsplus")]
+    BbsBls12381Sha256,
+    #[cfg(feature = "bbsplus")]
+    BbsBls12381Shake256,
+    #[cfg(feature = "cl03")]
+    CL03_CL1024_SHA256,
+    #[cfg(feature = "cl03")]
+    CL03_CL2048_SHA256,
+    #[cfg(feature = "cl03")]
+    CL03_CL3072_SHA384,
+    #[cfg(feature = "cl03")]
+    CL03_CL3072_SHA512,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AnyArtifact, Artifact, ArtifactKind, Envelope, SchemeId};
+    use crate::{
+        errors::Error,
+        keys::pair::KeyPair,
+        schemes::{
+            algorithms::{BbsBls12381Sha256, BbsBls12381Shake256},
+            generics::{PoKSignature, Signature},
+        },
+    };
+
+    #[test]
+    fn bbsplus_envelopes() {
+        let messages = [b"first".to_vec(), b"second".to_vec()];
+        let keypair = KeyPair::<BbsBls12381Sha256>::random().unwrap();
+        let pk = keypair.public_key();
+        let signature =
+            Signature::<BbsBls12381Sha256>::sign(Some(&messages), keypair.private_key(), pk, None)
+                .unwrap();
+        let proof = PoKSignature::<BbsBls12381Sha256>::proof_gen(
+            pk,
+            &signature.to_bytes(),
+            None,
+            None,
+            Some(&messages),
+            Some(&[0]),
+        )
+        .unwrap();
+
+        let artifacts = [
+            Artifact::PublicKey(pk.clone()),
+            Artifact::from(signature.clone()),
+            Artifact::from(proof),
+        ];
+        for artifact in artifacts {
+            let envelope = Envelope::seal(&artifact);
+            assert_eq!(envelope.scheme(), SchemeId::BBSplus);
+            assert_eq!(
+                envelope.ciphersuite(),
+                b"BBS_BLS12381G1_XMD:SHA-256_SSWU_RO_"
+            );
+            let stored = Envelope::from_bytes(&envelope.to_bytes()).unwrap();
+            assert_eq!(stored, envelope);
+            assert_eq!(stored.open::<BbsBls12381Sha256>().unwrap(), artifact);
+            assert_eq!(
+                stored.open_any().unwrap(),
+                AnyArtifact::BbsBls12381Sha256(artifact)
+            );
+            assert!(matches!(
+                stored.open::<BbsBls12381Shake256>(),
+                Err(Error::CiphersuiteMismatch)
+            ));
+        }
+
+        let envelope = Envelope::seal(&Artifact::from(signature));
+        match envelope.open_any().unwrap() {
+            AnyArtifact::BbsBls12381Sha256(Artifact::Signature(signature)) => {
+                signature.verify(pk, Some(&messages), None).unwrap()
+            }
+            other => panic!("unexpected artifact {:?}", other.kind()),
+        }
+    }
+
+    #[test]
+    fn malformed_envelopes() {
+        let keypair = KeyPair::<BbsBls12381Shake256>::random().unwrap();
+        let bytes = Envelope::seal::<BbsBls12381Shake256>(&Artifact::PublicKey(
+            keypair.public_key().clone(),
+        ))
+        .to_bytes();
+
+        let mut other_version = bytes.clone();
+        other_version[0] = 2;
+        let mut unknown_scheme = bytes.clone();
+        unknown_scheme[1] = 0;
+        let mut unknown_kind = bytes.clone();
+        unknown_kind[2] = 7;
+        for invalid in [
+            &bytes[..3],
+            &bytes[..20],
+            &other_version,
+            &unknown_scheme,
+            &unknown_kind,
+        ] {
+            assert!(Envelope::from_bytes(invalid).is_err());
+        }
+
+        // A truncated payload or a payload of another kind is rejected when opened
+        let truncated = Envelope::from_bytes(&bytes[..bytes.len() - 1]).unwrap();
+        assert!(truncated.open::<BbsBls12381Shake256>().is_err());
+        let mut as_signature = bytes;
+        as_signature[2] = ArtifactKind::Signature as u8;
+        assert!(Envelope::from_bytes(&as_signature)
+            .unwrap()
+            .open_any()
+            .is_err());
+    }
+
+    #[cfg(feature = "cl03")]
+    #[test]
+    fn cl03_envelopes() {
+        use crate::{
+            cl03::bases::Bases,
+            schemes::algorithms::{CL03_CL1024_SHA256, CL03_CL2048_SHA256},
+            utils::message::cl03_message::CL03Message,
+        };
+
+        let keypair = KeyPair::<CL03_CL1024_SHA256>::generate();
+        let bases = Bases::generate(keypair.public_key(), 1);
+        let messages = [CL03Message::map_message_to_integer_as_hash::<
+            crate::cl03::ciphersuites::CL1024Sha256,
+        >(b"message")];
+        let signature = Signature::<CL03_CL1024_SHA256>::sign_multiattr(
+            keypair.public_key(),
+            keypair.private_key(),
+            &bases,
+            &messages,
+        )
+        .unwrap();
+
+        let envelope = Envelope::seal(&Artifact::from(signature));
+        assert_eq!(envelope.scheme(), SchemeId::CL03);
+        assert_eq!(envelope.ciphersuite(), b"CL1024-SHA-256");
+        let stored = Envelope::from_bytes(&envelope.to_bytes()).unwrap();
+        match stored.open_any().unwrap() {
+            AnyArtifact::CL03_CL1024_SHA256(Artifact::Signature(signature)) => signature
+                .verify_multiattr(keypair.public_key(), &bases, &messages)
+                .unwrap(),
+            other => panic!("unexpected artifact {:?}", other.kind()),
+        }
+        assert!(matches!(
+            stored.open::<CL03_CL2048_SHA256>(),
+            Err(Error::CiphersuiteMismatch)
+        ));
+    }
+}
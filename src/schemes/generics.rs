@@ -13,8 +13,8 @@
 // limitations under the License.
 
 use super::algorithms::Scheme;
+use core::marker::PhantomData;
 use serde::{Deserialize, Serialize};
-use std::marker::PhantomData;
 
 #[cfg(feature = "cl03")]
 use crate::cl03::{
@@ -37,7 +37,7 @@ pub enum BlindSignature<S: Scheme> {
     BBSplus(BBSplusSignature),
     #[cfg(feature = "cl03")]
     CL03(CL03BlindSignature),
-    _Unreachable(std::marker::PhantomData<S>),
+    _Unreachable(core::marker::PhantomData<S>),
 }
 
 #[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
@@ -46,7 +46,7 @@ pub enum Commitment<S: Scheme> {
     BBSplus(BBSplusCommitment),
     #[cfg(feature = "cl03")]
     CL03(CL03Commitment),
-    _Unreachable(std::marker::PhantomData<S>),
+    _Unreachable(core::marker::PhantomData<S>),
 }
 
 #[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
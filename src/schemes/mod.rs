@@ -13,4 +13,6 @@
 // limitations under the License.
 
 pub mod algorithms;
+pub mod envelope;
 pub mod generics;
+pub mod traits;
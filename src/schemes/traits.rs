@@ -0,0 +1,450 @@
+// Copyright 2023 Fondazione LINKS
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::{
+    algorithms::Scheme,
+    generics::{PoKSignature, Signature},
+};
+use crate::errors::Error;
+use alloc::vec::Vec;
+
+#[cfg(feature = "bbsplus")]
+use crate::{bbsplus::ciphersuites::BbsCiphersuite, schemes::algorithms::BBSplus};
+#[cfg(feature = "bbsplus")]
+use elliptic_curve::hash2curve::ExpandMsg;
+
+#[cfg(feature = "cl03")]
+use crate::{
+    cl03::{bases::Bases, ciphersuites::CLCiphersuite, keys::CL03CommitmentPublicKey},
+    schemes::algorithms::CL03,
+    utils::message::cl03_message::CL03Message,
+};
+#[cfg(feature = "cl03")]
+use digest::Digest;
+
+/// Scheme-agnostic signature generation.
+pub trait Signer: Scheme {
+    /// Representation of a single signed message.
+    type Message: Clone;
+    /// Public scheme parameters needed to sign and verify (e.g., the header for BBS+, the bases for CL03).
+    type SignParams;
+
+    /// # Description
+    /// Computes a signature over the ordered list of `messages`.
+    ///
+    /// # Inputs:
+    /// * `sk` (REQUIRED), the Signer secret key.
+    /// * `pk` (REQUIRED), the Signer public key.
+    /// * `params` (REQUIRED), the scheme-specific signing parameters.
+    /// * `messages` (REQUIRED), the messages to be signed.
+    ///
+    /// # Output:
+    /// * a [`Signature`] or [`Error`].
+    fn sign(
+        sk: &Self::PrivKey,
+        pk: &Self::PubKey,
+        params: &Self::SignParams,
+        messages: &[Self::Message],
+    ) -> Result<Signature<Self>, Error>;
+}
+
+/// Scheme-agnostic verification of signatures and of proofs of knowledge of a signature.
+pub trait Verifier: Signer {
+    /// Public scheme parameters needed to generate and verify a proof of knowledge of a signature.
+    type ProofParams;
+
+    /// # Description
+    /// Checks that `signature` is valid for `messages` under `pk`.
+    ///
+    /// # Inputs:
+    /// * `signature` (REQUIRED), the signature to verify.
+    /// * `pk` (REQUIRED), the Signer public key.
+    /// * `params` (REQUIRED), the same parameters used during signing.
+    /// * `messages` (REQUIRED), the signed messages.
+    ///
+    /// # Output:
+    /// * [`Error`] if the signature is not valid.
+    fn verify(
+        signature: &Signature<Self>,
+        pk: &Self::PubKey,
+        params: &Self::SignParams,
+        messages: &[Self::Message],
+    ) -> Result<(), Error>;
+
+    /// # Description
+    /// Checks a proof of knowledge of a signature against the disclosed messages.
+    ///
+    /// # Inputs:
+    /// * `proof` (REQUIRED), the proof to verify.
+    /// * `pk` (REQUIRED), the Signer public key.
+    /// * `params` (REQUIRED), the same parameters used during proof generation.
+    /// * `disclosed_messages` (REQUIRED), the disclosed messages, ordered as `disclosed_indexes`.
+    /// * `disclosed_indexes` (REQUIRED), ascending indexes of the disclosed messages.
+    ///
+    /// # Output:
+    /// * [`Error`] if the proof is not valid.
+    fn proof_verify(
+        proof: &PoKSignature<Self>,
+        pk: &Self::PubKey,
+        params: &Self::ProofParams,
+        disclosed_messages: &[Self::Message],
+        disclosed_indexes: &[usize],
+    ) -> Result<(), Error>;
+}
+
+/// Scheme-agnostic generation of proofs of knowledge of a signature with selective disclosure.
+pub trait Prover: Verifier {
+    /// # Description
+    /// Computes a zero-knowledge proof of knowledge of `signature`, disclosing only the messages at `disclosed_indexes`.
+    ///
+    /// # Inputs:
+    /// * `signature` (REQUIRED), the signature held by the Prover.
+    /// * `pk` (REQUIRED), the Signer public key.
+    /// * `params` (REQUIRED), the scheme-specific proof parameters.
+    /// * `messages` (REQUIRED), all the signed messages.
+    /// * `disclosed_indexes` (REQUIRED), ascending indexes of the messages to disclose.
+    ///
+    /// # Output:
+    /// * a [`PoKSignature`] or [`Error`].
+    fn proof_gen(
+        signature: &Signature<Self>,
+        pk: &Self::PubKey,
+        params: &Self::ProofParams,
+        messages: &[Self::Message],
+        disclosed_indexes: &[usize],
+    ) -> Result<PoKSignature<Self>, Error>;
+}
+
+/// BBS+ signing parameters.
+#[cfg(feature = "bbsplus")]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BBSplusSignParams {
+    /// Header bound to the signature.
+    pub header: Option<Vec<u8>>,
+}
+
+/// BBS+ proof parameters.
+#[cfg(feature = "bbsplus")]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BBSplusProofParams {
+    /// Header bound to the signature.
+    pub header: Option<Vec<u8>>,
+    /// Presentation header bound to the proof.
+    pub ph: Option<Vec<u8>>,
+}
+
+#[cfg(feature = "bbsplus")]
+impl<CS: BbsCiphersuite> Signer for BBSplus<CS>
+where
+    CS::Expander: for<'a> ExpandMsg<'a>,
+{
+    type Message = Vec<u8>;
+    type SignParams = BBSplusSignParams;
+
+    fn sign(
+        sk: &Self::PrivKey,
+        pk: &Self::PubKey,
+        params: &Self::SignParams,
+        messages: &[Self::Message],
+    ) -> Result<Signature<Self>, Error> {
+        Signature::<Self>::sign(Some(messages), sk, pk, params.header.as_deref())
+    }
+}
+
+#[cfg(feature = "bbsplus")]
+impl<CS: BbsCiphersuite> Verifier for BBSplus<CS>
+where
+    CS::Expander: for<'a> ExpandMsg<'a>,
+{
+    type ProofParams = BBSplusProofParams;
+
+    fn verify(
+        signature: &Signature<Self>,
+        pk: &Self::PubKey,
+        params: &Self::SignParams,
+        messages: &[Self::Message],
+    ) -> Result<(), Error> {
+        signature.verify(pk, Some(messages), params.header.as_deref())
+    }
+
+    fn proof_verify(
+        proof: &PoKSignature<Self>,
+        pk: &Self::PubKey,
+        params: &Self::ProofParams,
+        disclosed_messages: &[Self::Message],
+        disclosed_indexes: &[usize],
+    ) -> Result<(), Error> {
+        proof.proof_verify(
+            pk,
+            Some(disclosed_messages),
+            Some(disclosed_indexes),
+            params.header.as_deref(),
+            params.ph.as_deref(),
+        )
+    }
+}
+
+#[cfg(all(feature = "bbsplus", feature = "std"))]
+impl<CS: BbsCiphersuite> Prover for BBSplus<CS>
+where
+    CS::Expander: for<'a> ExpandMsg<'a>,
+{
+    fn proof_gen(
+        signature: &Signature<Self>,
+        pk: &Self::PubKey,
+        params: &Self::ProofParams,
+        messages: &[Self::Message],
+        disclosed_indexes: &[usize],
+    ) -> Result<PoKSignature<Self>, Error> {
+        PoKSignature::<Self>::proof_gen(
+            pk,
+            &signature.to_bytes(),
+            params.header.as_deref(),
+            params.ph.as_deref(),
+            Some(messages),
+            Some(disclosed_indexes),
+        )
+    }
+}
+
+/// CL03 proof parameters.
+#[cfg(feature = "cl03")]
+pub struct CL03ProofParams {
+    /// Bases used to sign the messages, one per signed message.
+    pub a_bases: Bases,
+    /// Verifier commitment public key used by the signature proof of knowledge.
+    pub commitment_pk: CL03CommitmentPublicKey,
+}
+
+/// Returns the indexes in `0..n` not contained in `disclosed_indexes`, failing if any disclosed index is out of range.
+#[cfg(feature = "cl03")]
+fn cl03_undisclosed_indexes(n: usize, disclosed_indexes: &[usize]) -> Result<Vec<usize>, Error> {
+    if disclosed_indexes.iter().any(|&i| i >= n) {
+        return Err(Error::InvalidMessageIndex);
+    }
+    Ok((0..n).filter(|i| !disclosed_indexes.contains(i)).collect())
+}
+
+#[cfg(feature = "cl03")]
+impl<CS: CLCiphersuite> Signer for CL03<CS> {
+    type Message = CL03Message;
+    type SignParams = Bases;
+
+    fn sign(
+        sk: &Self::PrivKey,
+        pk: &Self::PubKey,
+        params: &Self::SignParams,
+        messages: &[Self::Message],
+    ) -> Result<Signature<Self>, Error> {
+        Signature::<Self>::sign_multiattr(pk, sk, params, messages)
+    }
+}
+
+#[cfg(feature = "cl03")]
+impl<CS: CLCiphersuite> Verifier for CL03<CS>
+where
+    CS::HashAlg: Digest,
+{
+    type ProofParams = CL03ProofParams;
+
+    fn verify(
+        signature: &Signature<Self>,
+        pk: &Self::PubKey,
+        params: &Self::SignParams,
+        messages: &[Self::Message],
+    ) -> Result<(), Error> {
+        signature.verify_multiattr(pk, params, messages)
+    }
+
+    fn proof_verify(
+        proof: &PoKSignature<Self>,
+        pk: &Self::PubKey,
+        params: &Self::ProofParams,
+        disclosed_messages: &[Self::Message],
+        disclosed_indexes: &[usize],
+    ) -> Result<(), Error> {
+        let n = params.a_bases.0.len();
+        let undisclosed_indexes = cl03_undisclosed_indexes(n, disclosed_indexes)?;
+        if disclosed_messages.len() != n - undisclosed_indexes.len() {
+            return Err(Error::PoKSVerificationError(
+                "Number of disclosed messages and indexes do not match".to_owned(),
+            ));
+        }
+        proof.proof_verify(
+            &params.commitment_pk,
+            pk,
+            &params.a_bases,
+            disclosed_messages,
+            &undisclosed_indexes,
+            n,
+        )
+    }
+}
+
+#[cfg(feature = "cl03")]
+impl<CS: CLCiphersuite> Prover for CL03<CS>
+where
+    CS::HashAlg: Digest,
+{
+    fn proof_gen(
+        signature: &Signature<Self>,
+        pk: &Self::PubKey,
+        params: &Self::ProofParams,
+        messages: &[Self::Message],
+        disclosed_indexes: &[usize],
+    ) -> Result<PoKSignature<Self>, Error> {
+        let undisclosed_indexes = cl03_undisclosed_indexes(messages.len(), disclosed_indexes)?;
+        PoKSignature::<Self>::proof_gen(
+            signature.cl03Signature(),
+            &params.commitment_pk,
+            pk,
+            &params.a_bases,
+            messages,
+            &undisclosed_indexes,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Prover;
+    use crate::errors::Error;
+
+    const DISCLOSED_INDEXES: [usize; 2] = [0, 2];
+
+    /// Exercises the whole sign/verify/prove flow through the scheme-agnostic traits only.
+    fn sign_and_prove<S: Prover>(
+        sk: &S::PrivKey,
+        pk: &S::PubKey,
+        sign_params: &S::SignParams,
+        proof_params: &S::ProofParams,
+        messages: &[S::Message],
+    ) -> Result<(), Error> {
+        let signature = S::sign(sk, pk, sign_params, messages)?;
+        S::verify(&signature, pk, sign_params, messages)?;
+
+        let proof = S::proof_gen(&signature, pk, proof_params, messages, &DISCLOSED_INDEXES)?;
+        let disclosed_messages: Vec<S::Message> = DISCLOSED_INDEXES
+            .iter()
+            .map(|&i| messages[i].clone())
+            .collect();
+        S::proof_verify(
+            &proof,
+            pk,
+            proof_params,
+            &disclosed_messages,
+            &DISCLOSED_INDEXES,
+        )?;
+
+        let mut tampered = disclosed_messages;
+        tampered.swap(0, 1);
+        assert!(
+            S::proof_verify(&proof, pk, proof_params, &tampered, &DISCLOSED_INDEXES).is_err(),
+            "proof verification should fail with tampered disclosed messages"
+        );
+
+        Ok(())
+    }
+
+    #[cfg(feature = "bbsplus")]
+    mod bbsplus {
+        use super::sign_and_prove;
+        use crate::{
+            bbsplus::ciphersuites::BbsCiphersuite,
+            keys::pair::KeyPair,
+            schemes::{
+                algorithms::{BBSplus, BbsBls12381Sha256, BbsBls12381Shake256, Scheme},
+                traits::{BBSplusProofParams, BBSplusSignParams},
+            },
+        };
+        use elliptic_curve::hash2curve::ExpandMsg;
+
+        fn traits_bbsplus<S: Scheme>()
+        where
+            S::Ciphersuite: BbsCiphersuite,
+            <S::Ciphersuite as BbsCiphersuite>::Expander: for<'a> ExpandMsg<'a>,
+        {
+            let keypair = KeyPair::<BBSplus<S::Ciphersuite>>::random().unwrap();
+            let messages: Vec<Vec<u8>> =
+                vec![b"first".to_vec(), b"second".to_vec(), b"third".to_vec()];
+            let sign_params = BBSplusSignParams {
+                header: Some(b"header".to_vec()),
+            };
+            let proof_params = BBSplusProofParams {
+                header: sign_params.header.clone(),
+                ph: Some(b"presentation header".to_vec()),
+            };
+
+            sign_and_prove::<BBSplus<S::Ciphersuite>>(
+                keypair.private_key(),
+                keypair.public_key(),
+                &sign_params,
+                &proof_params,
+                &messages,
+            )
+            .unwrap();
+        }
+
+        #[test]
+        fn traits_bbsplus_sha256() {
+            traits_bbsplus::<BbsBls12381Sha256>();
+        }
+
+        #[test]
+        fn traits_bbsplus_shake256() {
+            traits_bbsplus::<BbsBls12381Shake256>();
+        }
+    }
+
+    #[cfg(feature = "cl03")]
+    mod cl03 {
+        use super::sign_and_prove;
+        use crate::cl03::ciphersuites::CL1024Sha256;
+        use crate::{
+            cl03::{bases::Bases, keys::CL03CommitmentPublicKey},
+            keys::pair::KeyPair,
+            schemes::{
+                algorithms::{CL03, CL03_CL1024_SHA256},
+                traits::CL03ProofParams,
+            },
+            utils::message::cl03_message::CL03Message,
+        };
+
+        #[test]
+        fn traits_cl03_cl1024_sha256() {
+            let keypair = KeyPair::<CL03_CL1024_SHA256>::generate();
+            let messages: Vec<CL03Message> = [b"first", b"secnd", b"third"]
+                .iter()
+                .map(|m| CL03Message::map_message_to_integer_as_hash::<CL1024Sha256>(*m))
+                .collect();
+            let a_bases = Bases::generate(keypair.public_key(), messages.len());
+            let proof_params = CL03ProofParams {
+                a_bases: a_bases.clone(),
+                commitment_pk: CL03CommitmentPublicKey::generate::<CL1024Sha256>(
+                    Some(keypair.public_key().N.clone()),
+                    Some(messages.len()),
+                ),
+            };
+
+            sign_and_prove::<CL03<CL1024Sha256>>(
+                keypair.private_key(),
+                keypair.public_key(),
+                &a_bases,
+                &proof_params,
+                &messages,
+            )
+            .unwrap();
+        }
+    }
+}
@@ -0,0 +1,399 @@
+// Copyright 2023 Fondazione LINKS
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Minimal [CBOR](https://www.rfc-editor.org/rfc/rfc8949) encoder and decoder for the data items used by the crate.
+//! Only definite length items are supported, and items are always encoded with the shortest argument.
+
+use crate::errors::Error;
+use alloc::{borrow::ToOwned, boxed::Box, string::String, vec::Vec};
+
+const MAJOR_UNSIGNED: u8 = 0;
+const MAJOR_BYTES: u8 = 2;
+const MAJOR_TEXT: u8 = 3;
+const MAJOR_ARRAY: u8 = 4;
+const MAJOR_MAP: u8 = 5;
+const MAJOR_TAG: u8 = 6;
+
+/// Maximum nesting of arrays, maps and tags accepted by the decoder
+const MAX_DEPTH: usize = 16;
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub(crate) enum Value {
+    Unsigned(u64),
+    Bytes(Vec<u8>),
+    Text(String),
+    Array(Vec<Value>),
+    Map(Vec<(Value, Value)>),
+    Tag(u64, Box<Value>),
+}
+
+fn error(msg: &str) -> Error {
+    Error::DeserializationError(["CBOR: ", msg].concat())
+}
+
+impl Value {
+    pub(crate) fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        self.encode(&mut out);
+        out
+    }
+
+    /// Decodes a single data item spanning the whole `bytes`
+    pub(crate) fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        let mut input = bytes;
+        let value = Self::decode(&mut input, 0)?;
+        if !input.is_empty() {
+            return Err(error("trailing bytes"));
+        }
+        Ok(value)
+    }
+
+    fn encode(&self, out: &mut Vec<u8>) {
+        match self {
+            Value::Unsigned(n) => encode_head(out, MAJOR_UNSIGNED, *n),
+            Value::Bytes(b) => {
+                encode_head(out, MAJOR_BYTES, b.len() as u64);
+                out.extend_from_slice(b);
+            }
+            Value::Text(t) => {
+                encode_head(out, MAJOR_TEXT, t.len() as u64);
+                out.extend_from_slice(t.as_bytes());
+            }
+            Value::Array(items) => {
+                encode_head(out, MAJOR_ARRAY, items.len() as u64);
+                items.iter().for_each(|item| item.encode(out));
+            }
+            Value::Map(entries) => {
+                encode_head(out, MAJOR_MAP, entries.len() as u64);
+                for (key, value) in entries {
+                    key.encode(out);
+                    value.encode(out);
+                }
+            }
+            Value::Tag(tag, value) => {
+                encode_head(out, MAJOR_TAG, *tag);
+                value.encode(out);
+            }
+        }
+    }
+
+    fn decode(input: &mut &[u8], depth: usize) -> Result<Self, Error> {
+        if depth > MAX_DEPTH {
+            return Err(error("too deeply nested"));
+        }
+        let (major, arg) = decode_head(input)?;
+        match major {
+            MAJOR_UNSIGNED => Ok(Value::Unsigned(arg)),
+            MAJOR_BYTES => Ok(Value::Bytes(take(input, arg)?.to_vec())),
+            MAJOR_TEXT => {
+                let text = core::str::from_utf8(take(input, arg)?)
+                    .map_err(|_| error("invalid UTF-8 text"))?;
+                Ok(Value::Text(text.to_owned()))
+            }
+            MAJOR_ARRAY => {
+                // Every item takes at least one byte, which bounds the allocation
+                let mut items = Vec::with_capacity(bounded_len(input, arg)?);
+                for _ in 0..arg {
+                    items.push(Self::decode(input, depth + 1)?);
+                }
+                Ok(Value::Array(items))
+            }
+            MAJOR_MAP => {
+                let mut entries = Vec::with_capacity(bounded_len(input, arg)?);
+                for _ in 0..arg {
+                    let key = Self::decode(input, depth + 1)?;
+                    let value = Self::decode(input, depth + 1)?;
+                    entries.push((key, value));
+                }
+                Ok(Value::Map(entries))
+            }
+            MAJOR_TAG => Ok(Value::Tag(arg, Box::new(Self::decode(input, depth + 1)?))),
+            _ => Err(error("unsupported major type")),
+        }
+    }
+
+    #[cfg_attr(not(feature = "bbs-2023"), allow(dead_code))]
+    pub(crate) fn into_unsigned(self) -> Result<u64, Error> {
+        match self {
+            Value::Unsigned(n) => Ok(n),
+            _ => Err(error("expected an unsigned integer")),
+        }
+    }
+
+    #[cfg_attr(not(feature = "bbs-2023"), allow(dead_code))]
+    pub(crate) fn into_usize(self) -> Result<usize, Error> {
+        usize::try_from(self.into_unsigned()?).map_err(|_| error("integer out of range"))
+    }
+
+    pub(crate) fn into_bytes(self) -> Result<Vec<u8>, Error> {
+        match self {
+            Value::Bytes(b) => Ok(b),
+            _ => Err(error("expected a byte string")),
+        }
+    }
+
+    pub(crate) fn into_text(self) -> Result<String, Error> {
+        match self {
+            Value::Text(t) => Ok(t),
+            _ => Err(error("expected a text string")),
+        }
+    }
+
+    pub(crate) fn into_array(self) -> Result<Vec<Value>, Error> {
+        match self {
+            Value::Array(items) => Ok(items),
+            _ => Err(error("expected an array")),
+        }
+    }
+
+    #[cfg_attr(not(feature = "bbs-2023"), allow(dead_code))]
+    pub(crate) fn into_map(self) -> Result<Vec<(Value, Value)>, Error> {
+        match self {
+            Value::Map(entries) => Ok(entries),
+            _ => Err(error("expected a map")),
+        }
+    }
+
+    /// Returns the content of a data item tagged with `tag`
+    #[cfg_attr(not(feature = "bbs-2023"), allow(dead_code))]
+    pub(crate) fn untag(self, tag: u64) -> Result<Value, Error> {
+        match self {
+            Value::Tag(t, value) if t == tag => Ok(*value),
+            _ => Err(error("unexpected tag")),
+        }
+    }
+}
+
+fn encode_head(out: &mut Vec<u8>, major: u8, arg: u64) {
+    let major = major << 5;
+    match arg {
+        0..=23 => out.push(major | arg as u8),
+        24..=0xff => out.extend_from_slice(&[major | 24, arg as u8]),
+        0x100..=0xffff => {
+            out.push(major | 25);
+            out.extend_from_slice(&(arg as u16).to_be_bytes());
+        }
+        0x1_0000..=0xffff_ffff => {
+            out.push(major | 26);
+            out.extend_from_slice(&(arg as u32).to_be_bytes());
+        }
+        _ => {
+            out.push(major | 27);
+            out.extend_from_slice(&arg.to_be_bytes());
+        }
+    }
+}
+
+fn decode_head(input: &mut &[u8]) -> Result<(u8, u64), Error> {
+    let initial = take(input, 1)?[0];
+    let (major, info) = (initial >> 5, initial & 0x1f);
+    let arg = match info {
+        0..=23 => info as u64,
+        24 => take(input, 1)?[0] as u64,
+        25 => u16::from_be_bytes(take(input, 2)?.try_into().unwrap()) as u64,
+        26 => u32::from_be_bytes(take(input, 4)?.try_into().unwrap()) as u64,
+        27 => u64::from_be_bytes(take(input, 8)?.try_into().unwrap()),
+        _ => return Err(error("indefinite length items are not supported")),
+    };
+    Ok((major, arg))
+}
+
+fn take<'a>(input: &mut &'a [u8], len: u64) -> Result<&'a [u8], Error> {
+    let len = usize::try_from(len)
+        .ok()
+        .filter(|&len| len <= input.len())
+        .ok_or_else(|| error("unexpected end of input"))?;
+    let (head, tail) = input.split_at(len);
+    *input = tail;
+    Ok(head)
+}
+
+fn bounded_len(input: &[u8], len: u64) -> Result<usize, Error> {
+    usize::try_from(len)
+        .ok()
+        .filter(|&len| len <= input.len())
+        .ok_or_else(|| error("unexpected end of input"))
+}
+
+/// Encodes `payload` as the array `[ciphersuite_id, payload]`, with the identifier as a text string
+pub(crate) fn encode_with_ciphersuite(ciphersuite_id: &[u8], payload: &[u8]) -> Vec<u8> {
+    Value::Array(vec![
+        Value::Text(String::from_utf8_lossy(ciphersuite_id).into_owned()),
+        Value::Bytes(payload.to_vec()),
+    ])
+    .to_bytes()
+}
+
+/// Decodes an array produced by [`encode_with_ciphersuite`], failing with [`Error::CiphersuiteMismatch`] if it was
+/// encoded for a ciphersuite other than `ciphersuite_id`
+pub(crate) fn decode_with_ciphersuite(
+    ciphersuite_id: &[u8],
+    bytes: &[u8],
+) -> Result<Vec<u8>, Error> {
+    let items = Value::from_bytes(bytes)?.into_array()?;
+    let [id, payload]: [Value; 2] = items
+        .try_into()
+        .map_err(|_| error("expected an array of 2 items"))?;
+    if id.into_text()?.as_bytes() != ciphersuite_id {
+        return Err(Error::CiphersuiteMismatch);
+    }
+    payload.into_bytes()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode_with_ciphersuite, encode_with_ciphersuite, Value};
+    use crate::{
+        bbsplus::{
+            ciphersuites::{BbsCiphersuite, Bls12381Sha256, Bls12381Shake256},
+            keys::BBSplusPublicKey,
+        },
+        errors::Error,
+        keys::pair::KeyPair,
+        schemes::{
+            algorithms::BBSplus,
+            generics::{Commitment, PoKSignature, Signature},
+        },
+    };
+
+    #[test]
+    fn rfc8949_vectors() {
+        let vectors = [
+            (Value::Unsigned(0), "00"),
+            (Value::Unsigned(23), "17"),
+            (Value::Unsigned(24), "1818"),
+            (Value::Unsigned(1000), "1903e8"),
+            (Value::Unsigned(1000000), "1a000f4240"),
+            (Value::Unsigned(1000000000000), "1b000000e8d4a51000"),
+            (Value::Bytes(vec![1, 2, 3, 4]), "4401020304"),
+            (Value::Text("IETF".into()), "6449455446"),
+            (Value::Text("\u{00fc}".into()), "62c3bc"),
+            (
+                Value::Array(vec![
+                    Value::Unsigned(1),
+                    Value::Array(vec![Value::Unsigned(2), Value::Unsigned(3)]),
+                ]),
+                "8201820203",
+            ),
+            (
+                Value::Map(vec![
+                    (Value::Unsigned(1), Value::Unsigned(2)),
+                    (Value::Unsigned(3), Value::Unsigned(4)),
+                ]),
+                "a201020304",
+            ),
+            (
+                Value::Tag(1, Box::new(Value::Unsigned(1363896240))),
+                "c11a514b67b0",
+            ),
+        ];
+        for (value, encoded) in vectors {
+            assert_eq!(hex::encode(value.to_bytes()), encoded);
+            assert_eq!(
+                Value::from_bytes(&hex::decode(encoded).unwrap()).unwrap(),
+                value
+            );
+        }
+    }
+
+    #[test]
+    fn malformed_input() {
+        for encoded in ["", "18", "4401", "9f01ff", "8201", "0000", "62c3"] {
+            assert!(Value::from_bytes(&hex::decode(encoded).unwrap()).is_err());
+        }
+        // Declared length larger than the input
+        assert!(Value::from_bytes(&hex::decode("9bffffffffffffffff").unwrap()).is_err());
+        // Nesting deeper than the limit
+        assert!(Value::from_bytes(&[0x81; 64]).is_err());
+    }
+
+    #[test]
+    fn ciphersuite_payload() {
+        let encoded = encode_with_ciphersuite(b"ID", &[1, 2]);
+        assert_eq!(hex::encode(&encoded), "82624944420102");
+        assert_eq!(decode_with_ciphersuite(b"ID", &encoded).unwrap(), [1, 2]);
+        assert!(matches!(
+            decode_with_ciphersuite(b"OTHER", &encoded),
+            Err(Error::CiphersuiteMismatch)
+        ));
+        assert!(decode_with_ciphersuite(b"ID", &hex::decode("8162494442").unwrap()).is_err());
+    }
+
+    #[test]
+    fn bbsplus_round_trip() {
+        let messages = [b"first".to_vec(), b"second".to_vec()];
+        let keypair = KeyPair::<BBSplus<Bls12381Sha256>>::random().unwrap();
+        let (sk, pk) = (keypair.private_key(), keypair.public_key());
+
+        let encoded_pk = pk.to_cbor::<Bls12381Sha256>();
+        assert_eq!(
+            &BBSplusPublicKey::from_cbor::<Bls12381Sha256>(&encoded_pk).unwrap(),
+            pk
+        );
+        assert!(matches!(
+            BBSplusPublicKey::from_cbor::<Bls12381Shake256>(&encoded_pk),
+            Err(Error::CiphersuiteMismatch)
+        ));
+
+        let signature =
+            Signature::<BBSplus<Bls12381Sha256>>::sign(Some(&messages), sk, pk, None).unwrap();
+        let encoded_signature = signature.to_cbor();
+        let decoded = Signature::<BBSplus<Bls12381Sha256>>::from_cbor(&encoded_signature).unwrap();
+        decoded.verify(pk, Some(&messages), None).unwrap();
+        assert!(matches!(
+            Signature::<BBSplus<Bls12381Shake256>>::from_cbor(&encoded_signature),
+            Err(Error::CiphersuiteMismatch)
+        ));
+
+        let proof = PoKSignature::<BBSplus<Bls12381Sha256>>::proof_gen(
+            pk,
+            &signature.to_bytes(),
+            None,
+            None,
+            Some(&messages),
+            Some(&[1]),
+        )
+        .unwrap();
+        let encoded_proof = proof.to_cbor();
+        PoKSignature::<BBSplus<Bls12381Sha256>>::from_cbor(&encoded_proof)
+            .unwrap()
+            .proof_verify(pk, Some(&messages[1..]), Some(&[1]), None, None)
+            .unwrap();
+        assert!(matches!(
+            PoKSignature::<BBSplus<Bls12381Shake256>>::from_cbor(&encoded_proof),
+            Err(Error::CiphersuiteMismatch)
+        ));
+
+        let (commitment, _) =
+            Commitment::<BBSplus<Bls12381Sha256>>::commit(Some(&messages), None).unwrap();
+        let encoded_commitment = commitment.to_cbor();
+        assert_eq!(
+            Commitment::<BBSplus<Bls12381Sha256>>::from_cbor(&encoded_commitment)
+                .unwrap()
+                .to_bytes(),
+            commitment.to_bytes()
+        );
+        assert!(matches!(
+            Commitment::<BBSplus<Bls12381Shake256>>::from_cbor(&encoded_commitment),
+            Err(Error::CiphersuiteMismatch)
+        ));
+
+        // Truncated payloads are rejected instead of panicking
+        let truncated = encode_with_ciphersuite(Bls12381Sha256::ID, &[0; 10]);
+        assert!(Signature::<BBSplus<Bls12381Sha256>>::from_cbor(&truncated).is_err());
+        assert!(PoKSignature::<BBSplus<Bls12381Sha256>>::from_cbor(&truncated).is_err());
+        assert!(Commitment::<BBSplus<Bls12381Sha256>>::from_cbor(&truncated).is_err());
+        assert!(BBSplusPublicKey::from_cbor::<Bls12381Sha256>(&truncated).is_err());
+    }
+}
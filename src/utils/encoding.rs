@@ -0,0 +1,154 @@
+// Copyright 2023 Fondazione LINKS
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Text encodings used by the key representations (Multikey, JWK, did:key)
+
+use alloc::{string::String, vec::Vec};
+
+const BASE58_ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+const BASE64URL_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// Multibase prefix of the base58btc encoding
+pub const MULTIBASE_BASE58BTC: char = 'z';
+
+/// Encodes `data` in base58 with the Bitcoin alphabet
+pub fn base58btc_encode(data: &[u8]) -> String {
+    let zeros = data.iter().take_while(|&&b| b == 0).count();
+    // Little endian base 58 digits
+    let mut digits: Vec<u8> = Vec::with_capacity(data.len() * 138 / 100 + 1);
+    for &byte in &data[zeros..] {
+        let mut carry = byte as u32;
+        for digit in digits.iter_mut() {
+            carry += (*digit as u32) << 8;
+            *digit = (carry % 58) as u8;
+            carry /= 58;
+        }
+        while carry > 0 {
+            digits.push((carry % 58) as u8);
+            carry /= 58;
+        }
+    }
+
+    let mut encoded = String::with_capacity(zeros + digits.len());
+    encoded.extend(core::iter::repeat('1').take(zeros));
+    encoded.extend(
+        digits
+            .iter()
+            .rev()
+            .map(|&d| BASE58_ALPHABET[d as usize] as char),
+    );
+    encoded
+}
+
+/// Decodes a base58 string with the Bitcoin alphabet, `None` if it contains invalid characters
+pub fn base58btc_decode(encoded: &str) -> Option<Vec<u8>> {
+    let zeros = encoded.bytes().take_while(|&c| c == b'1').count();
+    // Little endian base 256 digits
+    let mut bytes: Vec<u8> = Vec::with_capacity(encoded.len() * 733 / 1000 + 1);
+    for c in encoded.bytes().skip(zeros) {
+        let mut carry = BASE58_ALPHABET.iter().position(|&a| a == c)? as u32;
+        for byte in bytes.iter_mut() {
+            carry += (*byte as u32) * 58;
+            *byte = carry as u8;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            bytes.push(carry as u8);
+            carry >>= 8;
+        }
+    }
+
+    let mut decoded = vec![0u8; zeros];
+    decoded.extend(bytes.iter().rev());
+    Some(decoded)
+}
+
+/// Encodes `data` in base64url without padding
+pub fn base64url_encode(data: &[u8]) -> String {
+    let mut encoded = String::with_capacity((data.len() * 4 + 2) / 3);
+    for chunk in data.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |n, (i, &b)| n | (b as u32) << (16 - 8 * i));
+        for i in 0..=chunk.len() {
+            encoded.push(BASE64URL_ALPHABET[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+        }
+    }
+    encoded
+}
+
+/// Decodes a base64url string without padding, `None` if it is not canonically encoded
+pub fn base64url_decode(encoded: &str) -> Option<Vec<u8>> {
+    if encoded.len() % 4 == 1 {
+        return None;
+    }
+    let mut decoded = Vec::with_capacity(encoded.len() * 3 / 4);
+    for chunk in encoded.as_bytes().chunks(4) {
+        let mut n = 0u32;
+        for (i, &c) in chunk.iter().enumerate() {
+            let value = BASE64URL_ALPHABET.iter().position(|&a| a == c)? as u32;
+            n |= value << (18 - 6 * i);
+        }
+        let len = chunk.len() - 1;
+        // The bits after the last encoded byte must be zero
+        if n & (0xffffff >> (8 * len)) != 0 {
+            return None;
+        }
+        decoded.extend(n.to_be_bytes()[1..=len].iter());
+    }
+    Some(decoded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn base58btc() {
+        let vectors: [(&[u8], &str); 4] = [
+            (b"", ""),
+            (b"Hello World!", "2NEpo7TZRRrLZSi2U"),
+            (&[0, 0, 0x28, 0x7f, 0xb4, 0xcd], "11233QC4"),
+            (
+                b"The quick brown fox jumps over the lazy dog.",
+                "USm3fpXnKG5EUBx2ndxBDMPVciP5hGey2Jh4NDv6gmeo1LkMeiKrLJUUBk6Z",
+            ),
+        ];
+        for (data, encoded) in vectors {
+            assert_eq!(base58btc_encode(data), encoded);
+            assert_eq!(base58btc_decode(encoded).unwrap(), data);
+        }
+        assert!(base58btc_decode("0OIl").is_none());
+    }
+
+    #[test]
+    fn base64url() {
+        let vectors: [(&[u8], &str); 5] = [
+            (b"", ""),
+            (b"f", "Zg"),
+            (b"fo", "Zm8"),
+            (b"foo", "Zm9v"),
+            (&[0xfb, 0xff, 0xbf], "-_-_"),
+        ];
+        for (data, encoded) in vectors {
+            assert_eq!(base64url_encode(data), encoded);
+            assert_eq!(base64url_decode(encoded).unwrap(), data);
+        }
+        assert!(base64url_decode("Zm9v=").is_none());
+        assert!(base64url_decode("Zh").is_none());
+        assert!(base64url_decode("Z").is_none());
+    }
+}
@@ -18,9 +18,11 @@ pub mod bbsplus_message {
     use crate::bbsplus::ciphersuites::BbsCiphersuite;
     use crate::errors::Error;
     use crate::utils::util::bbsplus_utils::hash_to_scalar;
+    use alloc::vec::Vec;
     use bls12_381_plus::Scalar;
     use elliptic_curve::hash2curve::ExpandMsg;
     use serde::{Deserialize, Serialize};
+    use zeroize::Zeroize;
 
     #[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
     pub struct BBSplusMessage {
@@ -61,6 +63,71 @@ pub mod bbsplus_message {
             Ok(msg_scalars)
         }
 
+        /// # Description
+        /// The `messages_to_scalar_with_integers` operation is used to map a list of messages to their respective scalar values, where the messages at `integer_indexes` are integers encoded with [`Self::from_integer`] instead of being hashed. This allows range proofs over those messages.
+        ///
+        /// # Inputs:
+        /// * `messages` (REQUIRED), a vector of octet strings. The integer messages MUST be 8 octets long (i.e., `u64` in big-endian order).
+        /// * `integer_indexes` (REQUIRED), vector of unsigned integers. Indexes of the integer messages, it could be empty.
+        /// * `api_id` (REQUIRED), octet string. It could be an empty octet string
+        ///
+        /// # Output:
+        /// * a vector of [`BBSplusMessage`], which is a wrapper to a `Scalar` or [`Error`].
+        ///
+        pub fn messages_to_scalar_with_integers<CS: BbsCiphersuite>(
+            messages: &[Vec<u8>],
+            integer_indexes: &[usize],
+            api_id: &[u8],
+        ) -> Result<Vec<Self>, Error>
+        where
+            CS::Expander: for<'a> ExpandMsg<'a>,
+        {
+            let map_dst = [api_id, CS::MAP_MSG_SCALAR].concat();
+            let mut msg_scalars: Vec<Self> = Vec::new();
+            for (i, m) in messages.iter().enumerate() {
+                if integer_indexes.contains(&i) {
+                    let value = <[u8; 8]>::try_from(m.as_slice())
+                        .map_err(|_| Error::MapMessageToScalarError)?;
+                    msg_scalars.push(Self::from_integer(u64::from_be_bytes(value)));
+                } else {
+                    let scalar = hash_to_scalar::<CS>(m, &map_dst)?;
+                    msg_scalars.push(Self { value: scalar })
+                }
+            }
+
+            Ok(msg_scalars)
+        }
+
+        /// # Description
+        /// Encodes an integer directly as a scalar value
+        ///
+        /// # Inputs:
+        /// * `value` (REQUIRED), an unsigned integer.
+        ///
+        /// # Output:
+        /// * a [`BBSplusMessage`], which is a wrapper to a `Scalar`.
+        ///
+        pub fn from_integer(value: u64) -> Self {
+            Self {
+                value: Scalar::from(value),
+            }
+        }
+
+        /// # Description
+        /// Decodes an integer encoded with [`Self::from_integer`]
+        ///
+        /// # Output:
+        /// * the integer or [`None`] if the scalar value is not an encoded integer.
+        ///
+        pub fn to_integer(&self) -> Option<u64> {
+            let bytes = self.value.to_be_bytes();
+            let (high, low) = bytes.split_at(Scalar::BYTES - 8);
+            if high.iter().any(|&b| b != 0) {
+                return None;
+            }
+            Some(u64::from_be_bytes(low.try_into().ok()?))
+        }
+
         /// # Description
         /// The `map_message_to_scalar_as_hash` operation is used to map a single message to its respective scalar value
         ///
@@ -97,6 +164,12 @@ pub mod bbsplus_message {
         }
     }
 
+    impl Zeroize for BBSplusMessage {
+        fn zeroize(&mut self) {
+            self.value.zeroize();
+        }
+    }
+
     #[cfg(test)]
     mod tests {
 
@@ -172,7 +245,7 @@ pub mod bbsplus_message {
                 };
             }
 
-            assert_eq!(boolean, true);
+            assert!(boolean);
         }
 
         fn messages_to_scalars<S: Scheme>(filename: &str)
@@ -212,9 +285,11 @@ pub mod bbsplus_message {
 pub mod cl03_message {
 
     use crate::cl03::ciphersuites::CLCiphersuite;
+    use crate::utils::util::cl03_utils::zeroize_integer;
     use digest::Digest;
     use rug::{integer::Order, Integer};
     use serde::{Deserialize, Serialize};
+    use zeroize::Zeroize;
 
     #[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
     pub struct CL03Message {
@@ -240,4 +315,10 @@ pub mod cl03_message {
             Self { value: msg_integer }
         }
     }
+
+    impl Zeroize for CL03Message {
+        fn zeroize(&mut self) {
+            zeroize_integer(&mut self.value);
+        }
+    }
 }
@@ -15,5 +15,8 @@
 #[cfg(feature = "cl03")]
 pub mod random;
 
+#[cfg(feature = "bbsplus")]
+pub(crate) mod cbor;
+pub mod encoding;
 pub mod message;
 pub mod util;
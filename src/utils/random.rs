@@ -12,55 +12,76 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use rand::Rng;
-use rug::rand::{RandGen, RandState};
+use rand::{thread_rng, CryptoRng, RngCore};
+use rug::rand::{ThreadRandGen, ThreadRandState};
 use rug::{Complete, Integer};
 use std::cmp::Ordering;
 
-struct Seed;
-impl RandGen for Seed {
+struct Seed<'a, R: RngCore + CryptoRng> {
+    rng: &'a mut R,
+}
+impl<R: RngCore + CryptoRng> ThreadRandGen for Seed<'_, R> {
     fn gen(&mut self) -> u32 {
-        let mut rng= rand::thread_rng();
-        rng.gen::<u32>()
+        self.rng.next_u32()
     }
 }
 
 pub fn random_bits(n: u32) -> Integer {
-    let mut seed = Seed;
-    let mut rand = RandState::new_custom(&mut seed);
+    random_bits_with_rng(n, &mut thread_rng())
+}
+
+pub fn random_bits_with_rng<R: RngCore + CryptoRng>(n: u32, rng: &mut R) -> Integer {
+    let mut seed = Seed { rng };
+    let mut rand = ThreadRandState::new_custom(&mut seed);
     let mut i = Integer::from(Integer::random_bits(n, &mut rand));
     i.set_bit(n - 1, true);
     i
 }
 
 pub fn random_number(n: Integer) -> Integer {
-    let mut seed = Seed;
-    let mut rand = RandState::new_custom(&mut seed);
+    random_number_with_rng(n, &mut thread_rng())
+}
+
+pub fn random_number_with_rng<R: RngCore + CryptoRng>(n: Integer, rng: &mut R) -> Integer {
+    let mut seed = Seed { rng };
+    let mut rand = ThreadRandState::new_custom(&mut seed);
     let number = n.random_below(&mut rand);
     number
 }
 
 pub fn random_prime(n: u32) -> Integer {
-    let r = random_bits(n);
+    random_prime_with_rng(n, &mut thread_rng())
+}
+
+pub fn random_prime_with_rng<R: RngCore + CryptoRng>(n: u32, rng: &mut R) -> Integer {
+    let r = random_bits_with_rng(n, rng);
     let prime = r.next_prime();
     prime
 }
 
 pub fn random_qr(n: &Integer) -> Integer {
-    let mut r = random_number(n.clone());
+    random_qr_with_rng(n, &mut thread_rng())
+}
+
+pub fn random_qr_with_rng<R: RngCore + CryptoRng>(n: &Integer, rng: &mut R) -> Integer {
+    let mut r = random_number_with_rng(n.clone(), rng);
     let mut qr = r.secure_pow_mod(&Integer::from(2), n);
     while !(qr.cmp(&Integer::from(1)) == Ordering::Greater
         && qr.clone().gcd(&n).cmp(&Integer::from(1)) == Ordering::Equal)
     {
-        r = random_number(n.clone());
+        r = random_number_with_rng(n.clone(), rng);
         qr = r.secure_pow_mod(&Integer::from(2), n);
     }
     qr
 }
 
 pub fn rand_int(a: Integer, b: Integer) -> Integer {
-    let mut seed = Seed;
-    let mut rand = RandState::new_custom(&mut seed);
+    rand_int_with_rng(a, b, &mut thread_rng())
+}
+
+pub fn rand_int_with_rng<R: RngCore + CryptoRng>(a: Integer, b: Integer, rng: &mut R) -> Integer {
+    let mut seed = Seed { rng };
+    let mut rand = ThreadRandState::new_custom(&mut seed);
     let range = (&b - &a).complete() + Integer::from(1);
     // NOTE: return a random integer in the range [a, b], including both end points.
     return a + range.random_below(&mut rand);
@@ -12,20 +12,25 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use alloc::vec::Vec;
+
 #[cfg(feature = "bbsplus")]
 pub mod bbsplus_utils {
     use crate::{
         bbsplus::ciphersuites::BbsCiphersuite, bbsplus::keys::BBSplusPublicKey, errors::Error,
         utils::message::bbsplus_message::BBSplusMessage,
     };
+    use alloc::{borrow::ToOwned, string::String, vec::Vec};
     use bls12_381_plus::{G1Affine, G1Projective, G2Affine, G2Projective, Scalar};
+    use core::any::{Any, TypeId};
     use elliptic_curve::{
         group::Curve,
         hash2curve::{ExpandMsg, Expander},
     };
     use ff::Field;
-    use rand::{thread_rng, RngCore};
-    use std::any::{Any, TypeId};
+    #[cfg(feature = "std")]
+    use rand::thread_rng;
+    use rand::{CryptoRng, RngCore};
 
     pub(crate) fn parse_g2_projective_compressed(slice: &[u8]) -> Result<G2Projective, Error> {
         let point = G2Affine::from_compressed(
@@ -67,8 +72,23 @@ pub mod bbsplus_utils {
     ///
     /// # Output
     /// * Vec<u8>, a secret
+    #[cfg(feature = "std")]
     pub fn generate_random_secret(n: usize) -> Vec<u8> {
-        let mut rng = thread_rng();
+        generate_random_secret_with_rng(n, &mut thread_rng())
+    }
+
+    /// # Description
+    /// Generate a random secret of `n` bytes using the supplied random number generator
+    /// # Input
+    /// * `n` (REQUIRED), number of bytes
+    /// * `rng` (REQUIRED), a cryptographically secure random number generator
+    ///
+    /// # Output
+    /// * Vec<u8>, a secret
+    pub fn generate_random_secret_with_rng<R: RngCore + CryptoRng>(
+        n: usize,
+        rng: &mut R,
+    ) -> Vec<u8> {
         let mut secret = vec![0; n]; // Initialize a vector of length n with zeros
         rng.fill_bytes(&mut secret); // Fill the vector with random bytes
         secret
@@ -193,8 +213,7 @@ pub mod bbsplus_utils {
 
     impl ScalarExt for Scalar {
         fn to_bytes_be(&self) -> [u8; 32] {
-            let bytes = self.to_be_bytes();
-            bytes
+            self.to_be_bytes()
         }
 
         fn from_bytes_be(bytes: &[u8]) -> Result<Self, Error> {
@@ -219,8 +238,8 @@ pub mod bbsplus_utils {
         T: Any,
     {
         let mut result: Vec<u8> = Vec::new();
-        if array.len() == 0 {
-            println!("Empty array");
+        if array.is_empty() {
+            log::debug!("Empty array");
             return result;
         }
 
@@ -257,7 +276,7 @@ pub mod bbsplus_utils {
                 }
             }
         } else {
-            println!("Unknown struct type");
+            log::warn!("Unknown struct type");
         }
 
         result
@@ -281,8 +300,7 @@ pub mod bbsplus_utils {
         out
     }
 
-    pub(crate) fn get_random() -> Scalar {
-        let rng = rand::thread_rng();
+    pub(crate) fn get_random_with_rng<R: RngCore + CryptoRng>(rng: &mut R) -> Scalar {
         Scalar::random(rng)
     }
 
@@ -298,17 +316,60 @@ pub mod bbsplus_utils {
     /// # Output:
     /// * a [`Vec<Scalar>`].
     ///
-    #[cfg(not(test))]
+    #[cfg(feature = "std")]
     pub fn calculate_random_scalars(count: usize) -> Vec<Scalar> {
+        calculate_random_scalars_with_rng(count, &mut thread_rng())
+    }
+
+    /// # Description
+    /// This operation returns the requested number of pseudo-random scalars, drawn from the supplied random number generator
+    ///
+    /// # Inputs:
+    ///
+    /// * `count` (REQUIRED), usize. The number of scalars to return.
+    /// * `rng` (REQUIRED), a cryptographically secure random number generator.
+    ///
+    /// # Output:
+    /// * a [`Vec<Scalar>`].
+    ///
+    pub fn calculate_random_scalars_with_rng<R: RngCore + CryptoRng>(
+        count: usize,
+        rng: &mut R,
+    ) -> Vec<Scalar> {
         let mut random_scalars: Vec<Scalar> = Vec::new();
 
         for _i in 0..count {
-            random_scalars.push(get_random());
+            random_scalars.push(get_random_with_rng(rng));
         }
 
         random_scalars
     }
 
+    /// # Description
+    /// The source of the random scalars of the BBS proofs and commitments.
+    pub(crate) enum Randomness<'a, R> {
+        /// Scalars drawn from a cryptographically secure random number generator
+        Rng(&'a mut R),
+        /// Mocked random scalars computed with [`seeded_random_scalars`] from `seed` and `dst`, as done to produce the fixtures of the drafts. Only available to the tests and with the `test-vectors` feature.
+        #[cfg(any(test, feature = "test-vectors"))]
+        Mocked { seed: &'a [u8], dst: &'a [u8] },
+    }
+
+    impl<R: RngCore + CryptoRng> Randomness<'_, R> {
+        /// Returns `count` random scalars
+        pub(crate) fn scalars<CS>(&mut self, count: usize) -> Vec<Scalar>
+        where
+            CS: BbsCiphersuite,
+            CS::Expander: for<'a> ExpandMsg<'a>,
+        {
+            match self {
+                Self::Rng(rng) => calculate_random_scalars_with_rng(count, *rng),
+                #[cfg(any(test, feature = "test-vectors"))]
+                Self::Mocked { seed, dst } => seeded_random_scalars::<CS>(count, seed, dst),
+            }
+        }
+    }
+
     /// https://datatracker.ietf.org/doc/html/draft-irtf-cfrg-bbs-signatures-07#name-mocked-random-scalars
     ///
     /// # Description
@@ -323,7 +384,6 @@ pub mod bbsplus_utils {
     /// # Output:
     /// * a [`Vec<Scalar>`].
     ///
-    #[cfg(test)]
     pub fn seeded_random_scalars<CS>(count: usize, seed: &[u8], dst: &[u8]) -> Vec<Scalar>
     where
         CS: BbsCiphersuite,
@@ -337,7 +397,7 @@ pub mod bbsplus_utils {
         let out_len = CS::EXPAND_LEN * count;
         let mut v = vec![0u8; out_len];
 
-        CS::Expander::expand_message(&[&seed], &[&dst], out_len)
+        CS::Expander::expand_message(&[seed], &[dst], out_len)
             .unwrap()
             .fill_bytes(&mut v);
 
@@ -378,7 +438,7 @@ pub mod bbsplus_utils {
         CS: BbsCiphersuite,
         CS::Expander: for<'a> ExpandMsg<'a>,
     {
-        if generators.len() == 0 {
+        if generators.is_empty() {
             return Err(Error::NotEnoughGenerators);
         }
 
@@ -465,38 +525,49 @@ pub mod bbsplus_utils {
 
 #[cfg(feature = "cl03")]
 pub mod cl03_utils {
+    use crate::errors::Error;
     use rug::{integer::Order, Integer};
 
     //b*x = a mod m -> return x
-    pub fn divm(a: &Integer, b: &Integer, m: &Integer) -> Integer {
+    pub fn divm(a: &Integer, b: &Integer, m: &Integer) -> Result<Integer, Error> {
+        if *m <= 0 {
+            return Err(Error::NotInvertibleElement);
+        }
         let mut num = a.clone();
         let den;
         let mut module = m.clone();
-        let r: Integer;
         let mut result = b.invert_ref(&m);
-        let mut ok = result.is_none();
-        if ok {
+        if result.is_none() {
             let mut gcd = Integer::from(a.gcd_ref(&b));
             gcd.gcd_mut(&m);
             num = Integer::from(a.div_exact_ref(&gcd));
             den = Integer::from(b.div_exact_ref(&gcd));
             module = Integer::from(m.div_exact_ref(&gcd));
             result = den.invert_ref(&module);
-            ok = result.is_none();
         }
 
-        if !ok {
-            r = Integer::from(result.unwrap());
-            let z = (r * num) % module;
-            z
-        } else {
-            panic!("No solution");
+        match result {
+            Some(inverse) => Ok((Integer::from(inverse) * num) % module),
+            None => Err(Error::NotInvertibleElement),
         }
     }
 
+    /// # Description
+    /// Overwrites with zeros the limbs used by `value` and sets it to zero, so that secret integers do not linger in memory.
+    pub fn zeroize_integer(value: &mut Integer) {
+        // importing as many zero bytes as the current magnitude reuses the same allocation
+        let len = value.significant_digits::<u8>();
+        value.assign_digits(&vec![0u8; len], Order::Lsf);
+    }
+
     pub trait IntegerExt {
         fn to_bytes_be(&self, len: usize) -> Vec<u8>;
         // fn from_bytes_be(bytes: &[u8], len: usize) -> Self;
+
+        /// # Description
+        /// Computes `self ** exponent mod modulus`, failing instead of panicking when the modulus is not positive
+        /// or when the exponent is negative and `self` is not invertible modulo `modulus`.
+        fn pow_mod_checked(&self, exponent: &Integer, modulus: &Integer) -> Result<Integer, Error>;
     }
 
     impl IntegerExt for Integer {
@@ -506,6 +577,15 @@ pub mod cl03_utils {
             bytes
         }
 
+        fn pow_mod_checked(&self, exponent: &Integer, modulus: &Integer) -> Result<Integer, Error> {
+            if *modulus <= 0 {
+                return Err(Error::NotInvertibleElement);
+            }
+            self.pow_mod_ref(exponent, modulus)
+                .map(Integer::from)
+                .ok_or(Error::NotInvertibleElement)
+        }
+
         // fn from_bytes_be(bytes: &[u8], len: usize) -> Self {
         //     let i = Integer::from_digits(&bytes[0usize .. len], Order::MsfBe);
         //     i
@@ -517,7 +597,7 @@ pub(crate) fn get_remaining_indexes(length: usize, indexes: &[usize]) -> Vec<usi
     let mut remaining: Vec<usize> = Vec::new();
 
     for i in 0..length {
-        if indexes.contains(&i) == false {
+        if !indexes.contains(&i) {
             remaining.push(i);
         }
     }